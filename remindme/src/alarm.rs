@@ -0,0 +1,168 @@
+// Full-screen terminal alarm for critical reminders (`remind alarm`)
+//
+// Desktop popups disappear after a few seconds and are easy to miss. This command keeps
+// watching the reminders and, when one at or above the chosen priority is due, takes over
+// the terminal with a flashing alert that stays until it is dismissed or snoozed. With
+// notifications.escalate_every set, an urgent reminder's sound keeps ringing, louder each time,
+// until then.
+
+use anyhow::Result;
+use chrono::{Duration, Local};
+use crossterm::{
+    event::{self, Event as TermEvent, KeyCode, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame, Terminal,
+};
+use std::io;
+use std::thread;
+use tracing::{debug, info};
+
+use crate::config::Config;
+use crate::holidays::Calendar;
+use crate::hooks::Event;
+use crate::reminder::{Priority, Reminder};
+use crate::sound::{Escalation, SoundPlayer};
+use crate::storage::Storage;
+use crate::utils;
+
+// How the user answered an alarm
+enum Response {
+    Dismiss,
+    Snooze,
+    Quit,
+}
+
+pub fn watch(mut storage: Storage, config: &Config, min_priority: Priority, interval: u64, snooze: i64) -> Result<()> {
+    let calendar = Calendar::from_config(&config.holidays)?;
+    let sounds = SoundPlayer::new(&config.notifications);
+    println!("Watching for due {:?} or higher priority reminders (Ctrl+C to stop)", min_priority);
+
+    loop {
+        let now = Local::now();
+        let next = storage
+            .load()?
+            .into_iter()
+            .filter(|r| r.is_due() && r.effective_priority(now, &config.priority) >= min_priority)
+            .min_by_key(|r| r.due_time);
+
+        let Some(mut reminder) = next else {
+            debug!(interval, "Nothing due for the alarm; waiting");
+            thread::sleep(std::time::Duration::from_secs(interval));
+            continue;
+        };
+
+        info!(id = %reminder.id, "Ringing alarm");
+        match ring(&reminder, reminder.effective_priority(now, &config.priority), &sounds, config)? {
+            Response::Dismiss => {
+                info!(id = %reminder.id, "Alarm dismissed");
+                storage.hooks().fire(Event::Due, &reminder);
+                reminder.mark_notified(&calendar);
+                reminder.catch_up(config.notifications.catch_up, Local::now(), &calendar);
+            }
+            Response::Snooze => {
+                reminder.snooze(Local::now() + Duration::minutes(snooze));
+                info!(id = %reminder.id, until = %reminder.due_time, "Alarm snoozed");
+                println!("Snoozed '{}' for {} minutes", reminder.text, snooze);
+            }
+            Response::Quit => return Ok(()),
+        }
+        storage.update_reminder(reminder)?;
+    }
+}
+
+fn ring(reminder: &Reminder, priority: Priority, sounds: &SoundPlayer, config: &Config) -> Result<Response> {
+    // Stops ringing once a key is pressed and it's dropped
+    let escalation = match priority {
+        Priority::Urgent => Escalation::start(&config.notifications),
+        _ => None,
+    };
+    if escalation.is_none()
+        && let Err(e) = sounds.play_notification(priority)
+    {
+        // The alert itself is enough; just note the failure
+        println!("Failed to play notification sound: {}", e);
+    }
+
+    // Set up terminal
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+    terminal.hide_cursor()?;
+
+    let result = run_alarm(&mut terminal, reminder, priority);
+
+    // Restore terminal
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    result
+}
+
+fn run_alarm<B: Backend>(terminal: &mut Terminal<B>, reminder: &Reminder, priority: Priority) -> Result<Response> {
+    let mut flash = true;
+    loop {
+        terminal.draw(|f| render(f, reminder, priority, flash))?;
+
+        // Redraw with the other colors twice a second until a key is pressed
+        if !event::poll(std::time::Duration::from_millis(500))? {
+            flash = !flash;
+            continue;
+        }
+
+        if let TermEvent::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Char('d') => return Ok(Response::Dismiss),
+                KeyCode::Char('s') => return Ok(Response::Snooze),
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(Response::Quit),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(Response::Quit),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn render(f: &mut Frame, reminder: &Reminder, priority: Priority, flash: bool) {
+    let (fg, bg) = if flash { (Color::White, Color::Red) } else { (Color::Red, Color::Black) };
+    let style = Style::default().fg(fg).bg(bg);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {:?} priority reminder ", priority))
+        .style(style);
+    f.render_widget(block, f.area());
+
+    // Center the message vertically
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(40), Constraint::Length(5), Constraint::Min(0)].as_ref())
+        .split(f.area());
+
+    let text = vec![
+        Line::from(Span::styled(reminder.headline(), style.add_modifier(Modifier::BOLD))),
+        Line::from(format!("Due {}", utils::format_due(&reminder.due_time, true))),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Enter", style.add_modifier(Modifier::BOLD)),
+            Span::raw(" dismiss   "),
+            Span::styled("s", style.add_modifier(Modifier::BOLD)),
+            Span::raw(" snooze   "),
+            Span::styled("q", style.add_modifier(Modifier::BOLD)),
+            Span::raw(" stop watching"),
+        ]),
+    ];
+    f.render_widget(Paragraph::new(text).style(style).alignment(Alignment::Center), rows[1]);
+}
@@ -0,0 +1,62 @@
+// User-defined command aliases from `[alias]` in config.toml
+//
+// An alias replaces the command word before clap sees the arguments, so
+// `remind standup --priority high` runs the alias' command with `--priority high` appended.
+// Built-in commands (and their short aliases ls, rm and done) always win over a user alias.
+
+use anyhow::Result;
+use clap::CommandFactory;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+
+use crate::cli::Cli;
+use crate::error::RemindError;
+
+pub fn expand(args: impl IntoIterator<Item = OsString>, aliases: &BTreeMap<String, String>) -> Result<Vec<OsString>> {
+    let mut args: Vec<OsString> = args.into_iter().collect();
+    if aliases.is_empty() {
+        return Ok(args);
+    }
+
+    let Some(position) = command_position(&args) else {
+        return Ok(args);
+    };
+    let Some(word) = args[position].to_str() else {
+        return Ok(args);
+    };
+    if Cli::command().find_subcommand(word).is_some() {
+        return Ok(args);
+    }
+    let Some(expansion) = aliases.get(word) else {
+        return Ok(args);
+    };
+
+    let words = shell_words::split(expansion)
+        .map_err(|e| RemindError::Config(format!("Invalid alias '{}' in the config: {}", word, e)))?;
+    args.splice(position..=position, words.into_iter().map(OsString::from));
+    Ok(args)
+}
+
+// Index of the command word: the first argument that isn't a global option or its value
+fn command_position(args: &[OsString]) -> Option<usize> {
+    let command = Cli::command();
+    let takes_value = |flag: &str| {
+        command.get_arguments().any(|arg| {
+            arg.get_action().takes_values()
+                && (flag.strip_prefix("--").is_some_and(|long| arg.get_long() == Some(long))
+                    || flag.strip_prefix('-').and_then(|short| short.chars().next()).is_some_and(|c| {
+                        flag.len() == 2 && arg.get_short() == Some(c)
+                    }))
+        })
+    };
+
+    let mut i = 1;
+    while i < args.len() {
+        let arg = args[i].to_str()?;
+        if arg == "--" || !arg.starts_with('-') {
+            return (arg != "--").then_some(i);
+        }
+        i += if takes_value(arg) { 2 } else { 1 };
+    }
+    None
+}
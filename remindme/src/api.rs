@@ -0,0 +1,172 @@
+// Newline-delimited JSON over stdin and stdout (`remind api`), for editor plugins
+//
+// Each line on stdin is one request and gets one line on stdout, so a plugin can keep a single
+// process running instead of starting the binary for every call:
+//
+//   {"id": 1, "method": "add", "params": {"text": "Review PR", "time": "15:00"}}
+//   {"id": 1, "result": {"id": "c7613d0e-...", "text": "Review PR", ...}}
+//   {"id": 2, "error": "No reminder found with ID 42"}
+//
+// The id is whatever the request sent and is only echoed back. Every request reads the
+// reminders file again, so changes made elsewhere (the notifier, the TUI) show up. The process
+// ends when stdin is closed.
+//
+// Methods and their params (IDs may be shortened to an unambiguous prefix):
+//   list      {"pending": true, "tag": "work"}, both optional           -> [reminder]
+//   get       {"id"}                                                    -> reminder
+//   add       as POST /reminders: {"text", "time", "date", "recurrence", "meta"} -> reminder
+//   update    {"id"} plus any fields of PATCH /reminders/:id            -> reminder
+//   delete    {"id"}                                                    -> {"deleted": ID}
+//   complete  {"id"}                                                    -> {"reminder", "follow_up"}
+//   snooze    {"id", "for": "30m"}                                      -> reminder
+//   search    {"query"}, as `remind search`                             -> [reminder]
+//   version   none                                                      -> {"version", "protocol"}
+
+use anyhow::Result;
+use chrono::Local;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+
+use crate::cli;
+use crate::config::Config;
+use crate::error::RemindError;
+use crate::holidays::Calendar;
+use crate::pick;
+use crate::reminder::Reminder;
+use crate::search;
+use crate::server::{NewReminder, ReminderPatch};
+use crate::storage::Storage;
+
+// Raised when a method's params or results change incompatibly
+const PROTOCOL: u32 = 1;
+
+#[derive(Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    params: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct Target {
+    id: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ListParams {
+    pending: bool,
+    tag: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Update {
+    id: String,
+    #[serde(flatten)]
+    patch: ReminderPatch,
+}
+
+#[derive(Deserialize)]
+struct Snooze {
+    id: String,
+    #[serde(rename = "for")]
+    duration: String,
+}
+
+#[derive(Deserialize)]
+struct Query {
+    query: String,
+}
+
+// Answer requests until stdin closes
+pub fn run(storage: &mut Storage, config: &Config) -> Result<()> {
+    let calendar = Calendar::from_config(&config.holidays)?;
+    let stdout = io::stdout();
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => {
+                let id = request.id.clone();
+                match handle(storage, config, &calendar, request) {
+                    Ok(result) => json!({ "id": id, "result": result }),
+                    Err(e) => json!({ "id": id, "error": e.to_string() }),
+                }
+            }
+            Err(e) => json!({ "id": Value::Null, "error": format!("Invalid request: {}", e) }),
+        };
+        let mut out = stdout.lock();
+        writeln!(out, "{}", response)?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+fn handle(storage: &mut Storage, config: &Config, calendar: &Calendar, request: Request) -> Result<Value> {
+    let params = request.params.unwrap_or_else(|| json!({}));
+    let date_order = config.input.date_order;
+    match request.method.as_str() {
+        "list" => {
+            let ListParams { pending, tag } = parse(params)?;
+            let mut reminders = storage.load()?;
+            reminders.retain(|r| {
+                !(pending && r.completed)
+                    && tag.as_deref().is_none_or(|tag| r.tags.iter().any(|t| t.eq_ignore_ascii_case(tag.trim_start_matches('#'))))
+            });
+            Ok(json!(reminders))
+        }
+        "get" => {
+            let Target { id } = parse(params)?;
+            Ok(json!(find(storage, &id)?))
+        }
+        "add" => {
+            let new: NewReminder = parse(params)?;
+            let reminder = new.build(date_order)?;
+            storage.add_reminder(reminder.clone())?;
+            Ok(json!(reminder))
+        }
+        "update" => {
+            let Update { id, patch } = parse(params)?;
+            let reminder = patch.apply(storage, find(storage, &id)?, calendar, date_order)?;
+            Ok(json!(reminder))
+        }
+        "delete" => {
+            let Target { id } = parse(params)?;
+            let reminder = find(storage, &id)?;
+            storage.delete_reminder(&reminder.id)?;
+            Ok(json!({ "deleted": reminder.id }))
+        }
+        "complete" => {
+            let Target { id } = parse(params)?;
+            let (reminder, follow_up) = storage.complete_reminder(find(storage, &id)?, calendar)?;
+            Ok(json!({ "reminder": reminder, "follow_up": follow_up }))
+        }
+        "snooze" => {
+            let Snooze { id, duration } = parse(params)?;
+            let mut reminder = find(storage, &id)?;
+            reminder.snooze(Local::now() + cli::parse_duration(&duration)?);
+            storage.update_reminder(reminder.clone())?;
+            Ok(json!(find(storage, &reminder.id)?))
+        }
+        "search" => {
+            let Query { query } = parse(params)?;
+            let reminders = storage.load()?;
+            let hits: Vec<&Reminder> = search::search(&reminders, &query).into_iter().map(|hit| hit.reminder).collect();
+            Ok(json!(hits))
+        }
+        "version" => Ok(json!({ "version": env!("CARGO_PKG_VERSION"), "protocol": PROTOCOL })),
+        method => Err(RemindError::InvalidInput(format!("Unknown method '{}'", method)).into()),
+    }
+}
+
+fn parse<T: serde::de::DeserializeOwned>(params: Value) -> Result<T> {
+    serde_json::from_value(params).map_err(|e| RemindError::InvalidInput(format!("Invalid params: {}", e)).into())
+}
+
+fn find(storage: &Storage, id: &str) -> Result<Reminder> {
+    pick::find(storage.load()?, id)
+}
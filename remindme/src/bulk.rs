@@ -0,0 +1,301 @@
+// Bulk editing in $EDITOR (`remind edit --all --editor`)
+//
+// Like `crontab -e`: the selected reminders are written to a plain text document, one per
+// line, and opened in the user's editor. When it is closed the document is compared with
+// what was written out, and every add, edit and delete is applied in a single save, so a
+// mistake on one line leaves the whole file untouched.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local};
+use std::collections::HashSet;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::process::Command;
+
+use crate::cli;
+use crate::config::DateOrder;
+use crate::error::RemindError;
+use crate::hooks::Event;
+use crate::i18n::tr;
+use crate::journal::Op;
+use crate::reminder::{Reminder, RecurrenceType};
+use crate::storage::{Change, Storage};
+
+const HEADER: &str = "\
+# One reminder per line: ID | DUE | RECURRENCE | TAGS | TEXT
+# Change a field to edit that reminder, delete a line to delete it, or add a line
+# with \"new\" as its ID to add one. Lines starting with # are ignored.
+# Exit without saving to cancel.
+";
+
+// Which reminders go into the document; completed ones are always left out
+pub struct Selection<'a> {
+    pub all: bool,
+    pub id: Option<&'a str>,
+    pub tag: Option<&'a str>,
+}
+
+impl Selection<'_> {
+    fn matches(&self, reminder: &Reminder) -> bool {
+        !reminder.completed
+            && self.id.is_none_or(|id| reminder.id == id)
+            && self.tag.is_none_or(|tag| reminder.tags.iter().any(|t| t.eq_ignore_ascii_case(tag.trim_start_matches('#'))))
+    }
+}
+
+#[derive(Default)]
+struct Changes {
+    added: Vec<Reminder>,
+    updated: Vec<Reminder>,
+    deleted: Vec<Reminder>,
+}
+
+pub fn run(storage: &Storage, date_order: DateOrder, selection: Selection) -> Result<()> {
+    // Opening every pending reminder has to be asked for
+    if !selection.all && selection.id.is_none() && selection.tag.is_none() {
+        return Err(RemindError::InvalidInput(tr!("Choose the reminders to edit with --all, --tag or --id.")).into());
+    }
+    let reminders = storage.load()?;
+    let selected: Vec<&Reminder> = reminders.iter().filter(|r| selection.matches(r)).collect();
+    if let Some(id) = selection.id
+        && selected.is_empty()
+    {
+        return Err(RemindError::NotFound(id.to_string()).into());
+    }
+
+    let document = render(&selected);
+    let path = env::temp_dir().join(format!("remindme-edit-{}.txt", std::process::id()));
+    fs::write(&path, &document).map_err(RemindError::access(&path))?;
+    let result = edit_until_valid(&path, &document, &selected, date_order);
+    let _ = fs::remove_file(&path);
+
+    let Some(mut changes) = result? else {
+        println!("{}", tr!("No changes."));
+        return Ok(());
+    };
+
+    if let Some(rules) = storage.rules() {
+        for reminder in &mut changes.added {
+            for tag in rules.tags(reminder) {
+                reminder.add_tag(&tag);
+            }
+        }
+    }
+
+    let deleted: HashSet<&str> = changes.deleted.iter().map(|r| r.id.as_str()).collect();
+    let mut updated = reminders.clone();
+    updated.retain(|r| !deleted.contains(r.id.as_str()));
+    for reminder in &changes.updated {
+        if let Some(existing) = updated.iter_mut().find(|r| r.id == reminder.id) {
+            *existing = reminder.clone();
+        }
+    }
+    updated.extend(changes.added.iter().cloned());
+
+    // The editor may stay open for a while; a `notify` run meanwhile shouldn't be undone
+    let base = |id: &str| reminders.iter().find(|r| r.id == id).cloned();
+    let edits = changes
+        .added
+        .iter()
+        .map(|r| Change { op: Op::Add { reminder: r.clone() }, base: None })
+        .chain(changes.updated.iter().map(|r| Change { op: Op::Update { reminder: r.clone() }, base: base(&r.id) }))
+        .chain(changes.deleted.iter().map(|r| Change { op: Op::Delete { id: r.id.clone() }, base: Some(r.clone()) }))
+        .collect();
+    storage.save_changes(&updated, edits)?;
+
+    for reminder in &changes.added {
+        storage.hooks().fire(Event::Add, reminder);
+    }
+    for reminder in &changes.deleted {
+        storage.hooks().fire(Event::Delete, reminder);
+    }
+    println!(
+        "{}",
+        tr!("{} added, {} changed, {} deleted.", changes.added.len(), changes.updated.len(), changes.deleted.len())
+    );
+    Ok(())
+}
+
+// Open the editor until the document parses or the user gives up; None when nothing changed
+fn edit_until_valid(path: &Path, original: &str, selected: &[&Reminder], date_order: DateOrder) -> Result<Option<Changes>> {
+    loop {
+        open_editor(path)?;
+        let edited = fs::read_to_string(path).map_err(RemindError::access(path))?;
+        if edited == original {
+            return Ok(None);
+        }
+
+        match parse(&edited, selected, date_order) {
+            Ok(changes) if changes.added.is_empty() && changes.updated.is_empty() && changes.deleted.is_empty() => {
+                return Ok(None);
+            }
+            Ok(changes) => return Ok(Some(changes)),
+            Err(errors) => {
+                for error in &errors {
+                    eprintln!("{}", error);
+                }
+                if !crate::confirm(&tr!("Edit again?")) {
+                    return Err(RemindError::InvalidInput("Nothing was changed".to_string()).into());
+                }
+            }
+        }
+    }
+}
+
+// $VISUAL or $EDITOR through sh, so either can carry arguments (e.g. "code --wait")
+fn open_editor(path: &Path) -> Result<()> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(format!("{} \"$@\"", editor)).arg("sh").arg(path);
+    // When stdin is a pipe (e.g. `remind pick | fzf | remind pick --action edit`) the editor
+    // still needs the terminal
+    if !io::stdin().is_terminal()
+        && let Ok(tty) = File::open("/dev/tty")
+    {
+        command.stdin(tty);
+    }
+    let status = command
+        .status()
+        .with_context(|| format!("Failed to start the editor '{}'", editor))?;
+    if !status.success() {
+        bail!("The editor '{}' exited with {}; nothing was changed", editor, status);
+    }
+    Ok(())
+}
+
+fn render(reminders: &[&Reminder]) -> String {
+    let recurrences: Vec<String> = reminders.iter().map(|r| r.recurrence.to_string()).collect();
+    let width = recurrences.iter().map(String::len).max().unwrap_or(0);
+
+    let mut document = HEADER.to_string();
+    document.push('\n');
+    for (reminder, recurrence) in reminders.iter().zip(&recurrences) {
+        document.push_str(&format!(
+            "{} | {} | {:<width$} | {} | {}\n",
+            reminder.id,
+            format_due(&reminder.due_time),
+            recurrence,
+            format_tags(&reminder.tags),
+            reminder.text,
+            width = width
+        ));
+    }
+    document
+}
+
+fn format_due(due_time: &DateTime<Local>) -> String {
+    due_time.format("%Y-%m-%d %H:%M").to_string()
+}
+
+fn format_tags(tags: &[String]) -> String {
+    tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ")
+}
+
+// Compare the edited document with the reminders it was made from; every problem is reported
+fn parse(document: &str, selected: &[&Reminder], date_order: DateOrder) -> Result<Changes, Vec<String>> {
+    let mut changes = Changes::default();
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (number, line) in document.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.splitn(5, '|').map(str::trim).collect();
+        let [id, due, recurrence, tags, text] = fields[..] else {
+            errors.push(format!("Line {}: expected ID | DUE | RECURRENCE | TAGS | TEXT", number + 1));
+            continue;
+        };
+
+        let result = if id.eq_ignore_ascii_case("new") {
+            new_reminder(due, recurrence, tags, text, date_order).map(|r| changes.added.push(r))
+        } else if !seen.insert(id) {
+            Err(anyhow::anyhow!("ID {} appears more than once", id))
+        } else if let Some(original) = selected.iter().find(|r| r.id == id) {
+            apply(original, due, recurrence, tags, text, date_order).map(|r| changes.updated.extend(r))
+        } else {
+            Err(anyhow::anyhow!("Unknown ID {}; use \"new\" to add a reminder", id))
+        };
+        if let Err(e) = result {
+            errors.push(format!("Line {}: {}", number + 1, e));
+        }
+    }
+
+    changes.deleted = selected.iter().filter(|r| !seen.contains(r.id.as_str())).map(|r| (*r).clone()).collect();
+    if errors.is_empty() { Ok(changes) } else { Err(errors) }
+}
+
+fn new_reminder(due: &str, recurrence: &str, tags: &str, text: &str, date_order: DateOrder) -> Result<Reminder> {
+    if text.is_empty() {
+        bail!("The text is empty");
+    }
+    let mut reminder = Reminder::new(text.to_string(), cli::parse_datetime(due, date_order)?, RecurrenceType::None);
+    set_recurrence(&mut reminder, recurrence, date_order)?;
+    set_tags(&mut reminder, tags);
+    Ok(reminder)
+}
+
+// The edited reminder, or None when its line is unchanged
+fn apply(
+    original: &Reminder,
+    due: &str,
+    recurrence: &str,
+    tags: &str,
+    text: &str,
+    date_order: DateOrder,
+) -> Result<Option<Reminder>> {
+    let mut reminder = original.clone();
+    if text.is_empty() {
+        bail!("The text is empty");
+    }
+    reminder.text = text.to_string();
+
+    // Only reparse what was edited, so values the document can't show exactly are kept
+    if due != format_due(&original.due_time) {
+        reminder.due_time = cli::parse_datetime(due, date_order)?;
+        reminder.nominal_due = None;
+        reminder.anchor = None;
+        reminder.clear_notified();
+    }
+    if recurrence != original.recurrence.to_string() {
+        set_recurrence(&mut reminder, recurrence, date_order)?;
+    }
+    if tags != format_tags(&original.tags) {
+        set_tags(&mut reminder, tags);
+    }
+
+    let changed = reminder.text != original.text
+        || reminder.due_time != original.due_time
+        || reminder.recurrence.to_string() != original.recurrence.to_string()
+        || reminder.tags != original.tags;
+    if changed {
+        reminder.updated_at = Some(Local::now());
+        reminder.touched_at = reminder.updated_at;
+    }
+    Ok(changed.then_some(reminder))
+}
+
+fn set_recurrence(reminder: &mut Reminder, recurrence: &str, date_order: DateOrder) -> Result<()> {
+    let schedule = cli::parse_schedule(recurrence)?;
+    if let Some(at) = schedule.time {
+        let date = reminder.due_time.format("%Y-%m-%d");
+        reminder.due_time = cli::parse_datetime(&format!("{} {}", date, at.format("%H:%M")), date_order)?;
+    }
+    reminder.due_time = schedule.recurrence.align(reminder.due_time);
+    reminder.recurrence = schedule.recurrence;
+    reminder.nominal_due = None;
+    reminder.anchor = None;
+    Ok(())
+}
+
+fn set_tags(reminder: &mut Reminder, tags: &str) {
+    reminder.tags.clear();
+    for tag in tags.split_whitespace() {
+        reminder.add_tag(tag);
+    }
+}
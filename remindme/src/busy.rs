@@ -0,0 +1,344 @@
+// Holding back reminders during meetings ([busy] calendar = "https://...")
+//
+// With a calendar configured (a Google calendar's secret iCal address, a CalDAV calendar's export
+// link, or an .ics file another tool keeps in sync), `notify` and the daemon look at it before
+// alerting. While an event shows you as busy, reminders that aren't urgent stay due and go out at
+// the first run after the meeting ends, or after the last of several back to back; urgent ones
+// get through as during do-not-disturb. Events marked free (TRANSP:TRANSPARENT), cancelled ones,
+// ones you declined and all-day events don't count. Recurring meetings are expanded from their
+// RRULE (daily, weekly, monthly or yearly, with INTERVAL, BYDAY, BYMONTHDAY, COUNT and UNTIL)
+// minus their EXDATEs, and an occurrence that was moved or cancelled on its own replaces the one
+// it overrides.
+//
+// An address is fetched with curl at most every refresh_minutes and kept as busy.ics next to the
+// config. When a fetch fails the last copy is used; without one nothing is held back, so a
+// calendar that can't be read never keeps a reminder from you.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::SystemTime;
+use tracing::{debug, warn};
+
+use crate::channels;
+use crate::config::BusyConfig;
+use crate::invite::{self, Zone};
+use crate::storage;
+use crate::utils;
+
+// Longest wait for the calendar server, in seconds
+const TIMEOUT_SECONDS: u64 = 30;
+
+// A DATE-TIME value with the zone it's in
+struct Stamp {
+    naive: NaiveDateTime,
+    utc: bool,
+    tzid: Option<String>,
+}
+
+impl Stamp {
+    // None for a DATE, which is how all-day events start
+    fn parse(value: &str, tzid: Option<&str>) -> Option<Stamp> {
+        let naive = NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S").ok()?;
+        Some(Stamp { naive, utc: value.ends_with('Z'), tzid: tzid.map(str::to_string) })
+    }
+
+    fn local(&self, zones: &[Zone]) -> DateTime<Local> {
+        self.at(self.naive, zones)
+    }
+
+    // Another time in the same zone, e.g. a later occurrence of a recurring event
+    fn at(&self, naive: NaiveDateTime, zones: &[Zone]) -> DateTime<Local> {
+        match &self.tzid {
+            _ if self.utc => Utc.from_utc_datetime(&naive).with_timezone(&Local),
+            Some(tzid) => invite::zoned(naive, tzid, zones).unwrap_or_else(|| utils::local(naive)),
+            None => utils::local(naive),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Event {
+    uid: String,
+    start: Option<Stamp>,
+    end: Option<Stamp>,
+    duration_minutes: Option<i64>,
+    rule: Option<String>,
+    exdates: Vec<Stamp>,
+    // Set on an occurrence of a series that was moved or cancelled on its own
+    recurrence_id: Option<Stamp>,
+    free: bool,
+    cancelled: bool,
+    declined: bool,
+}
+
+// The end of the meeting you're in at `now`, or of the last one following it without a break;
+// None when the calendar shows you as free or there's no [busy] calendar
+pub fn until(config: &BusyConfig, now: DateTime<Local>) -> Result<Option<DateTime<Local>>> {
+    let Some(ics) = calendar(config)? else {
+        return Ok(None);
+    };
+    let mut meetings = meetings(&ics, config.email.as_deref(), now - Duration::days(1), now + Duration::days(1));
+    meetings.sort_by_key(|(start, _)| *start);
+
+    let mut end = None;
+    for (start, finish) in meetings {
+        match end {
+            None if start <= now && now < finish => end = Some(finish),
+            Some(current) if start <= current => end = Some(finish.max(current)),
+            Some(_) => break,
+            None => {}
+        }
+    }
+    debug!(busy_until = ?end, "Checked the busy calendar");
+    Ok(end)
+}
+
+// The calendar's text: the file, or the last copy fetched from the address
+fn calendar(config: &BusyConfig) -> Result<Option<String>> {
+    let Some(source) = config.calendar.as_deref() else {
+        return Ok(None);
+    };
+    let Some(url) = address(source) else {
+        let path = shellexpand::tilde(source).to_string();
+        let ics = fs::read_to_string(&path).with_context(|| format!("Failed to read the busy calendar {}", path))?;
+        return Ok(Some(ics));
+    };
+
+    let cache = cache_path()?;
+    let age = fs::metadata(&cache)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+    if age.is_none_or(|age| age.as_secs() >= u64::from(config.refresh_minutes) * 60) {
+        match fetch(&url, &cache) {
+            Ok(()) => debug!("Fetched the busy calendar"),
+            Err(e) if cache.exists() => {
+                warn!(error = %format!("{:#}", e), "Failed to fetch the busy calendar; using the last copy");
+                // Tried again after refresh_minutes rather than on every run while offline
+                let _ = fs::File::options().write(true).open(&cache).and_then(|file| file.set_modified(SystemTime::now()));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    let ics = fs::read_to_string(&cache).with_context(|| format!("Failed to read {}", cache.display()))?;
+    Ok(Some(ics))
+}
+
+// The https:// address of an iCal link; None for a file
+fn address(source: &str) -> Option<String> {
+    let lower = source.to_ascii_lowercase();
+    if lower.starts_with("https://") || lower.starts_with("http://") {
+        Some(source.to_string())
+    } else if lower.starts_with("webcal://") {
+        Some(format!("https://{}", &source["webcal://".len()..]))
+    } else {
+        None
+    }
+}
+
+fn cache_path() -> Result<PathBuf> {
+    storage::state_path("busy.ics")
+}
+
+// The address goes to curl as a config file on stdin, since a secret address is as good as a
+// password and shouldn't show up in `ps`. It's downloaded next to the cache and only replaces
+// it once complete.
+fn fetch(url: &str, cache: &Path) -> Result<()> {
+    let partial = cache.with_extension("ics.part");
+    let config = format!(
+        "silent\nshow-error\nfail\nlocation\nurl = {}\noutput = {}\nmax-time = {}\n",
+        channels::quote(url),
+        channels::quote(&partial.to_string_lossy()),
+        TIMEOUT_SECONDS
+    );
+    let mut child = Command::new("curl")
+        .args(["--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run curl")?;
+    child.stdin.take().context("curl has no stdin")?.write_all(config.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let _ = fs::remove_file(&partial);
+        return Err(anyhow!("Failed to fetch the busy calendar: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    // A login page instead of the calendar, e.g. after the secret address was reset
+    let ics = fs::read_to_string(&partial).unwrap_or_default();
+    if !ics.contains("BEGIN:VCALENDAR") {
+        let _ = fs::remove_file(&partial);
+        return Err(anyhow!("The busy calendar address didn't return an iCal calendar"));
+    }
+    fs::rename(&partial, cache).with_context(|| format!("Failed to write {}", cache.display()))
+}
+
+// Start and end of each meeting that overlaps `from` to `to`
+fn meetings(ics: &str, email: Option<&str>, from: DateTime<Local>, to: DateTime<Local>) -> Vec<(DateTime<Local>, DateTime<Local>)> {
+    let unfolded = invite::unfold(ics);
+    let zones = invite::zones(&unfolded);
+    let events = events(&unfolded, email);
+    let overridden: Vec<(&str, DateTime<Local>)> = events
+        .iter()
+        .filter_map(|event| Some((event.uid.as_str(), event.recurrence_id.as_ref()?.local(&zones))))
+        .collect();
+
+    let mut meetings = Vec::new();
+    for event in events.iter().filter(|event| !event.free && !event.cancelled && !event.declined) {
+        let Some(start) = &event.start else { continue };
+        let first = start.local(&zones);
+        let length = match &event.end {
+            Some(end) => end.local(&zones) - first,
+            None => Duration::minutes(event.duration_minutes.unwrap_or(0)),
+        };
+        if length <= Duration::zero() {
+            continue;
+        }
+
+        let starts = match (&event.rule, &event.recurrence_id) {
+            (Some(rule), None) => {
+                let exdates: Vec<DateTime<Local>> = event.exdates.iter().map(|stamp| stamp.local(&zones)).collect();
+                // A day of slack on each side for the event's zone being ahead of or behind ours
+                let window = ((from - length).naive_local() - Duration::days(1), to.naive_local() + Duration::days(1));
+                repeats(start.naive, rule, window)
+                    .into_iter()
+                    .map(|naive| start.at(naive, &zones))
+                    .filter(|time| !exdates.contains(time) && !overridden.contains(&(event.uid.as_str(), *time)))
+                    .collect()
+            }
+            _ => vec![first],
+        };
+        meetings.extend(starts.into_iter().filter(|time| *time + length > from && *time < to).map(|time| (time, time + length)));
+    }
+    meetings
+}
+
+// Each VEVENT; alarms inside them and other components are left out
+fn events(unfolded: &str, email: Option<&str>) -> Vec<Event> {
+    let mut events: Vec<Event> = Vec::new();
+    let mut stack: Vec<String> = Vec::new();
+    for line in unfolded.lines() {
+        let Some(prop) = invite::property(line.trim_end()) else { continue };
+        match prop.name.as_str() {
+            "BEGIN" => {
+                let component = prop.value.to_uppercase();
+                if component == "VEVENT" {
+                    events.push(Event::default());
+                }
+                stack.push(component);
+                continue;
+            }
+            "END" => {
+                stack.pop();
+                continue;
+            }
+            _ => {}
+        }
+        if stack.last().map(String::as_str) != Some("VEVENT") {
+            continue;
+        }
+        let Some(event) = events.last_mut() else { continue };
+        let tzid = prop.param("TZID");
+        match prop.name.as_str() {
+            "UID" => event.uid = prop.value.to_string(),
+            "DTSTART" => event.start = Stamp::parse(prop.value, tzid),
+            "DTEND" => event.end = Stamp::parse(prop.value, tzid),
+            "DURATION" => event.duration_minutes = invite::duration_minutes(prop.value),
+            "RRULE" => event.rule = Some(prop.value.to_string()),
+            "EXDATE" => event.exdates.extend(prop.value.split(',').filter_map(|value| Stamp::parse(value, tzid))),
+            "RECURRENCE-ID" => event.recurrence_id = Stamp::parse(prop.value, tzid),
+            "TRANSP" => event.free = prop.value.eq_ignore_ascii_case("TRANSPARENT"),
+            "STATUS" => event.cancelled = prop.value.eq_ignore_ascii_case("CANCELLED"),
+            "ATTENDEE" => {
+                let address = prop.value.trim_start_matches("mailto:").trim_start_matches("MAILTO:");
+                if email.is_some_and(|email| address.eq_ignore_ascii_case(email)) {
+                    event.declined = prop.param("PARTSTAT").is_some_and(|status| status.eq_ignore_ascii_case("DECLINED"));
+                }
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+// Start times of a recurring event inside `window`, as times in its own zone. The first is always
+// one, as the standard has it; an unknown FREQ (say HOURLY) has no others.
+fn repeats(first: NaiveDateTime, rule: &str, window: (NaiveDateTime, NaiveDateTime)) -> Vec<NaiveDateTime> {
+    let parts = invite::rule_parts(rule);
+    let get = |key: &str| parts.iter().find(|(k, _)| *k == key).map(|(_, value)| *value);
+    let frequency = get("FREQ").unwrap_or("");
+    let interval: i64 = get("INTERVAL").and_then(|n| n.parse().ok()).unwrap_or(1).max(1);
+    let count: Option<usize> = get("COUNT").and_then(|n| n.parse().ok());
+    let until = get("UNTIL").and_then(|value| {
+        NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S")
+            .ok()
+            .or_else(|| NaiveDate::parse_from_str(value, "%Y%m%d").ok()?.and_hms_opt(23, 59, 59))
+    });
+    let days: Vec<(Option<i32>, Weekday)> = get("BYDAY").map_or_else(Vec::new, |days| days.split(',').filter_map(invite::by_day).collect());
+    let month_days: Vec<i32> = get("BYMONTHDAY").map_or_else(Vec::new, |days| days.split(',').filter_map(|day| day.parse().ok()).collect());
+
+    let start = first.date();
+    // Weeks run Monday to Sunday
+    let monday = |date: NaiveDate| date - Duration::days(i64::from(date.weekday().num_days_from_monday()));
+    let month = |date: NaiveDate| i64::from(date.year()) * 12 + i64::from(date.month0());
+    let on_weekday = |date: NaiveDate| {
+        days.iter().any(|(nth, weekday)| date.weekday() == *weekday && nth.is_none_or(|nth| nth_in_month(date, nth)))
+    };
+    let on_month_day = |date: NaiveDate| {
+        if month_days.is_empty() { date.day() == start.day() } else { month_days.iter().any(|n| nth_day(date, *n)) }
+    };
+    let matches = |date: NaiveDate| match frequency {
+        "DAILY" => (date - start).num_days() % interval == 0 && (days.is_empty() || on_weekday(date)),
+        "WEEKLY" => {
+            (monday(date) - monday(start)).num_weeks() % interval == 0
+                && if days.is_empty() { date.weekday() == start.weekday() } else { on_weekday(date) }
+        }
+        "MONTHLY" => (month(date) - month(start)) % interval == 0 && if days.is_empty() { on_month_day(date) } else { on_weekday(date) },
+        "YEARLY" => i64::from(date.year() - start.year()) % interval == 0 && date.month() == start.month() && date.day() == start.day(),
+        _ => false,
+    };
+
+    let (from, to) = window;
+    let last = until.map_or(to, |until| until.min(to));
+    let mut found = Vec::new();
+    if first >= from && first <= last {
+        found.push(first);
+    }
+    // Without a COUNT the occurrences before the window don't matter, so counting can start there
+    let mut date = match count {
+        Some(_) => start.succ_opt(),
+        None => Some(start.succ_opt().unwrap_or(start).max(from.date())),
+    };
+    let mut seen = 1;
+    while let Some(day) = date {
+        let time = day.and_time(first.time());
+        if time > last || count.is_some_and(|count| seen >= count) {
+            break;
+        }
+        if matches(day) {
+            seen += 1;
+            if time >= from {
+                found.push(time);
+            }
+        }
+        date = day.succ_opt();
+    }
+    found
+}
+
+// Whether `date` is the nth of its weekday in its month, counting from the end for a negative n
+fn nth_in_month(date: NaiveDate, nth: i32) -> bool {
+    let from_start = (date.day() as i32 - 1) / 7 + 1;
+    let from_end = (utils::days_in_month(date.month(), date.year()) as i32 - date.day() as i32) / 7 + 1;
+    nth == from_start || nth == -from_end
+}
+
+// Whether `date` is day n of its month, or the nth last for a negative n
+fn nth_day(date: NaiveDate, n: i32) -> bool {
+    date.day() as i32 == n || utils::days_in_month(date.month(), date.year()) as i32 - date.day() as i32 + 1 == -n
+}
@@ -0,0 +1,179 @@
+// Reminders held in memory across many operations, e.g. in the TUI
+//
+// The file is read once; after that reminders() answers from memory unless the reminders file
+// or its journal changed on disk (a `notify` run, another terminal), checked the same way the
+// index checks freshness. Changes go to memory and are marked dirty until flush() writes them
+// in one save. If the file changed in the meantime, the pending changes are made again on top of
+// it, as Storage::save_changes does; one to a reminder the other process changed too is dropped
+// and reported as a conflict rather than overwriting it. Hooks fire once a change is on disk, as
+// they would through Storage.
+
+use anyhow::Result;
+use chrono::Local;
+use std::path::Path;
+use tracing::{debug, warn};
+
+use crate::error::RemindError;
+use crate::holidays::Calendar;
+use crate::hooks::Event;
+use crate::index::Fingerprint;
+use crate::journal::{self, Op};
+use crate::reminder::Reminder;
+use crate::storage::{self, Change, Storage};
+
+pub struct CachedStorage {
+    storage: Storage,
+    reminders: Vec<Reminder>,
+    // The reminders file and journal as they were when `reminders` was read or flushed
+    source: Fingerprint,
+    // Changes not flushed yet, oldest first, each with the reminder as it was before
+    pending: Vec<Change>,
+    // Hooks to fire once the pending changes are written
+    events: Vec<(Event, Reminder)>,
+}
+
+impl CachedStorage {
+    pub fn new(storage: Storage) -> Result<Self> {
+        let source = Fingerprint::of(storage.path());
+        let reminders = storage.load()?;
+        Ok(CachedStorage { storage, reminders, source, pending: Vec::new(), events: Vec::new() })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.storage.path()
+    }
+
+    // Every reminder, including unflushed changes
+    pub fn reminders(&mut self) -> Result<&[Reminder]> {
+        if Fingerprint::of(self.storage.path()) != self.source {
+            debug!(path = %self.storage.path().display(), "Reminders changed on disk; reloading");
+            self.reload()?;
+        }
+        Ok(&self.reminders)
+    }
+
+    pub fn add_reminder(&mut self, mut reminder: Reminder) {
+        if let Some(rules) = self.storage.rules() {
+            for tag in rules.tags(&reminder) {
+                reminder.add_tag(&tag);
+            }
+        }
+        self.events.push((Event::Add, reminder.clone()));
+        self.change(Op::Add { reminder });
+    }
+
+    pub fn update_reminder(&mut self, mut reminder: Reminder) -> Result<()> {
+        let existing = self
+            .reminders
+            .iter()
+            .find(|r| r.id == reminder.id)
+            .ok_or_else(|| RemindError::NotFound(reminder.id.clone()))?;
+        reminder.updated_at = Some(Local::now());
+        reminder.touched_at = reminder.updated_at;
+        if reminder.completed && !existing.completed {
+            self.events.push((Event::Complete, reminder.clone()));
+        }
+        self.change(Op::Update { reminder });
+        Ok(())
+    }
+
+    // Complete the reminder's current occurrence as Storage::complete_reminder does: the
+    // on-complete hook fires also when a recurring reminder moves on to its next occurrence, and
+    // its follow-up, if it has one, is added and returned too
+    pub fn complete_reminder(&mut self, mut reminder: Reminder, calendar: &Calendar) -> Result<(Reminder, Option<Reminder>)> {
+        let moved_on = reminder.complete(calendar);
+        self.update_reminder(reminder.clone())?;
+        if moved_on {
+            self.events.push((Event::Complete, reminder.clone()));
+        }
+        let follow_up = reminder.follow_up(Local::now());
+        if let Some(next) = &follow_up {
+            self.add_reminder(next.clone());
+        }
+        Ok((reminder, follow_up))
+    }
+
+    // Put back a reminder deleted earlier; it isn't new, so the on-add hook doesn't fire
+    pub fn restore_reminder(&mut self, reminder: Reminder) {
+        self.change(Op::Add { reminder });
+    }
+
+    // Whether a reminder with `id` was there to delete
+    pub fn delete_reminder(&mut self, id: &str) -> bool {
+        let Some(deleted) = self.reminders.iter().find(|r| r.id == id).cloned() else {
+            return false;
+        };
+        self.events.push((Event::Delete, deleted));
+        self.change(Op::Delete { id: id.to_string() });
+        true
+    }
+
+    // Write the pending changes in one save. On a conflict with another process the pending
+    // changes are dropped, the reminders are read again, and the conflict is returned
+    pub fn flush(&mut self) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let result = self.storage.save_changes(&self.reminders, self.pending.clone());
+        if let Err(e) = result {
+            if !is_conflict(&e) {
+                return Err(e);
+            }
+            self.discard()?;
+            return Err(e);
+        }
+        self.source = Fingerprint::of(self.storage.path());
+        debug!(changes = self.pending.len(), "Flushed cached reminders");
+        self.pending.clear();
+        for (event, reminder) in self.events.drain(..) {
+            self.storage.hooks().fire(event, &reminder);
+        }
+        Ok(())
+    }
+
+    fn change(&mut self, op: Op) {
+        let base = match &op {
+            Op::Add { .. } => None,
+            Op::Update { reminder } => self.reminders.iter().find(|r| r.id == reminder.id).cloned(),
+            Op::Delete { id } => self.reminders.iter().find(|r| &r.id == id).cloned(),
+        };
+        journal::apply(&mut self.reminders, vec![op.clone()]);
+        self.pending.push(Change { op, base });
+    }
+
+    // Read the file again, with the pending changes made again on top
+    fn reload(&mut self) -> Result<()> {
+        self.source = Fingerprint::of(self.storage.path());
+        let mut reminders = self.storage.load()?;
+        if let Err(e) = storage::rebase(&mut reminders, self.pending.clone()) {
+            self.discard()?;
+            return Err(e);
+        }
+        self.reminders = reminders;
+        Ok(())
+    }
+
+    // Drop the pending changes and their hooks, keeping what's on disk
+    fn discard(&mut self) -> Result<()> {
+        warn!(changes = self.pending.len(), "Dropping cached changes that conflict with another process");
+        self.pending.clear();
+        self.events.clear();
+        self.source = Fingerprint::of(self.storage.path());
+        self.reminders = self.storage.load()?;
+        Ok(())
+    }
+}
+
+pub fn is_conflict(e: &anyhow::Error) -> bool {
+    matches!(e.downcast_ref::<RemindError>(), Some(RemindError::Conflict(_)))
+}
+
+// Changes still pending when the handle goes away are written rather than lost
+impl Drop for CachedStorage {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            warn!(error = %format!("{:#}", e), "Failed to save cached changes");
+            eprintln!("Failed to save changes: {:#}", e);
+        }
+    }
+}
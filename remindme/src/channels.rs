@@ -0,0 +1,148 @@
+// Push channels: due reminders sent to a phone or chat over HTTP
+//
+// Each channel has a name ("ntfy", "gotify", "matrix") that works like "terminal" and
+// "desktop": a configured channel gets every due reminder it wants, and custom rules can route
+// to it by name. Requests go through curl, which keeps TLS out of the binary. A send that fails
+// for a reason that might pass (no network, a timeout, a 5xx or 429 answer) is retried a couple
+// of times before it's reported; the rest, like a wrong token, fail right away.
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::config::Config;
+use crate::gotify::Gotify;
+use crate::matrix::Matrix;
+use crate::ntfy::Ntfy;
+use crate::reminder::{Priority, Reminder};
+
+const ATTEMPTS: u32 = 3;
+// Waited after the first failure, twice as long after the second
+const RETRY_PAUSE: Duration = Duration::from_secs(2);
+// Longest a single attempt may take
+const TIMEOUT_SECONDS: u64 = 15;
+
+pub trait Channel: Sync {
+    // Name used in routes
+    fn name(&self) -> &'static str;
+
+    // Whether a due reminder comes here when no rule routes it
+    fn wants(&self, reminder: &Reminder) -> bool;
+
+    // The HTTP request delivering `message`
+    fn request(&self, message: &Message) -> Result<Request>;
+}
+
+// What a channel says about a due reminder
+pub struct Message<'a> {
+    pub reminder: &'a Reminder,
+    pub title: String,
+    pub body: String,
+    pub priority: Priority,
+}
+
+pub struct Request {
+    pub method: &'static str,
+    pub url: String,
+    // "Name: value"
+    pub headers: Vec<String>,
+    pub body: Value,
+}
+
+// Every channel with enough config to send
+pub fn configured(config: &Config) -> Vec<Box<dyn Channel>> {
+    let mut channels: Vec<Box<dyn Channel>> = Vec::new();
+    if let Some(ntfy) = Ntfy::from_config(&config.ntfy, &config.server) {
+        channels.push(Box::new(ntfy));
+    }
+    if let Some(gotify) = Gotify::from_config(&config.gotify) {
+        channels.push(Box::new(gotify));
+    }
+    if let Some(matrix) = Matrix::from_config(&config.matrix) {
+        channels.push(Box::new(matrix));
+    }
+    channels
+}
+
+pub fn send(channel: &dyn Channel, message: &Message) -> Result<()> {
+    let request = channel.request(message)?;
+    let mut attempt = 1;
+    loop {
+        match perform(&request) {
+            Ok(()) => {
+                debug!(channel = channel.name(), id = %message.reminder.id, "Pushed");
+                return Ok(());
+            }
+            Err(Failure::Retry(e)) if attempt < ATTEMPTS => {
+                warn!(channel = channel.name(), attempt, error = %format!("{:#}", e), "Push failed; retrying");
+                thread::sleep(RETRY_PAUSE * attempt);
+                attempt += 1;
+            }
+            Err(Failure::Retry(e)) => return Err(e.context(format!("Gave up after {} attempts", ATTEMPTS))),
+            Err(Failure::Fatal(e)) => return Err(e),
+        }
+    }
+}
+
+enum Failure {
+    Retry(anyhow::Error),
+    Fatal(anyhow::Error),
+}
+
+// The request is handed to curl as a config file on stdin, so tokens stay out of `ps`
+fn perform(request: &Request) -> Result<(), Failure> {
+    let mut config = format!(
+        "silent\nshow-error\nrequest = {}\nurl = {}\nmax-time = {}\nwrite-out = {}\n",
+        quote(request.method),
+        quote(&request.url),
+        TIMEOUT_SECONDS,
+        quote("\n%{http_code}")
+    );
+    config.push_str(&format!("header = {}\n", quote("Content-Type: application/json")));
+    for header in &request.headers {
+        config.push_str(&format!("header = {}\n", quote(header)));
+    }
+    config.push_str(&format!("data-binary = {}\n", quote(&request.body.to_string())));
+
+    let run = || -> Result<std::process::Output> {
+        let mut child = Command::new("curl")
+            .args(["--config", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run curl")?;
+        child.stdin.take().context("curl has no stdin")?.write_all(config.as_bytes())?;
+        Ok(child.wait_with_output()?)
+    };
+    let output = run().map_err(Failure::Fatal)?;
+    if !output.status.success() {
+        // Couldn't connect, timed out, ...
+        let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(Failure::Retry(anyhow!("curl: {}", error)));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+    let status: u16 = status.trim().parse().unwrap_or(0);
+    match status {
+        200..=299 => Ok(()),
+        429 | 500..=599 => Err(Failure::Retry(anyhow!("HTTP {}: {}", status, body.trim()))),
+        _ => Err(Failure::Fatal(anyhow!("HTTP {}: {}", status, body.trim()))),
+    }
+}
+
+// A double-quoted curl config value
+pub fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
@@ -1,8 +1,17 @@
-use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
-use clap::{Parser, Subcommand};
-use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Weekday};
+use clap::{ArgGroup, Parser, Subcommand, ValueEnum};
+use anyhow::Result;
 
-use crate::reminder::RecurrenceType;
+use crate::compress::Compression;
+use crate::config::DateOrder;
+use crate::error::RemindError;
+use crate::reminder::{AdvanceOn, FollowUp, OffDayPolicy, Priority, RecurrenceType, Reminder, ReminderKind, SortOrder};
+use crate::pick::PickAction;
+use crate::sound::SoundAction;
+use crate::report::ReportFormat;
+use crate::status::StatusFormat;
+use crate::workspace::WorkspaceAction;
+use crate::suggest;
 
 #[derive(Parser)]
 #[command(name = "remindme")]
@@ -13,6 +22,30 @@ pub struct Cli {
     #[arg(short, long)]
     pub tui: bool,
     
+    /// With --tui, start with this reminder selected
+    #[arg(long, value_name = "ID", requires = "tui")]
+    pub focus: Option<String>,
+    
+    /// Use a custom reminders file, or ssh://host/path for one on a server (overrides REMINDME_STORAGE and the config file)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub storage_file: Option<String>,
+    
+    /// Use the team's shared reminders file (team.file in the config) instead of your own
+    #[arg(long, global = true, conflicts_with = "storage_file")]
+    pub shared: bool,
+    
+    /// Only read the reminders; commands that would change them fail right away (automatic when the file isn't writable)
+    #[arg(long, global = true)]
+    pub read_only: bool,
+    
+    /// Output for screen readers (orca, NVDA): no color-only cues, states and counts spelled out in words
+    #[arg(long, global = true)]
+    pub a11y: bool,
+    
+    /// Log what the program is doing to stderr (-v for info, -vv for debug, -vvv for trace)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+    
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -21,27 +54,175 @@ pub struct Cli {
 pub enum Commands {
     /// Add a new reminder
     Add {
-        /// Time of the reminder (HH:MM), date will default to today or tomorrow
+        /// Time of the reminder (17:00, 17, 5pm or 5:30 PM), date will default to today or tomorrow
+        /// (defaults to defaults.time in the config)
         #[arg(short, long)] // Change from -t to -i for time
-        time: String,
+        time: Option<String>,
         
         /// Text of the reminder
         #[arg(short = 'x', long)] // This will use -t by default based on first letter
         text: String,
 
-        /// Date of the reminder (YYYY-MM-DD), defaults to today/tomorrow based on time
+        /// Date of the reminder: YYYY-MM-DD, DD.MM.YYYY, MM/DD/YYYY, today, tomorrow, friday, next-friday or +3d
+        /// (defaults to today/tomorrow based on time)
         #[arg(short = 'd', long)]
         date: Option<String>,
+        
+        /// Kind of reminder; birthdays and anniversaries take --date as the original date and recur yearly
+        #[arg(long, value_enum, default_value = "reminder")]
+        kind: ReminderKind,
 
-        /// Recurrence pattern (none, daily, weekly, monthly, yearly)
-        #[arg(short, long, default_value = "none")]
-        recurrence: String,
+        /// Recurrence: none, daily, weekdays, weekly, monthly, last-day-of-month, yearly, or a phrase like
+        /// "every other tuesday" (defaults to defaults.recurrence in the config, or none)
+        #[arg(short, long)]
+        recurrence: Option<String>,
+        
+        /// What a recurring reminder does on weekends and holidays: keep, skip, or next (business day)
+        #[arg(long, value_enum, default_value = "keep")]
+        off_days: OffDayPolicy,
+        
+        /// When a recurring reminder moves to its next occurrence: once notified, or only when completed
+        #[arg(long, value_enum, default_value = "notify")]
+        advance: AdvanceOn,
+        
+        /// Keep notifying this often until completed, e.g. 10m or 2h
+        #[arg(long)]
+        renotify: Option<String>,
+        
+        /// Also send a heads-up this long before the due time (repeatable), e.g. --alert 1d --alert 1h
+        #[arg(long = "alert", value_parser = parse_alert)]
+        alerts: Vec<i64>,
+        
+        /// Archive it on `remind maintain` once overdue this long, e.g. 7d ("never" to keep it)
+        #[arg(long)]
+        expire_after: Option<String>,
+        
+        /// Tag the reminder (repeatable), e.g. --tag work
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        
+        /// GTD context where it can be done, e.g. --context @errands
+        #[arg(short, long)]
+        context: Option<String>,
+        
+        /// Who on the team it's for, in a shared reminders file
+        #[arg(long)]
+        assignee: Option<String>,
+        
+        /// Longer details, e.g. steps or links; `search` looks through them
+        #[arg(short, long)]
+        notes: Option<String>,
+        
+        /// An emoji to show before the text, or a desktop icon name for its notifications
+        #[arg(long)]
+        icon: Option<String>,
+        
+        /// Attach metadata for scripts and integrations (repeatable), e.g. --meta client=acme
+        #[arg(long, value_name = "KEY=VALUE", value_parser = parse_meta)]
+        meta: Vec<(String, String)>,
+        
+        /// Priority of the reminder (defaults to defaults.priority in the config, or medium)
+        #[arg(short, long, value_enum)]
+        priority: Option<Priority>,
+        
+        /// Raise the priority as the due time approaches (overrides priority.escalate in the config)
+        #[arg(long)]
+        escalate: Option<bool>,
+        
+        /// Add this as a new reminder once it's completed (repeatable, for a chain of steps), with --after
+        #[arg(long = "follow-up", value_name = "TEXT", requires = "after")]
+        follow_ups: Vec<String>,
+        
+        /// How long after completing the step before it each --follow-up is due (one per --follow-up), e.g. 3d
+        #[arg(long = "after", value_name = "DURATION", value_parser = parse_after, requires = "follow_ups")]
+        after: Vec<i64>,
+        
+        /// Print the next five occurrences instead of adding the reminder
+        #[arg(long)]
+        dry_run: bool,
+        
+        /// Add it even if other reminders are due around the same time
+        #[arg(short, long)]
+        force: bool,
+        
+        /// Add it even if --date and --time are in the past
+        #[arg(long)]
+        allow_past: bool,
+    },
+    
+    /// Add a one-off reminder relative to now, e.g. `remind in 25m "tea is ready"`
+    In {
+        /// How long from now: 25m, 1h30m, 90s, 2d (a bare number means minutes)
+        duration: String,
+        
+        /// Text of the reminder
+        text: String,
+    },
+    
+    /// Count down in the terminal, then notify like a due reminder
+    Timer {
+        /// How long to count down: 25m, 1h30m, 90s
+        duration: String,
+        
+        /// Text of the notification
+        #[arg(default_value = "Timer finished")]
+        text: String,
     },
 
     /// List all reminders
-    List,
+    #[command(visible_alias = "ls")]
+    List {
+        /// Custom output format, e.g. "{index}\t{due:%H:%M}\t{text}"
+        #[arg(short, long)]
+        format: Option<String>,
+        
+        /// Only show reminders that are due now (exits with status 3 if any)
+        #[arg(long)]
+        due_now: bool,
+        
+        /// Print nothing, only set the exit status
+        #[arg(short, long)]
+        quiet: bool,
+        
+        /// Only show reminders in this GTD context, e.g. @home
+        #[arg(short, long)]
+        context: Option<String>,
+        
+        /// Only show reminders assigned to this person ("me" for team.name or $USER)
+        #[arg(long)]
+        assignee: Option<String>,
+        
+        /// Show the reminders in sections by due day, week, tag or priority
+        #[arg(short, long, value_enum, conflicts_with = "format")]
+        group_by: Option<GroupBy>,
+        
+        /// Order by due time, priority, created or text (alphabetical); pinned reminders stay on top
+        #[arg(short, long, value_enum)]
+        sort: Option<SortOrder>,
+        
+        /// Show at most this many reminders
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+        
+        /// Skip this many reminders first, e.g. --offset 20 --limit 20 for the second page
+        #[arg(long, default_value_t = 0)]
+        offset: usize,
+        
+        /// Show reminders from every workspace, not only the one in use
+        #[arg(long)]
+        all: bool,
+        
+        /// Print pending reminders with `file` metadata as file:line:col:text, for :cexpr and grep tools
+        #[arg(long, conflicts_with_all = ["format", "group_by", "quickfix"])]
+        vimgrep: bool,
+        
+        /// Print pending reminders with `file` metadata as a JSON list of Vim quickfix items
+        #[arg(long, conflicts_with_all = ["format", "group_by"])]
+        quickfix: bool,
+    },
 
     /// Delete a reminder by ID or index
+    #[command(visible_alias = "rm")]
     Delete {
         /// ID of the reminder to delete
         #[arg(short, long, group = "delete_selector")]
@@ -53,42 +234,476 @@ pub enum Commands {
     },
     
     /// Edit an existing reminder
+    #[command(group(ArgGroup::new("changes").multiple(true).conflicts_with("editor")))]
     Edit {
         /// ID of the reminder to edit
+        #[arg(short, long, required_unless_present_any = ["all", "tag"])]
+        id: Option<String>,
+        
+        /// Edit the reminders as one document in $VISUAL or $EDITOR
         #[arg(short, long)]
-        id: String,
+        editor: bool,
+        
+        /// With --editor, edit every pending reminder
+        #[arg(long, requires = "editor", conflicts_with = "id")]
+        all: bool,
+        
+        /// With --editor, edit the pending reminders with this tag
+        #[arg(long, requires = "editor", conflicts_with = "id")]
+        tag: Option<String>,
         
         /// New text for the reminder
-        #[arg(short = 'x', long)]
+        #[arg(short = 'x', long, group = "changes")]
         text: Option<String>,
         
         /// New time for the reminder (YYYY-MM-DD HH:MM)
-        #[arg(short = 'd', long)]
+        #[arg(short = 'd', long, group = "changes")]
         time: Option<String>,
         
         /// New recurrence pattern
-        #[arg(short, long)]
+        #[arg(short, long, group = "changes")]
         recurrence: Option<String>,
+        
+        /// Make the reminder one-off, keeping its next due time
+        #[arg(long, conflicts_with = "recurrence", group = "changes")]
+        clear_recurrence: bool,
+        
+        /// Remove all tags
+        #[arg(long, group = "changes")]
+        clear_tags: bool,
+        
+        /// New weekend/holiday policy (keep, skip, next)
+        #[arg(long, value_enum, group = "changes")]
+        off_days: Option<OffDayPolicy>,
+        
+        /// Move to the next occurrence when notified or only when completed (notify, complete)
+        #[arg(long, value_enum, group = "changes")]
+        advance: Option<AdvanceOn>,
+        
+        /// How often to notify again while due, e.g. 10m ("off" for the default)
+        #[arg(long, group = "changes")]
+        renotify: Option<String>,
+        
+        /// Replace the heads-ups before the due time (repeatable), e.g. --alert 1d --alert 1h
+        #[arg(long = "alert", value_parser = parse_alert, group = "changes")]
+        alerts: Vec<i64>,
+        
+        /// Remove the heads-ups before the due time
+        #[arg(long, conflicts_with = "alerts", group = "changes")]
+        clear_alerts: bool,
+        
+        /// Archive it once overdue this long, e.g. 7d ("never" to keep it, "default" for maintenance.expire_after)
+        #[arg(long, group = "changes")]
+        expire_after: Option<String>,
+        
+        /// New GTD context, e.g. @home ("" clears it)
+        #[arg(short, long, group = "changes")]
+        context: Option<String>,
+        
+        /// Assign it to someone else ("" unassigns it)
+        #[arg(long, group = "changes")]
+        assignee: Option<String>,
+        
+        /// New notes ("" removes them)
+        #[arg(short, long, group = "changes")]
+        notes: Option<String>,
+        
+        /// Remove the notes
+        #[arg(long, conflicts_with = "notes", group = "changes")]
+        clear_notes: bool,
+        
+        /// New emoji or notification icon ("" clears it)
+        #[arg(long, group = "changes")]
+        icon: Option<String>,
+        
+        /// Set metadata (repeatable); an empty value removes the key, e.g. --meta client=
+        #[arg(long, value_name = "KEY=VALUE", value_parser = parse_meta, group = "changes")]
+        meta: Vec<(String, String)>,
+        
+        /// New priority
+        #[arg(short, long, value_enum, group = "changes")]
+        priority: Option<Priority>,
+        
+        /// Raise the priority as the due time approaches (true/false)
+        #[arg(long, group = "changes")]
+        escalate: Option<bool>,
+        
+        /// Replace the steps that follow it once completed (repeatable), with --after
+        #[arg(long = "follow-up", value_name = "TEXT", requires = "after", group = "changes")]
+        follow_ups: Vec<String>,
+        
+        /// How long after completing the step before it each --follow-up is due (one per --follow-up)
+        #[arg(long = "after", value_name = "DURATION", value_parser = parse_after, requires = "follow_ups")]
+        after: Vec<i64>,
+        
+        /// Remove the steps that would follow it
+        #[arg(long, conflicts_with = "follow_ups", group = "changes")]
+        clear_follow_ups: bool,
+        
+        /// Accept a new --time in the past
+        #[arg(long)]
+        allow_past: bool,
+    },
+    
+    /// List the next occurrences of a reminder
+    Occurrences {
+        /// ID of the reminder
+        #[arg(short, long)]
+        id: String,
+        
+        /// How many occurrences to show
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+    },
+    
+    /// Skip one date of a recurring reminder, like an iCalendar EXDATE
+    Except {
+        /// ID of the recurring reminder
+        #[arg(short, long)]
+        id: String,
+        
+        /// The date to skip, e.g. 2025-12-25
+        #[arg(short, long)]
+        date: String,
+        
+        /// Stop skipping the date
+        #[arg(long)]
+        remove: bool,
+    },
+    
+    /// Pin a reminder to the top of the list and the TUI
+    Pin {
+        /// ID of the reminder to pin
+        #[arg(short, long)]
+        id: String,
+        
+        /// Place among the pinned reminders, 1 being the top (default: below the others)
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+        position: Option<u32>,
+        
+        /// Unpin it instead
+        #[arg(long, conflicts_with = "position")]
+        undo: bool,
+    },
+    
+    /// Mark a reminder as completed; recurring reminders move on to their next occurrence
+    #[command(visible_alias = "done")]
+    Complete {
+        /// ID of the reminder to complete
+        #[arg(short, long)]
+        id: String,
+        
+        /// Mark it as not completed again
+        #[arg(long)]
+        undo: bool,
+    },
+    
+    /// Run a command, then complete the reminder if it succeeds or reschedule it if it fails
+    WhenDone {
+        /// ID of the reminder (or a unique start of it)
+        #[arg(short, long)]
+        id: String,
+        
+        /// When the command fails, the reminder is due again this much later, e.g. 30m or 1d
+        #[arg(long, default_value = "1h")]
+        retry_in: String,
+        
+        /// The command and its arguments, after --
+        #[arg(last = true, required = true, value_name = "COMMAND")]
+        command: Vec<String>,
     },
     
-    /// Check for due reminders and notify
+    /// Check for due reminders and notify (exits with status 3 if any were due)
     Notify {
         /// Send desktop notifications
         #[arg(short, long)]
         desktop: bool,
+        
+        /// Show what would be sent on each channel without sending or changing anything
+        #[arg(long)]
+        dry_run: bool,
     },
     
     /// Export reminders as JSON
-    Export,
+    Export {
+        /// Compress the output (zstd unless "gzip" is given); redirect it to a file
+        #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "zstd", value_name = "FORMAT", conflicts_with = "week")]
+        compress: Option<Compression>,
+        
+        /// json for the reminders as they're stored, html for a week view to open in a browser or print
+        #[arg(long, value_enum, default_value = "json")]
+        format: ExportFormat,
+        
+        /// With --format html: the week to show, by any day in it, e.g. 2025-06-03 or +1w (defaults to this week)
+        #[arg(long, num_args = 0..=1, default_missing_value = "today", value_name = "DATE")]
+        week: Option<String>,
+    },
     
     /// Show statistics about reminders
-    Stats,
+    Stats {
+        /// Print the statistics as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// Count reminders created, completed and overdue per day
+    Report {
+        /// First day (defaults to 29 days before --to)
+        #[arg(long)]
+        from: Option<String>,
+        
+        /// Last day (defaults to today)
+        #[arg(long)]
+        to: Option<String>,
+        
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: ReportFormat,
+    },
     
     /// Search for reminders
     Search {
-        /// Search query
+        /// Search query: words in the text, notes or tags, or meta.KEY=VALUE / meta.KEY to search metadata
         #[arg(short, long)]
         query: String,
+        
+        /// Show at most this many matches
+        #[arg(short, long)]
+        limit: Option<usize>,
+    },
+    
+    /// Sync the reminders file with another machine
+    Sync {
+        /// Commit to a git repository around the storage file and pull/push its remote
+        #[arg(long)]
+        git: bool,
+    },
+    
+    /// Merge another reminders file (e.g. a Syncthing/Dropbox conflict copy) into this one
+    Merge {
+        /// The other reminders file
+        file: String,
+        
+        /// Common ancestor of both files, for a true three-way merge
+        #[arg(short, long)]
+        base: Option<String>,
+        
+        /// Resolve conflicts automatically by keeping the most recently modified reminder
+        #[arg(long)]
+        newest: bool,
+        
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    
+    /// Serve a REST API for adding and managing reminders remotely
+    Serve {
+        /// Address to listen on (defaults to server.listen in config, 127.0.0.1:8675)
+        #[arg(short, long)]
+        listen: Option<String>,
+    },
+    
+    /// Answer newline-delimited JSON requests on stdin, for editor plugins and other programs
+    Api,
+    
+    /// Print a one-line summary of due and upcoming reminders for status bars
+    Status {
+        /// Output format for the bar program
+        #[arg(short, long, value_enum, default_value = "plain")]
+        format: StatusFormat,
+        
+        /// Count reminders due within this many hours as upcoming
+        #[arg(long, default_value_t = 24)]
+        hours: i64,
+    },
+    
+    /// Watch for important reminders and take over the terminal with a flashing alarm when one is due
+    Alarm {
+        /// Lowest priority that sounds the alarm
+        #[arg(short, long, value_enum, default_value = "high")]
+        priority: Priority,
+        
+        /// Seconds between checks
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+        
+        /// Minutes to snooze for
+        #[arg(long, default_value_t = 5)]
+        snooze: i64,
+    },
+    
+    /// Run pomodoro work/break cycles for a reminder: `remind pomodoro start --task ID`
+    Pomodoro {
+        /// start
+        action: String,
+        
+        /// ID of the reminder to work on
+        #[arg(long)]
+        task: String,
+        
+        /// Length of a work period
+        #[arg(long, default_value = "25m")]
+        work: String,
+        
+        /// Length of a break
+        #[arg(long = "break", default_value = "5m")]
+        rest: String,
+        
+        /// Number of work periods
+        #[arg(long, default_value_t = 4)]
+        cycles: u32,
+    },
+    
+    /// Walk through overdue and stale reminders to reschedule, complete or delete them
+    Review {
+        /// Reminders not touched for this many days count as stale
+        #[arg(long, default_value_t = 30)]
+        stale_days: i64,
+    },
+    
+    /// Add a reminder from a remind:// link, e.g. one clicked in a browser or an email
+    HandleUri {
+        /// The link, e.g. "remind://add?text=Call%20Sam&time=15:00"
+        #[arg(required_unless_present_any = ["install", "uninstall"])]
+        uri: Option<String>,
+        
+        /// Open remind:// links with this command from now on
+        #[arg(long, conflicts_with_all = ["uri", "uninstall"])]
+        install: bool,
+        
+        /// Stop opening remind:// links with this command
+        #[arg(long, conflicts_with = "uri")]
+        uninstall: bool,
+    },
+    
+    /// Find near-duplicate reminders, e.g. after importing twice, and merge or delete them
+    Dedupe {
+        /// Same text due at most this far apart counts as a duplicate, e.g. 30m or 1d
+        #[arg(long, default_value = "1h")]
+        within: String,
+        
+        /// Only list the duplicates
+        #[arg(long)]
+        dry_run: bool,
+    },
+    
+    /// Print a day's reminders as a sheet with checkboxes, for paper or a journal app
+    Print {
+        /// Today's sheet (the default)
+        #[arg(long, conflicts_with = "date")]
+        today: bool,
+        
+        /// Another day, e.g. tomorrow, 2025-06-03 or next-monday
+        #[arg(long)]
+        date: Option<String>,
+        
+        /// A Markdown task list instead of plain text
+        #[arg(long)]
+        markdown: bool,
+    },
+    
+    /// List pending reminders nobody has edited, snoozed or completed for a while
+    Stale {
+        /// Untouched for this many days (defaults to [stale] days, 14)
+        #[arg(long)]
+        days: Option<i64>,
+    },
+    
+    /// Check the config, storage, notification daemon, sound and systemd timer
+    Doctor,
+    
+    /// Stay in the foreground and notify the moment reminders fall due (instead of the systemd timer)
+    Daemon {
+        /// Send desktop notifications
+        #[arg(short, long)]
+        desktop: bool,
+    },
+    
+    /// Archive expired reminders and compact the journal
+    Maintain {
+        /// Show what would be archived without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    
+    /// Write a reminder as a signed bundle to send to someone else
+    Share {
+        /// ID of the reminder to share
+        #[arg(short, long)]
+        id: String,
+        
+        /// Bundle file to write (prints to stdout without it)
+        #[arg(short, long, value_name = "FILE")]
+        out: Option<String>,
+    },
+    
+    /// Add the reminder from a bundle made with `share`, or from a meeting invitation
+    #[command(group(ArgGroup::new("source").required(true).args(["file", "from_email"])))]
+    Import {
+        /// The bundle file
+        file: Option<String>,
+        
+        /// Import a bundle that isn't signed even though share.key is set
+        #[arg(long, conflicts_with = "from_email")]
+        allow_unsigned: bool,
+        
+        /// Email (.eml) or .ics file with a meeting invitation; an updated invitation reschedules the
+        /// reminder made from the first one, and a cancellation deletes it
+        #[arg(long, value_name = "FILE")]
+        from_email: Option<String>,
+        
+        /// Heads-up before the meeting (repeatable), in place of the invitation's alarms or the usual 15m
+        #[arg(long = "alert", value_parser = parse_alert, conflicts_with = "file")]
+        alerts: Vec<i64>,
+    },
+    
+    /// Print reminders for fzf/dmenu/rofi, or act on the line picked from them (read from stdin)
+    Pick {
+        /// What to do with the picked reminder; without it, print the lines to pick from
+        #[arg(short, long, value_enum)]
+        action: Option<PickAction>,
+        
+        /// Read just a reminder ID from stdin instead of a picked line
+        #[arg(long, requires = "action")]
+        stdin_id: bool,
+    },
+    
+    /// Turn do-not-disturb on or off, or on until a time (HH:MM); shows the status without arguments
+    Dnd {
+        /// on, off, or until
+        action: Option<String>,
+        
+        /// End time for `until` (HH:MM)
+        time: Option<String>,
+    },
+    
+    /// Stop the escalating alarm for urgent reminders: one reminder's, or every one ringing now
+    Ack {
+        /// Only this reminder's alarm
+        #[arg(short, long)]
+        id: Option<String>,
+    },
+    
+    /// List the notification sounds found, play one, or choose the one notifications play
+    Sound {
+        /// list (the default), test, or set
+        #[arg(value_enum)]
+        action: Option<SoundAction>,
+        
+        /// A sound file, or its number in `remind sound list`
+        sound: Option<String>,
+    },
+    
+    /// Switch the project `list` and `add` work in, without a separate reminders file
+    #[command(visible_alias = "ws")]
+    Workspace {
+        /// use, off, or list; shows the workspace in use without one
+        #[arg(value_enum)]
+        action: Option<WorkspaceAction>,
+        
+        /// The workspace to use, from [workspaces.NAME] in the config or any tag name
+        name: Option<String>,
     },
     
     /// Show help information
@@ -99,56 +714,461 @@ pub enum Commands {
     },
 }
 
-pub fn parse_datetime(datetime_str: &str) -> Result<DateTime<Local>> {
-    let naive_datetime = NaiveDateTime::parse_from_str(datetime_str, "%Y-%m-%d %H:%M")
-        .context("Invalid date time format. Expected YYYY-MM-DD HH:MM")?;
-    
-    let local_datetime = Local.from_local_datetime(&naive_datetime)
-        .single()
-        .context("Failed to convert to local datetime")?;
+impl Commands {
+    // Whether the command may change the reminders, so it's refused up front on read-only storage
+    pub fn writes(&self) -> bool {
+        match self {
+            Commands::List { .. }
+            | Commands::Occurrences { .. }
+            | Commands::Export { .. }
+            | Commands::Stats { .. }
+            | Commands::Report { .. }
+            | Commands::Search { .. }
+            | Commands::Status { .. }
+            | Commands::Share { .. }
+            | Commands::Doctor
+            | Commands::Api
+            | Commands::Stale { .. }
+            | Commands::Print { .. }
+            | Commands::Dnd { .. }
+            | Commands::Ack { .. }
+            | Commands::Sound { .. }
+            | Commands::Workspace { .. }
+            | Commands::Help { .. } => false,
+            Commands::Notify { dry_run, .. } | Commands::Merge { dry_run, .. } | Commands::Maintain { dry_run } | Commands::Dedupe { dry_run, .. } => !dry_run,
+            Commands::Pick { action, .. } => !matches!(action, None | Some(PickAction::Show)),
+            Commands::HandleUri { install, uninstall, .. } => !install && !uninstall,
+            _ => true,
+        }
+    }
+}
+
+// Output of `export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Json,
+    Html,
+}
+
+// Sections for `list --group-by`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GroupBy {
+    Day,
+    Week,
+    Tag,
+    Priority,
+}
+
+const TIME_FORMATS: &str = "17:00, 17, 5pm or 5:30 PM";
+
+// A date and a time separated by a space, e.g. "2025-06-01 17:00" or "01.06.2025 5:30 pm"
+pub fn parse_datetime(datetime_str: &str, order: DateOrder) -> Result<DateTime<Local>> {
+    let text = datetime_str.trim();
+    let Some((date, time)) = text.split_once(char::is_whitespace) else {
+        // Just a time, or an ISO timestamp like 2025-06-03T17:00
+        let suggestion = match text.split_once('T') {
+            Some((date, time)) => Some(format!("{} {}", date, time)),
+            None => parse_clock_time(text)
+                .or_else(|| suggest::time(text))
+                .map(|time| format!("{} {}", Local::now().format("%Y-%m-%d"), time.format("%H:%M"))),
+        };
+        return Err(invalid("date and time", text, None, suggestion, "a date and a time, e.g. 2025-06-03 17:00").into());
+    };
     
-    Ok(local_datetime)
+    let parsed_time = parse_clock_time(time);
+    let day = parse_day(date, parsed_time.unwrap_or(NaiveTime::MIN), order);
+    match (day, parsed_time) {
+        (Ok(day), Some(time)) => to_local(day.and_time(time)),
+        (Ok(day), None) => {
+            let understood = format!("read the date as {}", day.format("%a %Y-%m-%d"));
+            let suggestion = suggest::time(time).map(|time| format!("{} {}", day.format("%Y-%m-%d"), time.format("%H:%M")));
+            Err(invalid("time", time, Some(understood), suggestion, TIME_FORMATS).into())
+        }
+        (Err(_), Some(parsed)) => {
+            let understood = format!("read the time as {}", parsed.format("%H:%M"));
+            let suggestion = suggest::date(date, order).map(|date| format!("{} {}", date, parsed.format("%H:%M")));
+            Err(invalid("date", date, Some(understood), suggestion, &date_formats(order)).into())
+        }
+        (Err(_), None) => Err(invalid("date and time", text, None, None, "a date and a time, e.g. 2025-06-03 17:00").into()),
+    }
 }
 
-pub fn parse_datetime_with_default_date(time_str: &str, date_option: Option<&str>) -> Result<DateTime<Local>> {
+pub fn parse_datetime_with_default_date(time_str: &str, date_option: Option<&str>, order: DateOrder) -> Result<DateTime<Local>> {
     // Get current date/time
     let now = Local::now();
     
     // Parse the time part
-    let time_format = "%H:%M";
-    let naive_time = chrono::NaiveTime::parse_from_str(time_str, time_format)
-        .context("Invalid time format. Expected HH:MM")?;
+    let naive_time = parse_time(time_str)?;
     
     // If date is provided, use it
     if let Some(date_str) = date_option {
-        let date_time_str = format!("{} {}", date_str, time_str);
-        return parse_datetime(&date_time_str);
+        return to_local(parse_day(date_str, naive_time, order)?.and_time(naive_time));
     }
     
     // Otherwise use today's date
-    let today = now.date_naive();
-    let naive_datetime = today.and_time(naive_time);
-    
-    // Convert to DateTime<Local>
-    let mut local_datetime = Local.from_local_datetime(&naive_datetime)
-        .single()
-        .context("Failed to convert to local datetime")?;
+    let mut local_datetime = to_local(now.date_naive().and_time(naive_time))?;
     
     // If the time today has already passed, use tomorrow instead
     if local_datetime < now {
-        local_datetime = local_datetime + chrono::Duration::days(1);
+        local_datetime += chrono::Duration::days(1);
     }
     
     Ok(local_datetime)
 }
 
+// A date, or a keyword relative to today: today, tomorrow, a weekday, next-<weekday>, +3d or +2w.
+// A weekday is the next one whose `time` hasn't passed yet; next-<weekday> is the one a week after that.
+pub fn parse_day(text: &str, time: NaiveTime, order: DateOrder) -> Result<NaiveDate> {
+    let now = Local::now();
+    let today = now.date_naive();
+    let keyword = text.trim().to_lowercase();
+    
+    match keyword.as_str() {
+        "today" => return Ok(today),
+        "tomorrow" => return Ok(today + chrono::Duration::days(1)),
+        _ => {}
+    }
+    
+    if let Some(offset) = keyword.strip_prefix('+') {
+        let (count, unit) = match offset.strip_suffix('w') {
+            Some(weeks) => (weeks, 7),
+            None => (offset.strip_suffix('d').unwrap_or(offset), 1),
+        };
+        let count: i64 = count.parse().map_err(|_| invalid("date", text, None, None, "+3d or +2w"))?;
+        return Ok(today + chrono::Duration::days(count * unit));
+    }
+    
+    let (next, day) = match keyword.strip_prefix("next-") {
+        Some(day) => (true, day),
+        None => (false, keyword.as_str()),
+    };
+    let Some(weekday) = parse_weekday(day) else {
+        return parse_date(text, order);
+    };
+    
+    let ahead = (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+    let mut date = today + chrono::Duration::days(i64::from(ahead));
+    if date == today && time <= now.time() {
+        date += chrono::Duration::days(7);
+    }
+    if next {
+        date += chrono::Duration::days(7);
+    }
+    Ok(date)
+}
+
+// YYYY-MM-DD, DD.MM.YYYY, or a slash date read as MM/DD/YYYY or DD/MM/YYYY depending on `order`
+pub fn parse_date(text: &str, order: DateOrder) -> Result<NaiveDate> {
+    let text = text.trim();
+    let slashed = match order {
+        DateOrder::Mdy => "%m/%d/%Y",
+        DateOrder::Dmy => "%d/%m/%Y",
+    };
+    ["%Y-%m-%d", "%d.%m.%Y", slashed]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+        .ok_or_else(|| invalid("date", text, None, suggest::date(text, order), &date_formats(order)).into())
+}
+
+// 24-hour "17:00" or "17", or 12-hour "5pm" and "5:30 PM"
+pub fn parse_time(text: &str) -> Result<NaiveTime> {
+    parse_clock_time(text).ok_or_else(|| {
+        let suggestion = suggest::time(text).map(|time| time.format("%H:%M").to_string());
+        invalid("time", text, None, suggestion, TIME_FORMATS).into()
+    })
+}
+
+fn date_formats(order: DateOrder) -> String {
+    let slashed = match order {
+        DateOrder::Mdy => "MM/DD/YYYY",
+        DateOrder::Dmy => "DD/MM/YYYY",
+    };
+    format!("YYYY-MM-DD, DD.MM.YYYY, {}, today, tomorrow, friday, next-friday, +3d or +2w", slashed)
+}
+
+// "Invalid date 'x' (read the time as 17:00). Did you mean ...?" followed by what would have worked
+fn invalid(what: &str, text: &str, understood: Option<String>, suggestion: Option<String>, accepted: &str) -> RemindError {
+    let mut message = format!("Invalid {} '{}'", what, text.trim());
+    if let Some(understood) = understood {
+        message.push_str(&format!(" ({})", understood));
+    }
+    message.push('.');
+    if let Some(suggestion) = suggestion {
+        message.push_str(&format!(" Did you mean {}?", suggestion));
+    }
+    message.push_str(&format!("\n  Accepted: {}", accepted));
+    RemindError::InvalidInput(message)
+}
+
+fn to_local(naive: NaiveDateTime) -> Result<DateTime<Local>> {
+    Local
+        .from_local_datetime(&naive)
+        .single()
+        .ok_or_else(|| RemindError::InvalidInput("That time doesn't exist or is ambiguous in the local time zone".into()).into())
+}
+
+// A recurrence together with the time of day its phrase named, e.g. "weekdays at 9"
+pub struct Schedule {
+    pub recurrence: RecurrenceType,
+    pub time: Option<NaiveTime>,
+}
+
+impl Schedule {
+    // Give `reminder` this recurrence, at the phrase's time if it named one and moved onto a day
+    // the recurrence falls on; the occurrence it was on before no longer counts
+    pub fn apply(self, reminder: &mut Reminder, date_order: DateOrder) -> Result<()> {
+        if let Some(at) = self.time {
+            let date = reminder.due_time.format("%Y-%m-%d");
+            reminder.due_time = parse_datetime(&format!("{} {}", date, at.format("%H:%M")), date_order)?;
+        }
+        reminder.due_time = self.recurrence.align(reminder.due_time);
+        reminder.recurrence = self.recurrence;
+        reminder.nominal_due = None;
+        reminder.anchor = None;
+        Ok(())
+    }
+}
+
 pub fn parse_recurrence(recurrence_str: &str) -> Result<RecurrenceType> {
-    match recurrence_str.to_lowercase().as_str() {
-        "none" => Ok(RecurrenceType::None),
-        "daily" => Ok(RecurrenceType::Daily),
-        "weekly" => Ok(RecurrenceType::Weekly),
-        "monthly" => Ok(RecurrenceType::Monthly),
-        "yearly" => Ok(RecurrenceType::Yearly),
-        _ => Err(anyhow::anyhow!("Invalid recurrence type. Valid options are: none, daily, weekly, monthly, yearly"))
-    }
-}
\ No newline at end of file
+    Ok(parse_schedule(recurrence_str)?.recurrence)
+}
+
+// Keywords (daily, weekly, ...) or phrases like "every other tuesday" and "first monday of the month"
+pub fn parse_schedule(phrase: &str) -> Result<Schedule> {
+    let phrase = phrase.trim().to_lowercase();
+    let (pattern, time) = match phrase.rsplit_once(" at ") {
+        Some((pattern, time)) => {
+            let time = parse_clock_time(time).ok_or_else(|| {
+                let suggestion = suggest::time(time).map(|at| format!("\"{} at {}\"", pattern, at.format("%H:%M")));
+                invalid("time", time, Some(format!("in '{}'", phrase)), suggestion, "9, 9:30, 5pm or 17:00")
+            })?;
+            (pattern, Some(time))
+        }
+        None => (phrase.as_str(), None),
+    };
+    
+    let recurrence = parse_pattern(&pattern_words(pattern)).ok_or_else(|| {
+        // Only suggest a correction that would actually be understood
+        let suggestion = suggest::recurrence(pattern)
+            .filter(|fixed| parse_pattern(&pattern_words(fixed)).is_some())
+            .map(|fixed| format!("\"{}{}\"", fixed, &phrase[pattern.len()..]));
+        invalid(
+            "recurrence",
+            &phrase,
+            None,
+            suggestion,
+            "none, daily, weekdays, weekly, monthly, yearly, \
+             last-day-of-month, or phrases like \"every other tuesday\", \"2nd tuesday\", \"last friday\", \"last day\", \"weekdays at 9\"",
+        )
+    })?;
+    
+    Ok(Schedule { recurrence, time })
+}
+
+fn pattern_words(pattern: &str) -> Vec<&str> {
+    pattern.split_whitespace().filter(|w| !matches!(*w, "on" | "the")).collect()
+}
+
+fn parse_pattern(words: &[&str]) -> Option<RecurrenceType> {
+    match words {
+        [] | ["none"] | ["never"] => return Some(RecurrenceType::None),
+        ["daily"] => return Some(RecurrenceType::Daily),
+        ["weekdays"] | ["every", "weekday"] => return Some(RecurrenceType::Weekdays),
+        ["weekly"] => return Some(RecurrenceType::Weekly),
+        ["monthly"] | ["every", "month"] => return Some(RecurrenceType::Monthly),
+        ["last-day-of-month"] => return Some(RecurrenceType::MonthlyLastDay),
+        ["yearly"] | ["annually"] | ["every", "year"] => return Some(RecurrenceType::Yearly),
+        _ => {}
+    }
+    
+    // "every day", "every other day", "every 3 weeks", "every tuesday", "every 2 weeks on friday"
+    if let ["every", rest @ ..] = words {
+        let (interval, rest) = match rest.first().and_then(|w| parse_interval(w)) {
+            Some(interval) => (interval, &rest[1..]),
+            None => (1, rest),
+        };
+        return match rest {
+            [unit] if unit.trim_end_matches('s') == "day" => Some(match interval {
+                1 => RecurrenceType::Daily,
+                days => RecurrenceType::EveryDays(days),
+            }),
+            [unit] if unit.trim_end_matches('s') == "week" => Some(match interval {
+                1 => RecurrenceType::Weekly,
+                weeks => RecurrenceType::EveryDays(weeks * 7),
+            }),
+            [unit, day] if unit.trim_end_matches('s') == "week" => {
+                parse_weekday(day).map(|weekday| RecurrenceType::WeeklyOn { interval, weekday })
+            }
+            [day] => parse_weekday(day).map(|weekday| RecurrenceType::WeeklyOn { interval, weekday }),
+            _ => None,
+        };
+    }
+    
+    match words {
+        // "first monday of the month", "last friday of every month", or just "2nd tuesday", "last day";
+        // "monthly on the last friday" says the same
+        ["monthly", ordinal, day]
+        | [ordinal, day]
+        | [ordinal, day, "of", "month"]
+        | [ordinal, day, "of", "every" | "each", "month"] => {
+            let nth = match *ordinal {
+                "first" | "1st" => 1,
+                "second" | "2nd" => 2,
+                "third" | "3rd" => 3,
+                "fourth" | "4th" => 4,
+                "last" => -1,
+                _ => return None,
+            };
+            match *day {
+                "day" if nth == -1 => Some(RecurrenceType::MonthlyLastDay),
+                day => parse_weekday(day).map(|weekday| RecurrenceType::MonthlyOn { nth, weekday }),
+            }
+        }
+        // "tuesdays"
+        [day] => parse_weekday(day).map(|weekday| RecurrenceType::WeeklyOn { interval: 1, weekday }),
+        _ => None,
+    }
+}
+
+// "other" or a positive count like "3"
+fn parse_interval(word: &str) -> Option<u32> {
+    match word {
+        "other" => Some(2),
+        _ => word.parse().ok().filter(|n| *n > 0),
+    }
+}
+
+// "tuesday", "tuesdays" or "tue"
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    word.parse().ok().or_else(|| word.strip_suffix('s')?.parse().ok())
+}
+
+// "9", "9:30", "5pm", "17:00"
+fn parse_clock_time(text: &str) -> Option<NaiveTime> {
+    let text = text.trim().to_lowercase();
+    let (clock, pm) = match (text.strip_suffix("am"), text.strip_suffix("pm")) {
+        (Some(clock), _) => (clock.trim(), Some(false)),
+        (_, Some(clock)) => (clock.trim(), Some(true)),
+        _ => (text.as_str(), None),
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) => (hour.parse::<u32>().ok(), minute.parse::<u32>().ok()),
+        None => (clock.parse::<u32>().ok(), Some(0)),
+    };
+    
+    match (hour, minute, pm) {
+        (Some(hour), Some(minute), Some(pm)) if (1..=12).contains(&hour) => {
+            NaiveTime::from_hms_opt(hour % 12 + if pm { 12 } else { 0 }, minute, 0)
+        }
+        (Some(hour), Some(minute), None) => NaiveTime::from_hms_opt(hour, minute, 0),
+        _ => None,
+    }
+}
+
+// A re-notification interval in whole minutes; "off" means the default
+pub fn parse_renotify(text: &str) -> Result<Option<i64>> {
+    if text.eq_ignore_ascii_case("off") {
+        return Ok(None);
+    }
+    let minutes = parse_duration(text)?.num_minutes();
+    if minutes < 1 {
+        return Err(RemindError::InvalidInput("The re-notification interval must be at least a minute".into()).into());
+    }
+    Ok(Some(minutes))
+}
+
+// How long before the due time an --alert goes out, in whole minutes; "0m" is the due time itself
+pub fn parse_alert(text: &str) -> Result<i64> {
+    if text.trim().trim_end_matches(['s', 'm', 'h', 'd']).parse::<i64>() == Ok(0) {
+        return Ok(0);
+    }
+    let minutes = parse_duration(text)?.num_minutes();
+    if minutes < 1 {
+        return Err(RemindError::InvalidInput("--alert must be at least a minute before the due time, or 0m".into()).into());
+    }
+    Ok(minutes)
+}
+
+// How long after completing a reminder its --follow-up is due, in whole minutes
+pub fn parse_after(text: &str) -> Result<i64> {
+    let minutes = parse_duration(text)?.num_minutes();
+    if minutes < 0 {
+        return Err(RemindError::InvalidInput("--after can't be negative".into()).into());
+    }
+    Ok(minutes)
+}
+
+// The --follow-up steps paired with their --after delays
+pub fn follow_ups(texts: Vec<String>, after: &[i64]) -> Result<Vec<FollowUp>> {
+    if texts.len() != after.len() {
+        return Err(RemindError::InvalidInput(format!(
+            "Give one --after for each --follow-up ({} --follow-up, {} --after)",
+            texts.len(),
+            after.len()
+        ))
+        .into());
+    }
+    Ok(texts.into_iter().zip(after).map(|(text, after)| FollowUp { text, after_minutes: *after }).collect())
+}
+
+// How long a reminder may stay overdue before it's archived; "never" is stored as 0, "default" as None
+pub fn parse_expire_after(text: &str) -> Result<Option<i64>> {
+    match text.to_lowercase().as_str() {
+        "default" => return Ok(None),
+        "never" => return Ok(Some(0)),
+        _ => {}
+    }
+    let minutes = parse_duration(text)?.num_minutes();
+    if minutes < 1 {
+        return Err(RemindError::InvalidInput("--expire-after must be at least a minute, or \"never\"".into()).into());
+    }
+    Ok(Some(minutes))
+}
+
+// "key=value" for --meta; keys are letters, digits, '-', '_' and '.', so `meta.key` stays searchable
+pub fn parse_meta(text: &str) -> Result<(String, String)> {
+    let (key, value) = text.split_once('=').ok_or_else(|| {
+        RemindError::InvalidInput(format!("Invalid metadata '{}'. Expected KEY=VALUE, e.g. client=acme", text))
+    })?;
+    let key = key.trim();
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')) {
+        return Err(RemindError::InvalidInput(format!(
+            "Invalid metadata key '{}'. Use letters, digits, '-', '_' and '.'",
+            key
+        ))
+        .into());
+    }
+    Ok((key.to_string(), value.trim().to_string()))
+}
+
+// "25m", "1h30m", "90s", "2d"; a bare number is minutes
+pub fn parse_duration(text: &str) -> Result<chrono::Duration> {
+    let invalid = || RemindError::InvalidInput(format!("Invalid duration '{}'. Use e.g. 25m, 1h30m, 90s or 2d", text));
+    let text = text.trim().to_lowercase();
+    if let Ok(minutes) = text.parse::<i64>() {
+        return Ok(chrono::Duration::minutes(minutes));
+    }
+    
+    let mut total = chrono::Duration::zero();
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let amount: i64 = number.parse().map_err(|_| invalid())?;
+        number.clear();
+        total += match c {
+            's' => chrono::Duration::seconds(amount),
+            'm' => chrono::Duration::minutes(amount),
+            'h' => chrono::Duration::hours(amount),
+            'd' => chrono::Duration::days(amount),
+            _ => return Err(invalid().into()),
+        };
+    }
+    
+    if !number.is_empty() || total <= chrono::Duration::zero() {
+        return Err(invalid().into());
+    }
+    Ok(total)
+}
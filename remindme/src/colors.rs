@@ -0,0 +1,56 @@
+// Tag colors from `[display.tag_colors]` in config.toml
+//
+// A color is a name ("blue", "lightred"), a 256-color index ("208") or hex ("#ff8800"). They
+// are parsed the way ratatui does, so the TUI and the colored `list` output agree.
+
+use anyhow::Result;
+use ratatui::style::Color;
+use std::str::FromStr;
+
+use crate::config::DisplayConfig;
+use crate::error::RemindError;
+
+// The configured color for a tag; invalid colors are reported by `remind doctor` and ignored here
+pub fn tag_color(config: &DisplayConfig, tag: &str) -> Option<Color> {
+    config
+        .tag_colors
+        .iter()
+        .find(|(name, _)| name.trim_start_matches('#').eq_ignore_ascii_case(tag))
+        .and_then(|(_, color)| parse(color).ok())
+}
+
+pub fn parse(color: &str) -> Result<Color> {
+    Color::from_str(color).map_err(|_| {
+        RemindError::Config(format!(
+            "Invalid tag color '{}'. Expected a name like blue or lightred, a number from 0 to 255, or #rrggbb",
+            color
+        ))
+        .into()
+    })
+}
+
+// `text` in `color` for a terminal; only the foreground is reset afterwards, so bold survives
+pub fn paint(text: &str, color: Color) -> String {
+    let code = match color {
+        Color::Reset => return text.to_string(),
+        Color::Black => "30".to_string(),
+        Color::Red => "31".to_string(),
+        Color::Green => "32".to_string(),
+        Color::Yellow => "33".to_string(),
+        Color::Blue => "34".to_string(),
+        Color::Magenta => "35".to_string(),
+        Color::Cyan => "36".to_string(),
+        Color::Gray => "37".to_string(),
+        Color::DarkGray => "90".to_string(),
+        Color::LightRed => "91".to_string(),
+        Color::LightGreen => "92".to_string(),
+        Color::LightYellow => "93".to_string(),
+        Color::LightBlue => "94".to_string(),
+        Color::LightMagenta => "95".to_string(),
+        Color::LightCyan => "96".to_string(),
+        Color::White => "97".to_string(),
+        Color::Indexed(i) => format!("38;5;{}", i),
+        Color::Rgb(r, g, b) => format!("38;2;{};{};{}", r, g, b),
+    };
+    format!("\x1b[{}m{}\x1b[39m", code, text)
+}
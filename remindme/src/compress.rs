@@ -0,0 +1,51 @@
+// gzip and zstd compression for archives and exports
+//
+// Years of archived occurrences add up as pretty-printed JSON, which compresses very well.
+// Compressed data is recognized by its magic bytes, so readers never need to be told: the
+// archive keeps its name whatever its compression, and `merge` takes a compressed export as is.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+// zstd's default level; higher ones are much slower for little gain on JSON
+const ZSTD_LEVEL: i32 = 3;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+pub fn compress(data: &[u8], compression: Compression) -> Result<Vec<u8>> {
+    match compression {
+        Compression::None => Ok(data.to_vec()),
+        Compression::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish().context("Failed to gzip data")
+        }
+        Compression::Zstd => zstd::encode_all(data, ZSTD_LEVEL).context("Failed to compress data with zstd"),
+    }
+}
+
+// Undo whichever compression `data` has; anything else is returned unchanged
+pub fn decompress(data: Vec<u8>) -> Result<Vec<u8>> {
+    if data.starts_with(GZIP_MAGIC) {
+        let mut out = Vec::new();
+        GzDecoder::new(data.as_slice()).read_to_end(&mut out).context("Failed to decompress gzip data")?;
+        Ok(out)
+    } else if data.starts_with(ZSTD_MAGIC) {
+        zstd::decode_all(data.as_slice()).context("Failed to decompress zstd data")
+    } else {
+        Ok(data)
+    }
+}
@@ -0,0 +1,709 @@
+use anyhow::{anyhow, Result};
+use chrono::Weekday;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use crate::compress::Compression;
+use crate::error::RemindError;
+use crate::reminder::{Priority, SortOrder};
+
+// User configuration, read from ~/.config/remindme/config.toml
+// Every field has a default so a missing or partial file is fine
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub display: DisplayConfig,
+    pub input: InputConfig,
+    pub storage: StorageConfig,
+    pub sync: SyncConfig,
+    pub server: ServerConfig,
+    pub hooks: HooksConfig,
+    pub rules: RulesConfig,
+    pub notifications: NotificationConfig,
+    pub holidays: HolidaysConfig,
+    pub priority: PriorityConfig,
+    pub logging: LoggingConfig,
+    pub conflicts: ConflictsConfig,
+    pub maintenance: MaintenanceConfig,
+    pub stale: StaleConfig,
+    pub digest: DigestConfig,
+    pub busy: BusyConfig,
+    pub share: ShareConfig,
+    pub team: TeamConfig,
+    pub ntfy: NtfyConfig,
+    pub gotify: GotifyConfig,
+    pub matrix: MatrixConfig,
+    pub tui: TuiConfig,
+    pub defaults: DefaultsConfig,
+    /// Projects to switch between with `remind workspace use NAME` ([workspaces.NAME] tables)
+    pub workspaces: BTreeMap<String, WorkspaceConfig>,
+    /// Where reminders go by tag and priority ([[routes]] tables, the first match wins)
+    pub routes: Vec<RouteConfig>,
+    /// Custom commands, e.g. standup = "add --text 'Standup' --time 09:15 --recurrence daily"
+    pub alias: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// Show due times as "in 3h" / "2 days ago" instead of absolute timestamps
+    pub relative_times: bool,
+    /// Colors for tags in `list` and the TUI, e.g. work = "blue" or home = "#22aa55"
+    pub tag_colors: BTreeMap<String, String>,
+    /// First day of the week for `list --group-by week`: "monday" or "sunday"
+    pub week_start: WeekStart,
+    /// "24h" for 17:30 or "12h" for 5:30 PM
+    pub clock: Clock,
+    /// Locale for month and weekday names, e.g. "de_DE" (defaults to LC_ALL, LC_TIME or LANG)
+    pub locale: Option<String>,
+    /// Language for messages, e.g. "de" (defaults to LC_ALL, LC_MESSAGES or LANG; "en" for English)
+    pub language: Option<String>,
+    /// Output for screen readers: words instead of colors and symbols (same as --a11y)
+    pub a11y: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    #[default]
+    Monday,
+    Sunday,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Clock {
+    #[default]
+    #[serde(rename = "24h")]
+    TwentyFour,
+    #[serde(rename = "12h")]
+    Twelve,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputConfig {
+    /// How to read dates written with slashes: "mdy" for 06/01/2025 as June 1st, "dmy" for January 6th
+    pub date_order: DateOrder,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateOrder {
+    /// MM/DD/YYYY
+    #[default]
+    Mdy,
+    /// DD/MM/YYYY
+    Dmy,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// Custom reminders file, e.g. in a synced folder or ssh://host/path/reminders.json on a server
+    /// (overridden by REMINDME_STORAGE and --storage-file)
+    pub file: Option<String>,
+    /// Encrypt the reminders file on disk
+    pub encryption: EncryptionMode,
+    /// Key file used when `encryption = "keyfile"`
+    pub key_file: Option<String>,
+    /// Append changes to a journal instead of rewriting the whole file (ignored when encrypted)
+    pub journal: bool,
+    /// Fold the journal back into the reminders file once it has this many entries
+    pub compact_after: usize,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        StorageConfig {
+            file: None,
+            encryption: EncryptionMode::None,
+            key_file: None,
+            journal: true,
+            compact_after: 200,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EncryptionMode {
+    #[default]
+    None,
+    /// Passphrase from REMINDME_PASSPHRASE or an interactive prompt
+    Passphrase,
+    /// Secret read from `storage.key_file`
+    KeyFile,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncConfig {
+    /// Remote URL or name used by `sync --git` (defaults to the repository's `origin`)
+    pub git_remote: Option<String>,
+    /// Remote branch to pull from and push to (defaults to `main`)
+    pub git_branch: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    /// Address for `remind serve` (overridden by --listen)
+    pub listen: String,
+    /// Bearer token required by the API (REMINDME_API_TOKEN takes precedence)
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct HooksConfig {
+    /// Directory of hook scripts (defaults to ~/.config/remindme/hooks)
+    pub dir: Option<String>,
+    /// Shell commands run after a reminder is added
+    pub on_add: Vec<String>,
+    /// Shell commands run when a reminder is notified as due
+    pub on_due: Vec<String>,
+    /// Shell commands run when a route sends a heads-up before a reminder is due
+    pub on_upcoming: Vec<String>,
+    /// Shell commands run when a reminder is marked completed
+    pub on_complete: Vec<String>,
+    /// Shell commands run after a reminder is deleted
+    pub on_delete: Vec<String>,
+    /// Stop a hook or routed command still running after this many seconds (0 waits forever)
+    pub timeout_seconds: u64,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        HooksConfig {
+            dir: None,
+            on_add: Vec::new(),
+            on_due: Vec::new(),
+            on_upcoming: Vec::new(),
+            on_complete: Vec::new(),
+            on_delete: Vec::new(),
+            timeout_seconds: 30,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RulesConfig {
+    /// Rhai rules script (defaults to ~/.config/remindme/rules.rhai)
+    pub file: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PriorityConfig {
+    /// Raise priority as the due time approaches: high within `high_within_hours`, urgent once overdue
+    pub escalate: bool,
+    /// How close to the due time a reminder counts as high priority when escalating
+    pub high_within_hours: i64,
+}
+
+impl Default for PriorityConfig {
+    fn default() -> Self {
+        PriorityConfig {
+            escalate: false,
+            high_within_hours: 24,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DefaultsConfig {
+    /// Time for `add` and the TUI form when none is given, e.g. "09:00"
+    pub time: Option<String>,
+    /// Recurrence when none is given, e.g. "weekdays" (otherwise "none")
+    pub recurrence: Option<String>,
+    /// Priority when none is given: low, medium, high or urgent (otherwise medium)
+    pub priority: Option<String>,
+    /// Tags every new reminder gets, on top of any given, e.g. ["inbox"]
+    pub tags: Vec<String>,
+}
+
+impl DefaultsConfig {
+    pub fn priority(&self) -> Result<Priority> {
+        match &self.priority {
+            Some(priority) => Priority::from_str(priority, true).map_err(|_| {
+                RemindError::Config(format!("Unknown defaults.priority '{}'. Use low, medium, high or urgent", priority)).into()
+            }),
+            None => Ok(Priority::Medium),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceConfig {
+    /// Tags new reminders get while the workspace is in use, e.g. ["projectx"]
+    pub tags: Vec<String>,
+    /// `list` shows only reminders with one of these tags (defaults to `tags`)
+    pub filter: Option<Vec<String>>,
+    /// GTD context new reminders get, and the only one `list` shows, e.g. "@office"
+    pub context: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConflictsConfig {
+    /// Warn when adding a reminder due within this many minutes of a pending one (0 turns it off)
+    pub window_minutes: i64,
+}
+
+impl Default for ConflictsConfig {
+    fn default() -> Self {
+        ConflictsConfig { window_minutes: 15 }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MaintenanceConfig {
+    /// Archive one-off reminders left overdue for longer than this, e.g. "30d" (off by default)
+    pub expire_after: Option<String>,
+    /// Compress the archive: "none", "gzip" or "zstd"; an existing archive is converted on the next write
+    pub compression: Compression,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StaleConfig {
+    /// Pending reminders nobody has edited, snoozed or completed for this many days are stale
+    pub days: i64,
+    /// Send a weekly notification summarizing the stale reminders (off by default)
+    pub digest: bool,
+    /// Day of the week for the digest, e.g. "monday"
+    pub digest_day: String,
+    /// Time of day for the digest, e.g. "09:00"
+    pub digest_time: String,
+}
+
+impl Default for StaleConfig {
+    fn default() -> Self {
+        StaleConfig {
+            days: 14,
+            digest: false,
+            digest_day: "monday".to_string(),
+            digest_time: "09:00".to_string(),
+        }
+    }
+}
+
+impl StaleConfig {
+    pub fn digest_weekday(&self) -> Result<Weekday> {
+        self.digest_day.trim().parse().map_err(|_| {
+            RemindError::Config(format!("Unknown stale.digest_day '{}'. Use a weekday like monday", self.digest_day)).into()
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DigestConfig {
+    /// Time of day (HH:MM) to send one summary of the day's reminders, e.g. "07:30" (off by default)
+    pub time: Option<String>,
+    /// Show the summary as a notification
+    pub desktop: bool,
+    /// Also email the summary to this address
+    pub email: Option<String>,
+    /// Command that sends the email, reading the message with its headers on stdin
+    pub sendmail: String,
+    /// Send it on days with nothing scheduled too
+    pub when_empty: bool,
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        DigestConfig {
+            time: None,
+            desktop: true,
+            email: None,
+            sendmail: "sendmail -t".to_string(),
+            when_empty: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BusyConfig {
+    /// Calendar whose meetings hold back reminders that aren't urgent until they end: an iCal
+    /// address (https:// or webcal://), such as a Google calendar's secret address or a CalDAV
+    /// calendar's export link, or the path of an .ics file (off by default)
+    pub calendar: Option<String>,
+    /// How often to fetch the calendar again, in minutes
+    pub refresh_minutes: u32,
+    /// Your address in the calendar's invitations; meetings you declined don't count
+    pub email: Option<String>,
+}
+
+impl Default for BusyConfig {
+    fn default() -> Self {
+        BusyConfig {
+            calendar: None,
+            refresh_minutes: 15,
+            email: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShareConfig {
+    /// Secret shared with the people you exchange bundles with; signs `remind share` bundles and
+    /// checks imported ones (REMINDME_SHARE_KEY takes precedence)
+    pub key: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TeamConfig {
+    /// Reminders file shared with a team, e.g. an on-call rota in a git repo; `--shared` uses it
+    /// instead of your own file
+    pub file: Option<String>,
+    /// Your name as reminders are assigned to it (defaults to $USER); in the shared file you're
+    /// only notified of reminders assigned to you or to nobody
+    pub name: Option<String>,
+}
+
+impl TeamConfig {
+    // Who "me" is in `--assignee me`
+    pub fn me(&self) -> Option<String> {
+        self.name.clone().or_else(|| std::env::var("USER").ok()).filter(|name| !name.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NtfyConfig {
+    /// Topic due reminders are published to; ntfy is off until one is set. Anyone who knows
+    /// the topic can subscribe, so pick one that's hard to guess
+    pub topic: Option<String>,
+    /// ntfy server, for a self-hosted one
+    pub server: String,
+    /// Access token for a protected topic
+    pub token: Option<String>,
+    /// Only reminders with one of these tags go to ntfy (empty sends every due reminder)
+    pub tags: Vec<String>,
+    /// ntfy priority (1-5) for each reminder priority; unset ones are low = 2, medium = 3, high = 4, urgent = 5
+    pub priorities: PriorityLevels,
+    /// Address where the phone reaches `remind serve`, e.g. "https://home.example.org:8675";
+    /// adds a "Done" button that completes the reminder
+    pub api_url: Option<String>,
+}
+
+impl Default for NtfyConfig {
+    fn default() -> Self {
+        NtfyConfig {
+            topic: None,
+            server: "https://ntfy.sh".to_string(),
+            token: None,
+            tags: Vec::new(),
+            priorities: PriorityLevels::default(),
+            api_url: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GotifyConfig {
+    /// Gotify server, e.g. "https://gotify.example.org"; Gotify is off until this and a token are set
+    pub server: Option<String>,
+    /// Token of the Gotify application messages come from (REMINDME_GOTIFY_TOKEN takes precedence)
+    pub token: Option<String>,
+    /// Only reminders with one of these tags go to Gotify (empty sends every due reminder)
+    pub tags: Vec<String>,
+    /// Gotify priority (0-10) for each reminder priority; unset ones are low = 2, medium = 5, high = 7, urgent = 10
+    pub priorities: PriorityLevels,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MatrixConfig {
+    /// Homeserver URL, e.g. "https://matrix.org"; Matrix is off until this, a token and a room are set
+    pub homeserver: Option<String>,
+    /// Access token of the account that posts (REMINDME_MATRIX_TOKEN takes precedence)
+    pub access_token: Option<String>,
+    /// Room to post in, e.g. "!AbCdEf:matrix.org"; the account must have joined it
+    pub room_id: Option<String>,
+    /// Only reminders with one of these tags go to Matrix (empty sends every due reminder)
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TuiConfig {
+    /// "list", "split" for overdue reminders in a pane above the upcoming ones, or "calendar" for a
+    /// month calendar above the list; `s` cycles through them in the TUI
+    pub layout: TuiLayout,
+    /// Height of the top pane in "split" and "calendar", as a percentage; `+` and `-` change it
+    pub ratio: u16,
+    /// Order of the list: "due", "priority", "created" or "text"; `o` cycles through them
+    pub sort: SortOrder,
+    /// Minutes before its due time that a reminder's countdown turns yellow; overdue ones are red
+    pub soon_minutes: i64,
+}
+
+impl Default for TuiConfig {
+    fn default() -> Self {
+        TuiConfig { layout: TuiLayout::List, ratio: 40, sort: SortOrder::Created, soon_minutes: 60 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TuiLayout {
+    #[default]
+    List,
+    Split,
+    Calendar,
+}
+
+// One [[routes]] entry: which reminders it matches and how they're delivered
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RouteConfig {
+    /// Reminders with one of these tags match (empty matches any tags)
+    pub tags: Vec<String>,
+    /// Reminders at least this important match, e.g. "high" for high and urgent ones
+    pub priority: Option<String>,
+    /// Where matching reminders go: "terminal", "desktop", "ntfy", "gotify", "matrix" or a shell
+    /// command (empty keeps the default channels)
+    pub channels: Vec<String>,
+    /// Send a heads-up this long before the due time, e.g. "15m"
+    pub warn: Option<String>,
+    /// Notify again this often until completed, e.g. "5m", for reminders without their own --renotify
+    pub nag: Option<String>,
+}
+
+// A push service's own priority for each reminder priority
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PriorityLevels {
+    pub low: Option<u8>,
+    pub medium: Option<u8>,
+    pub high: Option<u8>,
+    pub urgent: Option<u8>,
+}
+
+impl PriorityLevels {
+    // The level set for `priority`, or the service's default from `defaults` (low, medium, high, urgent)
+    pub fn level(&self, priority: Priority, defaults: [u8; 4]) -> u8 {
+        let (level, default) = match priority {
+            Priority::Low => (self.low, defaults[0]),
+            Priority::Medium => (self.medium, defaults[1]),
+            Priority::High => (self.high, defaults[2]),
+            Priority::Urgent => (self.urgent, defaults[3]),
+        };
+        level.unwrap_or(default)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Also log to a file in ~/.local/share/remindme/logs, rotated daily
+    pub file: bool,
+    /// Level for the log file: "error", "warn", "info", "debug" or "trace"
+    pub level: String,
+    /// How many daily log files to keep
+    pub keep_files: usize,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        LoggingConfig {
+            file: false,
+            level: "info".to_string(),
+            keep_files: 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HolidaysConfig {
+    /// Built-in public holiday lists to observe: "us", "uk", "de"
+    pub regions: Vec<String>,
+    /// Extra days off as YYYY-MM-DD
+    pub dates: Vec<String>,
+    /// ICS calendar whose all-day events count as holidays
+    pub ics: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationConfig {
+    /// Extra broadcasts when desktop notifications are unavailable (e.g. over SSH): "tmux", "wall"
+    pub fallback_broadcast: Vec<Broadcast>,
+    /// Show one summary popup instead of individual ones when more than this many are due (0 never groups)
+    pub group_threshold: usize,
+    /// Most desktop popups per minute; the rest wait for the next run (0 for no limit)
+    pub max_per_minute: usize,
+    /// Daily do-not-disturb window, e.g. "22:00-07:00"; only urgent reminders get through
+    pub quiet_hours: Option<String>,
+    /// Time of day (HH:MM) to notify about birthdays, anniversaries and other all-day reminders
+    pub all_day_time: String,
+    /// What to do about recurring occurrences missed while nothing was running: "once", "each" or "skip"
+    pub catch_up: CatchUpPolicy,
+    /// With `catch_up = "skip"`, occurrences overdue by more than this many minutes count as missed
+    pub missed_after_minutes: i64,
+    /// Give reminder popups an "Open" button that opens the TUI on that reminder
+    pub open_action: bool,
+    /// Terminal the "Open" button starts; the remind command line is appended to it.
+    /// Empty opens Terminal.app on macOS and a console window on Windows
+    pub terminal: String,
+    /// Sound played with each popup: "default", "none", or a system sound on macOS ("Glass") or
+    /// Windows ("Reminder"); on Linux it's `sound_file` unless "none"
+    pub sound: String,
+    /// Sound file played with popups on Linux and Termux, instead of the first one found in the
+    /// usual places; REMINDME_SOUND overrides it
+    pub sound_file: Option<String>,
+    /// Volume of the sound file, from 0.0 (silent) to 1.0 (as recorded); more makes it louder.
+    /// Termux plays it at the phone's volume
+    pub volume: f32,
+    /// Times the sound file plays in a row for an urgent reminder
+    pub urgent_repeat: u32,
+    /// Seconds between rings of an escalating alarm: with `remind daemon` and `remind alarm`, an
+    /// urgent reminder's sound repeats, louder each time, until it's acknowledged (0 rings once)
+    pub escalate_every: u64,
+    /// Volume an escalating alarm rises to, from `volume`
+    pub escalate_volume: f32,
+    /// Identity popups are shown under: a bundle identifier on macOS, an AppUserModelID on Windows
+    pub app_id: Option<String>,
+    /// Vibrate with each popup on Termux
+    pub vibrate: bool,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        NotificationConfig {
+            fallback_broadcast: Vec::new(),
+            group_threshold: 3,
+            max_per_minute: 10,
+            quiet_hours: None,
+            all_day_time: "09:00".to_string(),
+            catch_up: CatchUpPolicy::Once,
+            missed_after_minutes: 60,
+            open_action: true,
+            terminal: default_terminal().to_string(),
+            sound: "default".to_string(),
+            sound_file: None,
+            volume: 1.0,
+            urgent_repeat: 1,
+            escalate_every: 0,
+            escalate_volume: 2.0,
+            app_id: None,
+            vibrate: true,
+        }
+    }
+}
+
+fn default_terminal() -> &'static str {
+    if cfg!(any(target_os = "macos", windows)) { "" } else { "x-terminal-emulator -e" }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CatchUpPolicy {
+    /// Notify once, then move on to the next future occurrence
+    #[default]
+    Once,
+    /// Notify every missed occurrence, one per run
+    Each,
+    /// Don't notify missed occurrences at all
+    Skip,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Broadcast {
+    /// `tmux display-message` on the attached tmux client
+    Tmux,
+    /// `wall` to every logged-in terminal
+    Wall,
+}
+
+impl ServerConfig {
+    // The API token: REMINDME_API_TOKEN, then server.token
+    pub fn token(&self) -> Option<String> {
+        std::env::var("REMINDME_API_TOKEN")
+            .ok()
+            .filter(|t| !t.is_empty())
+            .or_else(|| self.token.clone())
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            listen: "127.0.0.1:8675".to_string(),
+            token: None,
+        }
+    }
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        DisplayConfig {
+            relative_times: true,
+            tag_colors: BTreeMap::new(),
+            week_start: WeekStart::Monday,
+            clock: Clock::TwentyFour,
+            locale: None,
+            language: None,
+            a11y: false,
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+
+        let contents = fs::read_to_string(&path)
+            .map_err(RemindError::access(&path))?;
+
+        let config: Config = toml::from_str(&contents)
+            .map_err(|e| RemindError::Config(format!("Failed to parse config file {}: {}", path.display(), e)))?;
+
+        Ok(config)
+    }
+
+    // Set `key` in `[section]` of config.toml, leaving the rest of the file, comments included, as it is
+    pub fn set_value(section: &str, key: &str, value: impl Into<toml_edit::Value>) -> Result<()> {
+        let path = Self::path()?;
+        let contents = if path.exists() {
+            fs::read_to_string(&path).map_err(RemindError::access(&path))?
+        } else {
+            String::new()
+        };
+        let mut document: toml_edit::DocumentMut = contents
+            .parse()
+            .map_err(|e| RemindError::Config(format!("Failed to parse config file {}: {}", path.display(), e)))?;
+        document[section][key] = toml_edit::value(value);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(RemindError::access(parent))?;
+        }
+        fs::write(&path, document.to_string()).map_err(RemindError::access(&path))?;
+        Ok(())
+    }
+
+    pub fn path() -> Result<PathBuf> {
+        let config_dir = dirs::config_dir()
+            .ok_or_else(|| anyhow!("Failed to determine config directory"))?
+            .join("remindme");
+
+        Ok(config_dir.join("config.toml"))
+    }
+}
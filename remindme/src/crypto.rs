@@ -0,0 +1,142 @@
+// Optional encryption of the storage file
+//
+// Encrypted files are laid out as: MAGIC | salt (16 bytes) | nonce (12 bytes) | ciphertext.
+// The key is derived from a passphrase or key file with Argon2id and the data is sealed
+// with ChaCha20-Poly1305, so tampering or a wrong key is detected on decryption.
+
+use anyhow::{anyhow, Context, Result};
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use std::cell::{OnceCell, RefCell};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::{EncryptionMode, StorageConfig};
+
+const MAGIC: &[u8] = b"REMINDME-ENC1\n";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+enum KeySource {
+    Passphrase,
+    KeyFile(PathBuf),
+}
+
+pub struct Encryption {
+    source: KeySource,
+    secret: OnceCell<Vec<u8>>,
+    // Key derivation is deliberately slow, so remember the last (salt, key) pair
+    cached_key: RefCell<Option<([u8; SALT_LEN], [u8; 32])>>,
+}
+
+impl Encryption {
+    pub fn from_config(config: &StorageConfig) -> Result<Option<Self>> {
+        let source = match config.encryption {
+            EncryptionMode::None => return Ok(None),
+            EncryptionMode::Passphrase => KeySource::Passphrase,
+            EncryptionMode::KeyFile => {
+                let path = config.key_file.as_ref().ok_or_else(|| {
+                    anyhow!("storage.encryption is \"keyfile\" but storage.key_file is not set")
+                })?;
+                KeySource::KeyFile(PathBuf::from(shellexpand::tilde(path).to_string()))
+            }
+        };
+
+        Ok(Some(Encryption {
+            source,
+            secret: OnceCell::new(),
+            cached_key: RefCell::new(None),
+        }))
+    }
+
+    pub fn is_encrypted(data: &[u8]) -> bool {
+        data.starts_with(MAGIC)
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        // Reuse the salt from the last load/save so the cached key stays valid
+        let salt = match *self.cached_key.borrow() {
+            Some((salt, _)) => salt,
+            None => {
+                let mut salt = [0u8; SALT_LEN];
+                OsRng.fill_bytes(&mut salt);
+                salt
+            }
+        };
+
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&self.key_for(&salt)?));
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext)
+            .map_err(|_| anyhow!("Failed to encrypt reminders"))?;
+
+        let mut out = Vec::with_capacity(MAGIC.len() + SALT_LEN + NONCE_LEN + ciphertext.len());
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&salt);
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&ciphertext);
+        Ok(out)
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let body = data
+            .strip_prefix(MAGIC)
+            .filter(|body| body.len() >= SALT_LEN + NONCE_LEN)
+            .ok_or_else(|| anyhow!("Encrypted reminders file is truncated or corrupt"))?;
+
+        let (salt, rest) = body.split_at(SALT_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+        let mut salt_bytes = [0u8; SALT_LEN];
+        salt_bytes.copy_from_slice(salt);
+
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&self.key_for(&salt_bytes)?));
+        cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow!("Failed to decrypt reminders: wrong passphrase/key file or corrupted data"))
+    }
+
+    fn key_for(&self, salt: &[u8; SALT_LEN]) -> Result<[u8; 32]> {
+        if let Some((cached_salt, key)) = *self.cached_key.borrow()
+            && &cached_salt == salt
+        {
+            return Ok(key);
+        }
+
+        let mut key = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(self.secret()?, salt, &mut key)
+            .map_err(|e| anyhow!("Failed to derive encryption key: {}", e))?;
+
+        *self.cached_key.borrow_mut() = Some((*salt, key));
+        Ok(key)
+    }
+
+    fn secret(&self) -> Result<&[u8]> {
+        if let Some(secret) = self.secret.get() {
+            return Ok(secret);
+        }
+
+        let secret = match &self.source {
+            KeySource::Passphrase => match std::env::var("REMINDME_PASSPHRASE") {
+                Ok(passphrase) if !passphrase.is_empty() => passphrase.into_bytes(),
+                _ => rpassword::prompt_password("RemindMe passphrase: ")
+                    .context("Failed to read passphrase (set REMINDME_PASSPHRASE for non-interactive use)")?
+                    .into_bytes(),
+            },
+            KeySource::KeyFile(path) => {
+                let contents = fs::read(path)
+                    .with_context(|| format!("Failed to read key file {}", path.display()))?;
+                contents.trim_ascii().to_vec()
+            }
+        };
+
+        if secret.is_empty() {
+            return Err(anyhow!("Encryption passphrase/key file must not be empty"));
+        }
+
+        Ok(self.secret.get_or_init(|| secret))
+    }
+}
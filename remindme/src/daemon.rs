@@ -0,0 +1,173 @@
+// Long-running notifier (`remind daemon`)
+//
+// An alternative to the systemd timer, which starts `remind notify` once a minute. The daemon
+// wakes up when the next reminder falls due instead, on a single-threaded tokio runtime:
+//   - the scheduler sleeps until the earliest due time in the index, but never longer than a
+//     minute, so re-notifications, custom rules and the end of do-not-disturb still come round
+//   - the watcher polls the reminders file and its journal, and wakes the scheduler when
+//     they change, e.g. after `remind add` from another terminal or a sync
+//   - each notify run goes to a blocking thread, so a slow channel never holds up the
+//     scheduler; a run still going when the next one is due makes that one wait
+//   - urgent reminders a run notified keep ringing, louder each time, until acknowledged, when
+//     notifications.escalate_every is set (see escalation.rs)
+//
+// Config changes need a restart.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::{mpsc, Notify};
+use tokio::task::{self, JoinHandle};
+use tokio::time;
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::escalation::{Acknowledged, Ringing};
+use crate::journal;
+use crate::notification::Notifier;
+use crate::reminder::Reminder;
+use crate::storage::Storage;
+
+// Longest sleep between runs, as often as the systemd timer would run `notify`
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+// How often the watcher checks the reminders file for changes
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+struct Scheduler {
+    storage_file: Option<String>,
+    config: Config,
+    desktop: bool,
+    // Reminders due up to here were seen by the last run
+    last_run: Option<DateTime<Local>>,
+    running: Option<JoinHandle<Result<usize>>>,
+    ringing: Ringing,
+}
+
+pub fn run(storage_file: Option<String>, config: Config, desktop: bool) -> Result<()> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .context("Failed to start the async runtime")?;
+    let scheduler = Scheduler { storage_file, config, desktop, last_run: None, running: None, ringing: Ringing::default() };
+    runtime.block_on(scheduler.run())
+}
+
+impl Scheduler {
+    async fn run(mut self) -> Result<()> {
+        let storage = Storage::open(self.storage_file.as_deref(), &self.config)?;
+        let changed = Arc::new(Notify::new());
+        let mut watched = vec![storage.path().to_path_buf(), journal::path_for(storage.path())];
+        // So an alarm stops as soon as it's acknowledged
+        watched.extend(Acknowledged::path().ok());
+        tokio::spawn(watch(watched, changed.clone()));
+        println!("Watching {} for due reminders. Press Ctrl+C to stop.", storage.path().display());
+
+        // Reminders each run notified, sent as soon as they're out rather than when the run ends
+        let (notified, mut received) = mpsc::unbounded_channel();
+        loop {
+            let sleep = self.next_wake(&storage);
+            debug!(seconds = sleep.as_secs(), "Sleeping until the next reminder");
+            tokio::select! {
+                _ = time::sleep(sleep) => self.dispatch(notified.clone()).await,
+                // Something was added or rescheduled; it may be due sooner
+                _ = changed.notified() => debug!("Reminders changed"),
+                Some(reminders) = received.recv() => self.ringing.add(&reminders, &self.config),
+            }
+            if let Err(e) = self.ringing.update(&storage) {
+                warn!(error = %format!("{:#}", e), "Failed to check for acknowledged alarms");
+            }
+        }
+    }
+
+    // Time until the earliest reminder or --alert the last run hasn't seen yet, up to MAX_SLEEP
+    fn next_wake(&self, storage: &Storage) -> Duration {
+        if self.running.as_ref().is_some_and(|running| !running.is_finished()) {
+            return Duration::from_secs(1);
+        }
+        let Some(last_run) = self.last_run else {
+            return Duration::ZERO;
+        };
+        let entries = match storage.index() {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!(error = %format!("{:#}", e), "Failed to read the index");
+                return MAX_SLEEP;
+            }
+        };
+        // A reminder added with a due time that has already passed is due right away
+        let next = entries
+            .iter()
+            .filter(|e| !e.completed)
+            .flat_map(|e| [e.next_alert, Some(e.due_time)].into_iter().flatten())
+            .filter(|time| *time > last_run)
+            .min();
+        match next {
+            Some(due) => (due - Local::now()).to_std().unwrap_or(Duration::ZERO).min(MAX_SLEEP),
+            None => MAX_SLEEP,
+        }
+    }
+
+    // Start a notify run in the background, unless the previous one is still busy
+    async fn dispatch(&mut self, notified: mpsc::UnboundedSender<Vec<Reminder>>) {
+        if let Some(running) = self.running.take() {
+            if !running.is_finished() {
+                debug!("The previous notify run is still going");
+                self.running = Some(running);
+                return;
+            }
+            report(running.await);
+        }
+
+        let storage_file = self.storage_file.clone();
+        let config = self.config.clone();
+        let desktop = self.desktop;
+        self.last_run = Some(Local::now());
+        // The notifier blocks (popups, sound, commands), and is reopened each time to see the current file
+        self.running = Some(task::spawn_blocking(move || {
+            let storage = Storage::open(storage_file.as_deref(), &config)?;
+            let mut notifier = Notifier::new(storage, config);
+            let reminders = notifier.check_due_reminders(desktop)?;
+            let count = reminders.len();
+            // Before the notifier is dropped, which waits for popups with buttons to close
+            if desktop && count > 0 {
+                let _ = notified.send(reminders);
+            }
+            Ok(count)
+        }));
+    }
+}
+
+fn report(result: Result<Result<usize>, task::JoinError>) {
+    match result {
+        Ok(Ok(0)) => {}
+        Ok(Ok(count)) => info!(count, "Notified"),
+        Ok(Err(e)) => {
+            warn!(error = %format!("{:#}", e), "Notify run failed");
+            eprintln!("Error: {:#}", e);
+        }
+        Err(e) => {
+            warn!(error = %e, "Notify run panicked");
+            eprintln!("Error: the notify run stopped unexpectedly: {}", e);
+        }
+    }
+}
+
+// Wake the scheduler whenever one of `paths` is modified, created or removed
+async fn watch(paths: Vec<PathBuf>, changed: Arc<Notify>) {
+    let modified = || -> Vec<Option<SystemTime>> {
+        paths.iter().map(|path| fs::metadata(path).and_then(|m| m.modified()).ok()).collect()
+    };
+    let mut last = modified();
+    let mut interval = time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        let current = modified();
+        if current != last {
+            last = current;
+            changed.notify_one();
+        }
+    }
+}
@@ -0,0 +1,174 @@
+// Near-duplicate reminders (`remind dedupe [--within 1h] [--dry-run]`)
+//
+// Importing the same calendar or list twice leaves pairs of reminders that say the same thing at
+// (nearly) the same time. Pending reminders whose text matches once case, punctuation and spacing
+// are ignored, and whose due times are no further apart than --within, form a group; each group is
+// shown with the question whether to merge it into one, delete all but one, or leave it alone.
+//
+// Merging keeps one reminder and gives it what the others add: their tags, metadata keys it
+// doesn't have, notes, alerts, skipped dates, completions and pomodoros, and the highest priority.
+
+use anyhow::Result;
+use chrono::Duration;
+use std::io::{self, BufRead};
+
+use crate::cli;
+use crate::config::Config;
+use crate::i18n::tr;
+use crate::reminder::Reminder;
+use crate::review;
+use crate::storage::Storage;
+use crate::utils;
+
+// "Call Sam!" and "call  sam" are the same text
+fn normalize(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Groups of two or more pending reminders with the same text due within `within` of each other,
+// oldest first in each group
+pub fn groups(reminders: &[Reminder], within: Duration) -> Vec<Vec<&Reminder>> {
+    let mut pending: Vec<(String, &Reminder)> =
+        reminders.iter().filter(|r| !r.completed).map(|r| (normalize(&r.text), r)).collect();
+    pending.sort_by(|(a, x), (b, y)| a.cmp(b).then(x.due_time.cmp(&y.due_time)));
+
+    // Neighbours in that order chain together, so 9:00, 9:40 and 10:20 are one group within 1h
+    let mut groups: Vec<Vec<&Reminder>> = Vec::new();
+    let mut previous: Option<(&str, &Reminder)> = None;
+    for (text, reminder) in &pending {
+        let joins = previous.is_some_and(|(last_text, last)| last_text == text && reminder.due_time - last.due_time <= within);
+        match groups.last_mut() {
+            Some(group) if joins => group.push(reminder),
+            _ => groups.push(vec![reminder]),
+        }
+        previous = Some((text, reminder));
+    }
+    groups.retain(|group| group.len() > 1);
+    for group in &mut groups {
+        group.sort_by_key(|r| r.created_at);
+    }
+    groups
+}
+
+// Give `keeper` what `other` has that it lacks
+fn absorb(keeper: &mut Reminder, other: &Reminder) {
+    for tag in &other.tags {
+        keeper.add_tag(tag);
+    }
+    for (key, value) in &other.meta {
+        keeper.meta.entry(key.clone()).or_insert_with(|| value.clone());
+    }
+    keeper.notes = match (keeper.notes.take(), &other.notes) {
+        (Some(mine), Some(theirs)) if !mine.contains(theirs.as_str()) => Some(format!("{}\n\n{}", mine, theirs)),
+        (Some(mine), _) => Some(mine),
+        (None, theirs) => theirs.clone(),
+    };
+    keeper.context = keeper.context.take().or_else(|| other.context.clone());
+    keeper.assignee = keeper.assignee.take().or_else(|| other.assignee.clone());
+    keeper.icon = keeper.icon.take().or_else(|| other.icon.clone());
+    keeper.priority = keeper.priority.max(other.priority);
+    let alerts: Vec<i64> = keeper.alerts.iter().chain(&other.alerts).copied().collect();
+    keeper.set_alerts(&alerts);
+    keeper.exdates.extend(&other.exdates);
+    keeper.exdates.sort();
+    keeper.exdates.dedup();
+    keeper.completions.extend(&other.completions);
+    keeper.completions.sort();
+    keeper.pomodoros.extend(&other.pomodoros);
+    keeper.pomodoros.sort();
+}
+
+// `remind dedupe`: go through the groups one at a time
+pub fn run(storage: &mut Storage, config: &Config, within: &str, dry_run: bool) -> Result<()> {
+    let within = cli::parse_duration(within)?;
+    let reminders = storage.load()?;
+    let groups = groups(&reminders, within);
+    if groups.is_empty() {
+        println!("{}", tr!("No duplicate reminders found."));
+        return Ok(());
+    }
+
+    let mut input = io::stdin().lock();
+    let (mut merged, mut deleted) = (0, 0);
+    for group in &groups {
+        println!("\n{}", tr!("Possible duplicates ({}):", group.len()));
+        for (number, reminder) in group.iter().enumerate() {
+            println!("  {}. {}", number + 1, reminder.summary(config.display.relative_times));
+            println!("     {}", tr!("added {}", utils::format_datetime(&reminder.created_at)));
+        }
+        if dry_run {
+            continue;
+        }
+
+        let merge = match ask(&mut input)? {
+            Choice::Merge => true,
+            Choice::Delete => false,
+            Choice::Skip => continue,
+            Choice::Quit => break,
+        };
+        let Some(keep) = pick(&mut input, group.len())? else {
+            break;
+        };
+        let mut keeper = group[keep].clone();
+        for (_, other) in group.iter().enumerate().filter(|(number, _)| *number != keep) {
+            if merge {
+                absorb(&mut keeper, other);
+            }
+            storage.delete_reminder(&other.id)?;
+            deleted += 1;
+        }
+        if merge {
+            storage.update_reminder(keeper)?;
+            merged += 1;
+        }
+    }
+
+    if dry_run {
+        println!("\n{}", tr!("{} group(s) of duplicates. Nothing was changed.", groups.len()));
+    } else {
+        println!("\n{}", tr!("Merged {} group(s); deleted {} reminder(s).", merged, deleted));
+    }
+    Ok(())
+}
+
+enum Choice {
+    Merge,
+    Delete,
+    Skip,
+    Quit,
+}
+
+fn ask(input: &mut impl BufRead) -> Result<Choice> {
+    loop {
+        let Some(answer) = review::prompt(input, &tr!("[m]erge into one, [d]elete all but one, [s]kip, [q]uit > "))? else {
+            return Ok(Choice::Quit);
+        };
+        match answer.to_lowercase().as_str() {
+            "m" | "merge" => return Ok(Choice::Merge),
+            "d" | "delete" => return Ok(Choice::Delete),
+            "s" | "skip" | "" => return Ok(Choice::Skip),
+            "q" | "quit" => return Ok(Choice::Quit),
+            _ => println!("{}", tr!("Please answer m, d, s or q.")),
+        }
+    }
+}
+
+// Which reminder of the group stays, 0-based; the oldest unless another is named. None at end of input
+fn pick(input: &mut impl BufRead, count: usize) -> Result<Option<usize>> {
+    loop {
+        let Some(answer) = review::prompt(input, &tr!("Keep which one? [1] "))? else {
+            return Ok(None);
+        };
+        if answer.is_empty() {
+            return Ok(Some(0));
+        }
+        match answer.parse::<usize>() {
+            Ok(number) if (1..=count).contains(&number) => return Ok(Some(number - 1)),
+            _ => println!("{}", tr!("Please give a number from 1 to {}.", count)),
+        }
+    }
+}
@@ -0,0 +1,132 @@
+// Daily digest: one morning summary of the day's reminders ([digest] time = "07:30")
+//
+// The first `notify` or daemon run after the configured time sends one notification listing
+// today's pending reminders in order, and can email the same summary through a sendmail-style
+// command. It comes on top of the usual alerts at each due time, which are unchanged. When it
+// was last sent is kept in daily-digest.json next to the reminders, so it goes out once a day; a
+// day the machine is off before that time gets it at the first run afterwards.
+
+use anyhow::{anyhow, Context, Result};
+use base64::Engine;
+use chrono::{DateTime, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use crate::cli;
+use crate::config::{Config, DigestConfig};
+use crate::dnd::Dnd;
+use crate::i18n::tr;
+use crate::storage::{self, Storage};
+use crate::utils;
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct DigestLog {
+    last_sent: Option<DateTime<Local>>,
+}
+
+impl DigestLog {
+    fn path() -> Result<PathBuf> {
+        storage::state_path("daily-digest.json")
+    }
+
+    fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(DigestLog::default());
+        }
+
+        let data = fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_slice(&data).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        let json = serde_json::to_vec_pretty(self).context("Failed to serialize the digest log")?;
+        fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+pub struct Digest {
+    pub title: String,
+    // One line per reminder, e.g. "09:15  Standup #work"
+    pub lines: Vec<String>,
+}
+
+// Today's digest if it's due now; sending it is left to the notifier. Nothing is due while
+// do-not-disturb is on, so it goes out once DND ends
+pub fn daily(storage: &Storage, config: &Config, now: DateTime<Local>) -> Result<Option<Digest>> {
+    let Some(time) = &config.digest.time else {
+        return Ok(None);
+    };
+    let time = cli::parse_time(time)?;
+    let Some(slot) = Local.from_local_datetime(&now.date_naive().and_time(time)).earliest() else {
+        return Ok(None);
+    };
+    let mut log = DigestLog::load()?;
+    if now < slot || log.last_sent.is_some_and(|sent| sent >= slot) || Dnd::load()?.is_active(now, &config.notifications)? {
+        return Ok(None);
+    }
+    log.last_sent = Some(now);
+    log.save()?;
+
+    let reminders = storage.load()?;
+    let today = now.date_naive();
+    let mut due: Vec<_> = reminders.iter().filter(|r| !r.completed && r.due_time.date_naive() == today).collect();
+    due.sort_by_key(|r| r.due_time);
+    let overdue = reminders.iter().filter(|r| !r.completed && r.due_time.date_naive() < today).count();
+    if due.is_empty() && overdue == 0 && !config.digest.when_empty {
+        return Ok(None);
+    }
+
+    let mut lines: Vec<String> = due
+        .iter()
+        .map(|r| {
+            let tags: String = r.tags.iter().map(|tag| format!(" #{}", tag)).collect();
+            format!("{}  {}{}", utils::format_time(&r.due_time), r.headline(), tags)
+        })
+        .collect();
+    if overdue > 0 {
+        lines.push(tr!("Plus {} overdue from before today", overdue));
+    }
+    let title = if due.is_empty() {
+        tr!("Nothing scheduled today")
+    } else {
+        tr!("Today: {} reminder(s)", due.len())
+    };
+    Ok(Some(Digest { title, lines }))
+}
+
+// Hand the digest to the sendmail command as a plain-text message to `to`
+pub fn email(config: &DigestConfig, to: &str, digest: &Digest) -> Result<()> {
+    // Header values have to be ASCII; anything else is sent as an RFC 2047 encoded word
+    let subject = if digest.title.is_ascii() {
+        digest.title.clone()
+    } else {
+        format!("=?UTF-8?B?{}?=", base64::engine::general_purpose::STANDARD.encode(&digest.title))
+    };
+    let message = format!(
+        "To: {}\r\nSubject: {}\r\nMIME-Version: 1.0\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n{}\r\n",
+        to,
+        subject,
+        digest.lines.join("\r\n")
+    );
+
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(&config.sendmail)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to start {:?}", config.sendmail))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(message.as_bytes()).context("Failed to write the email")?;
+    }
+    let status = child.wait().context("Failed to wait for sendmail")?;
+    if !status.success() {
+        return Err(anyhow!("{:?} exited with {}", config.sendmail, status));
+    }
+    Ok(())
+}
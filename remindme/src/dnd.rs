@@ -0,0 +1,85 @@
+// Do-not-disturb: configured quiet hours plus a manual switch (`remind dnd on|off|until HH:MM`)
+//
+// While DND is active the notifier holds back due reminders (except urgent ones) instead of
+// marking them notified, so they stay due. Their ids are remembered here, and the first run
+// after DND ends delivers them together as one digest.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::NotificationConfig;
+use crate::error::RemindError;
+use crate::storage;
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Dnd {
+    pub on: bool,
+    pub until: Option<DateTime<Local>>,
+    pub queued: Vec<String>,
+}
+
+impl Dnd {
+    fn path() -> Result<PathBuf> {
+        storage::state_path("dnd.json")
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Dnd::default());
+        }
+
+        let data = fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_slice(&data).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        let json = serde_json::to_vec_pretty(self).context("Failed to serialize DND state")?;
+        fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    // Manually switched on, and not past its end time
+    pub fn manual_active(&self, now: DateTime<Local>) -> bool {
+        self.on && self.until.is_none_or(|until| now < until)
+    }
+
+    pub fn is_active(&self, now: DateTime<Local>, config: &NotificationConfig) -> Result<bool> {
+        if self.manual_active(now) {
+            return Ok(true);
+        }
+
+        match &config.quiet_hours {
+            Some(range) => in_quiet_hours(range, now.time()),
+            None => Ok(false),
+        }
+    }
+
+    pub fn queue(&mut self, id: &str) {
+        if !self.queued.iter().any(|q| q == id) {
+            self.queued.push(id.to_string());
+        }
+    }
+}
+
+// Whether `time` falls in a range like "22:00-07:00"; ranges may wrap past midnight
+pub fn in_quiet_hours(range: &str, time: NaiveTime) -> Result<bool> {
+    let (start, end) = parse_range(range)?;
+    Ok(if start <= end {
+        start <= time && time < end
+    } else {
+        time >= start || time < end
+    })
+}
+
+fn parse_range(range: &str) -> Result<(NaiveTime, NaiveTime)> {
+    let invalid = || RemindError::Config(format!("Invalid notifications.quiet_hours '{}'. Expected HH:MM-HH:MM, e.g. 22:00-07:00", range));
+    let (start, end) = range.split_once('-').ok_or_else(invalid)?;
+    let start = NaiveTime::parse_from_str(start.trim(), "%H:%M").map_err(|_| invalid())?;
+    let end = NaiveTime::parse_from_str(end.trim(), "%H:%M").map_err(|_| invalid())?;
+    Ok((start, end))
+}
@@ -0,0 +1,292 @@
+// Environment checks (`remind doctor`)
+//
+// Notification setups tend to break silently: the timer stops, the notification daemon
+// goes away, the config picks up a typo. This runs each piece on its own and prints what
+// passed and what failed, with a suggested fix, so a missed reminder can be explained.
+
+use anyhow::Result;
+use chrono::Local;
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+use crate::busy;
+use crate::cli;
+use crate::colors;
+use crate::config::Config;
+use crate::dnd;
+use crate::holidays::Calendar;
+use crate::i18n;
+use crate::routing::Routes;
+use crate::sound;
+use crate::storage::Storage;
+use crate::utils;
+
+const TIMER: &str = "remindme-check.timer";
+
+#[derive(PartialEq)]
+enum Outcome {
+    Pass,
+    Warn,
+    Fail,
+}
+
+struct Check {
+    name: &'static str,
+    outcome: Outcome,
+    detail: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Check { name, outcome: Outcome::Pass, detail: detail.into(), fix: None }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Check { name, outcome: Outcome::Warn, detail: detail.into(), fix: Some(fix.into()) }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Check { name, outcome: Outcome::Fail, detail: detail.into(), fix: Some(fix.into()) }
+    }
+}
+
+// Run every check; returns the exit code, 1 if anything failed
+pub fn run(storage_file: Option<&str>) -> Result<i32> {
+    // Later checks still run against the defaults when the config is broken
+    let (config_check, config) = check_config();
+    let checks = vec![
+        config_check,
+        check_storage(storage_file, &config),
+        check_timezone(),
+        check_notifications(&config),
+        check_sound(&config),
+        check_busy(&config),
+        check_timer(),
+    ];
+
+    for check in &checks {
+        let label = match check.outcome {
+            Outcome::Pass => "PASS",
+            Outcome::Warn => "WARN",
+            Outcome::Fail => "FAIL",
+        };
+        println!("{:<5} {:<14} {}", label, check.name, check.detail);
+        if let Some(fix) = &check.fix {
+            println!("{:<20} fix: {}", "", fix);
+        }
+    }
+
+    let failed = checks.iter().filter(|c| c.outcome == Outcome::Fail).count();
+    if failed == 0 {
+        println!("\nEverything needed for notifications looks fine.");
+        Ok(0)
+    } else {
+        println!("\n{} check(s) failed.", failed);
+        Ok(1)
+    }
+}
+
+fn check_config() -> (Check, Config) {
+    let name = "Config";
+    let path = match Config::path() {
+        Ok(path) => path,
+        Err(e) => return (Check::fail(name, e.to_string(), "Set HOME or XDG_CONFIG_HOME"), Config::default()),
+    };
+    let fix = format!("Correct {}, or move it aside to use the defaults", path.display());
+
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => return (Check::fail(name, format!("{:#}", e), fix), Config::default()),
+    };
+
+    // Settings that are only validated when they are first used
+    let problem = Calendar::from_config(&config.holidays).err().or_else(|| {
+        config
+            .notifications
+            .quiet_hours
+            .as_deref()
+            .and_then(|range| dnd::in_quiet_hours(range, Local::now().time()).err())
+            .or_else(|| config.display.tag_colors.values().find_map(|color| colors::parse(color).err()))
+            .or_else(|| utils::init_display(&config.display).err())
+            .or_else(|| i18n::init(&config.display).err())
+            .or_else(|| config.maintenance.expire_after.as_deref().and_then(|after| cli::parse_duration(after).err()))
+            .or_else(|| Routes::from_config(&config.routes).err())
+            .or_else(|| config.defaults.priority().err())
+            .or_else(|| config.defaults.time.as_deref().and_then(|time| cli::parse_time(time).err()))
+            .or_else(|| config.defaults.recurrence.as_deref().and_then(|recurrence| cli::parse_schedule(recurrence).err()))
+            .or_else(|| config.stale.digest_weekday().err())
+            .or_else(|| cli::parse_time(&config.stale.digest_time).err())
+            .or_else(|| config.digest.time.as_deref().and_then(|time| cli::parse_time(time).err()))
+    });
+    if let Some(e) = problem {
+        return (Check::fail(name, format!("{:#}", e), fix), config);
+    }
+
+    let detail = if path.exists() {
+        path.display().to_string()
+    } else {
+        "No config file; using the defaults".to_string()
+    };
+    (Check::pass(name, detail), config)
+}
+
+fn check_storage(storage_file: Option<&str>, config: &Config) -> Check {
+    let name = "Storage";
+    let loaded = Storage::open(storage_file, config).and_then(|storage| Ok((storage.load()?, storage)));
+    match loaded {
+        Ok((reminders, storage)) => {
+            Check::pass(name, format!("{} reminder(s) in {}", reminders.len(), storage.path().display()))
+        }
+        Err(e) => Check::fail(
+            name,
+            format!("{:#}", e),
+            "Check the file's permissions and that it is valid JSON; a .tmp file next to it may hold the last good copy",
+        ),
+    }
+}
+
+fn check_timezone() -> Check {
+    let name = "Timezone";
+    let now = Local::now();
+    let detail = format!("UTC{}, local time {}", now.format("%:z"), now.format("%Y-%m-%d %H:%M"));
+
+    // An unknown TZ or missing /etc/localtime silently falls back to UTC
+    if let Ok(tz) = env::var("TZ") {
+        let zone = tz.trim_start_matches(':');
+        let known = zone.is_empty() || Path::new(zone).exists() || Path::new("/usr/share/zoneinfo").join(zone).exists();
+        if !known {
+            return Check::warn(
+                name,
+                format!("TZ={} is not a known time zone; {}", tz, detail),
+                "Set TZ to a zone name like Europe/Berlin, or unset it",
+            );
+        }
+    } else if !Path::new("/etc/localtime").exists() {
+        return Check::warn(
+            name,
+            format!("No /etc/localtime, so times are probably UTC; {}", detail),
+            "Run `timedatectl set-timezone Region/City` or set TZ",
+        );
+    }
+    Check::pass(name, detail)
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn check_notifications(_config: &Config) -> Check {
+    let name = "Notifications";
+    if crate::termux::detected() {
+        return match crate::termux::check() {
+            Ok(()) => Check::pass(name, "Termux:API"),
+            Err(e) => Check::fail(
+                name,
+                format!("{:#}", e),
+                "Install the Termux:API app and run `pkg install termux-api`; \
+                 until then reminders fall back to the terminal",
+            ),
+        };
+    }
+    match notify_rust::get_server_information() {
+        Ok(server) => Check::pass(name, format!("{} {} ({})", server.name, server.version, server.vendor)),
+        Err(e) => Check::fail(
+            name,
+            format!("No notification daemon reachable over D-Bus: {}", e),
+            "Start a notification daemon (e.g. dunst or mako) and check DBUS_SESSION_BUS_ADDRESS; \
+             until then reminders fall back to the terminal",
+        ),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn check_notifications(config: &Config) -> Check {
+    let name = "Notifications";
+    let app_id = config.notifications.app_id.as_deref().unwrap_or(crate::notification::MACOS_APP_ID);
+    Check::pass(name, format!("Notification Center, as {}", app_id))
+}
+
+#[cfg(windows)]
+fn check_notifications(config: &Config) -> Check {
+    let name = "Notifications";
+    let sound = config.notifications.sound.as_str();
+    if !matches!(sound, "default" | "none") && sound.parse::<tauri_winrt_notification::Sound>().is_err() {
+        return Check::fail(
+            name,
+            format!("Unknown Windows sound '{}' in notifications.sound", sound),
+            "Use default, none, or one of Reminder, IM, Mail, SMS, Alarm..Alarm10, Call..Call10",
+        );
+    }
+    let app_id = config.notifications.app_id.as_deref().unwrap_or("Windows PowerShell");
+    Check::pass(name, format!("Toast notifications, as {}", app_id))
+}
+
+fn check_sound(config: &Config) -> Check {
+    let name = "Sound";
+    if config.notifications.sound == "none" {
+        return Check::pass(name, "Off (notifications.sound = \"none\")");
+    }
+    // Notification Center and toasts play the sound themselves
+    if cfg!(any(target_os = "macos", windows)) {
+        return Check::pass(name, format!("System sound '{}'", config.notifications.sound));
+    }
+    // Termux falls back to the Android notification sound
+    if crate::termux::detected() && !Path::new(&sound::get_sound_path(&config.notifications)).exists() {
+        return Check::pass(name, "Android notification sound");
+    }
+    match sound::check(&config.notifications) {
+        Ok(path) => Check::pass(name, path),
+        Err(e) => Check::warn(
+            name,
+            format!("{:#}", e),
+            "Set notifications.sound_file to a playable .oga, .ogg, .wav or .mp3 file, with `remind sound set`, or pick one in the TUI's settings (,)",
+        ),
+    }
+}
+
+fn check_busy(config: &Config) -> Check {
+    let name = "Busy calendar";
+    if config.busy.calendar.is_none() {
+        return Check::pass(name, "Off (no [busy] calendar)");
+    }
+    match busy::until(&config.busy, Local::now()) {
+        Ok(Some(until)) => Check::pass(name, format!("In a meeting until {}; reminders that aren't urgent wait", utils::format_time(&until))),
+        Ok(None) => Check::pass(name, "Free now"),
+        Err(e) => Check::warn(
+            name,
+            format!("{:#}", e),
+            "Check busy.calendar: an iCal address (https:// or webcal://) or the path of an .ics file; until then nothing is held back",
+        ),
+    }
+}
+
+fn check_timer() -> Check {
+    let name = "Systemd timer";
+    let query = |action: &str| {
+        Command::new("systemctl")
+            .args(["--user", action, TIMER])
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let enabled = match query("is-enabled") {
+        Ok(state) => state,
+        Err(_) => {
+            return Check::warn(
+                name,
+                "systemctl not found",
+                "Run `remind notify --desktop` every minute from another scheduler, e.g. cron",
+            );
+        }
+    };
+    let active = query("is-active").unwrap_or_default();
+
+    match (enabled.as_str(), active.as_str()) {
+        ("enabled", "active") => Check::pass(name, format!("{} is enabled and active", TIMER)),
+        ("" | "not-found", _) => Check::fail(name, format!("{} is not installed", TIMER), "Run setup.sh to install it"),
+        _ => Check::fail(
+            name,
+            format!("{} is {} and {}", TIMER, enabled, if active.is_empty() { "unknown" } else { &active }),
+            format!("systemctl --user enable --now {}", TIMER),
+        ),
+    }
+}
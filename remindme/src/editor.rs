@@ -0,0 +1,82 @@
+// Reminders as places in code (`list --vimgrep`, `list --quickfix`), for editor plugins
+//
+// A reminder points at code through its `file` metadata: "src/db.rs", "src/db.rs:120" or
+// "src/db.rs:120:7", e.g. `remind add --text "Drop this hack" --time 17:00 --meta file=src/db.rs:120`.
+// Pending reminders with one are printed the way `rg --vimgrep` prints matches, or as a JSON list
+// of Vim quickfix items, so :cexpr, :cgetfile or setqflist() can jump straight to the code.
+// Relative paths stay relative and resolve against the editor's working directory.
+
+use chrono::{DateTime, Local};
+use serde_json::{json, Value};
+
+use crate::reminder::Reminder;
+use crate::utils;
+
+// Metadata key holding the path and line
+pub const FILE_KEY: &str = "file";
+
+pub struct Location {
+    pub path: String,
+    pub line: u32,
+    pub col: u32,
+}
+
+impl Location {
+    // Where the reminder's `file` metadata points; the line and column default to 1
+    pub fn of(reminder: &Reminder) -> Option<Location> {
+        let value = reminder.meta.get(FILE_KEY)?.trim();
+        // Numbers are only taken from the end, so a Windows drive ("C:\src\db.rs:12") stays in the path
+        let mut numbers = Vec::new();
+        let mut path = value;
+        while numbers.len() < 2
+            && let Some((rest, number)) = path.rsplit_once(':')
+            && let Ok(number) = number.parse::<u32>()
+        {
+            numbers.insert(0, number);
+            path = rest;
+        }
+        if path.is_empty() {
+            return None;
+        }
+        let path = shellexpand::tilde(path).to_string();
+        Some(Location { path, line: numbers.first().copied().unwrap_or(1), col: numbers.get(1).copied().unwrap_or(1) })
+    }
+}
+
+// The pending reminders that point at code, with where they point
+pub fn located<'a>(reminders: impl IntoIterator<Item = &'a Reminder>) -> Vec<(&'a Reminder, Location)> {
+    reminders
+        .into_iter()
+        .filter(|reminder| !reminder.completed)
+        .filter_map(|reminder| Some((reminder, Location::of(reminder)?)))
+        .collect()
+}
+
+// "src/db.rs:120:1:Drop this hack (due 2025-06-01 17:00)"
+pub fn vimgrep(reminder: &Reminder, location: &Location) -> String {
+    format!("{}:{}:{}:{}", location.path, location.line, location.col, description(reminder))
+}
+
+// Items for setqflist(); overdue reminders are warnings, the others information
+pub fn quickfix(located: &[(&Reminder, Location)], now: DateTime<Local>) -> Value {
+    let items: Vec<Value> = located
+        .iter()
+        .map(|(reminder, location)| {
+            json!({
+                "filename": location.path,
+                "lnum": location.line,
+                "col": location.col,
+                "text": description(reminder),
+                "type": if reminder.due_time <= now { "W" } else { "I" },
+                "user_data": { "id": reminder.id },
+            })
+        })
+        .collect();
+    json!(items)
+}
+
+fn description(reminder: &Reminder) -> String {
+    // One line, as both formats need
+    let text = reminder.headline().replace(['\n', '\r'], " ");
+    format!("{} (due {})", text, utils::format_datetime(&reminder.due_time))
+}
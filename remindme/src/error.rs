@@ -0,0 +1,56 @@
+// Typed errors for the failures the CLI needs to tell apart
+//
+// Each kind of failure has its own variant, so code that reacts to one doesn't have to guess
+// from the wording of a message. Most failures are the command's own (a bad value, an unknown ID)
+// and exit with status 1, but some leave nothing to work with (storage that can't be opened or
+// accessed, a broken config). `is_fatal` decides which is which, and `main` picks the exit status
+// from it.
+
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+use crate::i18n::tr;
+
+#[derive(Debug, Error)]
+pub enum RemindError {
+    #[error("{}", tr!("Failed to initialize storage"))]
+    StorageInit(#[source] anyhow::Error),
+
+    #[error("{}", tr!("Cannot access {}", path.display()))]
+    Access {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("{0}")]
+    Config(String),
+
+    #[error("{0}")]
+    InvalidInput(String),
+
+    #[error("{}", tr!("No reminder found with ID {}", .0))]
+    NotFound(String),
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error("{0}")]
+    ReadOnly(String),
+}
+
+impl RemindError {
+    pub fn access(path: impl Into<PathBuf>) -> impl FnOnce(io::Error) -> Self {
+        let path = path.into();
+        move |source| RemindError::Access { path, source }
+    }
+
+    // Whether the program should give up with its own status rather than just report the error
+    pub fn is_fatal(&self) -> bool {
+        match self {
+            RemindError::StorageInit(_) | RemindError::Config(_) | RemindError::ReadOnly(_) => true,
+            RemindError::Access { source, .. } => source.kind() == io::ErrorKind::PermissionDenied,
+            RemindError::InvalidInput(_) | RemindError::NotFound(_) | RemindError::Conflict(_) => false,
+        }
+    }
+}
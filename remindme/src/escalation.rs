@@ -0,0 +1,123 @@
+// Escalating alarms for urgent reminders nobody has reacted to (`notifications.escalate_every`)
+//
+// Once `remind daemon` has sent an urgent reminder's popup, the sound rings again every few
+// seconds, louder each time, until someone reacts: the popup's "Acknowledge" button, `remind ack`,
+// or any change to the reminder, like completing, snoozing or deleting it. One alarm rings for
+// all of them. The button and `remind ack` run in other processes, so acknowledgements go through
+// acknowledged.json next to dnd.json, which the daemon watches. `remind alarm` rings the same way
+// until a key is pressed; `remind notify` runs are too short-lived to ring on.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use tracing::info;
+
+use crate::config::Config;
+use crate::reminder::{Priority, Reminder};
+use crate::sound::Escalation;
+use crate::storage::{self, Storage};
+
+// Acknowledgements older than this are dropped; nothing rings for that long unanswered
+const KEEP_DAYS: i64 = 7;
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Acknowledged {
+    // Everything ringing before this was acknowledged at once
+    pub all: Option<DateTime<Local>>,
+    // Reminder ids, and when each was acknowledged
+    pub ids: BTreeMap<String, DateTime<Local>>,
+}
+
+impl Acknowledged {
+    pub fn path() -> Result<PathBuf> {
+        storage::state_path("acknowledged.json")
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Acknowledged::default());
+        }
+
+        let data = fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_slice(&data).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        let json = serde_json::to_vec_pretty(self).context("Failed to serialize acknowledgements")?;
+        fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    // Acknowledge one reminder's alarm, or with no id every alarm ringing now
+    pub fn record(id: Option<&str>) -> Result<()> {
+        let mut acknowledged = Self::load()?;
+        let now = Local::now();
+        match id {
+            Some(id) => {
+                acknowledged.ids.insert(id.to_string(), now);
+            }
+            None => acknowledged.all = Some(now),
+        }
+        acknowledged.ids.retain(|_, at| now - *at < Duration::days(KEEP_DAYS));
+        acknowledged.save()
+    }
+
+    // Whether an alarm that started ringing at `since` was acknowledged
+    fn covers(&self, id: &str, since: DateTime<Local>) -> bool {
+        self.all.is_some_and(|at| at >= since) || self.ids.get(id).is_some_and(|at| *at >= since)
+    }
+}
+
+// The reminders the daemon is ringing for, and the alarm doing it
+#[derive(Default)]
+pub struct Ringing {
+    // Reminder ids, and when their popup went out
+    since: BTreeMap<String, DateTime<Local>>,
+    alarm: Option<Escalation>,
+}
+
+impl Ringing {
+    // Ring for the urgent ones among reminders just notified
+    pub fn add(&mut self, notified: &[Reminder], config: &Config) {
+        if config.notifications.escalate_every == 0 {
+            return;
+        }
+        let now = Local::now();
+        for reminder in notified {
+            if reminder.effective_priority(now, &config.priority) == Priority::Urgent {
+                info!(id = %reminder.id, "Ringing until acknowledged");
+                self.since.insert(reminder.id.clone(), now);
+            }
+        }
+        if !self.since.is_empty() && self.alarm.is_none() {
+            self.alarm = Escalation::start(&config.notifications);
+        }
+    }
+
+    // Stop ringing for whatever was acknowledged or changed, and stop the alarm once nothing's left
+    pub fn update(&mut self, storage: &Storage) -> Result<()> {
+        if self.since.is_empty() {
+            return Ok(());
+        }
+        let acknowledged = Acknowledged::load()?;
+        let reminders = storage.load()?;
+        self.since.retain(|id, since| {
+            let touched = reminders.iter().find(|r| r.id == *id).is_none_or(|r| r.updated_at.is_some_and(|at| at > *since));
+            let keep = !touched && !acknowledged.covers(id, *since);
+            if !keep {
+                info!(id = %id, "Alarm acknowledged");
+            }
+            keep
+        });
+        if self.since.is_empty() {
+            // Dropping the alarm stops it
+            self.alarm = None;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+// The TUI's add and edit forms
+//
+// Both forms show the same fields, defined once here: a field added to `Field` and `FIELDS` gets a
+// box, a place in the Tab order and a value in `Form` in both of them. Only the button differs.
+// Up and Down step the time and date fields, so a due time can be set without typing it.
+
+use anyhow::Result;
+use chrono::{Duration, Local, NaiveTime, Timelike};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::cli;
+use crate::config::{DateOrder, DefaultsConfig};
+use crate::i18n::tr;
+use crate::utils;
+
+// Minutes the time moves per Up/Down, and with Shift
+const TIME_STEP: i64 = 5;
+const TIME_BIG_STEP: i64 = 60;
+// Days the date moves per Up/Down, and with Shift
+const DATE_STEP: i64 = 1;
+const DATE_BIG_STEP: i64 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Text,
+    Time,
+    Date,
+    Recurrence,
+    Submit,
+}
+
+// Every field in Tab order, the button last
+pub const FIELDS: [Field; 5] = [Field::Text, Field::Time, Field::Date, Field::Recurrence, Field::Submit];
+
+impl Field {
+    fn title(self) -> String {
+        match self {
+            Field::Text => tr!("Reminder Text"),
+            Field::Time => tr!("Time (HH:MM; ↑/↓ 5 minutes, with Shift an hour)"),
+            Field::Date => tr!("Date (YYYY-MM-DD; ↑/↓ a day, with Shift a week)"),
+            Field::Recurrence => tr!("Recurrence (none/daily/weekdays/weekly/monthly/yearly, or e.g. last friday, 2nd tuesday, last day)"),
+            Field::Submit => String::new(),
+        }
+    }
+
+    // Shown in an empty field that isn't being typed in
+    fn placeholder(self) -> String {
+        match self {
+            Field::Date => tr!("(Optional - defaults to today/tomorrow)"),
+            _ => String::new(),
+        }
+    }
+
+    pub fn next(self) -> Field {
+        let i = FIELDS.iter().position(|f| *f == self).unwrap_or(0);
+        FIELDS[(i + 1) % FIELDS.len()]
+    }
+
+    pub fn previous(self) -> Field {
+        let i = FIELDS.iter().position(|f| *f == self).unwrap_or(0);
+        FIELDS[(i + FIELDS.len() - 1) % FIELDS.len()]
+    }
+}
+
+// What's been typed into the form so far, and the field being typed in
+pub struct Form {
+    pub text: String,
+    pub time: String,
+    pub date: String,
+    pub recurrence: String,
+    pub active: Field,
+}
+
+impl Form {
+    // An empty form, with the time and recurrence from [defaults] in the config filled in
+    pub fn new(defaults: &DefaultsConfig) -> Self {
+        Form {
+            text: String::new(),
+            time: defaults.time.clone().unwrap_or_default(),
+            date: String::new(),
+            recurrence: defaults.recurrence.clone().unwrap_or_else(|| String::from("none")),
+            active: Field::Text,
+        }
+    }
+
+    pub fn value(&self, field: Field) -> &str {
+        match field {
+            Field::Text => &self.text,
+            Field::Time => &self.time,
+            Field::Date => &self.date,
+            Field::Recurrence => &self.recurrence,
+            Field::Submit => "",
+        }
+    }
+
+    // The active field's value, or None on the button
+    pub fn active_value_mut(&mut self) -> Option<&mut String> {
+        match self.active {
+            Field::Text => Some(&mut self.text),
+            Field::Time => Some(&mut self.time),
+            Field::Date => Some(&mut self.date),
+            Field::Recurrence => Some(&mut self.recurrence),
+            Field::Submit => None,
+        }
+    }
+
+    // Move the active time or date field a step later (`up`) or earlier; `big` steps are an hour or
+    // a week. An empty field starts from now or today. Times land on whole steps, so 10:33 goes up
+    // to 10:35, and wrap around midnight without touching the date
+    pub fn step(&mut self, up: bool, big: bool, order: DateOrder) -> Result<()> {
+        let sign = if up { 1 } else { -1 };
+        match self.active {
+            Field::Time => {
+                let time = if self.time.trim().is_empty() { Local::now().time() } else { cli::parse_time(&self.time)? };
+                let step = if big { TIME_BIG_STEP } else { TIME_STEP };
+                let minutes = (time.hour() * 60 + time.minute()) as i64;
+                let snapped = if up { minutes.div_euclid(step) * step } else { (minutes + step - 1).div_euclid(step) * step };
+                let minutes = (snapped + sign * step).rem_euclid(24 * 60);
+                let time = NaiveTime::MIN + Duration::minutes(minutes);
+                self.time = time.format("%H:%M").to_string();
+            }
+            Field::Date => {
+                let date = if self.date.trim().is_empty() { Local::now().date_naive() } else { cli::parse_date(&self.date, order)? };
+                let days = sign * if big { DATE_BIG_STEP } else { DATE_STEP };
+                self.date = (date + Duration::days(days)).format("%Y-%m-%d").to_string();
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+// Draws a form with the given button; `typing` puts the cursor at the end of the active field
+pub struct FormWidget<'a> {
+    pub form: &'a Form,
+    pub submit: &'a str,
+    pub typing: bool,
+}
+
+impl FormWidget<'_> {
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        let constraints: Vec<Constraint> = FIELDS
+            .iter()
+            .map(|_| Constraint::Length(3))
+            .chain(std::iter::once(Constraint::Min(0)))
+            .collect();
+        let chunks = Layout::default().direction(Direction::Vertical).constraints(constraints).split(area);
+
+        for (field, chunk) in FIELDS.iter().zip(chunks.iter()) {
+            let active = *field == self.form.active;
+            if *field == Field::Submit {
+                let (style, border) = if active {
+                    (Style::default().fg(Color::Green).add_modifier(Modifier::BOLD), Style::default().fg(Color::Green))
+                } else {
+                    (Style::default(), Style::default())
+                };
+                let marker = if active && utils::accessible() { "> " } else { "" };
+                let button = Paragraph::new(format!("{}[ {} ]", marker, self.submit))
+                    .style(style)
+                    .alignment(Alignment::Center)
+                    .block(Block::default().borders(Borders::ALL).border_style(border));
+                f.render_widget(button, *chunk);
+                continue;
+            }
+
+            let value = self.form.value(*field);
+            let shown = if value.is_empty() && !(active && self.typing) { field.placeholder() } else { value.to_string() };
+            let style = if active { Style::default().fg(Color::Yellow) } else { Style::default() };
+            // The active field is only yellow otherwise
+            let title = if active && utils::accessible() { format!("> {}", field.title()) } else { field.title() };
+            let input = Paragraph::new(shown)
+                .style(style)
+                .block(Block::default().title(title).borders(Borders::ALL).border_style(style));
+            f.render_widget(input, *chunk);
+
+            // Inside the border, just past the text
+            if active && self.typing {
+                let x = chunk.x + 1 + value.chars().count() as u16;
+                f.set_cursor_position((x.min(chunk.x + chunk.width.saturating_sub(2)), chunk.y + 1));
+            }
+        }
+    }
+}
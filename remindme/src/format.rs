@@ -0,0 +1,140 @@
+// Template mini-language for custom `list --format` output
+//
+// Placeholders are written as `{field}` or `{field:spec}`. Date fields take a
+// chrono strftime spec (e.g. `{due:%H:%M}`), other fields ignore it.
+// `{meta.KEY}` is the reminder's metadata value for KEY, empty when unset.
+// Use `{{` and `}}` for literal braces, and `\t`, `\n`, `\\` for escapes so
+// templates can be passed from a shell without `$'...'` quoting.
+
+use anyhow::{anyhow, Result};
+use chrono::format::{Item, StrftimeItems};
+
+use crate::reminder::Reminder;
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+pub const FIELDS: &[&str] = &[
+    "index", "id", "short_id", "text", "due", "created", "recurrence", "priority", "status", "tags", "context", "pinned", "assignee",
+];
+
+// A parsed template, so the same format string isn't re-parsed for every reminder
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+enum Part {
+    Literal(String),
+    Field { name: String, spec: Option<String> },
+}
+
+impl Template {
+    pub fn parse(template: &str) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('t') => literal.push('\t'),
+                    Some('n') => literal.push('\n'),
+                    Some('\\') => literal.push('\\'),
+                    Some(other) => {
+                        literal.push('\\');
+                        literal.push(other);
+                    }
+                    None => literal.push('\\'),
+                },
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(ch) => placeholder.push(ch),
+                            None => return Err(anyhow!("Unclosed '{{' in format string")),
+                        }
+                    }
+
+                    let (name, spec) = match placeholder.split_once(':') {
+                        Some((name, spec)) => (name.trim(), Some(spec.to_string())),
+                        None => (placeholder.trim(), None),
+                    };
+
+                    if !FIELDS.contains(&name) && !name.starts_with("meta.") {
+                        return Err(anyhow!(
+                            "Unknown field '{{{}}}' in format string. Available fields: {}, meta.KEY",
+                            name,
+                            FIELDS.join(", ")
+                        ));
+                    }
+
+                    // chrono panics when displaying an invalid strftime spec, so reject it up front
+                    if let Some(spec) = &spec
+                        && matches!(name, "due" | "created")
+                        && StrftimeItems::new(spec).any(|item| matches!(item, Item::Error))
+                    {
+                        return Err(anyhow!("Invalid date format '{}' for field '{}'", spec, name));
+                    }
+
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(Part::Field { name: name.to_string(), spec });
+                }
+                '}' => return Err(anyhow!("Unmatched '}}' in format string (use '}}}}' for a literal brace)")),
+                _ => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+
+        Ok(Template { parts })
+    }
+
+    // Render the template for one reminder; `index` is the 1-based position shown in `list`
+    pub fn render(&self, index: usize, reminder: &Reminder) -> String {
+        let mut out = String::new();
+
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => out.push_str(text),
+                Part::Field { name, spec } => {
+                    let date_format = spec.as_deref().unwrap_or(DEFAULT_DATE_FORMAT);
+                    let value = match name.as_str() {
+                        "index" => index.to_string(),
+                        "id" => reminder.id.clone(),
+                        "short_id" => reminder.id.chars().take(8).collect(),
+                        "text" => reminder.text.clone(),
+                        "due" => reminder.due_time.format(date_format).to_string(),
+                        "created" => reminder.created_at.format(date_format).to_string(),
+                        "recurrence" => reminder.recurrence.to_string(),
+                        "priority" => format!("{:?}", reminder.priority).to_lowercase(),
+                        "status" => if reminder.completed { "completed" } else { "pending" }.to_string(),
+                        "tags" => reminder.tags.join(","),
+                        "context" => reminder.context.clone().unwrap_or_default(),
+                        "pinned" => reminder.pinned.map(|place| place.to_string()).unwrap_or_default(),
+                        "assignee" => reminder.assignee.clone().unwrap_or_default(),
+                        meta => meta
+                            .strip_prefix("meta.")
+                            .and_then(|key| reminder.meta.get(key))
+                            .cloned()
+                            .unwrap_or_default(),
+                    };
+                    out.push_str(&value);
+                }
+            }
+        }
+
+        out
+    }
+}
@@ -0,0 +1,58 @@
+// Gotify push notifications (https://gotify.net), for a self-hosted server
+//
+// Each due reminder becomes a message from a Gotify application, created in the Gotify web
+// UI; its token goes in the config. Bodies are markdown, which the Gotify app renders.
+
+use anyhow::Result;
+use serde_json::json;
+
+use crate::channels::{Channel, Message, Request};
+use crate::config::GotifyConfig;
+use crate::reminder::Reminder;
+
+pub struct Gotify {
+    config: GotifyConfig,
+    server: String,
+    token: String,
+}
+
+impl Gotify {
+    // None until both the server and an application token are set
+    pub fn from_config(config: &GotifyConfig) -> Option<Self> {
+        let server = config.server.clone().filter(|server| !server.is_empty())?;
+        let token = std::env::var("REMINDME_GOTIFY_TOKEN")
+            .ok()
+            .or_else(|| config.token.clone())
+            .filter(|token| !token.is_empty())?;
+        Some(Gotify { config: config.clone(), server, token })
+    }
+}
+
+impl Channel for Gotify {
+    fn name(&self) -> &'static str {
+        "gotify"
+    }
+
+    fn wants(&self, reminder: &Reminder) -> bool {
+        self.config.tags.is_empty() || self.config.tags.iter().any(|tag| reminder.tags.contains(tag))
+    }
+
+    fn request(&self, message: &Message) -> Result<Request> {
+        let mut body = format!("**{}**", message.body);
+        if !message.reminder.tags.is_empty() {
+            let tags: Vec<String> = message.reminder.tags.iter().map(|tag| format!("`#{}`", tag)).collect();
+            body.push_str(&format!("\n\n{}", tags.join(" ")));
+        }
+        Ok(Request {
+            method: "POST",
+            url: format!("{}/message", self.server.trim_end_matches('/')),
+            headers: vec![format!("X-Gotify-Key: {}", self.token)],
+            body: json!({
+                "title": message.title,
+                "message": body,
+                "priority": self.config.priorities.level(message.priority, [2, 5, 7, 10]),
+                "extras": { "client::display": { "contentType": "text/markdown" } },
+            }),
+        })
+    }
+}
@@ -0,0 +1,189 @@
+// Business-day calendar: weekends, public holidays of built-in regions, and holidays from an ICS file
+//
+// Used when advancing recurring reminders whose `off_days` policy skips or shifts
+// occurrences that land on a day off. Built-in regions compute their holidays per year,
+// including movable ones (nth weekday of a month, Easter-based), so no data files are needed.
+
+use anyhow::{Context, Result};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::collections::HashSet;
+use std::fs;
+
+use crate::config::HolidaysConfig;
+use crate::error::RemindError;
+
+pub const REGIONS: &[&str] = &["us", "uk", "de"];
+
+#[derive(Default)]
+pub struct Calendar {
+    regions: Vec<String>,
+    dates: HashSet<NaiveDate>,
+    // (month, day) of holidays that recur every year, e.g. from an ICS RRULE:FREQ=YEARLY
+    yearly: HashSet<(u32, u32)>,
+}
+
+impl Calendar {
+    pub fn from_config(config: &HolidaysConfig) -> Result<Self> {
+        let mut calendar = Calendar::default();
+
+        for region in &config.regions {
+            let region = region.to_lowercase();
+            if !REGIONS.contains(&region.as_str()) {
+                return Err(RemindError::Config(format!(
+                    "Unknown holiday region '{}'. Built-in regions: {}",
+                    region,
+                    REGIONS.join(", ")
+                ))
+                .into());
+            }
+            calendar.regions.push(region);
+        }
+
+        for date in &config.dates {
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| RemindError::Config(format!("Invalid holiday date '{}'. Expected YYYY-MM-DD", date)))?;
+            calendar.dates.insert(date);
+        }
+
+        if let Some(path) = &config.ics {
+            let path = shellexpand::tilde(path).to_string();
+            let contents = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read holiday calendar {}", path))?;
+            calendar.load_ics(&contents);
+        }
+
+        Ok(calendar)
+    }
+
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.is_holiday(date)
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.dates.contains(&date)
+            || self.yearly.contains(&(date.month(), date.day()))
+            || self.regions.iter().any(|region| region_holidays(region, date.year()).contains(&date))
+    }
+
+    // Take the all-day events of an ICS file as holidays; only DTSTART and a yearly RRULE are read
+    fn load_ics(&mut self, contents: &str) {
+        let mut start: Option<NaiveDate> = None;
+        let mut yearly = false;
+
+        for line in contents.lines().map(str::trim) {
+            if line == "BEGIN:VEVENT" {
+                start = None;
+                yearly = false;
+            } else if let Some(rest) = line.strip_prefix("DTSTART") {
+                // DTSTART;VALUE=DATE:20251225 or DTSTART:20251225T000000
+                let value = rest.rsplit(':').next().unwrap_or("");
+                start = value.get(..8).and_then(|d| NaiveDate::parse_from_str(d, "%Y%m%d").ok());
+            } else if let Some(rule) = line.strip_prefix("RRULE:") {
+                yearly = rule.split(';').any(|part| part == "FREQ=YEARLY");
+            } else if line == "END:VEVENT"
+                && let Some(date) = start
+            {
+                if yearly {
+                    self.yearly.insert((date.month(), date.day()));
+                } else {
+                    self.dates.insert(date);
+                }
+            }
+        }
+    }
+}
+
+fn region_holidays(region: &str, year: i32) -> Vec<NaiveDate> {
+    let date = |month, day| NaiveDate::from_ymd_opt(year, month, day);
+    let easter = easter_sunday(year);
+
+    let holidays = match region {
+        "us" => vec![
+            observed(date(1, 1)),
+            nth_weekday(year, 1, Weekday::Mon, 3),  // Martin Luther King Jr. Day
+            nth_weekday(year, 2, Weekday::Mon, 3),  // Presidents' Day
+            last_weekday(year, 5, Weekday::Mon),    // Memorial Day
+            observed(date(6, 19)),
+            observed(date(7, 4)),
+            nth_weekday(year, 9, Weekday::Mon, 1),  // Labor Day
+            nth_weekday(year, 10, Weekday::Mon, 2), // Columbus Day
+            observed(date(11, 11)),
+            nth_weekday(year, 11, Weekday::Thu, 4), // Thanksgiving
+            observed(date(12, 25)),
+        ],
+        "uk" => vec![
+            substitute(date(1, 1)),
+            easter.map(|e| e - Duration::days(2)),  // Good Friday
+            easter.map(|e| e + Duration::days(1)),  // Easter Monday
+            nth_weekday(year, 5, Weekday::Mon, 1),  // Early May bank holiday
+            last_weekday(year, 5, Weekday::Mon),    // Spring bank holiday
+            last_weekday(year, 8, Weekday::Mon),    // Summer bank holiday
+            substitute(date(12, 25)),
+            // Boxing Day moves past a substituted Christmas Day
+            date(12, 26).map(|d| match d.weekday() {
+                Weekday::Sat | Weekday::Sun | Weekday::Mon => d + Duration::days(2),
+                _ => d,
+            }),
+        ],
+        "de" => vec![
+            date(1, 1),
+            easter.map(|e| e - Duration::days(2)),  // Karfreitag
+            easter.map(|e| e + Duration::days(1)),  // Ostermontag
+            date(5, 1),
+            easter.map(|e| e + Duration::days(39)), // Christi Himmelfahrt
+            easter.map(|e| e + Duration::days(50)), // Pfingstmontag
+            date(10, 3),
+            date(12, 25),
+            date(12, 26),
+        ],
+        _ => Vec::new(),
+    };
+
+    holidays.into_iter().flatten().collect()
+}
+
+// US rule: a holiday on Saturday is observed on Friday, on Sunday the following Monday
+fn observed(date: Option<NaiveDate>) -> Option<NaiveDate> {
+    date.map(|d| match d.weekday() {
+        Weekday::Sat => d - Duration::days(1),
+        Weekday::Sun => d + Duration::days(1),
+        _ => d,
+    })
+}
+
+// UK rule: a holiday on a weekend moves to the following Monday
+fn substitute(date: Option<NaiveDate>) -> Option<NaiveDate> {
+    date.map(|d| match d.weekday() {
+        Weekday::Sat => d + Duration::days(2),
+        Weekday::Sun => d + Duration::days(1),
+        _ => d,
+    })
+}
+
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: u8) -> Option<NaiveDate> {
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, n)
+}
+
+fn last_weekday(year: i32, month: u32, weekday: Weekday) -> Option<NaiveDate> {
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, 5)
+        .or_else(|| NaiveDate::from_weekday_of_month_opt(year, month, weekday, 4))
+}
+
+// Gregorian Easter Sunday (anonymous Gregorian algorithm)
+fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
@@ -0,0 +1,213 @@
+// Lifecycle hooks: user commands run when reminders are added, fall due, are completed or deleted
+//
+// Hooks come from two places, both optional:
+//   - commands listed under [hooks] in config.toml, run with `sh -c`
+//   - executables in ~/.config/remindme/hooks/ named after the event, e.g. `on-add` or `on-add.py`
+// Each hook gets the reminder as JSON on stdin and REMINDME_EVENT / REMINDME_ID, plus a
+// REMINDME_META_<KEY> for each metadata entry, in its environment. A failing hook is reported but never fails the operation that fired it.
+// The hooks for an event run at the same time, and any still running after
+// `timeout-seconds` are killed, so one stuck hook can't hold up the others.
+
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+use crate::config::HooksConfig;
+use crate::reminder::Reminder;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Add,
+    Due,
+    Upcoming,
+    Complete,
+    Delete,
+}
+
+impl Event {
+    pub fn name(self) -> &'static str {
+        match self {
+            Event::Add => "on-add",
+            Event::Due => "on-due",
+            Event::Upcoming => "on-upcoming",
+            Event::Complete => "on-complete",
+            Event::Delete => "on-delete",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Hooks {
+    config: HooksConfig,
+    dir: Option<PathBuf>,
+}
+
+impl Hooks {
+    pub fn new(config: &HooksConfig) -> Self {
+        let dir = config
+            .dir
+            .as_ref()
+            .map(|dir| PathBuf::from(shellexpand::tilde(dir).to_string()))
+            .or_else(|| dirs::config_dir().map(|dir| dir.join("remindme").join("hooks")));
+
+        Hooks { config: config.clone(), dir }
+    }
+
+    // Run every hook registered for `event`; errors are printed, not returned
+    pub fn fire(&self, event: Event, reminder: &Reminder) {
+        let mut hooks: Vec<Command> = self
+            .commands(event)
+            .iter()
+            .map(|command| {
+                let mut cmd = Command::new("sh");
+                cmd.arg("-c").arg(command);
+                cmd
+            })
+            .collect();
+        hooks.extend(self.scripts(event).into_iter().map(Command::new));
+
+        if hooks.is_empty() {
+            return;
+        }
+
+        let payload = match serde_json::to_vec(reminder) {
+            Ok(payload) => payload,
+            Err(e) => {
+                eprintln!("Failed to serialize reminder for {} hooks: {}", event.name(), e);
+                return;
+            }
+        };
+
+        let timeout = self.timeout();
+        thread::scope(|scope| {
+            for mut hook in hooks {
+                let payload = &payload;
+                scope.spawn(move || {
+                    hook.env("REMINDME_EVENT", event.name()).env("REMINDME_ID", &reminder.id).envs(meta_env(reminder));
+                    debug!(event = event.name(), id = %reminder.id, hook = ?hook.get_program(), "Running hook");
+                    if let Err(e) = run(&mut hook, payload, timeout) {
+                        warn!(event = event.name(), error = %format!("{:#}", e), "Hook failed");
+                        eprintln!("{} hook failed: {:#}", event.name(), e);
+                    }
+                });
+            }
+        });
+    }
+
+    // How long a hook or routed command may run; None waits for it however long it takes
+    pub fn timeout(&self) -> Option<Duration> {
+        (self.config.timeout_seconds > 0).then(|| Duration::from_secs(self.config.timeout_seconds))
+    }
+
+    // The commands and scripts `fire` would run for `event`, e.g. for `notify --dry-run`
+    pub fn list(&self, event: Event) -> Vec<String> {
+        let scripts = self.scripts(event).into_iter().map(|path| path.display().to_string());
+        self.commands(event).iter().cloned().chain(scripts).collect()
+    }
+
+    fn commands(&self, event: Event) -> &[String] {
+        match event {
+            Event::Add => &self.config.on_add,
+            Event::Due => &self.config.on_due,
+            Event::Upcoming => &self.config.on_upcoming,
+            Event::Complete => &self.config.on_complete,
+            Event::Delete => &self.config.on_delete,
+        }
+    }
+
+    // Executables in the hooks directory named `<event>` or `<event>.<ext>`, in name order
+    fn scripts(&self, event: Event) -> Vec<PathBuf> {
+        let Some(entries) = self.dir.as_ref().and_then(|dir| fs::read_dir(dir).ok()) else {
+            return Vec::new();
+        };
+
+        let prefix = format!("{}.", event.name());
+        let mut scripts: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|name| name == event.name() || name.starts_with(&prefix))
+            })
+            .filter(|path| is_executable(path))
+            .collect();
+        scripts.sort();
+        scripts
+    }
+}
+
+// Run one shell command for `event` with the reminder on stdin, e.g. a route picked by rules
+pub fn run_shell(command: &str, event: Event, reminder: &Reminder, timeout: Option<Duration>) -> Result<()> {
+    let payload = serde_json::to_vec(reminder).context("Failed to serialize reminder")?;
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(command)
+        .env("REMINDME_EVENT", event.name())
+        .env("REMINDME_ID", &reminder.id)
+        .envs(meta_env(reminder));
+    run(&mut cmd, &payload, timeout)
+}
+
+// REMINDME_META_CLIENT=acme for meta client=acme
+fn meta_env(reminder: &Reminder) -> impl Iterator<Item = (String, &String)> {
+    reminder.meta.iter().map(|(key, value)| {
+        let name: String = key.chars().map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' }).collect();
+        (format!("REMINDME_META_{}", name), value)
+    })
+}
+
+fn run(hook: &mut Command, payload: &[u8], timeout: Option<Duration>) -> Result<()> {
+    let mut child = hook
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to start {:?}", hook.get_program()))?;
+
+    // A hook that ignores stdin may exit before reading it; that's not an error
+    if let Some(mut stdin) = child.stdin.take()
+        && let Err(e) = stdin.write_all(payload)
+        && e.kind() != std::io::ErrorKind::BrokenPipe
+    {
+        return Err(e).context("Failed to write reminder to hook");
+    }
+
+    let status = match timeout {
+        None => child.wait().context("Failed to wait for hook")?,
+        Some(timeout) => {
+            let deadline = Instant::now() + timeout;
+            loop {
+                if let Some(status) = child.try_wait().context("Failed to wait for hook")? {
+                    break status;
+                }
+                if Instant::now() >= deadline {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    // For `sh -c COMMAND` the command says more than "sh"
+                    let name = hook.get_args().nth(1).unwrap_or(hook.get_program());
+                    return Err(anyhow!("{:?} was stopped after running for {}s", name, timeout.as_secs()));
+                }
+                thread::sleep(Duration::from_millis(50));
+            }
+        }
+    };
+    if !status.success() {
+        return Err(anyhow!("{:?} exited with {}", hook.get_program(), status));
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
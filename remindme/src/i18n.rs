@@ -0,0 +1,124 @@
+// Translations of user-facing messages, gettext-style
+//
+// Messages are looked up by their English text, so a string missing from a catalog (or a
+// language without one) is simply shown in English. Catalogs live in locales/<lang>.toml
+// and are compiled in; each maps the English message to its translation, with `{}` standing
+// for the values filled in, in the same order:
+//
+//   "{} reminder(s) notified." = "{} Erinnerung(en) gemeldet."
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+use crate::config::DisplayConfig;
+use crate::error::RemindError;
+
+// (language code, catalog) for every language besides English
+const CATALOGS: &[(&str, &str)] = &[("de", include_str!("../locales/de.toml"))];
+
+static MESSAGES: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+// Pick the language once at startup from display.language, or LC_ALL, LC_MESSAGES or LANG
+pub fn init(config: &DisplayConfig) -> Result<()> {
+    let catalog = match &config.language {
+        Some(name) => {
+            let language = language_code(name);
+            if language == "en" {
+                None
+            } else {
+                let catalog = CATALOGS.iter().find(|(code, _)| *code == language).ok_or_else(|| {
+                    RemindError::Config(format!(
+                        "Unknown display.language '{}'. Available: en, {}",
+                        name,
+                        CATALOGS.iter().map(|(code, _)| *code).collect::<Vec<_>>().join(", ")
+                    ))
+                })?;
+                Some(catalog.1)
+            }
+        }
+        // Languages without a catalog fall back to English
+        None => env_language().and_then(|language| CATALOGS.iter().find(|(code, _)| *code == language)).map(|c| c.1),
+    };
+
+    let messages = match catalog {
+        Some(catalog) => toml::from_str(catalog)
+            .map_err(|e| RemindError::Config(format!("Broken translation catalog: {}", e)))?,
+        None => HashMap::new(),
+    };
+    let _ = MESSAGES.set(messages);
+    Ok(())
+}
+
+// "de_DE.UTF-8" -> "de"; "C" and "POSIX" are English
+fn language_code(name: &str) -> String {
+    match name.split(['_', '-', '.', '@']).next().unwrap_or(name) {
+        "C" | "POSIX" | "" => "en".to_string(),
+        code => code.to_lowercase(),
+    }
+}
+
+fn env_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .find_map(|var| env::var(var).ok().filter(|value| !value.is_empty()))
+        .map(|name| language_code(&name))
+}
+
+// The translation of `message`, or `message` itself
+pub fn translate(message: &str) -> &str {
+    MESSAGES
+        .get()
+        .and_then(|messages| messages.get(message))
+        .map_or(message, String::as_str)
+}
+
+// Replace each `{}` in `template` with the next argument
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut parts = template.split("{}");
+    let mut filled = parts.next().unwrap_or_default().to_string();
+    for (i, part) in parts.enumerate() {
+        if let Some(arg) = args.get(i) {
+            filled.push_str(&arg.to_string());
+        }
+        filled.push_str(part);
+    }
+    filled
+}
+
+// tr!("Reminder completed.") or tr!("Next occurrence: {}", due), translated
+macro_rules! tr {
+    ($message:literal) => {
+        $crate::i18n::translate($message).to_string()
+    };
+    ($message:literal, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::fill($crate::i18n::translate($message), &[$(&$arg as &dyn std::fmt::Display),+])
+    };
+}
+
+pub(crate) use tr;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A duplicate key or a stray quote breaks a whole language, and only shows once it's picked
+    #[test]
+    fn catalogs_parse_and_keep_placeholders() {
+        for (language, catalog) in CATALOGS {
+            let messages: HashMap<String, String> =
+                toml::from_str(catalog).unwrap_or_else(|e| panic!("locales/{}.toml: {}", language, e));
+            for (message, translation) in &messages {
+                assert_eq!(
+                    message.matches("{}").count(),
+                    translation.matches("{}").count(),
+                    "locales/{}.toml: {:?}",
+                    language,
+                    message
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// Sidecar index of ids and due times, kept next to the reminders file
+//
+// Parsing thousands of reminders just to learn that nothing is due is wasteful, and
+// `notify` runs every minute. The index holds what the hot paths need to decide whether
+// the full file must be loaded at all. It records the size and modification time of the
+// reminders file and journal it was built from, so edits by other tools (sync, merge, a
+// text editor) simply make it stale and it gets rebuilt on the next query.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::journal;
+use crate::reminder::Reminder;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexEntry {
+    pub id: String,
+    pub due_time: DateTime<Local>,
+    pub completed: bool,
+    #[serde(default)]
+    pub next_alert: Option<DateTime<Local>>, // Before due_time while an --alert is still to be sent
+}
+
+#[derive(Serialize, Deserialize)]
+struct IndexFile {
+    source: Fingerprint,
+    entries: Vec<IndexEntry>,
+}
+
+// Size and modification time of the reminders file and its journal when the index was built
+#[derive(PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fingerprint {
+    files: Vec<Option<(u64, u128)>>,
+}
+
+impl Fingerprint {
+    pub fn of(data_path: &Path) -> Self {
+        let stat = |path: &Path| {
+            let metadata = fs::metadata(path).ok()?;
+            let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+            Some((metadata.len(), modified.as_nanos()))
+        };
+        Fingerprint { files: vec![stat(data_path), stat(&journal::path_for(data_path))] }
+    }
+}
+
+pub fn path_for(data_path: &Path) -> PathBuf {
+    let mut name = data_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".idx");
+    data_path.with_file_name(name)
+}
+
+pub fn entries(reminders: &[Reminder]) -> Vec<IndexEntry> {
+    reminders
+        .iter()
+        .map(|r| IndexEntry {
+            id: r.id.clone(),
+            due_time: r.due_time,
+            completed: r.completed,
+            next_alert: Some(r.next_alert()).filter(|alert| *alert < r.due_time),
+        })
+        .collect()
+}
+
+// The index for `data_path`, or None if it is missing or out of date
+pub fn read(data_path: &Path) -> Option<Vec<IndexEntry>> {
+    let contents = fs::read(path_for(data_path)).ok()?;
+    let index: IndexFile = serde_json::from_slice(&contents).ok()?;
+    (index.source == Fingerprint::of(data_path)).then_some(index.entries)
+}
+
+// Record the index for the current state of `data_path`; call after writing the data file
+pub fn write(data_path: &Path, entries: Vec<IndexEntry>) -> Result<()> {
+    let source = Fingerprint::of(data_path);
+    let json = serde_json::to_vec(&IndexFile { source, entries })
+        .context("Failed to serialize reminders index")?;
+    fs::write(path_for(data_path), json).context("Failed to write reminders index")
+}
+
+pub fn remove(data_path: &Path) {
+    let _ = fs::remove_file(path_for(data_path));
+}
@@ -0,0 +1,661 @@
+// Reminders from emailed calendar invitations (`remind import --from-email`)
+//
+// The message is searched part by part for the invitation: a text/calendar part or an .ics
+// attachment, base64 or quoted-printable encoded as mail clients send them. A bare .ics file works
+// too. Its first event becomes the reminder: the summary as the text (the subject if it has none),
+// the start as the due time, the description as notes, and the location, organizer, meeting link
+// and UID as metadata. The heads-up comes from the invitation's own alarms, or is 15 minutes before.
+//
+// Organizers send the invitation again when a meeting moves or is called off. The UID finds the
+// reminder made from the first one, so importing an update reschedules it and a cancellation
+// deletes it, instead of leaving two reminders for one meeting.
+//
+// Start times in UTC, with an IANA TZID such as Europe/Berlin, or with a TZID defined by the
+// invitation's VTIMEZONE (Outlook's "W. Europe Standard Time") are converted to local time; a
+// floating time is already local.
+
+use anyhow::Result;
+use base64::Engine;
+use chrono::{DateTime, Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use std::fs;
+
+use crate::cli;
+use crate::config::Config;
+use crate::error::RemindError;
+use crate::holidays::Calendar;
+use crate::i18n::tr;
+use crate::reminder::{RecurrenceType, Reminder};
+use crate::storage::Storage;
+use crate::utils;
+
+// Heads-up before a meeting whose invitation has no alarm of its own
+const DEFAULT_ALERT_MINUTES: i64 = 15;
+
+// Metadata key holding the event's UID, which later invitations for the same meeting share
+const UID_KEY: &str = "event_uid";
+
+// The parts of an invitation that become the reminder
+struct Invite {
+    cancelled: bool,
+    uid: Option<String>,
+    summary: Option<String>,
+    start: DateTime<Local>,
+    all_day: bool,
+    recurrence: RecurrenceType,
+    // RRULE parts the recurrence above can't express, e.g. an end date
+    dropped_rule: bool,
+    exdates: Vec<NaiveDate>,
+    location: Option<String>,
+    organizer: Option<String>,
+    url: Option<String>,
+    description: Option<String>,
+    // Minutes before the start of each of the invitation's alarms
+    alarms: Vec<i64>,
+    // The start's TZID, when it was neither an IANA name nor defined in the invitation
+    unknown_zone: Option<String>,
+}
+
+// Add a reminder for the meeting invitation in the email (or .ics file) at `path`, or update or
+// delete the one made from an earlier invitation for it. `alerts` replace the invitation's alarms
+pub fn import(storage: &mut Storage, config: &Config, path: &str, alerts: &[i64]) -> Result<()> {
+    let path = shellexpand::tilde(path).to_string();
+    let data = fs::read(&path).map_err(RemindError::access(&path))?;
+    let message = String::from_utf8_lossy(&data).replace("\r\n", "\n");
+    let ics = find_calendar(&message)
+        .ok_or_else(|| RemindError::InvalidInput(format!("{} has no calendar invitation (.ics) in it", path)))?;
+    let subject = header_value(&message, "subject").map(|subject| decode_words(&subject));
+    let invite = parse(&ics, &config.notifications.all_day_time)?;
+    let existing = invite
+        .uid
+        .as_deref()
+        .and_then(|uid| storage.load().ok()?.into_iter().find(|r| r.meta.get(UID_KEY).is_some_and(|v| v == uid)));
+
+    if invite.cancelled {
+        match existing {
+            Some(reminder) => {
+                storage.delete_reminder(&reminder.id)?;
+                println!("{}", tr!("Deleted '{}': the meeting was cancelled.", reminder.text));
+            }
+            None => println!("{}", tr!("The meeting was cancelled; there's no reminder for it to delete.")),
+        }
+        return Ok(());
+    }
+
+    let text = invite
+        .summary
+        .clone()
+        .or(subject)
+        .filter(|text| !text.trim().is_empty())
+        .ok_or_else(|| RemindError::InvalidInput(format!("The invitation in {} has no title", path)))?;
+    let mut reminder = existing.clone().unwrap_or_else(|| {
+        let mut reminder = Reminder::new(text.clone(), invite.start, RecurrenceType::None);
+        for tag in &config.defaults.tags {
+            reminder.add_tag(tag);
+        }
+        reminder
+    });
+    if existing.is_none() {
+        reminder.priority = config.defaults.priority()?;
+    }
+    reminder.text = text;
+    reminder.due_time = invite.start;
+    reminder.nominal_due = None;
+    reminder.anchor = None;
+    reminder.recurrence = invite.recurrence.clone();
+    reminder.exdates = invite.exdates.clone();
+    let alerts = match (alerts, invite.alarms.as_slice()) {
+        ([], []) if invite.all_day => Vec::new(),
+        ([], []) => vec![DEFAULT_ALERT_MINUTES],
+        ([], alarms) => alarms.to_vec(),
+        (alerts, _) => alerts.to_vec(),
+    };
+    reminder.set_alerts(&alerts);
+    reminder.set_notes(invite.description.as_deref().unwrap_or(""));
+    reminder.set_meta("location", invite.location.as_deref().unwrap_or(""));
+    reminder.set_meta("organizer", invite.organizer.as_deref().unwrap_or(""));
+    reminder.set_meta("url", invite.url.as_deref().unwrap_or(""));
+    reminder.set_meta(UID_KEY, invite.uid.as_deref().unwrap_or(""));
+    reminder.clear_notified();
+
+    // A series imported after it started picks up at its next meeting
+    let now = Local::now();
+    let calendar = Calendar::from_config(&config.holidays)?;
+    reminder.skip_missed(now, Duration::zero(), &calendar);
+    if reminder.due_time <= now {
+        return Err(RemindError::InvalidInput(format!(
+            "'{}' already took place ({})",
+            reminder.text,
+            utils::format_datetime(&reminder.due_time)
+        ))
+        .into());
+    }
+
+    let due = utils::format_datetime(&reminder.due_time);
+    let headline = reminder.headline();
+    if existing.is_some() {
+        storage.update_reminder(reminder.clone())?;
+        println!("{}", tr!("Updated '{}' from the new invitation (due {}).", headline, due));
+    } else {
+        storage.add_reminder(reminder.clone())?;
+        println!("{}", tr!("Imported '{}' (due {}).", headline, due));
+    }
+    if !reminder.alerts.is_empty() {
+        let alerts: Vec<String> = reminder.alerts.iter().map(|minutes| utils::format_minutes(*minutes)).collect();
+        println!("{}", tr!("Heads-up {} before.", alerts.join(", ")));
+    }
+    if invite.dropped_rule {
+        println!("{}", tr!("Warning: the meeting repeats in a way remindme can't follow exactly; check the recurrence."));
+    }
+    if let Some(zone) = invite.unknown_zone {
+        println!("{}", tr!("Warning: unknown time zone '{}', so the start was read as local time.", zone));
+    }
+    Ok(())
+}
+
+// The calendar in a message: the first text/calendar part or .ics attachment, decoded, or the
+// whole text when it's a bare .ics file
+fn find_calendar(message: &str) -> Option<String> {
+    if message.trim_start().starts_with("BEGIN:VCALENDAR") {
+        return Some(message.to_string());
+    }
+    let (headers, body) = message.split_once("\n\n").unwrap_or((message, ""));
+    let content_type = header_value(headers, "content-type").unwrap_or_default();
+    let mime_type = content_type.split(';').next().unwrap_or("").trim().to_lowercase();
+
+    if mime_type.starts_with("multipart/") {
+        let boundary = param(&content_type, "boundary")?;
+        let delimiter = format!("--{}", boundary);
+        // Parts sit between delimiter lines; the text before the first and after the last isn't one
+        return body
+            .split(&delimiter)
+            .skip(1)
+            .take_while(|part| !part.starts_with("--"))
+            .find_map(|part| find_calendar(part.strip_prefix('\n').unwrap_or(part)));
+    }
+
+    let disposition = header_value(headers, "content-disposition").unwrap_or_default();
+    let filename = param(&disposition, "filename").or_else(|| param(&content_type, "name")).unwrap_or_default();
+    let is_calendar = matches!(mime_type.as_str(), "text/calendar" | "application/ics")
+        || filename.to_lowercase().ends_with(".ics");
+    if !is_calendar {
+        return None;
+    }
+    let encoding = header_value(headers, "content-transfer-encoding").unwrap_or_default().to_lowercase();
+    let decoded = match encoding.trim() {
+        "base64" => {
+            let compact: String = body.chars().filter(|c| !c.is_whitespace()).collect();
+            base64::engine::general_purpose::STANDARD.decode(compact).ok()?
+        }
+        "quoted-printable" => decode_quoted_printable(body),
+        _ => body.as_bytes().to_vec(),
+    };
+    let ics = String::from_utf8_lossy(&decoded).replace("\r\n", "\n");
+    ics.contains("BEGIN:VEVENT").then_some(ics)
+}
+
+// A header's value, with folded continuation lines joined; names are matched ignoring case
+fn header_value(headers: &str, name: &str) -> Option<String> {
+    let headers = headers.split("\n\n").next().unwrap_or("");
+    let mut value: Option<String> = None;
+    for line in headers.lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some(value) = value.as_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+            continue;
+        }
+        if value.is_some() {
+            break;
+        }
+        if let Some((key, rest)) = line.split_once(':')
+            && key.trim().eq_ignore_ascii_case(name)
+        {
+            value = Some(rest.trim().to_string());
+        }
+    }
+    value
+}
+
+// A `key=value` parameter of a header value, without its quotes
+fn param(value: &str, key: &str) -> Option<String> {
+    value.split(';').skip(1).find_map(|part| {
+        let (name, value) = part.split_once('=')?;
+        name.trim().eq_ignore_ascii_case(key).then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+fn decode_quoted_printable(body: &str) -> Vec<u8> {
+    let bytes = body.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'=' {
+            // "=" at the end of a line joins it to the next
+            if bytes.get(i + 1) == Some(&b'\n') {
+                i += 2;
+                continue;
+            }
+            if let Some(byte) = body.get(i + 1..i + 3).and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    out
+}
+
+// A subject with RFC 2047 encoded words, e.g. "=?UTF-8?B?...?=" or "=?utf-8?Q?...?=", as text
+fn decode_words(text: &str) -> String {
+    let mut out = String::new();
+    let mut after_encoded = false;
+    for word in text.split_whitespace() {
+        let encoded = word.strip_prefix("=?").and_then(|w| w.strip_suffix("?="));
+        // Only the space between two encoded words is dropped; next to plain text it stays
+        let joined = after_encoded && encoded.is_some();
+        if !out.is_empty() && !joined {
+            out.push(' ');
+        }
+        after_encoded = encoded.is_some();
+        let Some(inner) = encoded else {
+            out.push_str(word);
+            continue;
+        };
+        let mut fields = inner.splitn(3, '?');
+        let (_charset, encoding, encoded) = (fields.next(), fields.next().unwrap_or(""), fields.next().unwrap_or(""));
+        let bytes = if encoding.eq_ignore_ascii_case("b") {
+            base64::engine::general_purpose::STANDARD.decode(encoded).unwrap_or_default()
+        } else {
+            decode_quoted_printable(&encoded.replace('_', " "))
+        };
+        out.push_str(&String::from_utf8_lossy(&bytes));
+    }
+    out
+}
+
+// One content line of the calendar: "DTSTART;TZID=Europe/Berlin:20261020T100000"
+pub struct Property<'a> {
+    pub name: String,
+    pub params: Vec<(String, String)>,
+    pub value: &'a str,
+}
+
+impl Property<'_> {
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.params.iter().find(|(name, _)| name == key).map(|(_, value)| value.as_str())
+    }
+}
+
+pub fn property(line: &str) -> Option<Property<'_>> {
+    // The value starts at the first colon outside quotes, so "mailto:" in a value stays in it
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(i, c)| {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        (c == ':' && !quoted).then_some(i)
+    })?;
+    let mut parts = line[..colon].split(';');
+    let name = parts.next()?.to_uppercase();
+    let params = parts
+        .filter_map(|part| part.split_once('='))
+        .map(|(key, value)| (key.to_uppercase(), value.trim_matches('"').to_string()))
+        .collect();
+    Some(Property { name, params, value: &line[colon + 1..] })
+}
+
+// A time zone from a VTIMEZONE block: each STANDARD or DAYLIGHT part starts an offset at a time
+// that comes back every year
+pub struct Zone {
+    id: String,
+    parts: Vec<ZonePart>,
+}
+
+#[derive(Default)]
+struct ZonePart {
+    offset: Option<FixedOffset>,
+    start: Option<NaiveDateTime>,
+    // From the RRULE: the month and nth weekday (-1 for the last) it starts on every year
+    yearly: Option<(u32, i32, Weekday)>,
+}
+
+impl Zone {
+    // The offset in effect at a local time: that of the part that started most recently
+    fn offset_at(&self, time: NaiveDateTime) -> Option<FixedOffset> {
+        self.parts
+            .iter()
+            .filter_map(|part| Some((part.onset_before(time)?, part.offset?)))
+            .max_by_key(|(onset, _)| *onset)
+            .map(|(_, offset)| offset)
+    }
+}
+
+impl ZonePart {
+    fn onset_before(&self, time: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = self.start?;
+        let Some((month, nth, weekday)) = self.yearly else {
+            return (start <= time).then_some(start);
+        };
+        let onset = |year: i32| {
+            let date = if nth > 0 {
+                NaiveDate::from_weekday_of_month_opt(year, month, weekday, nth as u8)
+            } else {
+                NaiveDate::from_weekday_of_month_opt(year, month, weekday, 5)
+                    .or_else(|| NaiveDate::from_weekday_of_month_opt(year, month, weekday, 4))
+            };
+            date.map(|date| date.and_time(start.time()))
+        };
+        onset(time.year()).filter(|onset| *onset <= time).or_else(|| onset(time.year() - 1))
+    }
+}
+
+// Long lines are folded onto lines starting with a space or tab
+pub fn unfold(ics: &str) -> String {
+    ics.replace("\r\n", "\n").replace("\n ", "").replace("\n\t", "")
+}
+
+// The time zones defined by a calendar's VTIMEZONE blocks
+pub fn zones(unfolded: &str) -> Vec<Zone> {
+    let mut zones: Vec<Zone> = Vec::new();
+    let mut stack: Vec<String> = Vec::new();
+    for line in unfolded.lines() {
+        let Some(prop) = property(line.trim_end()) else { continue };
+        match prop.name.as_str() {
+            "BEGIN" => {
+                let component = prop.value.to_uppercase();
+                match component.as_str() {
+                    "VTIMEZONE" => zones.push(Zone { id: String::new(), parts: Vec::new() }),
+                    "STANDARD" | "DAYLIGHT" => {
+                        if let Some(zone) = zones.last_mut() {
+                            zone.parts.push(ZonePart::default());
+                        }
+                    }
+                    _ => {}
+                }
+                stack.push(component);
+                continue;
+            }
+            "END" => {
+                stack.pop();
+                continue;
+            }
+            _ => {}
+        }
+        let path: Vec<&str> = stack.iter().map(String::as_str).collect();
+        match path.as_slice() {
+            ["VCALENDAR", "VTIMEZONE"] if prop.name == "TZID" => {
+                if let Some(zone) = zones.last_mut() {
+                    zone.id = prop.value.to_string();
+                }
+            }
+            ["VCALENDAR", "VTIMEZONE", "STANDARD" | "DAYLIGHT"] => {
+                let Some(part) = zones.last_mut().and_then(|zone| zone.parts.last_mut()) else { continue };
+                match prop.name.as_str() {
+                    "TZOFFSETTO" => part.offset = parse_offset(prop.value),
+                    "DTSTART" => part.start = NaiveDateTime::parse_from_str(prop.value, "%Y%m%dT%H%M%S").ok(),
+                    "RRULE" => part.yearly = yearly_onset(prop.value),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    zones
+}
+
+// The invitation's METHOD and its first event
+fn parse(ics: &str, all_day_time: &str) -> Result<Invite> {
+    let unfolded = unfold(ics);
+    let zones = zones(&unfolded);
+    let mut cancelled = false;
+    let mut stack: Vec<String> = Vec::new();
+    let mut start: Option<(String, Option<String>)> = None;
+    let mut rule: Option<String> = None;
+    let mut exdates = Vec::new();
+    let mut invite = Invite {
+        cancelled: false,
+        uid: None,
+        summary: None,
+        start: Local::now(),
+        all_day: false,
+        recurrence: RecurrenceType::None,
+        dropped_rule: false,
+        exdates: Vec::new(),
+        location: None,
+        organizer: None,
+        url: None,
+        description: None,
+        alarms: Vec::new(),
+        unknown_zone: None,
+    };
+    let mut events = 0;
+
+    for line in unfolded.lines() {
+        let Some(prop) = property(line.trim_end()) else { continue };
+        match prop.name.as_str() {
+            "BEGIN" => {
+                let component = prop.value.to_uppercase();
+                if component == "VEVENT" {
+                    events += 1;
+                }
+                stack.push(component);
+                continue;
+            }
+            "END" => {
+                stack.pop();
+                continue;
+            }
+            _ => {}
+        }
+        let path: Vec<&str> = stack.iter().map(String::as_str).collect();
+        match path.as_slice() {
+            ["VCALENDAR"] if prop.name == "METHOD" => cancelled = prop.value.eq_ignore_ascii_case("CANCEL"),
+            // Only the first event is read; later ones are exceptions to a series or other meetings
+            ["VCALENDAR", "VEVENT"] if events == 1 => match prop.name.as_str() {
+                "UID" => invite.uid = Some(prop.value.to_string()),
+                "SUMMARY" => invite.summary = Some(unescape(prop.value)),
+                "LOCATION" => invite.location = Some(unescape(prop.value)).filter(|l| !l.is_empty()),
+                "DESCRIPTION" => invite.description = Some(unescape(prop.value)),
+                "STATUS" if prop.value.eq_ignore_ascii_case("CANCELLED") => invite.cancelled = true,
+                "URL" | "X-GOOGLE-CONFERENCE" | "X-MICROSOFT-SKYPETEAMSMEETINGURL" => {
+                    invite.url.get_or_insert_with(|| prop.value.to_string());
+                }
+                "ORGANIZER" => {
+                    let address = prop.value.trim_start_matches("mailto:").trim_start_matches("MAILTO:");
+                    invite.organizer = Some(match prop.param("CN") {
+                        Some(name) => format!("{} <{}>", name, address),
+                        None => address.to_string(),
+                    });
+                }
+                "DTSTART" => start = Some((prop.value.to_string(), prop.param("TZID").map(str::to_string))),
+                "RRULE" => rule = Some(prop.value.to_string()),
+                "EXDATE" => exdates.extend(prop.value.split(',').filter_map(|value| value.get(..8)).map(str::to_string)),
+                _ => {}
+            },
+            ["VCALENDAR", "VEVENT", "VALARM"] if events == 1 && prop.name == "TRIGGER" => {
+                // A trigger relative to the start, e.g. -PT15M; an absolute one is left out
+                if prop.param("VALUE").is_none_or(|value| !value.eq_ignore_ascii_case("DATE-TIME"))
+                    && let Some(minutes) = parse_trigger(prop.value)
+                {
+                    invite.alarms.push(minutes);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let (value, tzid) = start.ok_or_else(|| RemindError::InvalidInput("The invitation has no start time (DTSTART)".into()))?;
+    invite.cancelled |= cancelled;
+    invite.all_day = value.len() == 8;
+    invite.start = if invite.all_day {
+        let date = NaiveDate::parse_from_str(&value, "%Y%m%d")
+            .map_err(|_| RemindError::InvalidInput(format!("Unreadable start date '{}' in the invitation", value)))?;
+        let time = cli::parse_time(all_day_time)?;
+        utils::local(date.and_time(time))
+    } else {
+        let naive = NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S")
+            .map_err(|_| RemindError::InvalidInput(format!("Unreadable start time '{}' in the invitation", value)))?;
+        match tzid {
+            _ if value.ends_with('Z') => Utc.from_utc_datetime(&naive).with_timezone(&Local),
+            Some(tzid) => match zoned(naive, &tzid, &zones) {
+                Some(start) => start,
+                None => {
+                    invite.unknown_zone = Some(tzid);
+                    utils::local(naive)
+                }
+            },
+            None => utils::local(naive),
+        }
+    };
+    invite.exdates = exdates.iter().filter_map(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok()).collect();
+    if let Some(rule) = rule {
+        (invite.recurrence, invite.dropped_rule) = recurrence(&rule, invite.start.weekday());
+    }
+    Ok(invite)
+}
+
+// `naive` in the zone named `tzid`: an IANA name (also at the end of a path such as
+// "/mozilla.org/20050126_1/Europe/Berlin") or a zone defined in the invitation
+pub fn zoned(naive: NaiveDateTime, tzid: &str, zones: &[Zone]) -> Option<DateTime<Local>> {
+    let segments: Vec<&str> = tzid.split('/').collect();
+    let iana = (0..segments.len()).find_map(|i| segments[i..].join("/").parse::<chrono_tz::Tz>().ok());
+    if let Some(tz) = iana {
+        return tz.from_local_datetime(&naive).earliest().map(|time| time.with_timezone(&Local));
+    }
+    let offset = zones.iter().find(|zone| zone.id == tzid)?.offset_at(naive)?;
+    offset.from_local_datetime(&naive).single().map(|time| time.with_timezone(&Local))
+}
+
+// "+0100", "-0500" or "+053000"
+fn parse_offset(value: &str) -> Option<FixedOffset> {
+    let sign = if value.starts_with('-') { -1 } else { 1 };
+    let digits = value.trim_start_matches(['+', '-']);
+    let hours: i32 = digits.get(..2)?.parse().ok()?;
+    let minutes: i32 = digits.get(2..4)?.parse().ok()?;
+    let seconds: i32 = digits.get(4..6).and_then(|s| s.parse().ok()).unwrap_or(0);
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60 + seconds))
+}
+
+// The month and weekday a time zone's RRULE starts on, e.g. FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU
+fn yearly_onset(rule: &str) -> Option<(u32, i32, Weekday)> {
+    let parts = rule_parts(rule);
+    let month = parts.iter().find(|(key, _)| *key == "BYMONTH")?.1.parse().ok()?;
+    let (nth, weekday) = by_day(parts.iter().find(|(key, _)| *key == "BYDAY")?.1)?;
+    Some((month, nth.unwrap_or(1), weekday))
+}
+
+pub fn rule_parts(rule: &str) -> Vec<(&str, &str)> {
+    rule.split(';').filter_map(|part| part.split_once('=')).collect()
+}
+
+// "2TU" as (Some(2), Tuesday), "-1SU" as (Some(-1), Sunday), "MO" as (None, Monday)
+pub fn by_day(value: &str) -> Option<(Option<i32>, Weekday)> {
+    let split = value.len().checked_sub(2)?;
+    let weekday = match &value[split..] {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    };
+    let nth = &value[..split];
+    let nth = if nth.is_empty() { None } else { Some(nth.trim_start_matches('+').parse().ok()?) };
+    Some((nth, weekday))
+}
+
+// The recurrence closest to an event's RRULE, and whether anything in the rule was left out
+// (an end, or days this recurrence can't express)
+fn recurrence(rule: &str, start_day: Weekday) -> (RecurrenceType, bool) {
+    let parts = rule_parts(rule);
+    let get = |key: &str| parts.iter().find(|(k, _)| *k == key).map(|(_, value)| *value);
+    let interval: u32 = get("INTERVAL").and_then(|n| n.parse().ok()).unwrap_or(1).max(1);
+    let days: Vec<(Option<i32>, Weekday)> = get("BYDAY").map_or_else(Vec::new, |days| days.split(',').filter_map(by_day).collect());
+    let weekdays = [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri];
+    let ends = get("COUNT").is_some() || get("UNTIL").is_some();
+
+    let (recurrence, exact) = match get("FREQ").unwrap_or("") {
+        "DAILY" if interval == 1 && days.is_empty() => (RecurrenceType::Daily, true),
+        "DAILY" if days.is_empty() => (RecurrenceType::EveryDays(interval), true),
+        "WEEKLY" | "DAILY" if interval == 1 && days.len() == 5 && weekdays.iter().all(|d| days.iter().any(|(_, day)| day == d)) => {
+            (RecurrenceType::Weekdays, true)
+        }
+        "WEEKLY" => {
+            let exact = days.is_empty() || days.iter().all(|(_, day)| *day == start_day);
+            let recurrence = if interval == 1 { RecurrenceType::Weekly } else { RecurrenceType::WeeklyOn { interval, weekday: start_day } };
+            (recurrence, exact)
+        }
+        "MONTHLY" if get("BYMONTHDAY") == Some("-1") => (RecurrenceType::MonthlyLastDay, interval == 1),
+        "MONTHLY" => {
+            // "2TU", or "TU" with BYSETPOS=2
+            let nth = days.first().and_then(|(nth, _)| *nth).or_else(|| get("BYSETPOS").and_then(|n| n.parse().ok()));
+            match (days.as_slice(), nth) {
+                ([(_, weekday)], Some(nth)) if nth == -1 || (1..=4).contains(&nth) => {
+                    (RecurrenceType::MonthlyOn { nth, weekday: *weekday }, interval == 1)
+                }
+                ([], _) => (RecurrenceType::Monthly, interval == 1),
+                _ => (RecurrenceType::Monthly, false),
+            }
+        }
+        "YEARLY" => (RecurrenceType::Yearly, interval == 1 && days.is_empty()),
+        _ => (RecurrenceType::None, false),
+    };
+    (recurrence, ends || !exact)
+}
+
+// Minutes before the start for an alarm trigger such as "-PT15M", "-P1D" or "-PT1H30M"; None for
+// one after the start
+fn parse_trigger(value: &str) -> Option<i64> {
+    let minutes = -duration_minutes(value)?;
+    (minutes >= 0).then_some(minutes)
+}
+
+// A duration such as "PT1H30M", "P1D" or "-PT15M" in minutes, negative for one with a minus sign
+pub fn duration_minutes(value: &str) -> Option<i64> {
+    let sign = if value.starts_with('-') { -1 } else { 1 };
+    let duration = value.trim_start_matches(['+', '-']).strip_prefix('P')?;
+    let mut minutes = 0;
+    let mut number = String::new();
+    for c in duration.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                minutes += match unit {
+                    'W' => n * 7 * 24 * 60,
+                    'D' => n * 24 * 60,
+                    'H' => n * 60,
+                    'M' => n,
+                    'S' => 0,
+                    _ => return None,
+                };
+            }
+        }
+    }
+    Some(sign * minutes)
+}
+
+// Text values escape commas, semicolons, backslashes and newlines
+pub fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out.trim().to_string()
+}
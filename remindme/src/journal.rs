@@ -0,0 +1,140 @@
+// Append-only journal of changes to the reminders file
+//
+// Instead of rewriting the whole reminders file for every change, adds, updates and
+// deletes are appended as JSON lines to `<file>.journal`. Loading replays the journal on
+// top of the snapshot in the reminders file itself; once the journal grows past a limit
+// it is compacted by writing a new snapshot and starting an empty journal. The entries folded
+// into a snapshot are kept in `<file>.history`, the record of every change with its time;
+// past HISTORY_LIMIT_BYTES it is rotated to `<file>.history.1`, replacing the one before.
+//
+// A crash while appending can at worst leave a torn last line, which is skipped on load.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use crate::reminder::Reminder;
+
+// Size at which the history is rotated
+const HISTORY_LIMIT_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum Op {
+    Add { reminder: Reminder },
+    Update { reminder: Reminder },
+    Delete { id: String },
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    at: DateTime<Local>,
+    #[serde(flatten)]
+    op: Op,
+}
+
+pub fn path_for(data_path: &Path) -> PathBuf {
+    sibling(data_path, ".journal")
+}
+
+pub fn history_path_for(data_path: &Path) -> PathBuf {
+    sibling(data_path, ".history")
+}
+
+fn sibling(data_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = data_path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    data_path.with_file_name(name)
+}
+
+// Append one operation and flush it to disk; returns the number of entries now in the journal.
+// Lines are counted rather than parsed, and the journal never holds more than `compact_after`
+// of them, so this stays cheap however many reminders there are.
+pub fn append(data_path: &Path, op: Op) -> Result<usize> {
+    let path = path_for(data_path);
+    let mut line = serde_json::to_string(&Entry { at: Local::now(), op })
+        .context("Failed to serialize journal entry")?;
+    line.push('\n');
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open journal {}", path.display()))?;
+    file.write_all(line.as_bytes())
+        .and_then(|_| file.sync_data())
+        .with_context(|| format!("Failed to append to journal {}", path.display()))?;
+
+    let contents = fs::read(&path).with_context(|| format!("Failed to read journal {}", path.display()))?;
+    Ok(contents.iter().filter(|&&byte| byte == b'\n').count())
+}
+
+// Every operation in the journal, oldest first
+pub fn read(data_path: &Path) -> Result<Vec<Op>> {
+    let path = path_for(data_path);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read journal {}", path.display())),
+    };
+
+    let lines: Vec<&str> = contents.lines().filter(|l| !l.trim().is_empty()).collect();
+    let mut ops = Vec::with_capacity(lines.len());
+    for (i, line) in lines.iter().enumerate() {
+        match serde_json::from_str::<Entry>(line) {
+            Ok(entry) => ops.push(entry.op),
+            // Only the last line can be torn by a crash mid-append
+            Err(_) if i + 1 == lines.len() => {
+                eprintln!("Warning: ignoring incomplete last entry in {}", path.display());
+            }
+            Err(e) => return Err(anyhow!("Corrupt journal {} at line {}: {}", path.display(), i + 1, e)),
+        }
+    }
+
+    Ok(ops)
+}
+
+// Replay operations on top of a snapshot
+pub fn apply(reminders: &mut Vec<Reminder>, ops: Vec<Op>) {
+    for op in ops {
+        match op {
+            Op::Add { reminder } => reminders.push(reminder),
+            Op::Update { reminder } => {
+                if let Some(existing) = reminders.iter_mut().find(|r| r.id == reminder.id) {
+                    *existing = reminder;
+                }
+            }
+            Op::Delete { id } => reminders.retain(|r| r.id != id),
+        }
+    }
+}
+
+// Start a fresh journal after a new snapshot has been written, moving its entries to the history
+pub fn archive(data_path: &Path) -> Result<()> {
+    let path = path_for(data_path);
+    let entries = match fs::read(&path) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read journal {}", path.display())),
+    };
+
+    let history = history_path_for(data_path);
+    if fs::metadata(&history).is_ok_and(|meta| meta.len() >= HISTORY_LIMIT_BYTES) {
+        let mut rotated = history.clone().into_os_string();
+        rotated.push(".1");
+        fs::rename(&history, &rotated).with_context(|| format!("Failed to rotate {}", history.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&history)
+        .with_context(|| format!("Failed to open {}", history.display()))?;
+    file.write_all(&entries)
+        .and_then(|_| file.sync_data())
+        .with_context(|| format!("Failed to append to {}", history.display()))?;
+
+    fs::remove_file(&path).context("Failed to clear reminders journal")
+}
@@ -0,0 +1,72 @@
+// Diagnostic logging (`-v`, `-vv`, and the [logging] section of config.toml)
+//
+// Regular output is unchanged; log events go to stderr at the level picked with -v flags,
+// and optionally to a log file in the data directory that is rotated daily. The file is what
+// makes a failed `notify` run started by a systemd timer or cron diagnosable afterwards.
+
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{Builder, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::time::ChronoLocal;
+use tracing_subscriber::prelude::*;
+
+use crate::config::LoggingConfig;
+use crate::error::RemindError;
+
+// Install the subscriber; the returned guard flushes the log file when dropped
+pub fn init(verbosity: u8, config: &LoggingConfig) -> Result<Option<WorkerGuard>> {
+    // Errors are already reported by main, so stderr stays quiet unless asked for more
+    let stderr_level = match verbosity {
+        0 => LevelFilter::ERROR,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+    let stderr = tracing_subscriber::fmt::layer()
+        .with_writer(io::stderr)
+        .with_timer(ChronoLocal::rfc_3339())
+        .with_target(false)
+        .with_filter(stderr_level);
+
+    let (file, guard) = if config.file {
+        let level: LevelFilter = config.level.parse().map_err(|_| {
+            RemindError::Config(format!(
+                "Invalid logging.level '{}'. Expected error, warn, info, debug or trace",
+                config.level
+            ))
+        })?;
+        let dir = log_dir()?;
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create log directory {}", dir.display()))?;
+        let appender = Builder::new()
+            .rotation(Rotation::DAILY)
+            .filename_prefix("remindme")
+            .filename_suffix("log")
+            .max_log_files(config.keep_files.max(1))
+            .build(&dir)
+            .context("Failed to open the log file")?;
+        let (writer, guard) = tracing_appender::non_blocking(appender);
+        let layer = tracing_subscriber::fmt::layer()
+            .with_writer(writer)
+            .with_timer(ChronoLocal::rfc_3339())
+            .with_ansi(false)
+            .with_filter(level);
+        (Some(layer), Some(guard))
+    } else {
+        (None, None)
+    };
+
+    tracing_subscriber::registry().with(stderr).with(file).init();
+    Ok(guard)
+}
+
+// ~/.local/share/remindme/logs on Linux
+pub fn log_dir() -> Result<PathBuf> {
+    Ok(dirs::data_dir()
+        .ok_or_else(|| anyhow!("Failed to determine data directory"))?
+        .join("remindme")
+        .join("logs"))
+}
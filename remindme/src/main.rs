@@ -5,83 +5,388 @@ mod notification;
 mod utils;
 mod tui;  
 mod sound;
+mod format;
+mod config;
+mod crypto;
+mod merge;
+mod sync;
+mod server;
+mod hooks;
+mod rules;
+mod index;
+mod journal;
+mod status;
+mod dnd;
+mod holidays;
+mod alarm;
+mod pomodoro;
+mod review;
+mod error;
+mod logging;
+mod doctor;
+mod suggest;
+mod bulk;
+mod pick;
+mod colors;
+mod alias;
+mod i18n;
+mod maintain;
+mod daemon;
+mod termux;
+mod share;
+mod channels;
+mod ntfy;
+mod gotify;
+mod matrix;
+mod routing;
+mod remote;
+mod cache;
+mod compress;
+mod search;
+mod report;
+mod view;
+mod palette;
+mod toast;
+mod form;
+mod settings;
+mod escalation;
+mod invite;
+mod workspace;
+mod api;
+mod editor;
+mod stale;
+mod digest;
+mod week;
+mod sheet;
+mod uri;
+mod dedupe;
+mod when_done;
+mod busy;
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use clap::Parser;
-use cli::{Cli, Commands};
-use reminder::Reminder;
+use cli::{Cli, Commands, GroupBy};
+use reminder::{Priority, RecurrenceType, Reminder, ReminderKind, SortOrder};
+use holidays::Calendar;
 use storage::Storage;
 use notification::Notifier;
+use config::Config;
+use error::RemindError;
 use chrono::{DateTime, Datelike, Local};
+use std::io::{IsTerminal, Write};
+use i18n::tr;
+use sound::SoundAction;
+
+// Exit status used by `notify` and `list --due-now` when reminders are due,
+// so shell scripts and prompts can branch on it without parsing output
+const EXIT_REMINDERS_DUE: i32 = 3;
+
+// Exit status for errors that leave nothing to work with (see `RemindError::is_fatal`)
+const EXIT_FATAL: i32 = 4;
 
 fn main() {
-    if let Err(e) = run() {
-        // Print the error and its causes
-        eprintln!("Error: {}", e);
-        
-        let mut source = e.source();
-        while let Some(cause) = source {
-            eprintln!("Caused by: {}", cause);
-            source = cause.source();
+    match run() {
+        Ok(exit_code) => {
+            if exit_code != 0 {
+                std::process::exit(exit_code);
+            }
         }
-        
-        // Only exit for critical errors, otherwise continue
-        if is_critical_error(&e) {
-            eprintln!("Critical error - exiting program.");
-            std::process::exit(1);
+        Err(e) => {
+            // Print the error and its causes
+            eprintln!("{}", tr!("Error: {}", e));
+            
+            let mut source = e.source();
+            while let Some(cause) = source {
+                eprintln!("{}", tr!("Caused by: {}", cause));
+                source = cause.source();
+            }
+            
+            // Any failure, even a mistyped ID, is non-zero so scripts can tell it from success
+            let fatal = e.chain().any(|cause| cause.downcast_ref::<RemindError>().is_some_and(RemindError::is_fatal));
+            std::process::exit(if fatal { EXIT_FATAL } else { 1 });
         }
     }
 }
 
-// Determine if an error is critical enough to exit the program
-fn is_critical_error(error: &anyhow::Error) -> bool {
-    // Storage initialization errors are critical
-    if error.to_string().contains("Failed to initialize storage") {
-        return true;
-    }
+// Returns the process exit code on success
+fn run() -> Result<i32> {
+    // Load user configuration (defaults if no config file exists); a broken config only
+    // fails once we know the command isn't `doctor`
+    let config = Config::load();
     
-    // Access permission errors are critical
-    if error.to_string().contains("permission denied") {
-        return true;
+    // Parse command line arguments, with aliases from the config expanded first
+    let aliases = config.as_ref().map(|c| c.alias.clone()).unwrap_or_default();
+    let cli = Cli::parse_from(alias::expand(std::env::args_os(), &aliases)?);
+    
+    // The doctor has to work when the config or storage is broken, so it runs before loading them
+    if let Some(Commands::Doctor) = cli.command {
+        let shared = config.as_ref().ok().filter(|_| cli.shared).and_then(|config| config.team.file.clone());
+        return doctor::run(shared.as_deref().or(cli.storage_file.as_deref()));
     }
+    let mut config = config?;
+    config.display.a11y |= cli.a11y;
+    utils::init_display(&config.display)?;
+    i18n::init(&config.display)?;
+    
+    // Held until the end so buffered log lines reach the log file
+    let _log_guard = logging::init(cli.verbose, &config.logging)?;
+    tracing::debug!(args = ?std::env::args().skip(1).collect::<Vec<_>>(), "Starting");
+    
+    // --shared is a shortcut for the team's file
+    let storage_file = if cli.shared {
+        let file = config.team.file.clone().ok_or_else(|| {
+            RemindError::Config("--shared needs the team's reminders file as team.file in config.toml".into())
+        })?;
+        Some(file)
+    } else {
+        cli.storage_file.clone()
+    };
     
-    // Other errors can be handled gracefully without exiting
-    false
-}
-
-fn run() -> Result<()> {
     // Initialize the storage
-    let mut storage = Storage::new()
-        .context("Failed to initialize storage")?;
+    let mut storage = Storage::open(storage_file.as_deref(), &config)?;
+    if cli.read_only {
+        storage.set_read_only("--read-only was given");
+    }
     
-    // Parse command line arguments
-    let cli = Cli::parse();
+    // Commands that would change read-only reminders fail before doing anything; the TUI edits,
+    // so it counts as one of them
+    if cli.tui || cli.command.as_ref().is_some_and(Commands::writes) {
+        storage.check_writable()?;
+    }
     
     // If TUI mode is requested, start the TUI
     if cli.tui {
-        return tui::start_tui(storage);
+        tui::start_tui(storage, &config, cli.focus.as_deref())?;
+        return Ok(0);
     }
     
+    let mut exit_code = 0;
+    
     // Otherwise, continue with CLI mode
     match cli.command {
-        Some(Commands::Add { text, time, date, recurrence }) => {
-            // Use the helper function to parse time with default date logic
-            let due_time = cli::parse_datetime_with_default_date(&time, date.as_deref())?;
+        Some(Commands::Add { text, time, date, kind, recurrence, off_days, advance, renotify, alerts, expire_after, tags, context, assignee, notes, icon, meta, priority, escalate, follow_ups, after, dry_run, force, allow_past }) => {
+            let explicit_date = date.is_some();
+            // Whatever isn't given comes from [defaults] in the config
+            let defaults = &config.defaults;
+            let recurrence = recurrence.or_else(|| defaults.recurrence.clone()).unwrap_or_else(|| "none".to_string());
+            let priority = match priority {
+                Some(priority) => priority,
+                None => defaults.priority()?,
+            };
+            let mut reminder = if kind == ReminderKind::Reminder {
+                // Phrases like "weekdays at 9" carry their own time
+                let schedule = cli::parse_schedule(&recurrence)?;
+                let time = time
+                    .or_else(|| schedule.time.map(|t| t.format("%H:%M").to_string()))
+                    .or_else(|| defaults.time.clone())
+                    .ok_or_else(|| anyhow!("--time is required (HH:MM), or set defaults.time in config.toml"))?;
+                // Use the helper function to parse time with default date logic
+                let due_time = cli::parse_datetime_with_default_date(&time, date.as_deref(), config.input.date_order)?;
+                
+                // Start on the first day the pattern allows, e.g. the next Tuesday for "every tuesday"
+                let due_time = schedule.recurrence.align(due_time);
+                Reminder::new(text, due_time, schedule.recurrence)
+            } else {
+                // Birthdays and anniversaries are all-day: notify in the morning unless a time is given
+                let date = date.ok_or_else(|| anyhow!("--date is required for a {:?} (YYYY-MM-DD)", kind))?;
+                let since = cli::parse_date(&date, config.input.date_order)?;
+                let notify_at = cli::parse_time(time.as_deref().unwrap_or(&config.notifications.all_day_time))?;
+                Reminder::new_anniversary(kind, text, since, notify_at)?
+            };
+            reminder.off_days = off_days;
+            reminder.advance = advance;
+            if let Some(renotify) = renotify {
+                reminder.renotify_minutes = cli::parse_renotify(&renotify)?;
+            }
+            reminder.set_alerts(&alerts);
+            if let Some(expire_after) = expire_after {
+                reminder.expire_after_minutes = cli::parse_expire_after(&expire_after)?;
+            }
+            for tag in defaults.tags.iter().chain(&tags) {
+                reminder.add_tag(tag);
+            }
+            if let Some(context) = context {
+                reminder.set_context(&context);
+            }
+            if let Some(workspace) = workspace::current(&config)? {
+                workspace.apply(&mut reminder);
+            }
+            if let Some(assignee) = assignee {
+                reminder.set_assignee(&assignee);
+            }
+            if let Some(notes) = notes {
+                reminder.set_notes(&notes);
+            }
+            if let Some(icon) = icon {
+                reminder.set_icon(&icon);
+            }
+            for (key, value) in &meta {
+                reminder.set_meta(key, value);
+            }
+            reminder.priority = priority;
+            reminder.escalate = escalate;
+            reminder.follow_ups = cli::follow_ups(follow_ups, &after)?;
+            
+            if dry_run {
+                let calendar = Calendar::from_config(&config.holidays)?;
+                print_occurrences(&reminder, 5, &calendar);
+                return Ok(exit_code);
+            }
+            
+            confirm_month_end(&mut reminder, &Calendar::from_config(&config.holidays)?);
             
-            let recurrence_type = cli::parse_recurrence(&recurrence)?;
-            let reminder = Reminder::new(text, due_time, recurrence_type);
+            // Birthdays and anniversaries start in the past by design
+            if kind == ReminderKind::Reminder && explicit_date && !allow_past && !confirm_past(&reminder.due_time) {
+                println!("{}", tr!("Not added. Use --allow-past to add it anyway."));
+                return Ok(1);
+            }
+            
+            let window = chrono::Duration::minutes(config.conflicts.window_minutes);
+            if !force && window > chrono::Duration::zero() {
+                let existing = storage.load()?;
+                let conflicts = reminder.conflicts(&existing, window);
+                if !conflicts.is_empty() {
+                    println!("{}", tr!("Warning: {} other reminder(s) due within {} minutes:", conflicts.len(), window.num_minutes()));
+                    for other in &conflicts {
+                        println!("  - {}", tr!("{} (due {})", other.headline(), utils::format_datetime(&other.due_time)));
+                    }
+                    if !confirm(&tr!("Add it anyway?")) {
+                        println!("{}", tr!("Not added. Use --force to add it anyway."));
+                        return Ok(1);
+                    }
+                }
+            }
             storage.add_reminder(reminder)?;
-            println!("Reminder added successfully.");
+            println!("{}", tr!("Reminder added successfully."));
         },
         
-        Some(Commands::List) => {
-            let reminders = storage.load()?;
-            if reminders.is_empty() {
-                println!("No reminders found.");
+        Some(Commands::In { duration, text }) => {
+            let due_time = Local::now() + cli::parse_duration(&duration)?;
+            storage.add_reminder(Reminder::new(text, due_time, RecurrenceType::None))?;
+            println!("{}", tr!("Reminder set for {}.", due_time.format("%H:%M:%S")));
+        }
+        
+        Some(Commands::Timer { duration, text }) => {
+            let end = Local::now() + cli::parse_duration(&duration)?;
+            
+            utils::countdown(&text, end)?;
+            
+            // Hand the finished timer to the usual notification path (desktop, sound, hooks, fallbacks)
+            storage.add_reminder(Reminder::new(text, end, RecurrenceType::None))?;
+            let mut notifier = Notifier::new(storage, config);
+            notifier.check_due_reminders(true)?;
+        }
+        
+        Some(Commands::List { format, due_now, quiet, context, assignee, group_by, sort, limit, offset, all, vimgrep, quickfix }) => {
+            let now = Local::now();
+            // With --due-now the index usually shows nothing is due, so the file needn't be parsed
+            let mut reminders = if due_now && !storage.any_past_due(now)? {
+                Vec::new()
             } else {
-                println!("Your Reminders:");
-                for (i, reminder) in reminders.iter().enumerate() {
-                    println!("{}. {}", i + 1, reminder);
+                storage.load()?
+            };
+            if due_now {
+                reminders.retain(|r| !r.completed && r.due_time <= now);
+            }
+            if let Some(context) = context {
+                let context = reminder::normalize_context(&context);
+                reminders.retain(|r| r.context == context);
+            }
+            if let Some(assignee) = assignee {
+                let name = match assignee.as_str() {
+                    "me" => config.team.me().ok_or_else(|| anyhow!("Set team.name in config.toml to say who \"me\" is"))?,
+                    _ => assignee,
+                };
+                reminders.retain(|r| r.is_assigned_to(&name));
+            }
+            if due_now && !reminders.is_empty() {
+                exit_code = EXIT_REMINDERS_DUE;
+            }
+            
+            // Entries keep their number from the ungrouped list, so it still matches `delete --index`
+            let mut entries = list_entries(&reminders, group_by, sort, now, &config.priority);
+            let workspace = if all { None } else { workspace::current(&config)? };
+            if let Some(workspace) = &workspace {
+                // Filtered after numbering for the same reason
+                entries.retain(|(_, _, reminder)| workspace.includes(reminder));
+            }
+            let total = entries.len();
+            let page: Vec<_> = entries.into_iter().skip(offset).take(limit.unwrap_or(usize::MAX)).collect();
+            
+            if quiet {
+                // Only the exit code is wanted
+            } else if vimgrep || quickfix {
+                // Only the locations, whether or not any reminder has one, so an editor can parse it
+                let located = editor::located(page.iter().map(|(_, _, reminder)| *reminder));
+                if quickfix {
+                    println!("{}", editor::quickfix(&located, now));
+                } else {
+                    for (reminder, location) in &located {
+                        println!("{}", editor::vimgrep(reminder, location));
+                    }
+                }
+            } else if let Some(template) = format {
+                // Custom format: print only the rendered lines so the output can be piped
+                let template = format::Template::parse(&template)?;
+                for (_, i, reminder) in &page {
+                    println!("{}", template.render(*i, reminder));
+                }
+            } else if page.is_empty() {
+                if total > 0 {
+                    println!("{}", tr!("No reminders past --offset {} ({} in total).", offset, total));
+                } else if let Some(workspace) = &workspace {
+                    println!("{}", tr!("No reminders in workspace {} (list --all shows every one).", workspace.name));
+                } else {
+                    println!("{}", tr!("No reminders found."));
+                }
+            } else {
+                match &workspace {
+                    Some(workspace) => println!("{}", tr!("Your Reminders in workspace {}:", workspace.name)),
+                    None => println!("{}", tr!("Your Reminders:")),
+                }
+                // Color high and urgent reminders (after escalation) when printing to a terminal;
+                // for a screen reader they're named instead
+                let accessible = utils::accessible();
+                let color = std::io::stdout().is_terminal() && !accessible;
+                let mut section = None;
+                for (group, i, reminder) in &page {
+                    if group.is_some() && *group != section {
+                        println!("\n{}:", group.as_deref().unwrap_or_default());
+                        section = group.clone();
+                    }
+                    if accessible {
+                        let mut prefix = match reminder.effective_priority(now, &config.priority) {
+                            Priority::Urgent => tr!("Urgent: "),
+                            Priority::High => tr!("High priority: "),
+                            _ => String::new(),
+                        };
+                        if reminder.pinned.is_some() {
+                            prefix.push_str(&tr!("Pinned: "));
+                        }
+                        println!("{}. {}{}", i, prefix, reminder.spoken(config.display.relative_times));
+                        continue;
+                    }
+                    let summary = reminder.summary_with(config.display.relative_times, |tag| {
+                        match colors::tag_color(&config.display, tag).filter(|_| color) {
+                            Some(tag_color) => colors::paint(&format!("#{}", tag), tag_color),
+                            None => format!("#{}", tag),
+                        }
+                    });
+                    let pin = if reminder.pinned.is_some() { "📌 " } else { "" };
+                    let line = format!("{}. {}{}", i, pin, summary);
+                    match reminder.effective_priority(now, &config.priority) {
+                        Priority::Urgent if color => println!("\x1b[1;31m{}\x1b[0m", line),
+                        Priority::High if color => println!("\x1b[33m{}\x1b[0m", line),
+                        _ => println!("{}", line),
+                    }
+                }
+                let shown = offset + page.len();
+                if offset > 0 || shown < total {
+                    print!("\n{}", tr!("Showing {}-{} of {}.", offset + 1, shown, total));
+                    if shown < total {
+                        print!(" {}", tr!("Use --offset {} for more.", shown));
+                    }
+                    println!();
                 }
             }
         }
@@ -89,67 +394,295 @@ fn run() -> Result<()> {
         Some(Commands::Delete { id, index }) => {
             if let Some(id_str) = id {
                 let success = storage.delete_reminder(&id_str)?;
-                if success {
-                    println!("Reminder deleted successfully.");
-                } else {
-                    println!("No reminder found with that ID.");
+                if !success {
+                    return Err(RemindError::NotFound(id_str).into());
                 }
+                println!("{}", tr!("Reminder deleted successfully."));
             } else if let Some(idx) = index {
                 let reminders = storage.load()?;
                 if idx == 0 || idx > reminders.len() {
-                    println!("Invalid index. Use 'list' to see available reminders.");
-                } else {
-                    let id_to_delete = &reminders[idx - 1].id;
-                    storage.delete_reminder(id_to_delete)?;
-                    println!("Reminder deleted successfully.");
+                    return Err(RemindError::InvalidInput(tr!("Invalid index. Use 'list' to see available reminders.")).into());
                 }
+                let id_to_delete = &reminders[idx - 1].id;
+                storage.delete_reminder(id_to_delete)?;
+                println!("{}", tr!("Reminder deleted successfully."));
             } else {
-                println!("Please provide either an ID or an index.");
+                return Err(RemindError::InvalidInput(tr!("Please provide either an ID or an index.")).into());
             }
         }
         
-        Some(Commands::Edit { id, text, time, recurrence }) => {
-            let reminder_option = storage.get_reminder_by_id(&id)?;
-            
-            if let Some(mut reminder) = reminder_option {
+        Some(Commands::Edit { id, editor, all, tag, text, time, recurrence, clear_recurrence, clear_tags, off_days, advance, renotify, alerts, clear_alerts, expire_after, context, assignee, notes, clear_notes, icon, meta, priority, escalate, follow_ups, after, clear_follow_ups, allow_past }) => {
+            if editor {
+                let selection = bulk::Selection { all, id: id.as_deref(), tag: tag.as_deref() };
+                bulk::run(&storage, config.input.date_order, selection)?;
+            } else if let Some(mut reminder) = storage.get_reminder_by_id(id.as_deref().unwrap_or_default())? {
+                let rescheduled = time.is_some() || recurrence.is_some();
                 if let Some(new_text) = text {
                     reminder.text = new_text;
                 }
                 
                 if let Some(new_time) = time {
-                    reminder.due_time = cli::parse_datetime(&new_time)?;
+                    reminder.due_time = cli::parse_datetime(&new_time, config.input.date_order)?;
+                    reminder.nominal_due = None;
+                    reminder.anchor = None;
+                    if !allow_past && !confirm_past(&reminder.due_time) {
+                        println!("{}", tr!("Not updated. Use --allow-past to change it anyway."));
+                        return Ok(1);
+                    }
                 }
                 
                 if let Some(new_recurrence) = recurrence {
-                    reminder.recurrence = cli::parse_recurrence(&new_recurrence)?;
+                    cli::parse_schedule(&new_recurrence)?.apply(&mut reminder, config.input.date_order)?;
+                }
+                
+                if clear_recurrence {
+                    reminder.recurrence = RecurrenceType::None;
+                    reminder.nominal_due = None;
+                    reminder.anchor = None;
+                }
+                
+                if rescheduled {
+                    confirm_month_end(&mut reminder, &Calendar::from_config(&config.holidays)?);
+                }
+                
+                if clear_tags {
+                    reminder.tags.clear();
+                }
+                
+                if let Some(policy) = off_days {
+                    reminder.off_days = policy;
+                }
+                
+                if let Some(advance) = advance {
+                    reminder.advance = advance;
+                }
+                
+                if let Some(renotify) = renotify {
+                    reminder.renotify_minutes = cli::parse_renotify(&renotify)?;
+                }
+                
+                if !alerts.is_empty() || clear_alerts {
+                    reminder.set_alerts(&alerts);
+                }
+                
+                if let Some(expire_after) = expire_after {
+                    reminder.expire_after_minutes = cli::parse_expire_after(&expire_after)?;
+                }
+                
+                if let Some(context) = context {
+                    reminder.set_context(&context);
+                }
+                
+                if let Some(assignee) = assignee {
+                    reminder.set_assignee(&assignee);
+                }
+                
+                if let Some(notes) = notes {
+                    reminder.set_notes(&notes);
+                }
+                
+                if clear_notes {
+                    reminder.notes = None;
+                }
+                
+                if let Some(icon) = icon {
+                    reminder.set_icon(&icon);
+                }
+                
+                for (key, value) in &meta {
+                    reminder.set_meta(key, value);
+                }
+                
+                if let Some(priority) = priority {
+                    reminder.priority = priority;
+                }
+                
+                if escalate.is_some() {
+                    reminder.escalate = escalate;
+                }
+                
+                if !follow_ups.is_empty() {
+                    reminder.follow_ups = cli::follow_ups(follow_ups, &after)?;
+                }
+                
+                if clear_follow_ups {
+                    reminder.follow_ups.clear();
                 }
                 
                 storage.update_reminder(reminder)?;
-                println!("Reminder updated successfully.");
+                println!("{}", tr!("Reminder updated successfully."));
             } else {
-                println!("No reminder found with that ID.");
+                return Err(RemindError::NotFound(id.unwrap_or_default()).into());
+            }
+        }
+        
+        Some(Commands::Occurrences { id, count }) => {
+            match storage.get_reminder_by_id(&id)? {
+                Some(reminder) if reminder.completed => {
+                    println!("{}", tr!("'{}' is completed, so it has no upcoming occurrences.", reminder.text));
+                }
+                Some(reminder) => {
+                    let calendar = Calendar::from_config(&config.holidays)?;
+                    print_occurrences(&reminder, count, &calendar);
+                }
+                None => return Err(RemindError::NotFound(id).into()),
+            }
+        },
+        
+        Some(Commands::Except { id, date, remove }) => {
+            let date = cli::parse_date(&date, config.input.date_order)?;
+            let day = utils::format_date(date, "%a %Y-%m-%d");
+            match storage.get_reminder_by_id(&id)? {
+                Some(reminder) if reminder.recurrence.period().is_none() => {
+                    println!("{}", tr!("'{}' doesn't recur on a schedule, so it has no dates to skip.", reminder.text));
+                }
+                Some(reminder) if remove && !reminder.exdates.contains(&date) => {
+                    println!("{}", tr!("'{}' doesn't skip {}.", reminder.text, day));
+                }
+                Some(mut reminder) if remove => {
+                    reminder.exdates.retain(|d| *d != date);
+                    let text = reminder.text.clone();
+                    storage.update_reminder(reminder)?;
+                    println!("{}", tr!("'{}' occurs on {} again.", text, day));
+                }
+                Some(mut reminder) => {
+                    if !reminder.exdates.contains(&date) {
+                        reminder.exdates.push(date);
+                        reminder.exdates.sort();
+                    }
+                    // The current occurrence is the one skipped: move on to the next
+                    if !reminder.completed && reminder.is_excluded(reminder.nominal_due.unwrap_or(reminder.due_time)) {
+                        let calendar = Calendar::from_config(&config.holidays)?;
+                        reminder.schedule_next(&calendar);
+                        reminder.clear_notified();
+                    }
+                    let message = tr!("'{}' skips {}. Next occurrence: {}", reminder.text, day, utils::format_datetime(&reminder.due_time));
+                    storage.update_reminder(reminder)?;
+                    println!("{}", message);
+                }
+                None => return Err(RemindError::NotFound(id).into()),
             }
         }
         
-        Some(Commands::Notify { desktop }) => {
-            let mut notifier = Notifier::new(storage);
-            let due_reminders = notifier.check_due_reminders(desktop)?;
+        Some(Commands::Pin { id, position, undo }) => {
+            let reminders = storage.load()?;
+            let Some(target) = reminders.iter().find(|r| r.id == id) else {
+                return Err(RemindError::NotFound(id).into());
+            };
+            if undo && target.pinned.is_none() {
+                println!("{}", tr!("'{}' isn't pinned.", target.text));
+                return Ok(exit_code);
+            }
+            let text = target.text.clone();
+            
+            // The pinned order without this reminder, then it goes back in at its place
+            let mut pinned: Vec<&Reminder> = reminders.iter().filter(|r| r.pinned.is_some() && r.id != id).collect();
+            pinned.sort_by_key(|r| r.pinned);
+            let mut order: Vec<&str> = pinned.iter().map(|r| r.id.as_str()).collect();
+            if !undo {
+                let place = position.or(target.pinned).map_or(order.len(), |place| place as usize - 1);
+                order.insert(place.min(order.len()), &id);
+            }
+            
+            // Places stay 1, 2, 3, ... so a later --position means what the list shows
+            let places: Vec<(String, Option<u32>)> = reminders
+                .iter()
+                .map(|r| (r.id.clone(), order.iter().position(|o| *o == r.id).map(|place| place as u32 + 1)))
+                .collect();
+            let mut place = None;
+            for (mut reminder, (reminder_id, pinned)) in reminders.clone().into_iter().zip(places) {
+                if reminder_id == id {
+                    place = pinned;
+                }
+                if reminder.pinned != pinned {
+                    reminder.pinned = pinned;
+                    storage.update_reminder(reminder)?;
+                }
+            }
+            match place {
+                Some(place) => println!("{}", tr!("Pinned '{}' at position {}.", text, place)),
+                None => println!("{}", tr!("Unpinned '{}'.", text)),
+            }
+        }
+        
+        Some(Commands::Complete { id, undo }) => {
+            match storage.get_reminder_by_id(&id)? {
+                Some(mut reminder) if undo => {
+                    reminder.set_completed(false);
+                    storage.update_reminder(reminder)?;
+                    println!("{}", tr!("Reminder marked as not completed."));
+                }
+                Some(reminder) => {
+                    let calendar = Calendar::from_config(&config.holidays)?;
+                    let (reminder, follow_up) = storage.complete_reminder(reminder, &calendar)?;
+                    if reminder.completed {
+                        println!("{}", tr!("Reminder completed."));
+                    } else {
+                        println!("{}", tr!("Done for now. Next occurrence: {}", utils::format_datetime(&reminder.due_time)));
+                    }
+                    if let Some(next) = follow_up {
+                        println!("{}", tr!("Follow-up '{}' added, due {}.", next.text, utils::format_datetime(&next.due_time)));
+                    }
+                }
+                None => return Err(RemindError::NotFound(id).into()),
+            }
+        }
+        
+        Some(Commands::WhenDone { id, retry_in, command }) => {
+            exit_code = when_done::run(&mut storage, &config, &id, &retry_in, &command)?;
+        }
+        
+        Some(Commands::Notify { desktop, dry_run }) => {
+            let mut notifier = Notifier::new(storage, config);
+            let due_reminders = if dry_run {
+                notifier.preview_due_reminders(desktop)?
+            } else {
+                notifier.check_due_reminders(desktop)?
+            };
             
             if due_reminders.is_empty() {
-                println!("No reminders due.");
+                println!("{}", tr!("No reminders due."));
+            } else if dry_run {
+                println!("\n{}", tr!("{} reminder(s) would be notified. Nothing was sent or changed.", due_reminders.len()));
+                exit_code = EXIT_REMINDERS_DUE;
             } else {
-                println!("{} reminder(s) notified.", due_reminders.len());
+                println!("{}", tr!("{} reminder(s) notified.", due_reminders.len()));
+                exit_code = EXIT_REMINDERS_DUE;
+            }
+        }
+        
+        Some(Commands::Export { compress, format: cli::ExportFormat::Html, week }) => {
+            if compress.is_some() {
+                return Err(RemindError::InvalidInput(tr!("--compress is only for JSON exports.")).into());
             }
+            let day = match week {
+                Some(day) => cli::parse_day(&day, chrono::NaiveTime::MIN, config.input.date_order)?,
+                None => Local::now().date_naive(),
+            };
+            let calendar = Calendar::from_config(&config.holidays)?;
+            print!("{}", week::html(&storage.load()?, utils::start_of_week(day), &calendar));
         }
         
-        Some(Commands::Export) => {
+        Some(Commands::Export { compress, format: cli::ExportFormat::Json, week }) => {
+            if week.is_some() {
+                return Err(RemindError::InvalidInput(tr!("--week is for the week view; add --format html.")).into());
+            }
             let reminders = storage.load()?;
             let json = serde_json::to_string_pretty(&reminders)
                 .context("Failed to serialize reminders")?;
-            println!("{}", json);
+            match compress.filter(|&c| c != compress::Compression::None) {
+                Some(compression) => {
+                    let mut stdout = std::io::stdout();
+                    if stdout.is_terminal() {
+                        return Err(RemindError::InvalidInput("Compressed output isn't for the terminal; redirect it to a file, e.g. > reminders.json.zst".into()).into());
+                    }
+                    stdout.write_all(&compress::compress((json + "\n").as_bytes(), compression)?)?;
+                }
+                None => println!("{}", json),
+            }
         }
 
-        Some(Commands::Stats) => {
+        Some(Commands::Stats { json }) => {
             let reminders = storage.load()?;
             let total = reminders.len();
             let completed = reminders.iter().filter(|r| r.completed).count();
@@ -159,75 +692,768 @@ fn run() -> Result<()> {
             let overdue = reminders.iter()
                 .filter(|r| !r.completed && r.due_time < Local::now())
                 .count();
+            
+            // Pomodoros logged against reminders, most worked-on first
+            let mut worked: Vec<_> = reminders.iter().filter(|r| !r.pomodoros.is_empty()).collect();
+            worked.sort_by_key(|r| std::cmp::Reverse(r.pomodoros.len()));
+            let pomodoros = worked.iter().map(|r| r.pomodoros.len()).sum::<usize>();
+            let pomodoros_today = worked.iter().flat_map(|r| &r.pomodoros).filter(|t| is_today(t)).count();
+            
+            if json {
+                let stats = serde_json::json!({
+                    "total": total,
+                    "completed": completed,
+                    "active": total - completed,
+                    "due_today": due_today,
+                    "overdue": overdue,
+                    "pomodoros": {
+                        "total": pomodoros,
+                        "today": pomodoros_today,
+                        "reminders": worked.iter()
+                            .map(|r| serde_json::json!({ "id": r.id, "text": r.text, "count": r.pomodoros.len() }))
+                            .collect::<Vec<_>>(),
+                    },
+                });
+                println!("{}", serde_json::to_string_pretty(&stats)?);
+            } else {
+                println!("{}", tr!("Reminder Statistics:"));
+                println!("  {}", tr!("Total: {}", total));
+                println!("  {}", tr!("Completed: {}", completed));
+                println!("  {}", tr!("Active: {}", total - completed));
+                println!("  {}", tr!("Due today: {}", due_today));
+                println!("  {}", tr!("Overdue: {}", overdue));
                 
-            println!("Reminder Statistics:");
-            println!("  Total: {}", total);
-            println!("  Completed: {}", completed);
-            println!("  Active: {}", total - completed);
-            println!("  Due today: {}", due_today);
-            println!("  Overdue: {}", overdue);
+                if !worked.is_empty() {
+                    println!("  {}", tr!("Pomodoros: {} ({} today)", pomodoros, pomodoros_today));
+                    for reminder in worked.iter().take(5) {
+                        println!("    {:>3}  {}", reminder.pomodoros.len(), reminder.text);
+                    }
+                }
+            }
         }
 
-        Some(Commands::Search { query }) => {
+        Some(Commands::Report { from, to, format }) => {
+            let today = Local::now().date_naive();
+            let to = match to {
+                Some(to) => cli::parse_date(&to, config.input.date_order)?,
+                None => today,
+            };
+            let from = match from {
+                Some(from) => cli::parse_date(&from, config.input.date_order)?,
+                None => to - chrono::Duration::days(29),
+            };
+            if to > today {
+                return Err(RemindError::InvalidInput(tr!("The report can't go past today ({}).", today)).into());
+            }
+            if from > to {
+                return Err(RemindError::InvalidInput(tr!("--from ({}) is after --to ({}).", from, to)).into());
+            }
+            
+            // Archived reminders were created and completed too
+            let mut reminders = storage.load()?;
+            reminders.extend(storage.archived()?);
+            let days = report::days(&reminders, from, to, Local::now());
+            match format {
+                report::ReportFormat::Csv => print!("{}", report::csv(&days)),
+                report::ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&days)?),
+                report::ReportFormat::Table => {
+                    println!("{:<12} {:>8} {:>10} {:>8}", tr!("Date"), tr!("Created"), tr!("Completed"), tr!("Overdue"));
+                    for day in &days {
+                        println!("{:<12} {:>8} {:>10} {:>8}", day.date, day.created, day.completed, day.overdue);
+                    }
+                }
+            }
+        }
+
+        Some(Commands::Search { query, limit }) => {
             let reminders = storage.load()?;
-            let matches: Vec<_> = reminders.iter()
-                .filter(|r| r.text.to_lowercase().contains(&query.to_lowercase()))
-                .collect();
+            let hits: Vec<search::Hit> = match query.strip_prefix("meta.") {
+                // meta.client=acme, or meta.client for any value
+                Some(meta_query) => {
+                    let (key, value) = meta_query.split_once('=').unwrap_or((meta_query, ""));
+                    reminders.iter()
+                        .filter(|r| r.meta.get(key).is_some_and(|v| value.is_empty() || v.eq_ignore_ascii_case(value)))
+                        .map(|reminder| search::Hit { reminder, fields: Vec::new() })
+                        .collect()
+                }
+                None => search::search(&reminders, &query),
+            };
             
-            if matches.is_empty() {
-                println!("No reminders matching '{}'", query);
+            if hits.is_empty() {
+                println!("{}", tr!("No reminders matching '{}'", query));
             } else {
-                println!("Reminders matching '{}':", query);
-                for (i, reminder) in matches.iter().enumerate() {
-                    println!("{}. {}", i + 1, reminder);
+                println!("{}", tr!("Reminders matching '{}':", query));
+                // The match stands out in bold in a terminal
+                let bold = std::io::stdout().is_terminal();
+                let mark = |matched: &str| if bold { format!("\x1b[1m{}\x1b[0m", matched) } else { matched.to_string() };
+                let shown = limit.unwrap_or(usize::MAX).min(hits.len());
+                for (i, hit) in hits.iter().take(shown).enumerate() {
+                    println!("{}. {}", i + 1, hit.reminder.summary(config.display.relative_times));
+                    for (field, snippet) in &hit.fields {
+                        // The summary already shows a matching text in full
+                        if *field != "text" {
+                            println!("     {:<6} {}", format!("{}:", field), snippet.render(mark));
+                        }
+                    }
+                }
+                if shown < hits.len() {
+                    println!("\n{}", tr!("Showing {} of {} matches. Use a higher --limit for more.", shown, hits.len()));
+                }
+            }
+        }
+
+        Some(Commands::Sync { git }) => {
+            if !git {
+                return Err(RemindError::InvalidInput(tr!("Please choose a sync backend, e.g. 'remind sync --git'.")).into());
+            } else {
+                let report = sync::sync_git(&storage, &config.sync)?;
+                
+                if report.committed {
+                    println!("{}", tr!("Committed local changes."));
+                }
+                if report.merged {
+                    println!("{}", tr!("Merged remote changes."));
+                }
+                for conflict in &report.conflicts {
+                    println!(
+                        "{}",
+                        tr!(
+                            "Conflict in '{}' [{}] {}: local {} vs remote {}, kept {}",
+                            conflict.text,
+                            &conflict.id[..8.min(conflict.id.len())],
+                            conflict.field,
+                            conflict.local,
+                            conflict.remote,
+                            if conflict.kept_local { tr!("local") } else { tr!("remote") }
+                        )
+                    );
+                }
+                if report.pushed {
+                    println!("{}", tr!("Pushed to remote."));
+                }
+                println!("{}", tr!("Sync complete."));
+            }
+        }
+
+        Some(Commands::Merge { file, base, newest, dry_run }) => {
+            let read_other = |path: &str| -> Result<Vec<reminder::Reminder>> {
+                let path = shellexpand::tilde(path).to_string();
+                let data = std::fs::read(&path)
+                    .with_context(|| format!("Failed to read {}", path))?;
+                storage.parse(data)
+            };
+            
+            let other = read_other(&file)?;
+            let base = base.as_deref().map(read_other).transpose()?;
+            let local = storage.load()?;
+            
+            // Ask about each conflict when attached to a terminal, otherwise keep the newest
+            let interactive = !newest && std::io::stdin().is_terminal();
+            let mut resolve = |conflict: &merge::Conflict| {
+                if interactive {
+                    prompt_conflict(conflict)
+                } else {
+                    merge::newest_wins(conflict)
+                }
+            };
+            let result = merge::merge(base.as_deref(), &local, &other, &mut resolve)?;
+            
+            let local_ids: std::collections::HashSet<&str> = local.iter().map(|r| r.id.as_str()).collect();
+            let merged_ids: std::collections::HashSet<&str> = result.reminders.iter().map(|r| r.id.as_str()).collect();
+            let added = result.reminders.iter().filter(|r| !local_ids.contains(r.id.as_str())).count();
+            let removed = local.iter().filter(|r| !merged_ids.contains(r.id.as_str())).count();
+            
+            if !interactive {
+                for conflict in &result.conflicts {
+                    println!(
+                        "{}",
+                        tr!(
+                            "Conflict in '{}' [{}] {}: local {} vs other {}, kept {}",
+                            conflict.text,
+                            &conflict.id[..8.min(conflict.id.len())],
+                            conflict.field,
+                            conflict.local,
+                            conflict.remote,
+                            if conflict.kept_local { tr!("local") } else { tr!("other") }
+                        )
+                    );
+                }
+            }
+            
+            println!("{}", tr!("{} reminder(s) added, {} removed, {} conflict(s).", added, removed, result.conflicts.len()));
+            
+            if dry_run {
+                println!("{}", tr!("Dry run - nothing was written."));
+            } else {
+                storage.save(&result.reminders)?;
+                println!("{}", tr!("Merged {} into your reminders.", file));
+            }
+        }
+
+        Some(Commands::Serve { listen }) => {
+            let token = config.server.token()
+                .ok_or_else(|| anyhow::anyhow!("No API token configured. Set server.token in config.toml or REMINDME_API_TOKEN"))?;
+            let listen = listen.unwrap_or_else(|| config.server.listen.clone());
+            
+            let calendar = Calendar::from_config(&config.holidays)?;
+            server::serve(&mut storage, &listen, &token, &calendar, config.input.date_order)?;
+        }
+
+        Some(Commands::Api) => {
+            // Writes still fail one request at a time on read-only storage, with that request's error
+            api::run(&mut storage, &config)?;
+        }
+
+        Some(Commands::Status { format, hours }) => {
+            let reminders = storage.load()?;
+            let summary = status::Summary::new(&reminders, Local::now(), chrono::Duration::hours(hours));
+            println!("{}", summary.render(format));
+        }
+
+        Some(Commands::Alarm { priority, interval, snooze }) => {
+            alarm::watch(storage, &config, priority, interval, snooze)?;
+        }
+
+        Some(Commands::Pomodoro { action, task, work, rest, cycles }) => {
+            if action.to_lowercase() != "start" {
+                return Err(RemindError::InvalidInput(tr!("Usage: remind pomodoro start --task ID [--work 25m] [--break 5m] [--cycles 4]")).into());
+            } else {
+                let session = pomodoro::Session {
+                    task,
+                    work: cli::parse_duration(&work)?,
+                    rest: cli::parse_duration(&rest)?,
+                    cycles,
+                };
+                let mut notifier = Notifier::new(storage, config);
+                pomodoro::run(&mut notifier, &session)?;
+            }
+        }
+
+        Some(Commands::Review { stale_days }) => {
+            review::run(&mut storage, &config, chrono::Duration::days(stale_days))?;
+        }
+
+        Some(Commands::HandleUri { uri: _, install: true, .. }) => {
+            let place = uri::install(storage_file.as_deref())?;
+            println!("{}", tr!("remind:// links now open with this command ({}).", place));
+        }
+        
+        Some(Commands::HandleUri { uri: _, uninstall: true, .. }) => {
+            let place = uri::uninstall()?;
+            println!("{}", tr!("remind:// links are no longer handled ({}).", place));
+        }
+        
+        Some(Commands::HandleUri { uri, .. }) => {
+            let uri = uri.unwrap_or_default();
+            // Started from a browser there's usually no terminal, so the outcome is shown as a popup too
+            let outcome = uri::handle(&mut storage, &config, &uri);
+            let notifier = Notifier::new(storage, config);
+            match outcome {
+                Ok(reminder) => {
+                    let due = utils::format_datetime(&reminder.due_time);
+                    println!("{}", tr!("Added '{}' (due {}).", reminder.text, due));
+                    notifier.announce(&tr!("Reminder added"), &tr!("{} (due {})", reminder.headline(), due));
+                }
+                Err(e) => {
+                    notifier.announce(&tr!("Couldn't add the reminder"), &format!("{:#}", e));
+                    return Err(e);
+                }
+            }
+        }
+
+        Some(Commands::Dedupe { within, dry_run }) => {
+            dedupe::run(&mut storage, &config, &within, dry_run)?;
+        }
+
+        Some(Commands::Print { today: _, date, markdown }) => {
+            let day = match date {
+                Some(date) => cli::parse_day(&date, chrono::NaiveTime::MIN, config.input.date_order)?,
+                None => Local::now().date_naive(),
+            };
+            let calendar = Calendar::from_config(&config.holidays)?;
+            print!("{}", sheet::render(&storage.load()?, day, &calendar, markdown));
+        }
+
+        Some(Commands::Stale { days }) => {
+            stale::command(&storage, &config, days)?;
+        }
+
+        Some(Commands::Doctor) => unreachable!("handled before loading the config"),
+        
+        Some(Commands::Daemon { desktop }) => {
+            daemon::run(storage_file.clone(), config.clone(), desktop)?;
+        }
+        
+        Some(Commands::Maintain { dry_run }) => {
+            maintain::run(&storage, &config, dry_run)?;
+        }
+        
+        Some(Commands::Share { id, out }) => {
+            share::share(&storage, &config.share, &id, out.as_deref())?;
+        }
+        
+        Some(Commands::Import { file, allow_unsigned, from_email, alerts }) => {
+            match (file, from_email) {
+                (_, Some(message)) => invite::import(&mut storage, &config, &message, &alerts)?,
+                (Some(file), None) => share::import(&storage, &config.share, &file, allow_unsigned)?,
+                (None, None) => unreachable!("clap requires a file or --from-email"),
+            }
+        }
+        
+        Some(Commands::Pick { action, stdin_id }) => {
+            exit_code = pick::run(&mut storage, &config, action, stdin_id)?;
+        }
+        
+        Some(Commands::Dnd { action, time }) => {
+            let mut state = dnd::Dnd::load()?;
+            
+            match (action.as_deref().map(str::to_lowercase).as_deref(), time) {
+                (Some("on"), None) => {
+                    state.on = true;
+                    state.until = None;
+                    state.save()?;
+                    println!("{}", tr!("Do not disturb is on. Only urgent reminders will notify."));
+                }
+                (Some("off"), None) => {
+                    state.on = false;
+                    state.until = None;
+                    state.save()?;
+                    println!("{}", tr!("Do not disturb is off."));
+                }
+                (Some("until"), Some(time)) => {
+                    let until = cli::parse_datetime_with_default_date(&time, None, config.input.date_order)?;
+                    state.on = true;
+                    state.until = Some(until);
+                    state.save()?;
+                    println!("{}", tr!("Do not disturb is on until {}.", utils::format_datetime(&until)));
+                }
+                (None, None) => {
+                    let now = Local::now();
+                    match (state.manual_active(now), state.until) {
+                        (true, Some(until)) => println!("{}", tr!("Do not disturb is on until {}.", utils::format_datetime(&until))),
+                        (true, None) => println!("{}", tr!("Do not disturb is on.")),
+                        (false, _) => println!("{}", tr!("Do not disturb is off.")),
+                    }
+                    if let Some(range) = &config.notifications.quiet_hours {
+                        let quiet = dnd::in_quiet_hours(range, now.time())?;
+                        println!("{}", tr!("Quiet hours: {}{}", range, if quiet { tr!(" (now)") } else { String::new() }));
+                    }
+                    if !state.queued.is_empty() {
+                        println!("{}", tr!("{} reminder(s) held back for the digest.", state.queued.len()));
+                    }
                 }
+                _ => return Err(RemindError::InvalidInput(tr!("Usage: remind dnd [on | off | until HH:MM]")).into()),
+            }
+        }
+        
+        Some(Commands::Ack { id }) => {
+            escalation::Acknowledged::record(id.as_deref())?;
+            match id {
+                Some(id) => println!("{}", tr!("Alarm for {} acknowledged.", id)),
+                None => println!("{}", tr!("Alarms acknowledged.")),
             }
         }
 
+        Some(Commands::Sound { action, sound }) => {
+            sound::command(&config, action.unwrap_or(SoundAction::List), sound.as_deref())?;
+        }
+        
+        Some(Commands::Workspace { action, name }) => {
+            workspace::command(&config, action, name.as_deref())?;
+        }
+
         Some(Commands::Help { command }) => {
             if let Some(cmd) = command {
                 match cmd.to_lowercase().as_str() {
                     "add" => {
-                        println!("Add a new reminder:");
-                        println!("  remind add --text \"Your reminder text\" --time \"HH:MM\" [--date \"YYYY-MM-DD\"] [--recurrence daily|weekly|monthly|yearly] [--priority low|medium|high|urgent]");
-                        println!("\nExamples:");
+                        println!("{}", tr!("Add a new reminder:"));
+                        println!("  remind add --text \"Your reminder text\" --time \"HH:MM\" [--date \"YYYY-MM-DD\"] [--recurrence daily|weekdays|weekly|monthly|last-day-of-month|yearly|\"PHRASE\"] [--off-days keep|skip|next] [--advance notify|complete] [--renotify 10m] [--alert 1h]... [--expire-after 7d] [--priority low|medium|high|urgent] [--escalate true|false] [--tag TAG]... [--context @CONTEXT] [--assignee NAME] [--notes TEXT] [--icon EMOJI] [--meta KEY=VALUE]... [--follow-up \"TEXT\" --after 3d]... [--dry-run] [--force] [--allow-past]");
+                        println!("  remind add --kind birthday|anniversary --text \"Name\" --date \"YYYY-MM-DD\" [--time \"HH:MM\"]");
+                        println!("\n{}", tr!("--time, --recurrence and --priority default to [defaults] in config.toml, whose tags are added too."));
+                        println!("\n{}", tr!("Examples:"));
                         println!("  remind add --text \"Team meeting\" --time \"10:00\" --date \"2025-05-24\"");
                         println!("  remind add --text \"Daily standup\" --time \"09:00\" --recurrence daily");
+                        println!("  remind add --text \"Pay invoices\" --time \"10:00\" --recurrence monthly --off-days next");
+                        println!("  remind add --text \"Pay rent\" --time \"09:00\" --recurrence last-day-of-month");
+                        println!("  remind add --text \"1:1 with Sam\" --recurrence \"every other tuesday at 14:00\" --dry-run");
                         println!("  remind add --text \"Urgent deadline\" --time \"17:00\" --date \"2025-05-30\" --priority high");
+                        println!("  remind add --text \"Tax return\" --time \"12:00\" --date \"2025-07-31\" --alert 1d --alert 1h");
+                        println!("  remind add --text \"Send report\" --time \"16:00\" --tag work");
+                        println!("  remind add --text \"Email Sam\" --time \"10:00\" --follow-up \"Chase Sam\" --after 3d");
+                        println!("  remind add --kind birthday --text \"Anna\" --date \"1990-04-12\"");
                     },
                     "list" => {
-                        println!("List all reminders:");
-                        println!("  remind list");
-                        println!("\nThis command shows all your reminders with their IDs, text, due time, and status.");
+                        println!("{}", tr!("List all reminders:"));
+                        println!("  remind list [--format \"TEMPLATE\"] [--due-now] [--quiet] [--context @CONTEXT] [--assignee NAME|me] [--group-by day|week|tag|priority] [--sort due|priority|created|text] [--limit N] [--offset N] [--all] [--vimgrep|--quickfix]");
+                        println!("\n{}", tr!("This command shows all your reminders with their IDs, text, due time, and status."));
+                        println!("\n{}", tr!("Options:"));
+                        println!("  --format    {}", tr!("Print each reminder using a template instead of the default layout"));
+                        println!("  --due-now   {}", tr!("Only show pending reminders whose due time has passed"));
+                        println!("  --quiet     {}", tr!("Print nothing, only set the exit status"));
+                        println!("  --assignee  {}", tr!("Only show reminders assigned to NAME (\"me\" for team.name or $USER)"));
+                        println!("  --group-by  {}", tr!("Show sections by due day, week, tag or priority"));
+                        println!("  --sort      {}", tr!("Order by due time, priority, created or text; pinned reminders stay on top"));
+                        println!("  --limit     {}", tr!("Show at most N reminders, starting after --offset"));
+                        println!("  --all       {}", tr!("Ignore the workspace in use and show every reminder"));
+                        println!("  --vimgrep   {}", tr!("Print pending reminders with file metadata as path:line:col:text"));
+                        println!("  --quickfix  {}", tr!("The same as a JSON list of Vim quickfix items"));
+                        println!("\n{}", tr!("With --due-now, the exit status is {} if anything is due and 0 otherwise:", EXIT_REMINDERS_DUE));
+                        println!("  remind list --due-now --quiet || echo \"Something is due\"");
+                        println!("\n{}", tr!("Template fields: {}", format::FIELDS.iter().map(|f| format!("{{{}}}", f)).collect::<Vec<_>>().join(" ")));
+                        println!("{}", tr!("Date fields accept a strftime spec, e.g. {due:%H:%M}. Use \\t and \\n for tabs and newlines."));
+                        println!("\n{}", tr!("Examples:"));
+                        println!("  remind list --format \"{{index}}\\t{{due:%H:%M}}\\t{{text}}\"");
+                        println!("  remind list --format \"{{short_id}} {{text}}\" | dmenu");
+                        println!("  remind --shared list --assignee me");
+                        println!("\n{}", tr!("A reminder points at code with file metadata, e.g. --meta file=src/db.rs:120[:7]."));
+                        println!("{}", tr!("In Vim: :cexpr system('remind list --vimgrep')"));
                     },
                     "delete" => {
-                        println!("Delete a reminder:");
+                        println!("{}", tr!("Delete a reminder:"));
                         println!("  remind delete --id [ID]");
                         println!("  remind delete --index [NUMBER]");
-                        println!("\nExamples:");
+                        println!("\n{}", tr!("Examples:"));
                         println!("  remind delete --id c7613d0e");
                         println!("  remind delete --index 2");
-                        println!("\nUse the list command first to see reminder IDs and indexes.");
+                        println!("\n{}", tr!("Use the list command first to see reminder IDs and indexes."));
                     },
                     "edit" => {
-                        println!("Edit an existing reminder:");
-                        println!("  remind edit --id [ID] [--text \"New text\"] [--time \"YYYY-MM-DD HH:MM\"] [--recurrence daily|weekly|monthly|yearly] [--priority low|medium|high|urgent]");
-                        println!("\nExamples:");
+                        println!("{}", tr!("Edit an existing reminder:"));
+                        println!("  remind edit --id [ID] [--text \"New text\"] [--time \"YYYY-MM-DD HH:MM\"] [--recurrence daily|weekdays|weekly|monthly|last-day-of-month|yearly | --clear-recurrence] [--clear-tags] [--alert 1h... | --clear-alerts] [--assignee NAME] [--notes TEXT | --clear-notes] [--icon EMOJI] [--expire-after 7d|never|default] [--off-days keep|skip|next] [--priority low|medium|high|urgent] [--escalate true|false] [--follow-up \"TEXT\" --after 3d... | --clear-follow-ups] [--allow-past]");
+                        println!("\n{}", tr!("Examples:"));
                         println!("  remind edit --id c7613d0e --text \"Updated reminder\"");
                         println!("  remind edit --id c7613d0e --time \"2025-06-01 14:00\" --recurrence weekly");
+                        println!("  remind edit --id c7613d0e --clear-recurrence --clear-tags");
+                        println!("\n{}", tr!("Bulk editing:"));
+                        println!("  remind edit --editor [--all | --tag TAG | --id ID]");
+                        println!("\n{}", tr!("Opens the pending reminders in $VISUAL or $EDITOR, one per line as"));
+                        println!("{}", tr!("ID | DUE | RECURRENCE | TAGS | TEXT. Edit lines to change reminders, delete lines to"));
+                        println!("{}", tr!("delete them, and add lines with \"new\" as the ID to add some. All changes are saved"));
+                        println!("{}", tr!("together when the editor exits, or none if a line can't be read."));
                     },
                     "notify" => {
-                        println!("Check for due reminders and get notifications:");
-                        println!("  remind notify [--desktop]");
-                        println!("\nOptions:");
-                        println!("  --desktop    Send desktop notifications");
-                        println!("\nThis command checks for due reminders and notifies you about them.");
-                        println!("Use with --desktop to get desktop notifications instead of just terminal output.");
+                        println!("{}", tr!("Check for due reminders and get notifications:"));
+                        println!("  remind notify [--desktop] [--dry-run]");
+                        println!("\n{}", tr!("Options:"));
+                        println!("  --desktop    {}", tr!("Send desktop notifications"));
+                        println!("  --dry-run    {}", tr!("Show what each channel would send, and when each reminder is due next,"));
+                        println!("               {}", tr!("without sending anything, running hooks or changing reminders"));
+                        println!("\n{}", tr!("This command checks for due reminders and notifies you about them."));
+                        println!("{}", tr!("Use with --desktop to get desktop notifications instead of just terminal output."));
+                        println!("{}", tr!("With [digest] time = \"07:30\" in the config, the first run after that time each day also"));
+                        println!("{}", tr!("sends one summary of the day's reminders (and emails it with [digest] email)."));
+                    },
+                    "sync" => {
+                        println!("{}", tr!("Sync reminders through a git repository:"));
+                        println!("  remind sync --git");
+                        println!("\n{}", tr!("The directory containing the reminders file is used as a git repository."));
+                        println!("{}", tr!("Local changes are committed, the remote (sync.git_remote in config.toml, or 'origin')"));
+                        println!("{}", tr!("is pulled and merged reminder by reminder, and the result is pushed back."));
+                        println!("{}", tr!("When both machines changed the same field, the most recently modified reminder wins."));
+                    },
+                    "merge" => {
+                        println!("{}", tr!("Merge another reminders file into yours:"));
+                        println!("  remind merge FILE [--base BASE_FILE] [--newest] [--dry-run]");
+                        println!("\n{}", tr!("Useful when a sync tool (Syncthing, Dropbox) left a conflict copy of reminders.json."));
+                        println!("{}", tr!("Reminders are matched by ID. New reminders from either file are kept. Fields that"));
+                        println!("{}", tr!("differ are conflicts: you are asked which value to keep, or with --newest (or when"));
+                        println!("{}", tr!("not run from a terminal) the most recently modified reminder wins."));
+                        println!("\n{}", tr!("Options:"));
+                        println!("  --base      {}", tr!("Common ancestor of both files, so one-sided changes and deletions merge cleanly"));
+                        println!("  --newest    {}", tr!("Resolve conflicts automatically by modification time"));
+                        println!("  --dry-run   {}", tr!("Report what would change without writing"));
+                        println!("\n{}", tr!("Example:"));
+                        println!("  remind merge ~/Sync/reminders.sync-conflict-20250601.json");
+                    },
+                    "serve" => {
+                        println!("{}", tr!("Serve a REST API over your reminders:"));
+                        println!("  remind serve [--listen 127.0.0.1:8675]");
+                        println!("\n{}", tr!("Every request needs an 'Authorization: Bearer TOKEN' header. The token comes from"));
+                        println!("{}", tr!("REMINDME_API_TOKEN or server.token in config.toml."));
+                        println!("\n{}", tr!("Endpoints:"));
+                        println!("  GET    /reminders                {}", tr!("List reminders"));
+                        println!("  POST   /reminders                {}", tr!("Add {\"text\", \"time\", \"date\"?, \"recurrence\"?}"));
+                        println!("  GET    /reminders/ID             {}", tr!("Show one reminder"));
+                        println!("  PATCH  /reminders/ID             {}", tr!("Update text/time/date/recurrence/completed"));
+                        println!("  DELETE /reminders/ID             {}", tr!("Delete a reminder"));
+                        println!("  POST   /reminders/ID/complete    {}", tr!("Mark a reminder as completed"));
+                        println!("\n{}", tr!("Example:"));
+                        println!("  curl -H \"Authorization: Bearer $TOKEN\" -d '{{\"text\":\"Call mom\",\"time\":\"18:00\"}}' http://127.0.0.1:8675/reminders");
+                    },
+                    "api" => {
+                        println!("{}", tr!("Drive the reminders from another program over stdin and stdout:"));
+                        println!("  remind api");
+                        println!("\n{}", tr!("Each line in is a JSON request and gets one JSON line back, until stdin closes:"));
+                        println!("  {{\"id\": 1, \"method\": \"add\", \"params\": {{\"text\": \"Review PR\", \"time\": \"15:00\"}}}}");
+                        println!("  {{\"id\": 1, \"result\": {{...the new reminder...}}}}");
+                        println!("{}", tr!("The id is echoed back; a failed request gets {\"id\": ..., \"error\": \"MESSAGE\"} instead."));
+                        println!("\n{}", tr!("Methods (params):"));
+                        println!("  list      pending?, tag?");
+                        println!("  get       id (or an unambiguous prefix of it)");
+                        println!("  add       text, time, date?, recurrence?, meta? (as POST /reminders in `remind serve`)");
+                        println!("  update    id, and text?, time?, date?, recurrence?, completed?, meta?");
+                        println!("  delete    id");
+                        println!("  complete  id; returns the reminder and its follow-up, if any");
+                        println!("  snooze    id, for (e.g. \"30m\")");
+                        println!("  search    query");
+                        println!("  version   (none)");
+                    },
+                    "status" => {
+                        println!("{}", tr!("Print a one-line summary for status bars:"));
+                        println!("  remind status [--format plain|waybar|polybar] [--hours 24]");
+                        println!("\n{}", tr!("Counts overdue reminders and those due within the next --hours hours."));
+                        println!("{}", tr!("waybar gets JSON with text, tooltip and a class (urgent, upcoming or idle);"));
+                        println!("{}", tr!("polybar gets colored text; plain prints e.g. \"2 due, 1 upcoming\" (empty when idle)."));
+                        println!("{}", tr!("With --a11y, plain speaks it: \"2 reminders due now and 1 coming up.\" or \"No reminders due.\""));
+                        println!("\n{}", tr!("Example waybar module:"));
+                        println!("  \"custom/remindme\": {{ \"exec\": \"remindme status --format waybar\", \"return-type\": \"json\", \"interval\": 30 }}");
+                    },
+                    "dnd" => {
+                        println!("{}", tr!("Do not disturb:"));
+                        println!("  remind dnd              {}", tr!("Show whether DND is on"));
+                        println!("  remind dnd on           {}", tr!("Hold back notifications until turned off"));
+                        println!("  remind dnd until 14:00  {}", tr!("Hold back notifications until a time"));
+                        println!("  remind dnd off          {}", tr!("Turn DND off"));
+                        println!("\n{}", tr!("Daily quiet hours can be set in config.toml:"));
+                        println!("  [notifications]");
+                        println!("  quiet_hours = \"22:00-07:00\"");
+                        println!("\n{}", tr!("During DND, due reminders are held back and delivered as one digest when it ends."));
+                        println!("{}", tr!("Urgent reminders always notify."));
+                    },
+                    "ack" => {
+                        println!("{}", tr!("Stop escalating alarms:"));
+                        println!("  remind ack [--id ID]");
+                        println!("\n{}", tr!("Options:"));
+                        println!("  --id        {}", tr!("Only this reminder's alarm; without it, every alarm ringing now"));
+                        println!("\n{}", tr!("With notifications.escalate_every set, remind daemon keeps ringing for an urgent"));
+                        println!("{}", tr!("reminder, louder each time, until it's acknowledged here, with the popup's"));
+                        println!("{}", tr!("Acknowledge button, or by completing, snoozing, editing or deleting the reminder:"));
+                        println!("  [notifications]");
+                        println!("  escalate_every = 30     # seconds between rings");
+                        println!("  escalate_volume = 2.0   # the loudest it gets, from volume");
+                    },
+                    "in" | "timer" => {
+                        println!("{}", tr!("Reminders relative to now:"));
+                        println!("  remind in DURATION \"TEXT\"      {}", tr!("Add a one-off reminder DURATION from now"));
+                        println!("  remind timer DURATION [\"TEXT\"] Show a live countdown, then notify");
+                        println!("\n{}", tr!("DURATION is like 25m, 1h30m, 90s or 2d; a bare number means minutes."));
+                        println!("\n{}", tr!("Examples:"));
+                        println!("  remind in 25m \"tea is ready\"");
+                        println!("  remind timer 4m \"eggs\"");
+                    },
+                    "review" => {
+                        println!("{}", tr!("Weekly review:"));
+                        println!("  remind review [--stale-days 30]");
+                        println!("\n{}", tr!("Shows overdue reminders, then pending ones not touched for --stale-days days,"));
+                        println!("{}", tr!("and asks for each whether to reschedule, complete, delete or skip it."));
+                        println!("{}", tr!("A new time can be HH:MM, YYYY-MM-DD HH:MM, or relative like 2d or 3h."));
+                    },
+                    "handle-uri" => {
+                        println!("{}", tr!("Add reminders from remind:// links:"));
+                        println!("  remind handle-uri LINK");
+                        println!("  remind handle-uri --install      {}", tr!("Open remind:// links in browsers and mail with this command"));
+                        println!("  remind handle-uri --uninstall");
+                        println!("\n{}", tr!("A link only adds a reminder: remind://add?text=...&time=15:00 with any of date, in (30m),"));
+                        println!("{}", tr!("recurrence, tag, notes, priority, context and url, URL-encoded. The result also shows as a"));
+                        println!("{}", tr!("notification. --install writes a desktop entry and makes it the handler with xdg-mime"));
+                        println!("{}", tr!("(the registry on Windows); it keeps any --storage-file given."));
+                        println!("\n{}", tr!("Example:"));
+                        println!("  remind handle-uri \"remind://add?text=Call%20Sam&in=2h&tag=work\"");
+                    },
+                    "print" => {
+                        println!("{}", tr!("Print a daily sheet:"));
+                        println!("  remind print [--today | --date DATE] [--markdown]");
+                        println!("\n{}", tr!("The day's reminders in order of time, each with a checkbox and the first line of its"));
+                        println!("{}", tr!("notes, sized for a printed page. Completed occurrences are ticked; today's sheet also"));
+                        println!("{}", tr!("carries over reminders still overdue from earlier days. --markdown prints a task list"));
+                        println!("{}", tr!("for journal apps such as Obsidian or Logseq."));
+                        println!("\n{}", tr!("Examples:"));
+                        println!("  remind print --today | lp");
+                        println!("  remind print --date tomorrow --markdown >> journal.md");
+                    },
+                    "dedupe" => {
+                        println!("{}", tr!("Clean up duplicate reminders:"));
+                        println!("  remind dedupe [--within 1h] [--dry-run]");
+                        println!("\n{}", tr!("Groups pending reminders with the same text (ignoring case, punctuation and spacing)"));
+                        println!("{}", tr!("due no more than --within apart, as importing the same file twice leaves them. For each"));
+                        println!("{}", tr!("group, merge it into one reminder, which keeps the tags, metadata, notes and history of"));
+                        println!("{}", tr!("the others, or delete all but one. --dry-run only lists the groups."));
+                    },
+                    "stale" => {
+                        println!("{}", tr!("Reminders left alone:"));
+                        println!("  remind stale [--days 14]");
+                        println!("\n{}", tr!("Lists pending reminders nobody has edited, snoozed or completed for --days days"));
+                        println!("{}", tr!("(default: [stale] days in the config), the longest untouched first. Being notified"));
+                        println!("{}", tr!("doesn't count as touching one; recurring reminders that move on by themselves never go stale."));
+                        println!("\n{}", tr!("With digest = true under [stale] in the config, a notification lists them once a"));
+                        println!("{}", tr!("week, on digest_day at digest_time (monday 09:00 unless set)."));
+                    },
+                    "sound" => {
+                        println!("{}", tr!("Choose the sound notifications play:"));
+                        println!("  remind sound [list]          {}", tr!("List the sounds found, the one in use marked *"));
+                        println!("  remind sound test [SOUND]    {}", tr!("Play a sound, or the one in use"));
+                        println!("  remind sound set SOUND       {}", tr!("Save it as notifications.sound_file"));
+                        println!("\n{}", tr!("SOUND is a file, or its number in the list."));
+                        println!("{}", tr!("REMINDME_SOUND=FILE overrides the choice for a single run."));
+                        println!("\n{}", tr!("Examples:"));
+                        println!("  remind sound test 2");
+                        println!("  remind sound set ~/sounds/chime.ogg");
+                    },
+                    "workspace" => {
+                        println!("{}", tr!("Work in one project at a time, within one reminders file:"));
+                        println!("  remind workspace                {}", tr!("Show the workspace in use"));
+                        println!("  remind workspace use NAME       {}", tr!("Switch to it"));
+                        println!("  remind workspace off            {}", tr!("Stop using one"));
+                        println!("  remind workspace list           {}", tr!("Show the workspaces in the config"));
+                        println!("\n{}", tr!("While a workspace is in use, `list` shows only its reminders (--all shows every one)"));
+                        println!("{}", tr!("and `add` gives new reminders its tags and context. Define workspaces in config.toml:"));
+                        println!("  [workspaces.projectx]");
+                        println!("  tags = [\"projectx\"]        # new reminders get these");
+                        println!("  filter = [\"projectx\", \"px\"] # list shows reminders with one of these (default: tags)");
+                        println!("  context = \"@office\"");
+                        println!("\n{}", tr!("A NAME not in the config shows and tags reminders with #NAME."));
+                        println!("{}", tr!("REMINDME_WORKSPACE=NAME overrides the choice for one shell (\"\" for none)."));
+                    },
+                    "doctor" => {
+                        println!("{}", tr!("Check the environment notifications depend on:"));
+                        println!("  remind doctor");
+                        println!("\n{}", tr!("Checks that the config is valid, the reminders file can be read, the time zone is set,"));
+                        println!("{}", tr!("a notification daemon answers on D-Bus, the sound plays, and the systemd timer is active."));
+                        println!("{}", tr!("Each failed check comes with a suggested fix; the exit status is 1 if any failed."));
+                    },
+                    "daemon" => {
+                        println!("{}", tr!("Notify as reminders fall due, instead of the systemd timer:"));
+                        println!("  remind daemon [--desktop]");
+                        println!("\n{}", tr!("Runs in the foreground and sleeps until the next reminder is due (at most a minute)."));
+                        println!("{}", tr!("Changes to the reminders file are noticed within seconds; config changes need a restart."));
+                    },
+                    "maintain" => {
+                        println!("{}", tr!("Archive expired reminders:"));
+                        println!("  remind maintain [--dry-run]");
+                        println!("\n{}", tr!("One-off reminders still overdue after their --expire-after (or maintenance.expire_after"));
+                        println!("{}", tr!("in config.toml) move to reminders.json.archive, and the journal is compacted."));
+                        println!("{}", tr!("Restore archived reminders with `remind merge reminders.json.archive`."));
+                    },
+                    "share" => {
+                        println!("{}", tr!("Write a reminder to a bundle to send to someone:"));
+                        println!("  remind share --id ID [--out FILE]");
+                        println!("\n{}", tr!("The bundle is a small JSON file, signed with share.key (or REMINDME_SHARE_KEY) when set."));
+                        println!("{}", tr!("Without --out it is printed to stdout."));
+                        println!("\n{}", tr!("Example:"));
+                        println!("  remind share --id 3f2a... --out standup.rmd");
+                    },
+                    "import" => {
+                        println!("{}", tr!("Add the reminder from a bundle made with `remind share`:"));
+                        println!("  remind import FILE [--allow-unsigned]");
+                        println!("\n{}", tr!("The reminder gets a new ID and keeps its text, schedule, recurrence, tags and metadata."));
+                        println!("{}", tr!("With share.key set, a bundle whose signature doesn't match it is refused, and so is an"));
+                        println!("{}", tr!("unsigned one unless --allow-unsigned is given. Without a key, unsigned bundles are only"));
+                        println!("{}", tr!("checked for damage and signed ones are refused, since they can't be checked."));
+                        println!("\n{}", tr!("Add a reminder for a meeting from an emailed invitation:"));
+                        println!("  remind import --from-email MESSAGE.eml [--alert DURATION]...");
+                        println!("\n{}", tr!("The message (or a bare .ics file) is searched for the calendar invitation. Its title,"));
+                        println!("{}", tr!("start, description, location, organizer and meeting link go into the reminder, with a"));
+                        println!("{}", tr!("heads-up from the invitation's alarms or 15 minutes before. Importing an updated"));
+                        println!("{}", tr!("invitation for the same meeting reschedules that reminder; a cancellation deletes it."));
+                    },
+                    "pick" => {
+                        println!("{}", tr!("Pick a reminder with fzf, dmenu or rofi and act on it:"));
+                        println!("  remind pick [--action complete|delete|edit|show] [--stdin-id]");
+                        println!("\n{}", tr!("Without --action, prints one line per pending reminder, soonest first."));
+                        println!("{}", tr!("With --action, reads the picked line from stdin and finds the reminder by the"));
+                        println!("{}", tr!("short ID it starts with (--stdin-id: stdin holds only an ID or ID prefix)."));
+                        println!("{}", tr!("Exits with status 1 when nothing was picked."));
+                        println!("\n{}", tr!("Examples:"));
+                        println!("  remind pick | fzf | remind pick --action complete");
+                        println!("  remind pick | rofi -dmenu | remind pick --action show");
+                    },
+                    "pomodoro" => {
+                        println!("{}", tr!("Pomodoro work/break cycles:"));
+                        println!("  remind pomodoro start --task ID [--work 25m] [--break 5m] [--cycles 4]");
+                        println!("\n{}", tr!("Counts down each work period and break, with a notification at the end of each."));
+                        println!("{}", tr!("Finished work periods are logged on the reminder and shown by `remind stats`."));
+                    },
+                    "occurrences" => {
+                        println!("{}", tr!("Preview upcoming occurrences:"));
+                        println!("  remind occurrences --id [ID] [--count 10]");
+                        println!("\n{}", tr!("Lists the next occurrences as they will be scheduled, including moves off weekends"));
+                        println!("{}", tr!("and holidays, so a recurrence can be checked before relying on it."));
+                    },
+                    "except" => {
+                        println!("{}", tr!("Skip one date of a recurring reminder:"));
+                        println!("  remind except --id [ID] --date [DATE] [--remove]");
+                        println!("\n{}", tr!("The occurrence scheduled on that date is left out, like an iCalendar EXDATE; the"));
+                        println!("{}", tr!("date is the one the recurrence gives, before any move off a weekend or holiday."));
+                        println!("{}", tr!("--remove brings the date back."));
+                    },
+                    "search" => {
+                        println!("{}", tr!("Search reminders:"));
+                        println!("  remind search --query TEXT [--limit N]");
+                        println!("  remind search --query meta.KEY[=VALUE]");
+                        println!("\n{}", tr!("Looks through the text, notes and tags, ignoring case. Matches in the notes or"));
+                        println!("{}", tr!("tags are shown below the reminder with some of the text around them."));
+                    },
+                    "export" => {
+                        println!("{}", tr!("Export reminders:"));
+                        println!("  remind export [--compress [zstd|gzip]]         {}", tr!("All reminders as JSON, as they're stored"));
+                        println!("  remind export --format html [--week [DATE]]    A week as an HTML page");
+                        println!("\n{}", tr!("The week view has one column per day with each occurrence due in it, for opening in a"));
+                        println!("{}", tr!("browser or printing. --week takes any day in the week, e.g. 2025-06-03, +1w or"));
+                        println!("{}", tr!("next-monday; without one it shows this week."));
+                        println!("\n{}", tr!("Example:"));
+                        println!("  remind export --format html --week > week.html");
+                    },
+                    "stats" => {
+                        println!("{}", tr!("Reminder statistics:"));
+                        println!("  remind stats [--json]");
+                        println!("\n{}", tr!("Counts total, completed, due and overdue reminders, and the pomodoros logged on them."));
+                    },
+                    "report" => {
+                        println!("{}", tr!("Per-day activity:"));
+                        println!("  remind report [--from DATE] [--to DATE] [--format table|csv|json]");
+                        println!("\n{}", tr!("One row per day with the reminders created, the occurrences completed and how many"));
+                        println!("{}", tr!("were overdue at the end of the day, archived reminders included. Without dates it"));
+                        println!("{}", tr!("covers the last 30 days."));
+                        println!("\n{}", tr!("Example:"));
+                        println!("  remind report --from 2025-01-01 --format csv > activity.csv");
+                    },
+                    "pin" => {
+                        println!("{}", tr!("Pin a reminder to the top:"));
+                        println!("  remind pin --id [ID] [--position N]");
+                        println!("  remind pin --id [ID] --undo");
+                        println!("\n{}", tr!("Pinned reminders come first in `remind list` and the TUI whatever their due time,"));
+                        println!("{}", tr!("in the order of their positions; without --position a reminder goes below the others."));
+                        println!("{}", tr!("Their numbers in `list` stay the same, so `delete --index` still matches."));
+                    },
+                    "complete" => {
+                        println!("{}", tr!("Complete a reminder:"));
+                        println!("  remind complete --id [ID] [--undo]");
+                        println!("\n{}", tr!("Recurring reminders added with --advance complete move on to their next occurrence"));
+                        println!("{}", tr!("only when completed; others are simply marked as completed."));
+                        println!("{}", tr!("A reminder with a --follow-up adds it now, due its --after from now."));
+                    },
+                    "when-done" => {
+                        println!("{}", tr!("Complete a reminder when a command succeeds:"));
+                        println!("  remind when-done --id ID [--retry-in 1h] -- COMMAND [ARGS...]");
+                        println!("\n{}", tr!("Runs the command. Exit status 0 completes the reminder as remind complete does;"));
+                        println!("{}", tr!("any other leaves it pending and due again after --retry-in. remind exits with the"));
+                        println!("{}", tr!("command's exit status. An unknown ID or a bad --retry-in exits with status 1"));
+                        println!("{}", tr!("without running the command."));
+                        println!("\n{}", tr!("Example:"));
+                        println!("  remind when-done --id 3f2a --retry-in 1d -- certbot renew");
+                    },
+                    "alarm" => {
+                        println!("{}", tr!("Full-screen alarm for important reminders:"));
+                        println!("  remind alarm [--priority low|medium|high|urgent] [--interval SECONDS] [--snooze MINUTES]");
+                        println!("\n{}", tr!("Keeps running and checks for due reminders at or above the priority (default high)."));
+                        println!("{}", tr!("When one is due, the terminal flashes until you press Enter to dismiss it,"));
+                        println!("{}", tr!("s to snooze it (default 5 minutes), or q to stop watching."));
+                        println!("\n{}", tr!("Example:"));
+                        println!("  remind alarm --priority urgent --snooze 10");
                     },
                     // Add other commands
                     _ => {
-                        println!("Unknown command: {}", cmd);
-                        println!("Run 'remind help' to see all available commands.");
+                        return Err(RemindError::InvalidInput(tr!("Unknown command: {}. Run 'remind help' to see all available commands.", cmd)).into());
                     }
                 }
             } else {
@@ -241,7 +1467,175 @@ fn run() -> Result<()> {
         }
     }
     
-    Ok(())
+    Ok(exit_code)
+}
+
+// Ask which side of a merge conflict to keep; returns true for the local value
+fn prompt_conflict(conflict: &merge::Conflict) -> bool {
+    println!("\n{}", tr!("Conflict in '{}' [{}], field '{}':", conflict.text, &conflict.id[..8.min(conflict.id.len())], conflict.field));
+    println!("  [l] {}", tr!("local: {}", conflict.local));
+    println!("  [o] {}", tr!("other: {}", conflict.remote));
+    
+    let default = if conflict.local_is_newer { "l" } else { "o" };
+    loop {
+        print!("{}", tr!("Keep which value? [l/o] (default {}, newest): ", default));
+        let _ = std::io::stdout().flush();
+        
+        let mut answer = String::new();
+        if std::io::stdin().read_line(&mut answer).unwrap_or(0) == 0 {
+            return conflict.local_is_newer;
+        }
+        
+        match answer.trim().to_lowercase().as_str() {
+            "" => return conflict.local_is_newer,
+            "l" | "local" => return true,
+            "o" | "other" => return false,
+            _ => println!("{}", tr!("Please answer 'l' or 'o'.")),
+        }
+    }
+}
+
+// Ask a yes/no question; without a terminal to ask on, the answer is no
+fn confirm(question: &str) -> bool {
+    if !std::io::stdin().is_terminal() {
+        return false;
+    }
+    print!("{} {} ", question, tr!("[y/N]"));
+    let _ = std::io::stdout().flush();
+    
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    // English answers work in every language
+    let answer = answer.trim().to_lowercase();
+    ["y", "yes"].contains(&answer.as_str()) || answer == tr!("y") || answer == tr!("yes")
+}
+
+// A due time in the past fires on the next notify run; make sure that's intended
+fn confirm_past(due_time: &DateTime<Local>) -> bool {
+    if *due_time >= Local::now() {
+        return true;
+    }
+    println!("{}", tr!("Warning: {} is in the past, so the reminder would be due right away.", utils::format_datetime(due_time)));
+    confirm(&tr!("Continue anyway?"))
+}
+
+// A monthly reminder on the 29th to 31st falls on the last day of shorter months; show where it
+// would fall, and when it's due on the last day of a shorter month offer last-day-of-month
+// instead, which the 31st already is
+fn confirm_month_end(reminder: &mut Reminder, calendar: &Calendar) {
+    if !reminder.moves_in_short_months() {
+        return;
+    }
+    let print_next = |reminder: &Reminder| {
+        for (due, _) in reminder.occurrences(3, calendar) {
+            println!("  {}", utils::format_datetime(&due));
+        }
+    };
+    println!("{}", tr!("Note: not every month has day {}, so shorter months get it on their last day:", reminder.due_time.day()));
+    print_next(reminder);
+    
+    let mut last_day = reminder.clone();
+    last_day.recurrence = RecurrenceType::MonthlyLastDay;
+    if reminder.due_time.day() == 31 || last_day.recurrence.align(reminder.due_time) != reminder.due_time {
+        return;
+    }
+    println!("{}", tr!("With --recurrence last-day-of-month they would be:"));
+    print_next(&last_day);
+    if confirm(&tr!("Use last-day-of-month instead?")) {
+        reminder.recurrence = RecurrenceType::MonthlyLastDay;
+    }
+}
+
+// Numbered reminders in display order, with the section each is listed under; with tags a
+// reminder is listed once per tag
+fn list_entries<'a>(
+    reminders: &'a [Reminder],
+    group_by: Option<GroupBy>,
+    sort: Option<SortOrder>,
+    now: DateTime<Local>,
+    priorities: &config::PriorityConfig,
+) -> Vec<(Option<String>, usize, &'a Reminder)> {
+    let mut numbered: Vec<(usize, &Reminder)> = reminders.iter().enumerate().map(|(i, r)| (i + 1, r)).collect();
+    if let Some(sort) = sort {
+        numbered.sort_by(|(_, a), (_, b)| sort.compare(a, b, now, priorities));
+    }
+    numbered.sort_by_key(|(_, r)| r.pin_rank());
+    let Some(group_by) = group_by else {
+        return numbered.into_iter().map(|(i, r)| (None, i, r)).collect();
+    };
+    
+    // (sort key, section title) for each section a reminder belongs to
+    let today = now.date_naive();
+    let sections = |reminder: &Reminder| -> Vec<(String, String)> {
+        // Pinned reminders are listed once, in a section above the others
+        if reminder.pinned.is_some() {
+            return vec![(String::new(), tr!("Pinned"))];
+        }
+        match group_by {
+            GroupBy::Day => {
+                let day = reminder.due_time.date_naive();
+                let title = match (day - today).num_days() {
+                    0 => tr!("Today"),
+                    1 => tr!("Tomorrow"),
+                    -1 => tr!("Yesterday"),
+                    _ => utils::format_date(day, "%A %Y-%m-%d"),
+                };
+                vec![(reminder.due_time.naive_local().to_string(), title)]
+            }
+            GroupBy::Week => {
+                let week = utils::start_of_week(reminder.due_time.date_naive());
+                let title = match (week - utils::start_of_week(today)).num_days() {
+                    0 => tr!("This week"),
+                    7 => tr!("Next week"),
+                    -7 => tr!("Last week"),
+                    _ => tr!("Week of {}", utils::format_date(week, "%-d %B %Y")),
+                };
+                vec![(reminder.due_time.naive_local().to_string(), title)]
+            }
+            GroupBy::Tag if reminder.tags.is_empty() => vec![("1".to_string(), tr!("Untagged"))],
+            GroupBy::Tag => reminder.tags.iter().map(|t| (format!("0{}", t.to_lowercase()), format!("#{}", t))).collect(),
+            GroupBy::Priority => {
+                let priority = reminder.effective_priority(now, priorities);
+                vec![(format!("{}", Priority::Urgent as u8 - priority as u8), format!("{:?}", priority))]
+            }
+        }
+    };
+    
+    let mut entries: Vec<(String, String, usize, &Reminder)> = numbered
+        .into_iter()
+        .flat_map(|(i, r)| sections(r).into_iter().map(move |(key, title)| (key, title, i, r)))
+        .collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries.into_iter().map(|(_, title, i, r)| (Some(title), i, r)).collect()
+}
+
+// List the next `count` occurrences, noting the ones moved off a weekend or holiday
+fn print_occurrences(reminder: &Reminder, count: usize, calendar: &Calendar) {
+    println!("{}", tr!("{} ({}), next occurrences:", reminder.text, reminder.recurrence));
+    for (due_time, moved_from) in reminder.occurrences(count, calendar) {
+        match moved_from {
+            Some(from) => println!(
+                "  {} {}  {}",
+                utils::format_date(due_time.date_naive(), "%a %Y-%m-%d"),
+                utils::format_time(&due_time),
+                tr!("(moved from {})", utils::format_date(from.date_naive(), "%a %Y-%m-%d"))
+            ),
+            None => println!("  {} {}", utils::format_date(due_time.date_naive(), "%a %Y-%m-%d"), utils::format_time(&due_time)),
+        }
+    }
+    
+    let current = reminder.nominal_due.unwrap_or(reminder.due_time).date_naive();
+    let skipped: Vec<String> = reminder
+        .exdates
+        .iter()
+        .filter(|date| **date > current)
+        .map(|date| utils::format_date(*date, "%a %Y-%m-%d"))
+        .collect();
+    if !skipped.is_empty() {
+        println!("{}", tr!("Skipped: {}", skipped.join(", ")));
+    }
 }
 
 // Helper function
@@ -251,32 +1645,63 @@ fn is_today(dt: &DateTime<Local>) -> bool {
 }
 
 fn display_general_help() {
-    println!("REMINDER CLI - A command line reminder application");
-    println!("\nAVAILABLE COMMANDS:");
-    println!("  add       Add a new reminder");
-    println!("  list      List all reminders");
-    println!("  delete    Delete a reminder by ID or index");
-    println!("  edit      Edit an existing reminder");
-    println!("  notify    Check for due reminders and send notifications");
-    println!("  complete  Mark a reminder as completed or not completed");
-    println!("  export    Export reminders as JSON");
-    println!("  search    Search for reminders");
-    println!("  stats     Show statistics about reminders");
-    println!("  help      Show this help message or help for a specific command");
+    println!("{}", tr!("REMINDER CLI - A command line reminder application"));
+    println!("\n{}", tr!("AVAILABLE COMMANDS:"));
+    println!("  add          {}", tr!("Add a new reminder"));
+    println!("  in           {}", tr!("Add a one-off reminder in 25m, 1h30m, ..."));
+    println!("  timer        {}", tr!("Count down in the terminal, then notify"));
+    println!("  list         {}", tr!("List all reminders"));
+    println!("  delete       {}", tr!("Delete a reminder by ID or index"));
+    println!("  edit         {}", tr!("Edit an existing reminder"));
+    println!("  notify       {}", tr!("Check for due reminders and send notifications"));
+    println!("  complete     {}", tr!("Mark a reminder as completed or not completed"));
+    println!("  when-done    {}", tr!("Run a command and complete the reminder if it succeeds"));
+    println!("  occurrences  {}", tr!("Preview the next occurrences of a reminder"));
+    println!("  except       {}", tr!("Skip one date of a recurring reminder"));
+    println!("  pin          {}", tr!("Pin a reminder to the top of the list"));
+    println!("  export       {}", tr!("Export reminders as JSON, or a week as an HTML page"));
+    println!("  search       {}", tr!("Search for reminders"));
+    println!("  stats        {}", tr!("Show statistics about reminders"));
+    println!("  report       {}", tr!("Count reminders created, completed and overdue per day"));
+    println!("  sync         {}", tr!("Sync reminders with a git remote"));
+    println!("  merge        {}", tr!("Merge another reminders file into yours"));
+    println!("  serve        {}", tr!("Serve a REST API for remote access"));
+    println!("  api          {}", tr!("Answer JSON requests on stdin, for editor plugins"));
+    println!("  status       {}", tr!("One-line summary for status bars (waybar, polybar, i3)"));
+    println!("  dnd          {}", tr!("Turn do-not-disturb on or off"));
+    println!("  ack          {}", tr!("Stop the escalating alarm for urgent reminders"));
+    println!("  alarm        {}", tr!("Flash a full-screen alarm when important reminders are due"));
+    println!("  pomodoro     {}", tr!("Run work/break cycles for a reminder"));
+    println!("  review       {}", tr!("Go through overdue and stale reminders one by one"));
+    println!("  print        {}", tr!("A day's reminders as a sheet to print or paste into a journal"));
+    println!("  handle-uri   {}", tr!("Add a reminder from a remind:// link; --install registers the links"));
+    println!("  stale        {}", tr!("List reminders nobody has touched in a while"));
+    println!("  dedupe       {}", tr!("Merge or delete near-duplicate reminders"));
+    println!("  doctor       {}", tr!("Check why notifications might not be arriving"));
+    println!("  sound        {}", tr!("List, play and choose the notification sound"));
+    println!("  workspace    {}", tr!("Switch the project list and add work in"));
+    println!("  pick         {}", tr!("Choose a reminder with fzf/dmenu/rofi and complete, delete, edit or show it"));
+    println!("  maintain     {}", tr!("Archive reminders left overdue too long"));
+    println!("  daemon       {}", tr!("Notify the moment reminders fall due, without the systemd timer"));
+    println!("  share        {}", tr!("Write a reminder to a bundle file to send to someone"));
+    println!("  import       {}", tr!("Add a reminder from a shared bundle or a meeting invitation"));
+    println!("  help         {}", tr!("Show this help message or help for a specific command"));
+    
+    println!("\n{}", tr!("Short aliases: ls (list), rm (delete), done (complete), ws (workspace). Define your own under [alias] in config.toml."));
     
-    println!("\nFor detailed help on a specific command, run:");
+    println!("\n{}", tr!("For detailed help on a specific command, run:"));
     println!("  remind help --command COMMAND");
     
-    println!("\nEXAMPLES:");
+    println!("\n{}", tr!("EXAMPLES:"));
     println!("  remind add --text \"Team meeting\" --time \"10:00\" --date \"2025-05-24\"");
     println!("  remind list");
     println!("  remind notify --desktop");
     println!("  remind help --command add");
     
-    println!("\nSETUP AS SYSTEM SERVICE:");
-    println!("  To receive automatic notifications, set up the systemd timer:");
+    println!("\n{}", tr!("SETUP AS SYSTEM SERVICE:"));
+    println!("  {}", tr!("To receive automatic notifications, set up the systemd timer:"));
     println!("    1. mkdir -p ~/.config/systemd/user/");
-    println!("    2. Create timer files (see documentation)");
+    println!("    2. {}", tr!("Create timer files (see documentation)"));
     println!("    3. systemctl --user enable remind-check.timer");
     println!("    4. systemctl --user start remind-check.timer");
 }
\ No newline at end of file
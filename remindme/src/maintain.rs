@@ -0,0 +1,55 @@
+// Housekeeping (`remind maintain`)
+//
+// One-off reminders nobody acted on pile up in `list` long after they stopped mattering.
+// Those still pending longer than their --expire-after past due (or maintenance.expire_after
+// from the config) are moved to the archive next to the reminders file, where `remind merge`
+// can bring them back. The journal is compacted on the same run. Nothing expires unless one
+// of the two is set.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Local};
+
+use crate::cli;
+use crate::config::Config;
+use crate::i18n::tr;
+use crate::reminder::{RecurrenceType, Reminder};
+use crate::storage::Storage;
+use crate::utils;
+
+pub fn run(storage: &Storage, config: &Config, dry_run: bool) -> Result<()> {
+    let default = config.maintenance.expire_after.as_deref().map(cli::parse_duration).transpose()?;
+    let now = Local::now();
+    let (expired, kept): (Vec<Reminder>, Vec<Reminder>) =
+        storage.load()?.into_iter().partition(|r| is_expired(r, default, now));
+
+    for reminder in &expired {
+        println!("{}", tr!("Expired: {} (due {})", reminder.headline(), utils::format_datetime(&reminder.due_time)));
+    }
+    if dry_run {
+        println!("{}", tr!("Dry run - nothing was written."));
+        return Ok(());
+    }
+
+    if expired.is_empty() {
+        storage.compact()?;
+        println!("{}", tr!("Nothing to archive."));
+    } else {
+        // Archive first: a crash in between leaves a reminder in both files rather than in neither
+        storage.archive(&expired)?;
+        storage.save(&kept)?;
+        println!("{}", tr!("{} reminder(s) archived to {}.", expired.len(), storage.archive_path().display()));
+    }
+    Ok(())
+}
+
+// A pending one-off reminder overdue for longer than it may be; recurring ones never expire
+fn is_expired(reminder: &Reminder, default: Option<Duration>, now: DateTime<Local>) -> bool {
+    let expire_after = match reminder.expire_after_minutes {
+        Some(0) => None,
+        Some(minutes) => Some(Duration::minutes(minutes)),
+        None => default,
+    };
+    !reminder.completed
+        && matches!(reminder.recurrence, RecurrenceType::None)
+        && expire_after.is_some_and(|after| now - reminder.due_time > after)
+}
@@ -0,0 +1,79 @@
+// Matrix messages (https://matrix.org)
+//
+// Posts each due reminder into a room, e.g. a personal one shared with your own account, as
+// the user the access token belongs to. A bot account is best, since messages you send
+// yourself don't notify you.
+
+use anyhow::Result;
+use chrono::Local;
+use serde_json::json;
+
+use crate::channels::{Channel, Message, Request};
+use crate::config::MatrixConfig;
+use crate::reminder::Reminder;
+
+pub struct Matrix {
+    config: MatrixConfig,
+    homeserver: String,
+    token: String,
+    room: String,
+}
+
+impl Matrix {
+    // None until the homeserver, an access token and a room are all set
+    pub fn from_config(config: &MatrixConfig) -> Option<Self> {
+        let homeserver = config.homeserver.clone().filter(|homeserver| !homeserver.is_empty())?;
+        let token = std::env::var("REMINDME_MATRIX_TOKEN")
+            .ok()
+            .or_else(|| config.access_token.clone())
+            .filter(|token| !token.is_empty())?;
+        let room = config.room_id.clone().filter(|room| !room.is_empty())?;
+        Some(Matrix { config: config.clone(), homeserver, token, room })
+    }
+}
+
+impl Channel for Matrix {
+    fn name(&self) -> &'static str {
+        "matrix"
+    }
+
+    fn wants(&self, reminder: &Reminder) -> bool {
+        self.config.tags.is_empty() || self.config.tags.iter().any(|tag| reminder.tags.contains(tag))
+    }
+
+    fn request(&self, message: &Message) -> Result<Request> {
+        // The transaction ID makes a retried request post the message only once
+        let transaction = format!("{}-{}", message.reminder.id, Local::now().timestamp_millis());
+        Ok(Request {
+            method: "PUT",
+            url: format!(
+                "{}/_matrix/client/v3/rooms/{}/send/m.room.message/{}",
+                self.homeserver.trim_end_matches('/'),
+                encode(&self.room),
+                encode(&transaction)
+            ),
+            headers: vec![format!("Authorization: Bearer {}", self.token)],
+            body: json!({
+                "msgtype": "m.text",
+                "body": format!("{}\n{}", message.title, message.body),
+                "format": "org.matrix.custom.html",
+                "formatted_body": format!("<b>{}</b><br>{}", escape(&message.title), escape(&message.body)),
+            }),
+        })
+    }
+}
+
+// Percent-encode a URL path segment; room IDs look like "!abc:example.org"
+fn encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
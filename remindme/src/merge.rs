@@ -0,0 +1,159 @@
+// Merging of two versions of the reminders file, used by `sync` and `merge`
+//
+// Reminders are matched by id. With a common ancestor (`base`) the merge is three-way:
+// a field changed on only one side takes that side's value, and a reminder deleted on
+// one side stays deleted unless the other side modified it. Fields changed on both
+// sides (or that differ when there is no base) are conflicts, settled by a resolver:
+// `sync` keeps the most recently modified reminder, `merge` can ask the user.
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+use crate::reminder::Reminder;
+
+pub struct Conflict {
+    pub id: String,
+    pub text: String,
+    pub field: String,
+    pub local: Value,
+    pub remote: Value,
+    pub local_is_newer: bool,
+    pub kept_local: bool,
+}
+
+// Resolver that keeps the value from the most recently modified reminder
+pub fn newest_wins(conflict: &Conflict) -> bool {
+    conflict.local_is_newer
+}
+
+pub struct MergeResult {
+    pub reminders: Vec<Reminder>,
+    pub conflicts: Vec<Conflict>,
+}
+
+// `resolve` returns true to keep the local value of a conflicting field
+pub fn merge(
+    base: Option<&[Reminder]>,
+    local: &[Reminder],
+    remote: &[Reminder],
+    resolve: &mut dyn FnMut(&Conflict) -> bool,
+) -> Result<MergeResult> {
+    let base_by_id: HashMap<&str, &Reminder> = base
+        .unwrap_or(&[])
+        .iter()
+        .map(|r| (r.id.as_str(), r))
+        .collect();
+    let remote_by_id: HashMap<&str, &Reminder> = remote.iter().map(|r| (r.id.as_str(), r)).collect();
+    let local_ids: HashSet<&str> = local.iter().map(|r| r.id.as_str()).collect();
+
+    let mut reminders = Vec::new();
+    let mut conflicts = Vec::new();
+
+    // Keep the local ordering, then append reminders that only exist remotely
+    for local_reminder in local {
+        let base_reminder = base_by_id.get(local_reminder.id.as_str()).copied();
+
+        match remote_by_id.get(local_reminder.id.as_str()) {
+            Some(remote_reminder) => {
+                reminders.push(merge_reminder(base_reminder, local_reminder, remote_reminder, &mut conflicts, resolve)?);
+            }
+            None => {
+                // Deleted remotely: drop it unless it was changed locally since the common ancestor
+                if !is_unchanged(base_reminder, local_reminder)? {
+                    reminders.push(local_reminder.clone());
+                }
+            }
+        }
+    }
+
+    for remote_reminder in remote {
+        if local_ids.contains(remote_reminder.id.as_str()) {
+            continue;
+        }
+
+        // Deleted locally: drop it unless it was changed remotely since the common ancestor
+        let base_reminder = base_by_id.get(remote_reminder.id.as_str()).copied();
+        if !is_unchanged(base_reminder, remote_reminder)? {
+            reminders.push(remote_reminder.clone());
+        }
+    }
+
+    Ok(MergeResult { reminders, conflicts })
+}
+
+// A reminder counts as unchanged only if it existed in the base with identical contents
+fn is_unchanged(base: Option<&Reminder>, reminder: &Reminder) -> Result<bool> {
+    match base {
+        Some(base) => Ok(to_fields(base)? == to_fields(reminder)?),
+        None => Ok(false),
+    }
+}
+
+fn merge_reminder(
+    base: Option<&Reminder>,
+    local: &Reminder,
+    remote: &Reminder,
+    conflicts: &mut Vec<Conflict>,
+    resolve: &mut dyn FnMut(&Conflict) -> bool,
+) -> Result<Reminder> {
+    let base_fields = base.map(to_fields).transpose()?;
+    let local_fields = to_fields(local)?;
+    let remote_fields = to_fields(remote)?;
+    let local_is_newer = local.modified_at() >= remote.modified_at();
+
+    let keys: Vec<&String> = local_fields
+        .keys()
+        .chain(remote_fields.keys().filter(|k| !local_fields.contains_key(*k)))
+        .collect();
+
+    let mut merged = Map::new();
+    for key in keys {
+        let local_value = local_fields.get(key).cloned().unwrap_or(Value::Null);
+        let remote_value = remote_fields.get(key).cloned().unwrap_or(Value::Null);
+
+        if local_value == remote_value {
+            merged.insert(key.clone(), local_value);
+            continue;
+        }
+
+        let base_value = base_fields.as_ref().and_then(|fields| fields.get(key));
+        let value = if base_value == Some(&local_value) {
+            // Only the remote side changed this field
+            remote_value
+        } else if base_value == Some(&remote_value) {
+            // Only the local side changed this field
+            local_value
+        } else if key == "updated_at" {
+            // The modification timestamp isn't a real conflict: keep the newest
+            if local_is_newer { local_value } else { remote_value }
+        } else {
+            // Changed on both sides, or no base to tell which side changed it
+            let mut conflict = Conflict {
+                id: local.id.clone(),
+                text: local.text.clone(),
+                field: key.clone(),
+                local: local_value,
+                remote: remote_value,
+                local_is_newer,
+                kept_local: false,
+            };
+            conflict.kept_local = resolve(&conflict);
+            let value = if conflict.kept_local { conflict.local.clone() } else { conflict.remote.clone() };
+            conflicts.push(conflict);
+            value
+        };
+
+        merged.insert(key.clone(), value);
+    }
+
+    serde_json::from_value(Value::Object(merged))
+        .with_context(|| format!("Failed to merge reminder {}", local.id))
+}
+
+fn to_fields(reminder: &Reminder) -> Result<Map<String, Value>> {
+    match serde_json::to_value(reminder).context("Failed to serialize reminder")? {
+        Value::Object(fields) => Ok(fields),
+        _ => unreachable!("reminders always serialize to JSON objects"),
+    }
+}
@@ -1,61 +1,871 @@
-use crate::reminder::Reminder;
-use crate::storage::Storage;
-use crate::sound;
+use crate::busy;
+use crate::channels::{self, Channel, Message};
+use crate::config::{Broadcast, CatchUpPolicy, Config};
+use crate::holidays::Calendar;
+use crate::hooks::{self, Event};
+use crate::i18n::tr;
+use crate::digest;
+use crate::dnd::Dnd;
+use crate::escalation::Acknowledged;
+use crate::reminder::{Priority, Reminder};
+use crate::routing::Routes;
+use crate::stale;
+use crate::storage::{self, Storage};
+use crate::sound::{self, SoundPlayer};
+use crate::termux;
+use crate::utils;
 use anyhow::Result;
-use notify_rust::Notification;
+use chrono::{DateTime, Duration, Local};
+use std::cell::RefCell;
+use std::ffi::OsString;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::io::Write;
+use std::process::Command;
+use std::thread::{self, JoinHandle};
+use tracing::{debug, info, warn};
+
+// Freedesktop icon for popups, unless a reminder sets its own with --icon; macOS and Windows
+// only show icons given as a file
+const DEFAULT_ICON: &str = "appointment-soon";
+// How long the phone vibrates for a popup on Termux
+const VIBRATION_MS: u32 = 400;
+// Popups on macOS come from Terminal unless notifications.app_id names another app
+#[cfg(target_os = "macos")]
+pub const MACOS_APP_ID: &str = "com.apple.Terminal";
+
+// A reminder `notify` has something to send for
+struct Due {
+    reminder: Reminder,
+    channels: Vec<String>,
+    // Only a heads-up from a route or an --alert; the reminder isn't due yet
+    upcoming: bool,
+    // A route's heads-up is due, not only an --alert
+    warned: bool,
+    // renotify_minutes was filled in from a route's nag and isn't saved
+    nagged: bool,
+}
 
 pub struct Notifier {
     pub storage: Storage,
+    pub config: Config,
+    // Configured push channels, e.g. ntfy
+    channels: Vec<Box<dyn Channel>>,
+    // Popups still waiting for a click on "Open"
+    waiting: RefCell<Vec<JoinHandle<()>>>,
+    // Plays the sound in the background; dropped after the popups, it waits for it to finish
+    sounds: SoundPlayer,
 }
 
 impl Notifier {
-    pub fn new(storage: Storage) -> Self {
-        Notifier { storage }
+    pub fn new(storage: Storage, config: Config) -> Self {
+        let channels = channels::configured(&config);
+        let sounds = SoundPlayer::new(&config.notifications);
+        Notifier { storage, config, channels, waiting: RefCell::new(Vec::new()), sounds }
+    }
+
+    pub fn storage(&mut self) -> &mut Storage {
+        &mut self.storage
     }
 
     pub fn check_due_reminders(&mut self, send_desktop: bool) -> Result<Vec<Reminder>> {
-        let mut reminders = self.storage.load()?;
+        self.send_digests()?;
+        
+        // Most runs find nothing due; the index answers that without loading every reminder.
+        // Custom rules can make future reminders due, and routes warn before the due time, so
+        // those always need the full check.
+        let warns = Routes::from_config(&self.config.routes)?.warns();
+        if self.storage.rules().is_none() && !warns && !self.storage.any_past_due(Local::now())? {
+            debug!("Index shows nothing past due");
+            return Ok(Vec::new());
+        }
+        
+        // Recurring reminders may skip or shift occurrences that land on weekends and holidays
+        let calendar = Calendar::from_config(&self.config.holidays)?;
+        let catch_up = self.config.notifications.catch_up;
+        let (mut upcoming, mut due): (Vec<Due>, Vec<Due>) =
+            self.find_due(send_desktop, &calendar, true)?.into_iter().partition(|due| due.upcoming);
+        
+        // During do-not-disturb only urgent reminders get through; the rest stay due and are
+        // remembered so they can be delivered as one digest once DND ends
+        let mut dnd = Dnd::load()?;
+        let quiet = dnd.is_active(Local::now(), &self.config.notifications)?;
+        let digest = !quiet && !dnd.queued.is_empty();
+        if quiet {
+            due.retain(|due| {
+                if due.reminder.effective_priority(Local::now(), &self.config.priority) == Priority::Urgent {
+                    return true;
+                }
+                dnd.queue(&due.reminder.id);
+                info!(id = %due.reminder.id, "Held back by do not disturb");
+                println!("{}", tr!("Do not disturb: holding back '{}'", due.reminder.text));
+                false
+            });
+            // Heads-ups aren't queued; one still ahead of its due time goes out once DND ends
+            upcoming.retain(|due| due.reminder.effective_priority(Local::now(), &self.config.priority) == Priority::Urgent);
+        } else {
+            dnd.queued.clear();
+        }
+        dnd.save()?;
+        
+        self.hold_for_meeting(&mut due, &mut upcoming);
+        
+        // Desktop popups are grouped and rate limited; reminders over the limit wait for the next run
+        let group = self.groups(&due, digest);
+        let mut sent_log = SentLog::load();
+        let mut budget = self.budget(&sent_log);
+        
+        let grouped_sent = group && budget > 0;
+        if grouped_sent {
+            let grouped: Vec<&Reminder> = due
+                .iter()
+                .filter(|due| wants_desktop(&due.channels))
+                .map(|due| &due.reminder)
+                .collect();
+            self.send_group_notification(&grouped)?;
+            sent_log.record();
+            budget -= 1;
+        }
+        
         let mut due_reminders = Vec::new();
+        for Due { mut reminder, channels, nagged, .. } in due {
+            let deferred = wants_desktop(&channels) && if group { !grouped_sent } else { budget == 0 };
+            if deferred {
+                info!(id = %reminder.id, "Deferred by the notification rate limit");
+                println!("{}", tr!("Notification limit reached; '{}' will be retried on the next run", reminder.text));
+                continue;
+            }
+            
+            self.deliver(&reminder, &channels, Event::Due, group)?;
+            if wants_desktop(&channels) && !group {
+                sent_log.record();
+                budget -= 1;
+            }
+            
+            // Mark as notified and update
+            due_reminders.push(reminder.clone());
+            reminder.mark_notified(&calendar);
+            reminder.catch_up(catch_up, Local::now(), &calendar);
+            if nagged {
+                reminder.renotify_minutes = None;
+            }
+            debug!(id = %reminder.id, completed = reminder.completed, next = %reminder.due_time, "Marked notified");
+            self.storage.update_notified(reminder)?;
+        }
         
-        for reminder in reminders.iter_mut() {
-            if reminder.is_due() {
-                due_reminders.push(reminder.clone());
-                
-                // Notify in terminal
-                println!("REMINDER: {}", reminder.text);
-                
-                // Send desktop notification if requested
-                if send_desktop {
-                    self.send_desktop_notification(reminder)?;
+        // Heads-ups leave the reminder due as before, and are sent once per occurrence
+        for Due { mut reminder, channels, nagged, warned, .. } in upcoming {
+            if wants_desktop(&channels) {
+                if budget == 0 {
+                    info!(id = %reminder.id, "Heads-up deferred by the notification rate limit");
+                    continue;
                 }
-                
-                // Mark as notified and update
-                reminder.mark_notified();
-                self.storage.update_reminder(reminder.clone())?;
+                sent_log.record();
+                budget -= 1;
+            }
+            self.deliver(&reminder, &channels, Event::Upcoming, false)?;
+            
+            if warned {
+                reminder.warned_for = Some(reminder.due_time);
+            }
+            reminder.mark_alerted(Local::now());
+            if nagged {
+                reminder.renotify_minutes = None;
             }
+            debug!(id = %reminder.id, due = %reminder.due_time, "Sent heads-up");
+            self.storage.update_notified(reminder)?;
         }
         
+        sent_log.save();
         Ok(due_reminders)
     }
     
+    // Send a reminder to each of its channels and fire the hooks for `event`: Due for the reminder
+    // itself, Upcoming for a route's heads-up. With `grouped`, its popup was already part of a group.
+    fn deliver(&self, reminder: &Reminder, channels: &[String], event: Event, grouped: bool) -> Result<()> {
+        let upcoming = event == Event::Upcoming;
+        
+        // Routed commands and hooks run alongside each other, each with its own time limit
+        let hooks = self.storage.hooks();
+        thread::scope(|scope| -> Result<()> {
+            for channel in channels {
+                match channel.as_str() {
+                    // Notify in terminal
+                    "terminal" if upcoming => println!("{}", tr!("COMING UP: {} (due {})", reminder.headline(), self.due_label(reminder))),
+                    "terminal" => println!("{}", tr!("REMINDER: {} (due {})", reminder.headline(), self.due_label(reminder))),
+                    // Already covered by the group notification
+                    "desktop" if grouped => {}
+                    "desktop" if upcoming => self.send_heads_up_notification(reminder),
+                    "desktop" => self.send_desktop_notification(reminder)?,
+                    name if let Some(channel) = self.channel(name) => {
+                        let message = self.message(reminder, upcoming);
+                        scope.spawn(move || {
+                            if let Err(e) = channels::send(channel, &message) {
+                                warn!(id = %message.reminder.id, channel = name, error = %format!("{:#}", e), "Push failed");
+                                eprintln!("{}", tr!("Failed to send reminder to {}: {}", name, format!("{:#}", e)));
+                            }
+                        });
+                    }
+                    command => {
+                        scope.spawn(move || {
+                            if let Err(e) = hooks::run_shell(command, event, reminder, hooks.timeout()) {
+                                warn!(id = %reminder.id, route = command, error = %format!("{:#}", e), "Routing failed");
+                                eprintln!("{}", tr!("Failed to route reminder to '{}': {}", command, format!("{:#}", e)));
+                            }
+                        });
+                    }
+                }
+            }
+            scope.spawn(|| hooks.fire(event, reminder));
+            Ok(())
+        })
+    }
+    
+    // In a meeting on the [busy] calendar only urgent reminders get through, as during DND, but
+    // nothing is queued: the rest are simply still due at the first run after the meeting ends
+    fn hold_for_meeting(&self, due: &mut Vec<Due>, upcoming: &mut Vec<Due>) {
+        let until = busy::until(&self.config.busy, Local::now()).unwrap_or_else(|e| {
+            warn!(error = %format!("{:#}", e), "Failed to check the busy calendar");
+            eprintln!("{}", tr!("Failed to check the busy calendar: {}", format!("{:#}", e)));
+            None
+        });
+        let Some(until) = until else { return };
+        due.retain(|due| {
+            if due.reminder.effective_priority(Local::now(), &self.config.priority) == Priority::Urgent {
+                return true;
+            }
+            info!(id = %due.reminder.id, until = %until, "Held back by a meeting");
+            println!("{}", tr!("In a meeting until {}: holding back '{}'", utils::format_time(&until), due.reminder.text));
+            false
+        });
+        upcoming.retain(|due| due.reminder.effective_priority(Local::now(), &self.config.priority) == Priority::Urgent);
+    }
+    
+    // What `notify` would do now: each due reminder with what every channel would send and when
+    // it's due next, printed without sending, running hooks, or saving anything
+    pub fn preview_due_reminders(&mut self, send_desktop: bool) -> Result<Vec<Reminder>> {
+        let calendar = Calendar::from_config(&self.config.holidays)?;
+        let catch_up = self.config.notifications.catch_up;
+        let (mut upcoming, mut due): (Vec<Due>, Vec<Due>) =
+            self.find_due(send_desktop, &calendar, false)?.into_iter().partition(|due| due.upcoming);
+        
+        let dnd = Dnd::load()?;
+        let quiet = dnd.is_active(Local::now(), &self.config.notifications)?;
+        if quiet {
+            due.retain(|due| {
+                let urgent = due.reminder.effective_priority(Local::now(), &self.config.priority) == Priority::Urgent;
+                if !urgent {
+                    println!("{}", tr!("Do not disturb: holding back '{}'", due.reminder.text));
+                }
+                urgent
+            });
+            upcoming.retain(|due| due.reminder.effective_priority(Local::now(), &self.config.priority) == Priority::Urgent);
+        }
+        self.hold_for_meeting(&mut due, &mut upcoming);
+        
+        let group = self.groups(&due, !quiet && !dnd.queued.is_empty());
+        let mut budget = self.budget(&SentLog::load());
+        let grouped_sent = group && budget > 0;
+        if grouped_sent {
+            let grouped: Vec<&Reminder> = due.iter().filter(|due| wants_desktop(&due.channels)).map(|due| &due.reminder).collect();
+            let (summary, body) = group_popup(&grouped);
+            println!("{}", tr!("Grouped desktop popup: {}", summary));
+            for line in body.lines() {
+                println!("  {}", line);
+            }
+            budget -= 1;
+        }
+        
+        let mut previewed = Vec::new();
+        for Due { reminder, channels, upcoming, .. } in due.into_iter().chain(upcoming) {
+            println!("\n{}", reminder.headline());
+            let deferred = wants_desktop(&channels) && if group && !upcoming { !grouped_sent } else { budget == 0 };
+            if deferred {
+                println!("  {}", tr!("Deferred by the notification limit until the next run"));
+                continue;
+            }
+            for channel in &channels {
+                match channel.as_str() {
+                    "terminal" if upcoming => println!("  terminal  {}", tr!("COMING UP: {} (due {})", reminder.headline(), self.due_label(&reminder))),
+                    "terminal" => println!("  terminal  {}", tr!("REMINDER: {} (due {})", reminder.headline(), self.due_label(&reminder))),
+                    "desktop" if group && !upcoming => println!("  desktop   {}", tr!("(in the grouped popup)")),
+                    "desktop" => {
+                        let summary = if upcoming { tr!("Coming up") } else { tr!("RemindMe Reminder") };
+                        println!("  desktop   {} | {}", summary, self.desktop_body(&reminder).replace('\n', " | "));
+                        budget = budget.saturating_sub(1);
+                    }
+                    name if self.channel(name).is_some() => {
+                        let message = self.message(&reminder, upcoming);
+                        println!("  {:<9} {} | {}", name, message.title, message.body);
+                    }
+                    command => println!("  route     {}", command),
+                }
+            }
+            let event = if upcoming { Event::Upcoming } else { Event::Due };
+            for hook in self.storage.hooks().list(event) {
+                println!("  hook      {}", hook);
+            }
+            if upcoming {
+                println!("  then      {}", tr!("Heads-up only; it isn't due yet"));
+                continue;
+            }
+            
+            let mut next = reminder.clone();
+            next.mark_notified(&calendar);
+            next.catch_up(catch_up, Local::now(), &calendar);
+            let then = if next.completed {
+                tr!("Done; it doesn't recur")
+            } else if next.due_time == reminder.due_time {
+                tr!("Stays due")
+            } else {
+                tr!("Next due {}", utils::format_datetime(&next.due_time))
+            };
+            println!("  then      {}", then);
+            previewed.push(reminder);
+        }
+        Ok(previewed)
+    }
+    
+    // Due reminders with the channels each goes to, plus heads-ups that routes and --alert send
+    // before the due time; with `save`, skipped missed occurrences are stored
+    fn find_due(&mut self, send_desktop: bool, calendar: &Calendar, save: bool) -> Result<Vec<Due>> {
+        let routes = Routes::from_config(&self.config.routes)?;
+        let mut reminders = self.storage.load()?;
+        
+        // With catch_up = "skip", occurrences missed while the machine was off are dropped silently
+        if self.config.notifications.catch_up == CatchUpPolicy::Skip {
+            let grace = Duration::minutes(self.config.notifications.missed_after_minutes);
+            for reminder in reminders.iter_mut() {
+                if reminder.is_due() && reminder.skip_missed(Local::now(), grace, calendar) && save {
+                    info!(id = %reminder.id, next = %reminder.due_time, "Skipped missed occurrences");
+                    self.storage.update_notified(reminder.clone())?;
+                }
+            }
+        }
+        
+        // Work out which reminders are due and where each one goes
+        let now = Local::now();
+        let mut due = Vec::new();
+        for mut reminder in reminders {
+            // In a team's shared file, other people's reminders are theirs to be notified of
+            if !self.storage.notifies(&reminder) {
+                continue;
+            }
+            let route = routes.find(&reminder, reminder.effective_priority(now, &self.config.priority));
+            
+            // A route's nag keeps the reminder coming back until completed, unless it sets its own interval
+            let nag = route.and_then(|route| route.nag).filter(|_| reminder.renotify_minutes.is_none());
+            if nag.is_some() {
+                reminder.renotify_minutes = nag;
+            }
+            
+            // Custom rules may override when a pending reminder counts as due
+            let is_due = match self.storage.rules() {
+                Some(rules) if !reminder.completed => rules.is_due(&reminder).unwrap_or_else(|| reminder.is_due()),
+                _ => reminder.is_due(),
+            };
+            let warned = !is_due && route.is_some_and(|route| route.warns(&reminder, now));
+            let upcoming = !is_due && (warned || reminder.alert_due(now));
+            
+            if is_due || upcoming {
+                // Rules can route a reminder elsewhere, then the first matching route; by default it goes to
+                // the terminal (and desktop if requested) and to the push channels that want it
+                let channels = self
+                    .storage
+                    .rules()
+                    .and_then(|rules| rules.route(&reminder))
+                    .or_else(|| route.map(|route| route.channels.clone()).filter(|channels| !channels.is_empty()))
+                    .unwrap_or_else(|| {
+                        let mut channels = vec!["terminal".to_string()];
+                        if send_desktop {
+                            channels.push("desktop".to_string());
+                        }
+                        for channel in self.channels.iter().filter(|channel| channel.wants(&reminder)) {
+                            channels.push(channel.name().to_string());
+                        }
+                        channels
+                    });
+                debug!(id = %reminder.id, due = %reminder.due_time, ?channels, upcoming, "Reminder is due");
+                due.push(Due { reminder, channels, upcoming, warned, nagged: nag.is_some() });
+            }
+        }
+        Ok(due)
+    }
+    
+    // Whether the desktop popups are merged into one
+    fn groups(&self, due: &[Due], digest: bool) -> bool {
+        let desktop_count = due.iter().filter(|due| wants_desktop(&due.channels)).count();
+        let threshold = self.config.notifications.group_threshold;
+        desktop_count > 1 && (digest || (threshold > 0 && desktop_count > threshold))
+    }
+    
+    // How many more popups may be shown this minute
+    fn budget(&self, sent_log: &SentLog) -> usize {
+        match self.config.notifications.max_per_minute {
+            0 => usize::MAX,
+            max => max.saturating_sub(sent_log.sent_within_minute()),
+        }
+    }
+    
+    // One popup standing in for many due reminders
+    fn send_group_notification(&self, reminders: &[&Reminder]) -> Result<()> {
+        let (summary, body) = group_popup(reminders);
+        println!("{}", tr!("Sending desktop notification: {}", summary));
+        
+        // The group is as urgent as its most urgent reminder
+        let now = Local::now();
+        let highest = reminders.iter().map(|r| r.effective_priority(now, &self.config.priority)).max();
+        let priority = highest.unwrap_or(Priority::Medium);
+        
+        let earliest_id = reminders.iter().min_by_key(|r| r.due_time).map(|r| r.id.as_str());
+        self.show_popup(&summary, &body, priority, DEFAULT_ICON, earliest_id, || {
+            for reminder in reminders {
+                self.send_fallback_notification(reminder);
+            }
+        });
+        Ok(())
+    }
+    
+    // A route's heads-up before the due time
+    fn send_heads_up_notification(&self, reminder: &Reminder) {
+        println!("{}", tr!("Sending heads-up for: {}", reminder.headline()));
+        
+        let body = self.desktop_body(reminder);
+        let priority = reminder.effective_priority(Local::now(), &self.config.priority);
+        let icon = reminder.icon_name().unwrap_or(DEFAULT_ICON);
+        self.show_popup(&tr!("Coming up"), &body, priority, icon, Some(&reminder.id), || {
+            self.send_fallback_message(&tr!("Coming up: {} (due {})", reminder.headline(), self.due_label(reminder)))
+        });
+    }
+    
     fn send_desktop_notification(&self, reminder: &Reminder) -> Result<()> {
-        println!("Sending desktop notification for: {}", reminder.text);
-        
-        // Show the notification
-        Notification::new()
-            .summary("RemindMe Reminder")
-            .body(&reminder.text)
-            .icon("appointment-soon")
-            .timeout(5000)
-            .show()?;
+        println!("{}", tr!("Sending desktop notification for: {}", reminder.headline()));
+        
+        let body = self.desktop_body(reminder);
+        let priority = reminder.effective_priority(Local::now(), &self.config.priority);
+        let icon = reminder.icon_name().unwrap_or(DEFAULT_ICON);
+        self.show_popup(&tr!("RemindMe Reminder"), &body, priority, icon, Some(&reminder.id), || self.send_fallback_notification(reminder));
+        Ok(())
+    }
+    
+    // Show a popup with its sound, or use `fallback` without a notification service (SSH, no D-Bus).
+    // With `open`, the popup gets an "Open" button that opens the TUI on that reminder, and for an
+    // urgent one with an escalating alarm, an "Acknowledge" button that stops the alarm.
+    fn show_popup(&self, summary: &str, body: &str, priority: Priority, icon: &str, open: Option<&str>, fallback: impl FnOnce()) {
+        let result = if termux::detected() {
+            self.termux_popup(summary, body, priority, open.unwrap_or(summary))
+        } else {
+            let acknowledge = open.filter(|_| priority == Priority::Urgent && self.config.notifications.escalate_every > 0);
+            self.popup(summary, body, priority, icon, open.filter(|_| self.config.notifications.open_action), acknowledge)
+        };
+        if let Err(e) = result {
+            warn!(error = %e, summary, "Desktop notification unavailable; using the fallback");
+            println!("{}", tr!("Desktop notification unavailable ({}), using terminal fallback", e));
+            fallback();
+            return;
+        }
+        
+        info!(summary, "Desktop notification sent");
+        println!("{}", tr!("Desktop notification sent successfully"));
+    }
+    
+    // Android notification through Termux:API, replacing the previous one for the same `tag`
+    fn termux_popup(&self, summary: &str, body: &str, priority: Priority, tag: &str) -> Result<()> {
+        let sound = self.config.notifications.sound != "none";
+        // Without a sound file, Android plays its default notification sound
+        let sound_file = sound && Path::new(&sound::get_sound_path(&self.config.notifications)).exists();
+        termux::notify(summary, body, priority, tag, sound && !sound_file)?;
+        
+        if sound_file {
+            self.play_sound(priority);
+        }
+        if self.config.notifications.vibrate && let Err(e) = termux::vibrate(VIBRATION_MS) {
+            warn!(error = %e, "Failed to vibrate");
+        }
+        Ok(())
+    }
+    
+    // Freedesktop notification daemon over D-Bus (dunst, mako, GNOME, KDE, ...)
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn popup(&self, summary: &str, body: &str, priority: Priority, icon: &str, open: Option<&str>, acknowledge: Option<&str>) -> Result<()> {
+        use notify_rust::{Notification, Urgency};
+        
+        let urgency = match priority {
+            Priority::Low => Urgency::Low,
+            Priority::Medium | Priority::High => Urgency::Normal,
+            Priority::Urgent => Urgency::Critical,
+        };
+        let mut notification = Notification::new();
+        notification
+            .summary(summary)
+            .body(body)
+            .urgency(urgency)
+            .icon(icon)
+            .timeout(5000);
+        if open.is_some() {
+            notification.action("open", &tr!("Open"));
+        }
+        if acknowledge.is_some() {
+            notification.action("acknowledge", &tr!("Acknowledge"));
+        }
+        let handle = notification.show()?;
+        
+        // The daemon has no say in sounds, so play one ourselves
+        if self.config.notifications.sound != "none" {
+            self.play_sound(priority);
+        }
         
-        // Play notification sound
-        if let Err(e) = sound::play_notification_sound() {
-            // Just log the error but don't fail the notification
-            println!("Failed to play notification sound: {}", e);
+        // The click arrives whenever the user gets to it, so wait in the background until the popup
+        // closes. Opening the reminder counts as acknowledging it too
+        if open.is_some() || acknowledge.is_some() {
+            let command = open.map(|id| self.open_command(id));
+            let acknowledge = acknowledge.map(str::to_string);
+            self.wait(move || {
+                handle.wait_for_action(|action| {
+                    if !matches!(action, "open" | "acknowledge") {
+                        return;
+                    }
+                    if let Some(id) = &acknowledge
+                        && let Err(e) = Acknowledged::record(Some(id))
+                    {
+                        warn!(error = %e, id, "Failed to acknowledge the alarm");
+                    }
+                    if action == "open"
+                        && let Some(command) = command
+                    {
+                        open_tui(command);
+                    }
+                });
+            });
         }
+        Ok(())
+    }
+    
+    // Notification Center, with the sound played by the system
+    #[cfg(target_os = "macos")]
+    fn popup(&self, summary: &str, body: &str, _priority: Priority, icon: &str, open: Option<&str>, _acknowledge: Option<&str>) -> Result<()> {
+        use mac_notification_sys::{MainButton, Notification, NotificationResponse, Sound};
+        use std::sync::Once;
+        
+        // Popups are shown as coming from this app, which can only be chosen once per process
+        static APPLICATION: Once = Once::new();
+        let app_id = self.config.notifications.app_id.as_deref().unwrap_or(MACOS_APP_ID);
+        APPLICATION.call_once(|| {
+            if let Err(e) = mac_notification_sys::set_application(app_id) {
+                warn!(error = %e, app_id, "Failed to set the notifying application");
+            }
+        });
+        
+        let sound = match self.config.notifications.sound.as_str() {
+            "none" => None,
+            "default" => Some(Sound::Default),
+            name => Some(Sound::Custom(name.to_string())),
+        };
+        let image = Path::new(icon).is_file().then(|| icon.to_string());
+        let send = move |summary: &str, body: &str, button: Option<&str>| {
+            let mut notification = Notification::new();
+            notification.title(summary).message(body).maybe_sound(sound);
+            if let Some(image) = &image {
+                notification.content_image(image);
+            }
+            match button {
+                Some(label) => notification.main_button(MainButton::SingleAction(label)),
+                None => notification.asynchronous(true),
+            };
+            notification.send()
+        };
+        
+        let Some(id) = open else {
+            send(summary, body, None)?;
+            return Ok(());
+        };
+        // With a button, sending waits for the user, so it happens in the background; a popup
+        // failing there is only logged, as the run has already moved past its fallback
+        let command = self.open_command(id);
+        let (summary, body) = (summary.to_string(), body.to_string());
+        self.wait(move || match send(&summary, &body, Some(&tr!("Open"))) {
+            Ok(NotificationResponse::ActionButton(_)) => open_tui(command),
+            Ok(_) => {}
+            Err(e) => warn!(error = %e, summary, "Desktop notification failed"),
+        });
+        Ok(())
+    }
+    
+    // Toast notification, with the sound played by the system
+    #[cfg(windows)]
+    fn popup(&self, summary: &str, body: &str, priority: Priority, icon: &str, open: Option<&str>, _acknowledge: Option<&str>) -> Result<()> {
+        use crate::error::RemindError;
+        use std::sync::mpsc;
+        use tauri_winrt_notification::{IconCrop, Scenario, Sound, Toast};
         
-        println!("Desktop notification sent successfully");
+        let app_id = self.config.notifications.app_id.as_deref().unwrap_or(Toast::POWERSHELL_APP_ID);
+        let sound = match self.config.notifications.sound.as_str() {
+            "none" => None,
+            "default" => Some(Sound::Default),
+            name => Some(name.parse::<Sound>().map_err(|_| {
+                RemindError::Config(format!("Unknown Windows sound '{}' in notifications.sound", name))
+            })?),
+        };
+        
+        // A toast has a line of text per field
+        let (first, rest) = body.split_once('\n').unwrap_or((body, ""));
+        let mut toast = Toast::new(app_id).title(summary).text1(first).sound(sound);
+        if !rest.is_empty() {
+            toast = toast.text2(rest);
+        }
+        if Path::new(icon).is_file() {
+            toast = toast.icon(Path::new(icon), IconCrop::Square, "");
+        }
+        // Urgent toasts stay on screen until dismissed
+        if priority == Priority::Urgent {
+            toast = toast.scenario(Scenario::Reminder);
+        }
+        
+        let Some(id) = open else {
+            toast.show()?;
+            return Ok(());
+        };
+        // Clicks are only delivered while we're running, so wait until the toast is clicked or goes away
+        let command = self.open_command(id);
+        let (clicked, answer) = mpsc::channel();
+        let dismissed = clicked.clone();
+        toast
+            .add_button(&tr!("Open"), "open")
+            .on_activated(move |action| {
+                let _ = clicked.send(action);
+                Ok(())
+            })
+            .on_dismissed(move |_| {
+                let _ = dismissed.send(None);
+                Ok(())
+            })
+            .show()?;
+        self.wait(move || {
+            if answer.recv().ok().flatten().as_deref() == Some("open") {
+                open_tui(command);
+            }
+        });
+        Ok(())
+    }
+    
+    // Start the notification sound without waiting for it; a failure is only logged, the popup is
+    // what matters
+    fn play_sound(&self, priority: Priority) {
+        if let Err(e) = self.sounds.play_notification(priority) {
+            warn!(error = %e, "Failed to play notification sound");
+            println!("{}", tr!("Failed to play notification sound: {}", e));
+        }
+    }
+    
+    // Run `waiter` in the background; the notifier stays around until it's done
+    fn wait(&self, waiter: impl FnOnce() + Send + 'static) {
+        self.waiting.borrow_mut().push(thread::spawn(waiter));
+    }
+    
+    // `<terminal> remind --storage-file PATH --tui --focus ID`
+    fn open_command(&self, id: &str) -> Command {
+        let remind = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("remind"));
+        let args: Vec<OsString> = vec![
+            remind.into(),
+            "--storage-file".into(),
+            self.storage.path().into(),
+            "--tui".into(),
+            "--focus".into(),
+            id.into(),
+        ];
+        terminal_command(&self.config.notifications.terminal, args)
+    }
+    
+    // The daily summary and the weekly list of stale reminders, when they're switched on and due
+    fn send_digests(&self) -> Result<()> {
+        let now = Local::now();
+        if let Some(daily) = digest::daily(&self.storage, &self.config, now)? {
+            info!(reminders = daily.lines.len(), "Sent the daily digest");
+            if self.config.digest.desktop {
+                self.announce(&daily.title, &daily.lines.join("\n"));
+            }
+            // A failed email shouldn't hold up the reminders themselves
+            if let Some(to) = &self.config.digest.email
+                && let Err(e) = digest::email(&self.config.digest, to, &daily)
+            {
+                warn!(error = %format!("{:#}", e), "Failed to email the daily digest");
+                eprintln!("{}", tr!("Failed to email the daily digest: {}", format!("{:#}", e)));
+            }
+        }
+        if let Some((summary, body)) = stale::digest(&self.storage, &self.config, now)? {
+            info!("Sent the stale reminder digest");
+            self.announce(&summary, &body);
+        }
         Ok(())
     }
-}
\ No newline at end of file
+    
+    // A popup that isn't about a particular reminder, e.g. the end of a pomodoro
+    pub fn announce(&self, summary: &str, body: &str) {
+        self.show_popup(summary, body, Priority::Medium, DEFAULT_ICON, None, || self.send_fallback_message(&format!("{}: {}", summary, body)));
+    }
+    
+    fn send_fallback_notification(&self, reminder: &Reminder) {
+        self.send_fallback_message(&tr!("Reminder: {} (due {})", reminder.headline(), self.due_label(reminder)));
+    }
+    
+    // Ring the bell and print a bold banner on the controlling terminal, plus any configured broadcasts
+    fn send_fallback_message(&self, message: &str) {
+        let banner = format!("\x07\x1b[1m*** {} ***\x1b[0m\n", message);
+        
+        // /dev/tty reaches the user even when stdout is redirected; without one (cron, systemd) use stdout
+        match OpenOptions::new().write(true).open("/dev/tty") {
+            Ok(mut tty) => {
+                let _ = tty.write_all(banner.as_bytes());
+            }
+            Err(_) => print!("{}", banner),
+        }
+        
+        for broadcast in &self.config.notifications.fallback_broadcast {
+            let status = match broadcast {
+                Broadcast::Tmux => Command::new("tmux").args(["display-message", message]).status(),
+                Broadcast::Wall => Command::new("wall").arg(message).status(),
+            };
+            
+            match status {
+                Ok(status) if status.success() => {}
+                Ok(status) => {
+                    warn!(?broadcast, %status, "Broadcast failed");
+                    println!("{:?} broadcast failed: {}", broadcast, status);
+                }
+                Err(e) => {
+                    warn!(?broadcast, error = %e, "Broadcast failed");
+                    println!("{:?} broadcast failed: {}", broadcast, e);
+                }
+            }
+        }
+    }
+    
+    fn channel(&self, name: &str) -> Option<&dyn Channel> {
+        self.channels.iter().find(|channel| channel.name() == name).map(|channel| channel.as_ref())
+    }
+    
+    // The push message for a due reminder, or for a route's heads-up before it's due
+    fn message<'a>(&self, reminder: &'a Reminder, upcoming: bool) -> Message<'a> {
+        Message {
+            reminder,
+            title: if upcoming { tr!("Coming up: {}", reminder.headline()) } else { reminder.headline() },
+            body: tr!("Due {}", self.due_label(reminder)),
+            priority: reminder.effective_priority(Local::now(), &self.config.priority),
+        }
+    }
+    
+    fn desktop_body(&self, reminder: &Reminder) -> String {
+        if reminder.is_all_day() {
+            reminder.headline()
+        } else {
+            format!("{}\n{}", reminder.headline(), tr!("Due {}", self.due_label(reminder)))
+        }
+    }
+    
+    fn due_label(&self, reminder: &Reminder) -> String {
+        if reminder.is_all_day() {
+            return utils::format_date(reminder.due_time.date_naive(), "%a %Y-%m-%d");
+        }
+        utils::format_due(&reminder.due_time, self.config.display.relative_times)
+    }
+}
+
+// Stay around until every popup with an "Open" button is closed, or clicks would go unanswered
+impl Drop for Notifier {
+    fn drop(&mut self) {
+        for waiter in self.waiting.take() {
+            let _ = waiter.join();
+        }
+    }
+}
+
+fn wants_desktop(channels: &[String]) -> bool {
+    channels.iter().any(|c| c == "desktop")
+}
+
+// Summary and body of the popup standing in for many due reminders
+fn group_popup(reminders: &[&Reminder]) -> (String, String) {
+    let earliest = reminders.iter().min_by_key(|r| r.due_time).map(|r| r.headline()).unwrap_or_default();
+    let summary = tr!("{} reminders due — earliest: {}", reminders.len(), earliest);
+    
+    let mut body: Vec<String> = reminders.iter().take(5).map(|r| format!("• {}", r.headline())).collect();
+    if reminders.len() > 5 {
+        body.push(tr!("…and {} more", reminders.len() - 5));
+    }
+    (summary, body.join("\n"))
+}
+
+fn open_tui(mut command: Command) {
+    info!(command = ?command, "Opening the TUI from a notification");
+    if let Err(e) = command.spawn() {
+        warn!(error = %e, "Failed to start the terminal");
+        eprintln!("Failed to open a terminal for the reminder: {}", e);
+    }
+}
+
+// The terminal command line, through sh so the terminal setting can carry arguments
+#[cfg(unix)]
+fn terminal_command(terminal: &str, args: Vec<OsString>) -> Command {
+    // Terminal.app takes its command as AppleScript
+    if cfg!(target_os = "macos") && terminal.is_empty() {
+        let line = shell_words::join(args.iter().map(|arg| arg.to_string_lossy()));
+        let script = format!(
+            "tell application \"Terminal\" to do script \"{}\"",
+            line.replace('\\', "\\\\").replace('"', "\\\"")
+        );
+        let mut command = Command::new("osascript");
+        command.args(["-e", &script, "-e", "tell application \"Terminal\" to activate"]);
+        return command;
+    }
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(format!("{} \"$@\"", terminal)).arg("sh").args(args);
+    command
+}
+
+// `start` opens a console window for the command unless the terminal setting names another one, e.g. `wt`
+#[cfg(windows)]
+fn terminal_command(terminal: &str, args: Vec<OsString>) -> Command {
+    let mut command = Command::new("cmd");
+    command
+        .args(["/C", "start", ""])
+        .args(shell_words::split(terminal).unwrap_or_default())
+        .args(args);
+    command
+}
+
+// Times of recent desktop notifications, kept across runs since `notify` is started every minute
+struct SentLog {
+    sent: Vec<DateTime<Local>>,
+}
+
+impl SentLog {
+    fn path() -> Option<PathBuf> {
+        storage::state_path("notify-log.json").ok()
+    }
+
+    fn load() -> Self {
+        let sent = Self::path()
+            .and_then(|path| fs::read(path).ok())
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default();
+        SentLog { sent }
+    }
+
+    fn sent_within_minute(&self) -> usize {
+        let cutoff = Local::now() - Duration::minutes(1);
+        self.sent.iter().filter(|t| **t > cutoff).count()
+    }
+
+    fn record(&mut self) {
+        self.sent.push(Local::now());
+    }
+
+    // Only the last minute matters, so older entries are dropped; failures just lose the history
+    fn save(&mut self) {
+        let cutoff = Local::now() - Duration::minutes(1);
+        self.sent.retain(|t| *t > cutoff);
+        if let (Some(path), Ok(json)) = (Self::path(), serde_json::to_vec(&self.sent)) {
+            let _ = fs::write(path, json);
+        }
+    }
+}
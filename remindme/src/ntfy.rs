@@ -0,0 +1,70 @@
+// ntfy.sh push notifications (https://ntfy.sh)
+//
+// Publishes each due reminder to a topic, which the ntfy app on a phone subscribes to; the
+// public server needs no account. Messages go as JSON to the server root, so titles with
+// umlauts or emoji need no header encoding.
+
+use anyhow::Result;
+use serde_json::json;
+
+use crate::channels::{Channel, Message, Request};
+use crate::config::{NtfyConfig, ServerConfig};
+use crate::i18n::tr;
+use crate::reminder::Reminder;
+
+pub struct Ntfy {
+    config: NtfyConfig,
+    topic: String,
+    // Token for the "Done" button's call to `remind serve`
+    api_token: Option<String>,
+}
+
+impl Ntfy {
+    // None until a topic is set
+    pub fn from_config(config: &NtfyConfig, server: &ServerConfig) -> Option<Self> {
+        let topic = config.topic.clone().filter(|topic| !topic.is_empty())?;
+        Some(Ntfy { config: config.clone(), topic, api_token: server.token() })
+    }
+}
+
+impl Channel for Ntfy {
+    fn name(&self) -> &'static str {
+        "ntfy"
+    }
+
+    fn wants(&self, reminder: &Reminder) -> bool {
+        self.config.tags.is_empty() || self.config.tags.iter().any(|tag| reminder.tags.contains(tag))
+    }
+
+    fn request(&self, message: &Message) -> Result<Request> {
+        let mut body = json!({
+            "topic": self.topic,
+            "title": message.title,
+            "message": message.body,
+            "priority": self.config.priorities.level(message.priority, [2, 3, 4, 5]),
+            "tags": message.reminder.tags,
+        });
+        // Completes the reminder through the REST API, from the notification itself
+        if let (Some(api_url), Some(token)) = (&self.config.api_url, &self.api_token) {
+            body["actions"] = json!([{
+                "action": "http",
+                "label": tr!("Done"),
+                "url": format!("{}/reminders/{}/complete", api_url.trim_end_matches('/'), message.reminder.id),
+                "method": "POST",
+                "headers": { "Authorization": format!("Bearer {}", token) },
+                "clear": true,
+            }]);
+        }
+
+        let mut headers = Vec::new();
+        if let Some(token) = &self.config.token {
+            headers.push(format!("Authorization: Bearer {}", token));
+        }
+        Ok(Request {
+            method: "POST",
+            url: self.config.server.trim_end_matches('/').to_string(),
+            headers,
+            body,
+        })
+    }
+}
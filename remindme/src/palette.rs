@@ -0,0 +1,82 @@
+// The TUI's command palette (`:`): every action by name, found by fuzzy search
+//
+// The first word picks the command and anything after it is its argument, e.g. ":snooze 30m" or
+// ":filter work". The word only needs the command's letters in order, so ":snz" finds snooze.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Add,
+    Edit,
+    Delete,
+    Complete,
+    Snooze,
+    Filter,
+    Tags,
+    Layout,
+    Sort,
+    Copy,
+    CopyDetails,
+    Settings,
+    Help,
+    Quit,
+}
+
+pub struct Command {
+    pub name: &'static str,
+    pub action: Action,
+    pub description: &'static str,
+}
+
+pub const COMMANDS: &[Command] = &[
+    Command { name: "add", action: Action::Add, description: "Add a new reminder" },
+    Command { name: "edit", action: Action::Edit, description: "Edit the selected reminder" },
+    Command { name: "delete", action: Action::Delete, description: "Delete the selected reminder" },
+    Command { name: "complete", action: Action::Complete, description: "Complete the selected reminder" },
+    Command { name: "snooze", action: Action::Snooze, description: "Put the selected reminder off, e.g. snooze 30m (default 10m)" },
+    Command { name: "filter", action: Action::Filter, description: "Show only reminders with a tag, e.g. filter work; no tag shows all" },
+    Command { name: "tags", action: Action::Tags, description: "Edit the selected reminder's tags" },
+    Command { name: "layout", action: Action::Layout, description: "Switch layout, or pick one: layout list|split|calendar" },
+    Command { name: "sort", action: Action::Sort, description: "Change the order, or pick one: sort due|priority|created|text" },
+    Command { name: "copy", action: Action::Copy, description: "Copy the selected reminder's text" },
+    Command { name: "copy-details", action: Action::CopyDetails, description: "Copy everything about the selected reminder" },
+    Command { name: "settings", action: Action::Settings, description: "Test notifications and choose their sound" },
+    Command { name: "help", action: Action::Help, description: "Show the keyboard shortcuts" },
+    Command { name: "quit", action: Action::Quit, description: "Leave the TUI" },
+];
+
+// Commands matching the first word of `input`, best first; all of them before anything is typed
+pub fn matches(input: &str) -> Vec<&'static Command> {
+    let word = input.split_whitespace().next().unwrap_or("").to_lowercase();
+    let mut scored: Vec<(i32, &Command)> = COMMANDS
+        .iter()
+        .filter_map(|command| score(&word, command.name).map(|score| (score, command)))
+        .collect();
+    // Stable, so equally good matches keep the order above
+    scored.sort_by_key(|(score, _)| -score);
+    scored.into_iter().map(|(_, command)| command).collect()
+}
+
+// Everything after the command word
+pub fn argument(input: &str) -> &str {
+    let input = input.trim_start();
+    input.find(char::is_whitespace).map_or("", |i| input[i..].trim())
+}
+
+// How well `pattern` matches `name`, or None unless its letters all appear in order. Letters
+// right after each other and a match at the start count for more
+fn score(pattern: &str, name: &str) -> Option<i32> {
+    let mut score = 0;
+    let mut last: Option<usize> = None;
+    let mut chars = name.char_indices();
+    for p in pattern.chars() {
+        let (i, _) = chars.by_ref().find(|(_, c)| *c == p)?;
+        score += match last {
+            Some(last) if i == last + 1 => 3,
+            None if i == 0 => 5,
+            _ => 1,
+        };
+        last = Some(i);
+    }
+    // Shorter names win among equal matches, so "copy" comes before "copy-details"
+    Some(score * 100 - name.len() as i32)
+}
@@ -0,0 +1,130 @@
+// Keyboard-driven triage with an external picker (`remind pick`)
+//
+// Without --action the pending reminders are printed one per line, soonest first, for fzf,
+// dmenu or rofi. The line chosen there is piped back into `remind pick --action ...`, which
+// finds the reminder by the short ID at the start of the line:
+//
+//   remind pick | fzf | remind pick --action complete
+
+use anyhow::Result;
+use chrono::Local;
+use clap::ValueEnum;
+use std::io::{self, Read};
+
+use crate::bulk;
+use crate::config::Config;
+use crate::error::RemindError;
+use crate::holidays::Calendar;
+use crate::i18n::tr;
+use crate::reminder::Reminder;
+use crate::storage::Storage;
+use crate::utils;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PickAction {
+    Complete,
+    Delete,
+    Edit,
+    Show,
+}
+
+// Returns the exit code: 1 when nothing was picked, so `&&` chains stop after an escaped picker
+pub fn run(storage: &mut Storage, config: &Config, action: Option<PickAction>, stdin_id: bool) -> Result<i32> {
+    let Some(action) = action else {
+        print_lines(&storage.load()?);
+        return Ok(0);
+    };
+
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    // A picked line starts with the short ID; with --stdin-id the input is nothing but an ID
+    let chosen = if stdin_id { input.trim() } else { input.split_whitespace().next().unwrap_or_default() };
+    if chosen.is_empty() {
+        return Ok(1);
+    }
+    let reminder = find(storage.load()?, chosen)?;
+
+    match action {
+        PickAction::Complete => {
+            let calendar = Calendar::from_config(&config.holidays)?;
+            let (reminder, follow_up) = storage.complete_reminder(reminder, &calendar)?;
+            if reminder.completed {
+                println!("{}", tr!("Completed '{}'.", reminder.text));
+            } else {
+                println!("{}", tr!("Done for now. Next occurrence: {}", utils::format_datetime(&reminder.due_time)));
+            }
+            if let Some(next) = follow_up {
+                println!("{}", tr!("Follow-up '{}' added, due {}.", next.text, utils::format_datetime(&next.due_time)));
+            }
+        }
+        PickAction::Delete => {
+            storage.delete_reminder(&reminder.id)?;
+            println!("{}", tr!("Deleted '{}'.", reminder.text));
+        }
+        PickAction::Edit => {
+            let selection = bulk::Selection { all: false, id: Some(&reminder.id), tag: None };
+            bulk::run(storage, config.input.date_order, selection)?;
+        }
+        PickAction::Show => show(&reminder, config),
+    }
+    Ok(0)
+}
+
+fn print_lines(reminders: &[Reminder]) {
+    let mut pending: Vec<&Reminder> = reminders.iter().filter(|r| !r.completed).collect();
+    pending.sort_by_key(|r| r.due_time);
+    for reminder in pending {
+        let tags: String = reminder.tags.iter().map(|t| format!(" #{}", t)).collect();
+        println!(
+            "{}  {}  {}{}",
+            reminder.id.chars().take(8).collect::<String>(),
+            utils::format_datetime(&reminder.due_time),
+            reminder.text,
+            tags
+        );
+    }
+}
+
+// The reminder whose ID starts with `prefix`, which must be unambiguous
+pub fn find(reminders: Vec<Reminder>, prefix: &str) -> Result<Reminder> {
+    let mut matches: Vec<Reminder> = reminders.into_iter().filter(|r| r.id.starts_with(prefix)).collect();
+    match matches.len() {
+        0 => Err(RemindError::NotFound(prefix.to_string()).into()),
+        1 => Ok(matches.remove(0)),
+        n => Err(RemindError::InvalidInput(format!("ID '{}' matches {} reminders", prefix, n)).into()),
+    }
+}
+
+fn show(reminder: &Reminder, config: &Config) {
+    print!("{}", details(reminder, config));
+}
+
+// Everything about a reminder, one field per line; also what the TUI copies with `Y`
+pub fn details(reminder: &Reminder, config: &Config) -> String {
+    let mut lines = vec![
+        reminder.text.clone(),
+        format!("  ID:         {}", reminder.id),
+        format!("  Due:        {}", utils::format_due(&reminder.due_time, config.display.relative_times)),
+        format!("  Recurrence: {}", reminder.recurrence),
+        format!("  Priority:   {:?} (now {:?})", reminder.priority, reminder.effective_priority(Local::now(), &config.priority)),
+    ];
+    if !reminder.tags.is_empty() {
+        lines.push(format!("  Tags:       {}", reminder.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ")));
+    }
+    if let Some(context) = &reminder.context {
+        lines.push(format!("  Context:    {}", context));
+    }
+    if let Some(assignee) = &reminder.assignee {
+        lines.push(format!("  Assignee:   {}", assignee));
+    }
+    lines.push(format!("  Created:    {}", utils::format_datetime(&reminder.created_at)));
+    for (i, step) in reminder.follow_ups.iter().enumerate() {
+        let label = if i == 0 { "  Follow-up:  " } else { "              " };
+        lines.push(format!("{}{} ({} after)", label, step.text, utils::format_minutes(step.after_minutes)));
+    }
+    if let Some(notes) = &reminder.notes {
+        lines.push("  Notes:".to_string());
+        lines.extend(notes.lines().map(|line| format!("    {}", line)));
+    }
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
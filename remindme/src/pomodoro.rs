@@ -0,0 +1,55 @@
+// Pomodoro sessions (`remind pomodoro start --task ID`)
+//
+// Runs work/break cycles in the terminal with a countdown, announcing the end of each
+// phase through the notifier. Every finished work period is recorded on the linked
+// reminder, so `stats` can show how much focused time each task received.
+
+use anyhow::{anyhow, Result};
+use chrono::{Duration, Local};
+
+use crate::error::RemindError;
+use crate::notification::Notifier;
+use crate::utils;
+
+pub struct Session {
+    pub task: String,
+    pub work: Duration,
+    pub rest: Duration,
+    pub cycles: u32,
+}
+
+pub fn run(notifier: &mut Notifier, session: &Session) -> Result<()> {
+    let task = notifier
+        .storage()
+        .get_reminder_by_id(&session.task)?
+        .ok_or_else(|| RemindError::NotFound(session.task.clone()))?;
+
+    for cycle in 1..=session.cycles {
+        println!("Pomodoro {}/{}: {}", cycle, session.cycles, task.text);
+        utils::countdown("Work", Local::now() + session.work)?;
+
+        // Reload in case the reminder changed while we were counting down
+        let storage = notifier.storage();
+        let mut reminder = storage
+            .get_reminder_by_id(&task.id)?
+            .ok_or_else(|| anyhow!("Reminder {} was deleted during the pomodoro", task.id))?;
+        reminder.pomodoros.push(Local::now());
+        let total = reminder.pomodoros.len();
+        storage.update_reminder(reminder)?;
+
+        if cycle == session.cycles {
+            notifier.announce("Pomodoro done", &format!("{} ({} pomodoros so far)", task.text, total));
+            break;
+        }
+
+        let rest = match session.rest.num_minutes() {
+            0 => format!("{} seconds", session.rest.num_seconds()),
+            minutes => format!("{} minutes", minutes),
+        };
+        notifier.announce("Time for a break", &rest);
+        utils::countdown("Break", Local::now() + session.rest)?;
+        notifier.announce("Back to work", &task.text);
+    }
+
+    Ok(())
+}
@@ -1,19 +1,114 @@
-use chrono::{DateTime, Datelike, Local, TimeZone, Timelike};
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Weekday};
+use clap::ValueEnum;
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use uuid::Uuid;
 
+use crate::config::{CatchUpPolicy, PriorityConfig};
+use crate::holidays::Calendar;
+use crate::i18n::tr;
+use crate::utils::{self, local};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RecurrenceType {
     None,
     Daily,
+    Weekdays, // Monday to Friday
     Weekly,
     Monthly,
     Yearly,
+    EveryDays(u32), // "every other day", "every 3 days"
+    WeeklyOn { interval: u32, weekday: Weekday }, // "every tuesday", "every other tuesday"
+    MonthlyOn { nth: i32, weekday: Weekday }, // "first monday of the month"; nth -1 is the last one
+    MonthlyLastDay, // "last-day-of-month": the 31st, 30th, or the end of February
     Custom(String), // For cron-like expressions (optional for future)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// What a recurring reminder does when its next occurrence lands on a weekend or holiday
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OffDayPolicy {
+    #[default]
+    Keep,
+    /// Skip that occurrence and move on to the next one on a business day
+    Skip,
+    /// Move that occurrence to the next business day
+    Next,
+}
+
+// When a recurring reminder moves on to its next occurrence
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum AdvanceOn {
+    /// As soon as it has been notified
+    #[default]
+    Notify,
+    /// Only when it is completed, so missed occurrences stay overdue and visible
+    Complete,
+}
+
+// Orders for `list --sort` and the TUI's `o`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Soonest due first
+    Due,
+    /// Most important first (escalation included), then soonest due
+    Priority,
+    /// Oldest first, the order they were added in
+    #[default]
+    Created,
+    /// Alphabetically by text
+    #[value(alias = "alphabetical")]
+    Text,
+}
+
+impl SortOrder {
+    pub fn compare(self, a: &Reminder, b: &Reminder, now: DateTime<Local>, priorities: &PriorityConfig) -> std::cmp::Ordering {
+        match self {
+            SortOrder::Due => a.due_time.cmp(&b.due_time),
+            SortOrder::Priority => b
+                .effective_priority(now, priorities)
+                .cmp(&a.effective_priority(now, priorities))
+                .then(a.due_time.cmp(&b.due_time)),
+            SortOrder::Created => a.created_at.cmp(&b.created_at),
+            SortOrder::Text => a.text.to_lowercase().cmp(&b.text.to_lowercase()),
+        }
+    }
+
+    // The one after this, round again after the last
+    pub fn next(self) -> Self {
+        match self {
+            SortOrder::Due => SortOrder::Priority,
+            SortOrder::Priority => SortOrder::Created,
+            SortOrder::Created => SortOrder::Text,
+            SortOrder::Text => SortOrder::Due,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortOrder::Due => "due time",
+            SortOrder::Priority => "priority",
+            SortOrder::Created => "created",
+            SortOrder::Text => "alphabetical",
+        }
+    }
+}
+
+// Birthdays and anniversaries recur yearly on a date, are notified in the morning, and count the years
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ReminderKind {
+    #[default]
+    Reminder,
+    Birthday,
+    Anniversary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ValueEnum)]
 pub enum Priority {
     Low,
     Medium,
@@ -28,9 +123,77 @@ pub struct Reminder {
     pub due_time: DateTime<Local>,
     pub recurrence: RecurrenceType,
     pub created_at: DateTime<Local>,
-    pub last_notified: Option<DateTime<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_notified: Option<DateTime<Local>>, // From before the alert ledger; kept until the next alert is recorded
     pub completed: bool,
     pub priority: Priority,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Local>>, // Used to pick the newest side when merging synced files
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub off_days: OffDayPolicy,
+    #[serde(default)]
+    pub nominal_due: Option<DateTime<Local>>, // Unshifted occurrence when `off_days = next` moved the due time
+    #[serde(default)]
+    pub anchor: Option<NaiveDateTime>, // Occurrence the schedule is kept on when the due time had to move: a day past the end of a short month, or a time the clocks skipped
+    #[serde(default)]
+    pub kind: ReminderKind,
+    #[serde(default)]
+    pub since: Option<NaiveDate>, // Date of birth or of the event, for birthdays and anniversaries
+    #[serde(default)]
+    pub pomodoros: Vec<DateTime<Local>>, // When each pomodoro worked on this reminder finished
+    #[serde(default)]
+    pub context: Option<String>, // GTD context such as "@home": where or with what it can be done
+    #[serde(default)]
+    pub escalate: Option<bool>, // Overrides `priority.escalate` from the config for this reminder
+    #[serde(default)]
+    pub advance: AdvanceOn,
+    #[serde(default)]
+    pub renotify_minutes: Option<i64>, // Notify again this often while still due, overriding the recurrence-based default
+    #[serde(default)]
+    pub icon: Option<String>, // An emoji shown before the text, or a desktop icon name/path for its notifications
+    #[serde(default)]
+    pub meta: BTreeMap<String, String>, // Free-form key-value pairs for scripts and integrations, e.g. client = "acme"
+    #[serde(default)]
+    pub expire_after_minutes: Option<i64>, // Archive a one-off reminder overdue this long, overriding `maintenance.expire_after`; 0 never does
+    #[serde(default)]
+    pub warned_for: Option<DateTime<Local>>, // Due time of the occurrence a route's heads-up was last sent for
+    #[serde(default)]
+    pub exdates: Vec<NaiveDate>, // Dates a recurring reminder skips, like iCalendar's EXDATE
+    #[serde(default)]
+    pub alerts: Vec<i64>, // Minutes before the due time to send a heads-up (--alert); the due time itself always notifies
+    #[serde(default)]
+    pub notified: Vec<Alert>, // Alerts sent for the current occurrence
+    #[serde(default)]
+    pub pinned: Option<u32>, // Place among the pinned reminders, 1 at the top; None when not pinned
+    #[serde(default)]
+    pub assignee: Option<String>, // Who on a team it's for, in a shared reminders file
+    #[serde(default)]
+    pub notes: Option<String>, // Longer details beyond the text, e.g. steps or links; searched by `search`
+    #[serde(default)]
+    pub completions: Vec<DateTime<Local>>, // When each occurrence was completed, for `remind report`
+    #[serde(default)]
+    pub follow_ups: Vec<FollowUp>, // Steps added as new reminders one at a time, each when the one before is completed
+    #[serde(default)]
+    pub touched_at: Option<DateTime<Local>>, // Last edited, snoozed or completed by someone; notifying doesn't count, so `stale` sees what's been left alone
+}
+
+// A step after a reminder (--follow-up): once it's completed, `text` is added as a reminder due
+// `after_minutes` later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowUp {
+    pub text: String,
+    pub after_minutes: i64,
+}
+
+// One alert sent for an occurrence: `offset` minutes before the occurrence due at `due`; 0 is
+// the due-time notification, which is sent again when it renotifies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    pub offset: i64,
+    pub due: DateTime<Local>,
+    pub sent: DateTime<Local>,
 }
 
 impl Reminder {
@@ -44,104 +207,678 @@ impl Reminder {
             last_notified: None,
             completed: false,
             priority: Priority::Medium, // Default priority
+            updated_at: None,
+            tags: Vec::new(),
+            off_days: OffDayPolicy::Keep,
+            nominal_due: None,
+            anchor: None,
+            kind: ReminderKind::Reminder,
+            since: None,
+            pomodoros: Vec::new(),
+            context: None,
+            escalate: None,
+            advance: AdvanceOn::Notify,
+            renotify_minutes: None,
+            icon: None,
+            meta: BTreeMap::new(),
+            expire_after_minutes: None,
+            warned_for: None,
+            exdates: Vec::new(),
+            alerts: Vec::new(),
+            notified: Vec::new(),
+            pinned: None,
+            assignee: None,
+            notes: None,
+            completions: Vec::new(),
+            follow_ups: Vec::new(),
+            touched_at: None,
         }
     }
 
-    pub fn new_with_id(id: String, text: String, due_time: DateTime<Local>, recurrence: RecurrenceType) -> Self {
-        Self {
-            id,
-            text,
-            due_time,
-            recurrence,
-            completed: false,
-            created_at: Local::now(),
-            last_notified: None, // Add this field
-            priority: Priority::Medium, // Add this field
+    // A yearly reminder for the next anniversary of `since` (today included), notified at `notify_at`
+    pub fn new_anniversary(kind: ReminderKind, text: String, since: NaiveDate, notify_at: NaiveTime) -> Result<Self> {
+        let today = Local::now().date_naive();
+        let mut year = today.year();
+        let next = loop {
+            // People born on 29 February celebrate on the 28th in other years
+            let date = NaiveDate::from_ymd_opt(year, since.month(), since.day().min(utils::days_in_month(since.month(), year)))
+                .ok_or_else(|| anyhow!("Invalid date {}", since))?;
+            if date >= today {
+                break date;
+            }
+            year += 1;
+        };
+        
+        let due_time = Local.from_local_datetime(&next.and_time(notify_at))
+            .earliest()
+            .ok_or_else(|| anyhow!("Failed to convert to local datetime"))?;
+        let mut reminder = Reminder::new(text, due_time, RecurrenceType::Yearly);
+        reminder.kind = kind;
+        reminder.since = Some(since);
+        Ok(reminder)
+    }
+
+    // Priority after escalation: at least high close to the due time and urgent once overdue
+    pub fn effective_priority(&self, now: DateTime<Local>, config: &PriorityConfig) -> Priority {
+        if !self.escalate.unwrap_or(config.escalate) || self.completed {
+            return self.priority;
+        }
+        
+        let escalated = if self.due_time <= now {
+            Priority::Urgent
+        } else if self.due_time - now <= chrono::Duration::hours(config.high_within_hours) {
+            Priority::High
+        } else {
+            Priority::Low
+        };
+        self.priority.max(escalated)
+    }
+
+    pub fn is_all_day(&self) -> bool {
+        self.kind != ReminderKind::Reminder
+    }
+
+    // What the notification says: "Anna turns 35", "Wedding anniversary (10 years)", or just the text
+    pub fn headline(&self) -> String {
+        let years = self.since.map(|since| self.due_time.year() - since.year()).filter(|years| *years > 0);
+        let text = match (self.kind, years) {
+            (ReminderKind::Birthday, Some(years)) => format!("{} turns {}", self.text, years),
+            (ReminderKind::Anniversary, Some(1)) => format!("{} (1 year)", self.text),
+            (ReminderKind::Anniversary, Some(years)) => format!("{} ({} years)", self.text, years),
+            _ => self.text.clone(),
+        };
+        match self.emoji() {
+            Some(emoji) => format!("{} {}", emoji, text),
+            None => text,
         }
     }
 
+    // The icon when it is an emoji (anything but plain ASCII), to show next to the text
+    pub fn emoji(&self) -> Option<&str> {
+        self.icon.as_deref().filter(|icon| !icon.is_ascii())
+    }
+
+    // The icon when it is a desktop icon name like "call-start" or a path to an image
+    pub fn icon_name(&self) -> Option<&str> {
+        self.icon.as_deref().filter(|icon| icon.is_ascii())
+    }
+
+    // Sort key putting pinned reminders first, in their pinned order; a stable sort keeps the rest as they were
+    pub fn pin_rank(&self) -> (bool, u32) {
+        (self.pinned.is_none(), self.pinned.unwrap_or_default())
+    }
+
+    // When the reminder last changed, falling back to its creation time for older files
+    pub fn modified_at(&self) -> DateTime<Local> {
+        self.updated_at.unwrap_or(self.created_at)
+    }
+
+    // When someone last did something with it: added, edited, snoozed or completed it
+    pub fn last_touched(&self) -> DateTime<Local> {
+        [self.touched_at, self.completions.last().copied()]
+            .into_iter()
+            .flatten()
+            .fold(self.created_at, DateTime::max)
+    }
+
+    // Pending and left alone for longer than `after`. A recurring reminder that moves on by itself
+    // once notified isn't waiting on anyone, so it never counts
+    pub fn is_stale(&self, now: DateTime<Local>, after: chrono::Duration) -> bool {
+        let moves_on = !matches!(self.recurrence, RecurrenceType::None) && self.advance == AdvanceOn::Notify;
+        !self.completed && !moves_on && now - self.last_touched() > after
+    }
+
     pub fn is_due(&self) -> bool {
         let now = Local::now();
         self.due_time <= now && !self.completed && 
-            // If already notified, check if it's a recurring reminder that should be notified again
-            self.last_notified.map_or(true, |last| {
-                match self.recurrence {
-                    RecurrenceType::None => false, // Non-recurring, only notify once
-                    // Only notify again if at least a day has passed since last notification
-                    _ => (now - last).num_hours() >= 24
-                }
+            // If already notified, check whether it should be notified again
+            self.last_notified().is_none_or(|last| {
+                // Notifying moves the due time of these to the next occurrence, so a past due time hasn't been notified yet
+                let advances = !matches!(self.recurrence, RecurrenceType::None | RecurrenceType::Custom(_))
+                    && self.advance == AdvanceOn::Notify;
+                advances || self.renotify_window().is_some_and(|window| now - last >= window)
             })
     }
 
-    pub fn mark_notified(&mut self) {
-        self.last_notified = Some(Local::now());
+    // How long a still-due reminder waits before being notified again: the per-reminder setting,
+    // or the recurrence interval up to a day. Non-recurring reminders notify once unless set.
+    pub fn renotify_window(&self) -> Option<chrono::Duration> {
+        if let Some(minutes) = self.renotify_minutes {
+            return Some(chrono::Duration::minutes(minutes));
+        }
+        match &self.recurrence {
+            RecurrenceType::None => None,
+            recurrence => Some(recurrence.period().unwrap_or(chrono::Duration::days(1)).min(chrono::Duration::days(1))),
+        }
+    }
+
+    // When the due-time alert of the current occurrence was last sent
+    pub fn last_notified(&self) -> Option<DateTime<Local>> {
+        self.notified
+            .iter()
+            .filter(|alert| alert.offset == 0 && alert.due == self.due_time)
+            .map(|alert| alert.sent)
+            .max()
+            .or(self.last_notified)
+    }
+
+    // Whether an --alert before the due time has come and hasn't been sent for this occurrence.
+    // Once the reminder is due they're moot: the due-time notification takes over.
+    pub fn alert_due(&self, now: DateTime<Local>) -> bool {
+        !self.completed && self.due_time > now && self.pending_alerts(now).next().is_some()
+    }
+
+    // Record the alerts before the due time that have come, so each is sent once per occurrence
+    pub fn mark_alerted(&mut self, now: DateTime<Local>) {
+        let pending: Vec<i64> = self.pending_alerts(now).collect();
+        for offset in pending {
+            self.record_alert(offset, now);
+        }
+    }
+
+    // Put off the current occurrence until `until`, keeping the original so a recurring
+    // reminder's schedule doesn't shift
+    pub fn snooze(&mut self, until: DateTime<Local>) {
+        self.nominal_due.get_or_insert(self.due_time);
+        self.due_time = until;
+        // Its --alert heads-ups went out before the original due time
+        self.mark_alerted(Local::now());
+    }
+
+    // When the next alert goes out: the earliest unsent one before the due time, or the due time
+    pub fn next_alert(&self) -> DateTime<Local> {
+        self.alerts
+            .iter()
+            .filter(|&&offset| offset > 0 && !self.alerted(offset))
+            .map(|&offset| self.due_time - chrono::Duration::minutes(offset))
+            .min()
+            .map_or(self.due_time, |alert| alert.min(self.due_time))
+    }
+
+    // Forget every alert sent, e.g. once the reminder is rescheduled by hand
+    pub fn clear_notified(&mut self) {
+        self.notified.clear();
+        self.last_notified = None;
+    }
+
+    // Offsets of the alerts before the due time that have come but weren't sent
+    fn pending_alerts(&self, now: DateTime<Local>) -> impl Iterator<Item = i64> + '_ {
+        self.alerts
+            .iter()
+            .copied()
+            .filter(move |&offset| offset > 0 && self.due_time - chrono::Duration::minutes(offset) <= now && !self.alerted(offset))
+    }
+
+    fn alerted(&self, offset: i64) -> bool {
+        self.notified.iter().any(|alert| alert.offset == offset && alert.due == self.due_time)
+    }
+
+    // Note an alert for the current occurrence; the ledger only keeps that occurrence's alerts
+    fn record_alert(&mut self, offset: i64, now: DateTime<Local>) {
+        let due = self.due_time;
+        self.notified.retain(|alert| alert.due == due && alert.offset != offset);
+        self.notified.push(Alert { offset, due, sent: now });
+        self.last_notified = None;
+    }
+
+    pub fn mark_notified(&mut self, calendar: &Calendar) {
+        self.record_alert(0, Local::now());
         
-        // For recurring reminders, reschedule
-        match self.recurrence {
-            RecurrenceType::None => {
-                self.completed = true;
+        // For recurring reminders, reschedule; one-off reminders set to nag stay due until completed
+        if let RecurrenceType::None = self.recurrence {
+            self.completed = self.renotify_minutes.is_none();
+            if self.completed {
+                self.completions.push(Local::now());
             }
-            RecurrenceType::Daily => {
-                self.due_time = self.due_time + chrono::Duration::days(1);
+            return;
+        }
+        
+        // Otherwise the occurrence stays overdue until it is completed
+        if self.advance == AdvanceOn::Notify {
+            self.schedule_next(calendar);
+        }
+    }
+
+    // Complete the current occurrence; recurring reminders waiting for completion move on to
+    // their next occurrence instead of finishing. Returns whether the reminder moved on.
+    pub fn complete(&mut self, calendar: &Calendar) -> bool {
+        self.completions.push(Local::now());
+        if matches!(self.recurrence, RecurrenceType::None | RecurrenceType::Custom(_)) || self.advance == AdvanceOn::Notify {
+            self.completed = true;
+            return false;
+        }
+        
+        // Completing catches up: the next occurrence is the first one still ahead
+        let now = Local::now();
+        self.schedule_next(calendar);
+        for _ in 0..10_000 {
+            if self.due_time > now {
+                break;
             }
-            RecurrenceType::Weekly => {
-                self.due_time = self.due_time + chrono::Duration::weeks(1);
+            self.schedule_next(calendar);
+        }
+        self.clear_notified();
+        true
+    }
+
+    // The reminder for the next step, due its delay after `completed_at`; it takes the tags,
+    // context, assignee and priority along, and the steps after it
+    pub fn follow_up(&self, completed_at: DateTime<Local>) -> Option<Reminder> {
+        let (step, rest) = self.follow_ups.split_first()?;
+        let due_time = completed_at + chrono::Duration::minutes(step.after_minutes);
+        let mut next = Reminder::new(step.text.clone(), due_time, RecurrenceType::None);
+        next.tags = self.tags.clone();
+        next.context = self.context.clone();
+        next.assignee = self.assignee.clone();
+        next.priority = self.priority;
+        next.follow_ups = rest.to_vec();
+        Some(next)
+    }
+
+    // Mark as completed or not outside the usual flow (`complete --undo`, the REST API), keeping
+    // the completion times in step
+    pub fn set_completed(&mut self, completed: bool) {
+        if completed && !self.completed {
+            self.completions.push(Local::now());
+        } else if !completed && self.completed {
+            self.completions.pop();
+        }
+        self.completed = completed;
+    }
+
+    // After a notification, deal with occurrences that passed while nothing was running
+    pub fn catch_up(&mut self, policy: CatchUpPolicy, now: DateTime<Local>, calendar: &Calendar) {
+        if policy == CatchUpPolicy::Each {
+            // The next run notifies the next missed occurrence
+            return;
+        }
+        self.advance_while(calendar, |r| r.due_time <= now);
+    }
+
+    // Silently drop occurrences more than `grace` overdue; returns whether any were dropped
+    pub fn skip_missed(&mut self, now: DateTime<Local>, grace: chrono::Duration, calendar: &Calendar) -> bool {
+        let before = self.due_time;
+        self.advance_while(calendar, |r| r.due_time + grace < now);
+        self.due_time != before
+    }
+
+    fn advance_while(&mut self, calendar: &Calendar, condition: impl Fn(&Reminder) -> bool) {
+        if matches!(self.recurrence, RecurrenceType::None | RecurrenceType::Custom(_))
+            || self.completed
+            || self.advance == AdvanceOn::Complete
+        {
+            return;
+        }
+        // Bounded in case a schedule can't move past `now`
+        for _ in 0..10_000 {
+            if !condition(self) {
+                break;
+            }
+            self.schedule_next(calendar);
+        }
+    }
+
+    // Move the due time to the next occurrence, honoring the weekend/holiday policy
+    pub fn schedule_next(&mut self, calendar: &Calendar) {
+        // Advance from the unshifted occurrence so shifting to a business day doesn't drift the schedule
+        let from = self.nominal_due.unwrap_or(self.due_time);
+        // and go back to the scheduled day and time once the month has that day and the clocks don't skip it
+        let anchor = self.anchor.unwrap_or(from.naive_local());
+        let mut next = self.next_after(from, anchor);
+        self.nominal_due = None;
+        
+        match self.off_days {
+            OffDayPolicy::Keep => self.due_time = next,
+            OffDayPolicy::Skip => {
+                // Bounded so a calendar with no business days can't loop forever
+                for _ in 0..366 {
+                    if calendar.is_business_day(next.date_naive()) {
+                        break;
+                    }
+                    next = self.next_after(next, anchor);
+                }
+                self.due_time = next;
+            }
+            OffDayPolicy::Next => {
+                let mut shifted = next.date_naive();
+                for _ in 0..366 {
+                    if calendar.is_business_day(shifted) {
+                        break;
+                    }
+                    shifted += chrono::Duration::days(1);
+                }
+                let shifted = at_same_time(shifted, next);
+                if shifted != next {
+                    self.nominal_due = Some(next);
+                }
+                self.due_time = shifted;
+            }
+        }
+        let moved_day = matches!(self.recurrence, RecurrenceType::Monthly | RecurrenceType::Yearly) && next.day() != anchor.day();
+        self.anchor = Some(anchor).filter(|anchor| moved_day || next.time() != anchor.time());
+    }
+    
+    // The occurrence after `from`, passing over excluded dates
+    fn next_after(&self, from: DateTime<Local>, anchor: NaiveDateTime) -> DateTime<Local> {
+        let mut next = self.recurrence.step(from.naive_local(), anchor);
+        // Bounded in case every occurrence is excluded
+        for _ in 0..10_000 {
+            if !self.exdates.contains(&next.date()) {
+                break;
+            }
+            next = self.recurrence.step(next, anchor);
+        }
+        local(next)
+    }
+    
+    // Whether the occurrence originally at `time` is excluded; exceptions apply to the scheduled
+    // date, before any move off a weekend or holiday, as EXDATE does
+    pub fn is_excluded(&self, time: DateTime<Local>) -> bool {
+        self.exdates.contains(&time.date_naive())
+    }
+    
+    // Pending reminders due within `window` of this one
+    pub fn conflicts<'a>(&self, others: &'a [Reminder], window: chrono::Duration) -> Vec<&'a Reminder> {
+        others
+            .iter()
+            .filter(|other| !other.completed && other.id != self.id && (other.due_time - self.due_time).abs() <= window)
+            .collect()
+    }
+    
+    // A plain monthly reminder on the 29th to 31st lands on the last day of shorter months and then
+    // goes back to its day, e.g. Jan 30, Feb 28, Mar 30; worth a note when it's set up
+    pub fn moves_in_short_months(&self) -> bool {
+        matches!(self.recurrence, RecurrenceType::Monthly) && self.due_time.day() > 28
+    }
+    
+    // The current occurrence and the ones after it, each paired with its original time
+    // when a weekend/holiday policy or a snooze moved it
+    pub fn occurrences(&self, count: usize, calendar: &Calendar) -> Vec<(DateTime<Local>, Option<DateTime<Local>>)> {
+        let mut next = self.clone();
+        let mut occurrences = Vec::new();
+        for _ in 0..count {
+            occurrences.push((next.due_time, next.nominal_due));
+            if let RecurrenceType::None | RecurrenceType::Custom(_) = next.recurrence {
+                break;
+            }
+            next.schedule_next(calendar);
+        }
+        occurrences
+    }
+}
+
+impl RecurrenceType {
+    // The occurrence after `from`; non-recurring and custom reminders stay where they are
+    pub fn advance(&self, from: DateTime<Local>) -> DateTime<Local> {
+        local(self.step(from.naive_local(), from.naive_local()))
+    }
+
+    // The local date and time of the occurrence after `from`, at the time of day of `anchor` and,
+    // for monthly and yearly ones, on its day of the month. Counted in calendar days rather than
+    // hours so a clock change doesn't move the time of day
+    pub fn step(&self, from: NaiveDateTime, anchor: NaiveDateTime) -> NaiveDateTime {
+        let date = from.date();
+        let next = match self {
+            RecurrenceType::None | RecurrenceType::Custom(_) => return from,
+            RecurrenceType::Daily => date + chrono::Duration::days(1),
+            RecurrenceType::Weekdays => {
+                let mut next = date + chrono::Duration::days(1);
+                while matches!(next.weekday(), Weekday::Sat | Weekday::Sun) {
+                    next += chrono::Duration::days(1);
+                }
+                next
+            }
+            RecurrenceType::Weekly => date + chrono::Duration::weeks(1),
+            RecurrenceType::EveryDays(days) => date + chrono::Duration::days(i64::from(*days)),
+            RecurrenceType::WeeklyOn { interval, .. } => date + chrono::Duration::weeks(i64::from(*interval)),
+            RecurrenceType::MonthlyOn { nth, weekday } => {
+                let (year, month) = next_month(date);
+                nth_weekday_of_month(year, month, *weekday, *nth)
+            }
+            RecurrenceType::MonthlyLastDay => {
+                let (year, month) = next_month(date);
+                last_day_of_month(year, month)
             }
             RecurrenceType::Monthly => {
-                // This is a simplification; months have different lengths
-                let new_month = self.due_time.month() % 12 + 1;
-                let new_year = self.due_time.year() + if new_month == 1 { 1 } else { 0 };
-                self.due_time = Local.with_ymd_and_hms(
-                    new_year,
-                    new_month,
-                    self.due_time.day().min(days_in_month(new_month, new_year)),
-                    self.due_time.hour(),
-                    self.due_time.minute(),
-                    self.due_time.second(),
-                ).unwrap();
+                // The last day of shorter months; see `moves_in_short_months`
+                let (year, month) = next_month(date);
+                on_day(year, month, anchor.day())
+            }
+            RecurrenceType::Yearly => on_day(date.year() + 1, date.month(), anchor.day()),
+        };
+        next.and_time(anchor.time())
+    }
+
+    // Typical time between occurrences; None for non-recurring and custom schedules
+    pub fn period(&self) -> Option<chrono::Duration> {
+        match self {
+            RecurrenceType::None | RecurrenceType::Custom(_) => None,
+            RecurrenceType::Daily | RecurrenceType::Weekdays => Some(chrono::Duration::days(1)),
+            RecurrenceType::Weekly => Some(chrono::Duration::weeks(1)),
+            RecurrenceType::Monthly | RecurrenceType::MonthlyOn { .. } | RecurrenceType::MonthlyLastDay => {
+                Some(chrono::Duration::days(28))
+            }
+            RecurrenceType::Yearly => Some(chrono::Duration::days(365)),
+            RecurrenceType::EveryDays(days) => Some(chrono::Duration::days(i64::from(*days))),
+            RecurrenceType::WeeklyOn { interval, .. } => Some(chrono::Duration::weeks(i64::from(*interval))),
+        }
+    }
+
+    // The first occurrence at or after `from`, for patterns tied to particular days
+    pub fn align(&self, from: DateTime<Local>) -> DateTime<Local> {
+        match self {
+            RecurrenceType::Weekdays => {
+                let mut first = from.date_naive();
+                while matches!(first.weekday(), Weekday::Sat | Weekday::Sun) {
+                    first += chrono::Duration::days(1);
+                }
+                at_same_time(first, from)
             }
-            RecurrenceType::Yearly => {
-                self.due_time = Local.with_ymd_and_hms(
-                    self.due_time.year() + 1,
-                    self.due_time.month(),
-                    self.due_time.day(),
-                    self.due_time.hour(),
-                    self.due_time.minute(),
-                    self.due_time.second(),
-                ).unwrap();
+            RecurrenceType::WeeklyOn { weekday, .. } => {
+                let days = (weekday.num_days_from_monday() + 7 - from.weekday().num_days_from_monday()) % 7;
+                at_same_time(from.date_naive() + chrono::Duration::days(i64::from(days)), from)
             }
-            RecurrenceType::Custom(_) => {
-                // For future implementation with cron-like expressions
+            RecurrenceType::MonthlyOn { nth, weekday } => {
+                let this_month = at_same_time(nth_weekday_of_month(from.year(), from.month(), *weekday, *nth), from);
+                if this_month >= from { this_month } else { self.advance(this_month) }
             }
+            RecurrenceType::MonthlyLastDay => at_same_time(last_day_of_month(from.year(), from.month()), from),
+            _ => from,
         }
     }
 }
 
-impl fmt::Display for Reminder {
+impl fmt::Display for RecurrenceType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "[{}] {} (Due: {}) {}",
-            self.id, // Show full UUID
-            self.text,
-            self.due_time.format("%Y-%m-%d %H:%M"),
-            if self.completed { "[COMPLETED]" } else { "" }
-        )
+        let every = |n: u32| match n {
+            1 => "every".to_string(),
+            2 => "every other".to_string(),
+            n => format!("every {}", n),
+        };
+        match self {
+            RecurrenceType::None => write!(f, "none"),
+            RecurrenceType::Daily => write!(f, "daily"),
+            RecurrenceType::Weekdays => write!(f, "weekdays"),
+            RecurrenceType::Weekly => write!(f, "weekly"),
+            RecurrenceType::Monthly => write!(f, "monthly"),
+            RecurrenceType::MonthlyLastDay => write!(f, "last-day-of-month"),
+            RecurrenceType::Yearly => write!(f, "yearly"),
+            RecurrenceType::EveryDays(1) => write!(f, "daily"),
+            RecurrenceType::EveryDays(2) => write!(f, "every other day"),
+            RecurrenceType::EveryDays(days) => write!(f, "every {} days", days),
+            RecurrenceType::WeeklyOn { interval, weekday } if *interval > 2 => {
+                write!(f, "every {} weeks on {}", interval, weekday_name(*weekday))
+            }
+            RecurrenceType::WeeklyOn { interval, weekday } => write!(f, "{} {}", every(*interval), weekday_name(*weekday)),
+            RecurrenceType::MonthlyOn { nth, weekday } => {
+                let ordinal = match nth {
+                    1 => "first",
+                    2 => "second",
+                    3 => "third",
+                    4 => "fourth",
+                    _ => "last",
+                };
+                write!(f, "{} {} of the month", ordinal, weekday_name(*weekday))
+            }
+            RecurrenceType::Custom(expr) => write!(f, "{}", expr),
+        }
     }
 }
 
-// Helper function to get days in a month
-fn days_in_month(month: u32, year: i32) -> u32 {
-    match month {
-        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
-        4 | 6 | 9 | 11 => 30,
-        2 => {
-            if (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0) {
-                29
+impl Reminder {
+    // One-line summary used by `list` and `search`, with the due time relative or absolute
+    pub fn summary(&self, relative_times: bool) -> String {
+        self.summary_with(relative_times, |tag| format!("#{}", tag))
+    }
+
+    // The summary with each tag rendered by `tag`, e.g. to color it
+    pub fn summary_with(&self, relative_times: bool, tag: impl Fn(&str) -> String) -> String {
+        tr!(
+            "[{}] {}{} (Due: {}) {}",
+            self.id, // Show full UUID
+            self.headline(),
+            self.tags
+                .iter()
+                .map(|t| format!(" {}", tag(t)))
+                .chain(self.context.as_ref().map(|c| format!(" {}", c)))
+                .chain(self.assignee.as_ref().map(|a| format!(" → {}", a)))
+                .collect::<String>(),
+            if self.is_all_day() {
+                tr!("{}, all day", self.due_time.format("%Y-%m-%d"))
             } else {
-                28
-            }
+                utils::format_due(&self.due_time, relative_times)
+            },
+            if self.completed { tr!("[COMPLETED]") } else { String::new() }
+        )
+    }
+
+    // The summary worded for a screen reader: the text first, then the rest as words rather than
+    // symbols, and the ID last since it's long and only needed to act on the reminder
+    pub fn spoken(&self, relative_times: bool) -> String {
+        let mut parts = vec![self.headline()];
+        parts.push(if self.is_all_day() {
+            tr!("due {}, all day", self.due_time.format("%Y-%m-%d"))
+        } else {
+            tr!("due {}", utils::format_due(&self.due_time, relative_times))
+        });
+        if !self.tags.is_empty() {
+            parts.push(tr!("tagged {}", self.tags.join(", ")));
+        }
+        if let Some(context) = &self.context {
+            parts.push(tr!("context {}", context.trim_start_matches('@')));
+        }
+        if let Some(assignee) = &self.assignee {
+            parts.push(tr!("assigned to {}", assignee));
+        }
+        if self.completed {
+            parts.push(tr!("completed"));
+        }
+        tr!("{}. ID {}", parts.join(", "), self.id)
+    }
+
+    pub fn add_tag(&mut self, tag: &str) {
+        let tag = tag.trim().trim_start_matches('#');
+        if !tag.is_empty() && !self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            self.tags.push(tag.to_string());
+        }
+    }
+
+    // Set the context from "@home" or "home"; an empty string clears it
+    pub fn set_context(&mut self, context: &str) {
+        self.context = normalize_context(context);
+    }
+
+    // Set metadata from "key=value"; an empty value removes the key
+    pub fn set_meta(&mut self, key: &str, value: &str) {
+        if value.is_empty() {
+            self.meta.remove(key);
+        } else {
+            self.meta.insert(key.to_string(), value.to_string());
         }
-        _ => panic!("Invalid month"),
     }
-}
\ No newline at end of file
+
+    // An empty string clears the icon
+    pub fn set_icon(&mut self, icon: &str) {
+        let icon = icon.trim();
+        self.icon = (!icon.is_empty()).then(|| icon.to_string());
+    }
+
+    // Empty notes remove them
+    pub fn set_notes(&mut self, notes: &str) {
+        let notes = notes.trim();
+        self.notes = (!notes.is_empty()).then(|| notes.to_string());
+    }
+
+    // An empty name unassigns it
+    pub fn set_assignee(&mut self, name: &str) {
+        let name = name.trim();
+        self.assignee = (!name.is_empty()).then(|| name.to_string());
+    }
+
+    // Whether it's assigned to `name`, ignoring case
+    pub fn is_assigned_to(&self, name: &str) -> bool {
+        self.assignee.as_deref().is_some_and(|assignee| assignee.eq_ignore_ascii_case(name))
+    }
+
+    // Earliest first; 0 (the due time) is kept so the list reads as given, though it always notifies
+    pub fn set_alerts(&mut self, offsets: &[i64]) {
+        self.alerts = offsets.to_vec();
+        self.alerts.sort_unstable_by(|a, b| b.cmp(a));
+        self.alerts.dedup();
+    }
+}
+
+// "home" and "@Home" both become "@home"
+pub fn normalize_context(context: &str) -> Option<String> {
+    let name = context.trim().trim_start_matches('@');
+    (!name.is_empty()).then(|| format!("@{}", name.to_lowercase()))
+}
+
+impl fmt::Display for Reminder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.summary(false))
+    }
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+// The nth (1-4) or, for -1, last `weekday` of a month
+fn nth_weekday_of_month(year: i32, month: u32, weekday: Weekday, nth: i32) -> NaiveDate {
+    if nth > 0
+        && let Some(date) = NaiveDate::from_weekday_of_month_opt(year, month, weekday, nth as u8)
+    {
+        return date;
+    }
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, 5)
+        .or_else(|| NaiveDate::from_weekday_of_month_opt(year, month, weekday, 4))
+        .expect("every month has four of each weekday")
+}
+
+fn last_day_of_month(year: i32, month: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, utils::days_in_month(month, year)).expect("valid month")
+}
+
+// `date` at the time of day of `time`
+fn at_same_time(date: NaiveDate, time: DateTime<Local>) -> DateTime<Local> {
+    local(date.and_time(time.time()))
+}
+
+// The year and month after the one `date` is in
+fn next_month(date: NaiveDate) -> (i32, u32) {
+    if date.month() == 12 { (date.year() + 1, 1) } else { (date.year(), date.month() + 1) }
+}
+
+// `day` of the month, or its last day when the month is shorter
+fn on_day(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day.min(utils::days_in_month(month, year))).expect("valid month")
+}
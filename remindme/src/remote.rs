@@ -0,0 +1,204 @@
+// Reminders kept on another machine over SSH (`ssh://[user@]host[:port]/path/reminders.json`)
+//
+// The file is fetched with sftp when storage is opened and worked on as a local copy, so the
+// index, encryption and merging work as they do for a local file. Every save pushes the whole
+// file back; the journal is off, since other clients only ever see the remote file.
+//
+// Locking is optimistic: right before a push the remote file is fetched again, and if another
+// client pushed since this copy was read, both versions are merged reminder by reminder (see
+// merge.rs) and the result is pushed instead. Uploads go to a temporary name that is renamed
+// over the file, so no client ever reads a half-written one.
+//
+// sftp runs in batch mode, which needs key-based login (ssh-agent or a key without a passphrase);
+// host aliases and options from ~/.ssh/config apply as usual.
+
+use anyhow::{anyhow, Context, Result};
+use std::cell::RefCell;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tracing::{debug, warn};
+
+use crate::error::RemindError;
+
+pub struct Remote {
+    // ssh://... as configured
+    location: String,
+    // [user@]host, as ssh takes it
+    host: String,
+    port: Option<u16>,
+    // Absolute, or relative to the remote home for ssh://host/~/...
+    path: String,
+    // The remote file as last fetched or pushed
+    base: RefCell<Base>,
+}
+
+// The remote file when this copy was fetched (None if there was none) and as it is now
+pub struct Change {
+    pub base: Option<Vec<u8>>,
+    pub current: Vec<u8>,
+}
+
+enum Base {
+    // The host couldn't be reached, so only the local copy was read
+    Unreachable,
+    // No reminders file on the host yet
+    Missing,
+    Contents(Vec<u8>),
+}
+
+impl Remote {
+    // The remote behind an ssh:// or sftp:// location; None for a plain file path
+    pub fn parse(location: &str) -> Result<Option<Self>> {
+        let Some(rest) = location.strip_prefix("ssh://").or_else(|| location.strip_prefix("sftp://")) else {
+            return Ok(None);
+        };
+        let invalid = || RemindError::Config(format!("Invalid remote storage '{}'. Use ssh://[user@]host[:port]/path/reminders.json", location));
+        let (authority, path) = rest.split_once('/').ok_or_else(invalid)?;
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port.parse::<u16>().map_err(|_| invalid())?)),
+            None => (authority, None),
+        };
+        let path = match path.strip_prefix("~/") {
+            Some(relative) => relative.to_string(),
+            None => format!("/{}", path),
+        };
+        if host.is_empty() || path.is_empty() || path.ends_with('/') {
+            return Err(invalid().into());
+        }
+        Ok(Some(Remote { location: location.to_string(), host: host.to_string(), port, path, base: RefCell::new(Base::Missing) }))
+    }
+
+    // Where the local copy lives: one file per remote under the data directory
+    pub fn cache_path(&self, data_dir: &Path) -> PathBuf {
+        let name: String = format!("{}-{}", self.host, self.path)
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '@') { c } else { '_' })
+            .collect();
+        data_dir.join("remote").join(name)
+    }
+
+    // Bring the local copy at `cache` up to date; without a connection it is kept as it is and
+    // saving is refused, so changes made elsewhere are never overwritten blindly
+    pub fn pull(&self, cache: &Path) -> Result<()> {
+        let fetched = match self.fetch(cache) {
+            Ok(fetched) => fetched,
+            Err(e) => {
+                warn!(remote = %self, error = %format!("{:#}", e), "Remote storage unreachable");
+                eprintln!("Warning: couldn't fetch reminders from {} ({:#}); showing the local copy, read-only", self, e);
+                *self.base.borrow_mut() = Base::Unreachable;
+                return Ok(());
+            }
+        };
+        let contents = fetched.clone().unwrap_or_default();
+        // Leave an unchanged copy alone, so the index built from it stays fresh
+        if fs::read(cache).ok().as_ref() != Some(&contents) {
+            fs::write(cache, &contents).map_err(RemindError::access(cache))?;
+        }
+        debug!(remote = %self, bytes = contents.len(), "Fetched reminders");
+        *self.base.borrow_mut() = fetched.map_or(Base::Missing, Base::Contents);
+        Ok(())
+    }
+
+    // Whether the last pull reached the host; without it the local copy is only for reading
+    pub fn reachable(&self) -> bool {
+        !matches!(*self.base.borrow(), Base::Unreachable)
+    }
+
+    // What another client pushed since this copy was fetched; None when nothing changed
+    pub fn changed(&self, cache: &Path) -> Result<Option<Change>> {
+        let base = match &*self.base.borrow() {
+            Base::Unreachable => {
+                return Err(anyhow!(
+                    "Not saved: {} couldn't be reached when the reminders were read, and saving could overwrite newer changes there",
+                    self
+                ));
+            }
+            Base::Missing => None,
+            Base::Contents(data) => Some(data.clone()),
+        };
+        let current = self.fetch(cache).with_context(|| format!("Not saved: couldn't check {} for changes", self))?;
+        if current == base {
+            return Ok(None);
+        }
+        Ok(Some(Change { base, current: current.unwrap_or_default() }))
+    }
+
+    // Upload `data` to the host and take it as the new base
+    pub fn push(&self, cache: &Path, data: Vec<u8>) -> Result<()> {
+        let upload = sibling(cache, "upload");
+        fs::write(&upload, &data).map_err(RemindError::access(&upload))?;
+        let tmp = format!("{}.tmp-{}", self.path, std::process::id());
+        let result = self.sftp(&format!(
+            "put {} {}\nrename {} {}\n",
+            quote(&upload.to_string_lossy()),
+            quote(&tmp),
+            quote(&tmp),
+            quote(&self.path)
+        ));
+        let _ = fs::remove_file(&upload);
+        result.with_context(|| format!("Failed to push reminders to {}; they're saved locally only", self))?;
+        debug!(remote = %self, bytes = data.len(), "Pushed reminders");
+        *self.base.borrow_mut() = Base::Contents(data);
+        Ok(())
+    }
+
+    // The remote file, or None if it doesn't exist
+    fn fetch(&self, cache: &Path) -> Result<Option<Vec<u8>>> {
+        let download = sibling(cache, "download");
+        let _ = fs::remove_file(&download);
+        // "-get" lets a missing file pass; a failed connection still fails the batch
+        self.sftp(&format!("-get {} {}\n", quote(&self.path), quote(&download.to_string_lossy())))?;
+        match fs::read(&download) {
+            Ok(data) => {
+                let _ = fs::remove_file(&download);
+                Ok(Some(data))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(RemindError::access(&download)(e).into()),
+        }
+    }
+
+    fn sftp(&self, batch: &str) -> Result<()> {
+        let mut command = Command::new("sftp");
+        command.args(["-q", "-b", "-"]);
+        if let Some(port) = self.port {
+            command.args(["-P", &port.to_string()]);
+        }
+        let mut child = command
+            .arg(&self.host)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run sftp (is OpenSSH installed?)")?;
+        child.stdin.take().context("sftp has no stdin")?.write_all(batch.as_bytes())?;
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(anyhow!("sftp: {}", if error.is_empty() { output.status.to_string() } else { error }));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Remote {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.location)
+    }
+}
+
+// A scratch file next to the local copy
+fn sibling(cache: &Path, extension: &str) -> PathBuf {
+    let mut name = cache.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(extension);
+    cache.with_file_name(name)
+}
+
+// A double-quoted sftp batch argument
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,75 @@
+// Per-day activity for spreadsheets and charts (`remind report`)
+//
+// Each day counts the reminders created, the occurrences completed, and the reminders still
+// overdue when the day ended (or now, for today). Completion times are kept from when they were
+// first recorded; a reminder completed before that counts as completed when it was last changed.
+// Recurring reminders only know their current occurrence, so a late earlier occurrence doesn't
+// show up as overdue on the days it was late.
+
+use chrono::{DateTime, Days, Local, NaiveDate, NaiveTime, TimeZone};
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::reminder::Reminder;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Day {
+    pub date: NaiveDate,
+    pub created: usize,
+    pub completed: usize,
+    pub overdue: usize,
+}
+
+// One row per day from `from` to `to`, both included
+pub fn days(reminders: &[Reminder], from: NaiveDate, to: NaiveDate, now: DateTime<Local>) -> Vec<Day> {
+    from.iter_days()
+        .take_while(|date| *date <= to)
+        .map(|date| {
+            let start = midnight(date);
+            let end = date.checked_add_days(Days::new(1)).map_or(now, midnight).min(now);
+            let on_day = |t: &DateTime<Local>| *t >= start && *t < end;
+            Day {
+                date,
+                created: reminders.iter().filter(|r| on_day(&r.created_at)).count(),
+                completed: reminders.iter().flat_map(completions).filter(|t| on_day(t)).count(),
+                overdue: reminders
+                    .iter()
+                    .filter(|r| r.created_at < end && r.due_time < end && !done_by(r, end))
+                    .count(),
+            }
+        })
+        .collect()
+}
+
+pub fn csv(days: &[Day]) -> String {
+    let mut out = String::from("date,created,completed,overdue\n");
+    for day in days {
+        out.push_str(&format!("{},{},{},{}\n", day.date, day.created, day.completed, day.overdue));
+    }
+    out
+}
+
+// When each occurrence was completed, falling back to the last change for older files
+fn completions(reminder: &Reminder) -> Vec<DateTime<Local>> {
+    if reminder.completions.is_empty() && reminder.completed {
+        return reminder.updated_at.into_iter().collect();
+    }
+    reminder.completions.clone()
+}
+
+// Whether the reminder was finished for good by `end`
+fn done_by(reminder: &Reminder, end: DateTime<Local>) -> bool {
+    reminder.completed && completions(reminder).last().is_none_or(|t| *t < end)
+}
+
+fn midnight(date: NaiveDate) -> DateTime<Local> {
+    let naive = date.and_time(NaiveTime::MIN);
+    Local.from_local_datetime(&naive).earliest().unwrap_or_else(|| Local.from_utc_datetime(&naive))
+}
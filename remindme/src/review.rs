@@ -0,0 +1,130 @@
+// Guided weekly review (`remind review`)
+//
+// Goes through the reminders that need a decision, one at a time: first the overdue ones,
+// then the stale ones nobody has touched in a while. Each can be rescheduled, completed,
+// deleted or skipped, so the list stays trustworthy without editing reminders by ID.
+
+use anyhow::Result;
+use chrono::{Duration, Local};
+use std::io::{self, BufRead, Write};
+
+use crate::cli;
+use crate::config::{Config, DateOrder};
+use crate::holidays::Calendar;
+use crate::i18n::tr;
+use crate::reminder::Reminder;
+use crate::storage::Storage;
+use crate::utils;
+
+pub fn run(storage: &mut Storage, config: &Config, stale_after: Duration) -> Result<()> {
+    let now = Local::now();
+    let calendar = Calendar::from_config(&config.holidays)?;
+    let reminders = storage.load()?;
+
+    let mut overdue: Vec<&Reminder> = reminders.iter().filter(|r| !r.completed && r.due_time < now).collect();
+    let mut stale: Vec<&Reminder> = reminders
+        .iter()
+        .filter(|r| r.due_time >= now && r.is_stale(now, stale_after))
+        .collect();
+    overdue.sort_by_key(|r| r.due_time);
+    stale.sort_by_key(|r| r.last_touched());
+
+    if overdue.is_empty() && stale.is_empty() {
+        println!("{}", tr!("Nothing to review. All reminders are current."));
+        return Ok(());
+    }
+
+    let sections = [(tr!("Overdue"), overdue), (tr!("Not touched in a while"), stale)];
+    let mut input = io::stdin().lock();
+    for (title, reminders) in sections {
+        if reminders.is_empty() {
+            continue;
+        }
+        println!("\n== {} ({}) ==", title, reminders.len());
+
+        for reminder in reminders {
+            println!("\n{}", reminder.summary(config.display.relative_times));
+            if !review_one(storage, &calendar, config.input.date_order, &mut input, reminder.clone())? {
+                println!("{}", tr!("Review stopped."));
+                return Ok(());
+            }
+        }
+    }
+
+    println!("\n{}", tr!("Review complete."));
+    Ok(())
+}
+
+// Ask what to do with one reminder; returns false when the user quits the review
+fn review_one(
+    storage: &mut Storage,
+    calendar: &Calendar,
+    date_order: DateOrder,
+    input: &mut impl BufRead,
+    mut reminder: Reminder,
+) -> Result<bool> {
+    loop {
+        let Some(answer) = prompt(input, &tr!("[r]eschedule, [c]omplete, [d]elete, [s]kip, [q]uit > "))? else {
+            return Ok(false);
+        };
+
+        match answer.to_lowercase().as_str() {
+            "r" | "reschedule" => {
+                let Some(when) = prompt(input, &tr!("New time (17:00, 5pm, YYYY-MM-DD HH:MM, or from now like 2d): "))? else {
+                    return Ok(false);
+                };
+                match parse_when(&when, date_order) {
+                    Ok(due_time) => {
+                        reminder.due_time = due_time;
+                        reminder.nominal_due = None;
+                        reminder.anchor = None;
+                        reminder.clear_notified();
+                        storage.update_reminder(reminder)?;
+                        println!("{}", tr!("Rescheduled to {}.", utils::format_datetime(&due_time)));
+                        return Ok(true);
+                    }
+                    Err(e) => println!("{}", e),
+                }
+            }
+            "c" | "complete" => {
+                let (reminder, follow_up) = storage.complete_reminder(reminder, calendar)?;
+                if reminder.completed {
+                    println!("{}", tr!("Completed."));
+                } else {
+                    println!("{}", tr!("Completed; next occurrence {}.", utils::format_datetime(&reminder.due_time)));
+                }
+                if let Some(next) = follow_up {
+                    println!("{}", tr!("Follow-up '{}' added, due {}.", next.text, utils::format_datetime(&next.due_time)));
+                }
+                return Ok(true);
+            }
+            "d" | "delete" => {
+                storage.delete_reminder(&reminder.id)?;
+                println!("{}", tr!("Deleted."));
+                return Ok(true);
+            }
+            "s" | "skip" | "" => return Ok(true),
+            "q" | "quit" => return Ok(false),
+            _ => println!("{}", tr!("Please answer r, c, d, s or q.")),
+        }
+    }
+}
+
+// A line from the user, or None at end of input
+pub fn prompt(input: &mut impl BufRead, question: &str) -> Result<Option<String>> {
+    print!("{}", question);
+    io::stdout().flush()?;
+
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
+
+fn parse_when(when: &str, date_order: DateOrder) -> Result<chrono::DateTime<Local>> {
+    cli::parse_datetime(when, date_order)
+        .or_else(|_| cli::parse_datetime_with_default_date(when, None, date_order))
+        .or_else(|_| cli::parse_duration(when).map(|duration| Local::now() + duration))
+        .map_err(|_| anyhow::anyhow!(tr!("Could not understand '{}'. Try 17:00, 2025-06-01 09:00 or 3d.", when)))
+}
@@ -0,0 +1,83 @@
+// Routing rules from config.toml ([[routes]])
+//
+// Each route matches reminders by tag and priority and says where they go, so e.g. work
+// reminders can reach Slack a quarter of an hour early while medication keeps nagging on the
+// desktop and the phone. Routes are checked in order and the first match wins; a custom rule's
+// route() still comes first, and reminders no route matches go to the default channels.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Local};
+use clap::ValueEnum;
+
+use crate::cli;
+use crate::config::RouteConfig;
+use crate::error::RemindError;
+use crate::reminder::{Priority, Reminder};
+
+pub struct Route {
+    tags: Vec<String>,
+    priority: Option<Priority>,
+    pub channels: Vec<String>,
+    // Heads-up this long before the due time
+    pub warn: Option<Duration>,
+    // Repeat interval in minutes, standing in for a reminder's own renotify_minutes
+    pub nag: Option<i64>,
+}
+
+#[derive(Default)]
+pub struct Routes(Vec<Route>);
+
+impl Routes {
+    pub fn from_config(routes: &[RouteConfig]) -> Result<Self> {
+        let mut parsed = Vec::new();
+        for (n, route) in routes.iter().enumerate() {
+            let invalid = |e: anyhow::Error| RemindError::Config(format!("Route {}: {:#}", n + 1, e));
+            let priority = match &route.priority {
+                Some(priority) => Some(Priority::from_str(priority, true).map_err(|_| {
+                    RemindError::Config(format!(
+                        "Route {}: unknown priority '{}'. Use low, medium, high or urgent",
+                        n + 1,
+                        priority
+                    ))
+                })?),
+                None => None,
+            };
+            let warn = route.warn.as_deref().map(cli::parse_duration).transpose().map_err(invalid)?;
+            let nag = route.nag.as_deref().map(cli::parse_renotify).transpose().map_err(invalid)?.flatten();
+            parsed.push(Route {
+                tags: route.tags.iter().map(|tag| tag.trim_start_matches('#').to_string()).collect(),
+                priority,
+                channels: route.channels.clone(),
+                warn,
+                nag,
+            });
+        }
+        Ok(Routes(parsed))
+    }
+
+    // Whether any route sends heads-ups, which makes reminders that aren't due yet worth a look
+    pub fn warns(&self) -> bool {
+        self.0.iter().any(|route| route.warn.is_some())
+    }
+
+    // The first route for `reminder`, judged by its priority at the moment
+    pub fn find(&self, reminder: &Reminder, priority: Priority) -> Option<&Route> {
+        self.0.iter().find(|route| {
+            let tagged = route.tags.is_empty() || route.tags.iter().any(|tag| reminder.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)));
+            tagged && route.priority.is_none_or(|min| priority >= min)
+        })
+    }
+}
+
+impl Route {
+    // Whether the heads-up for the reminder's current occurrence is due and hasn't been sent
+    pub fn warns(&self, reminder: &Reminder, now: DateTime<Local>) -> bool {
+        let Some(warn) = self.warn else {
+            return false;
+        };
+        !reminder.completed
+            && reminder.due_time > now
+            && reminder.due_time - warn <= now
+            && reminder.warned_for != Some(reminder.due_time)
+    }
+}
@@ -0,0 +1,132 @@
+// User-defined rules written in Rhai, loaded from ~/.config/remindme/rules.rhai
+//
+// The script may define any of these functions; missing ones keep the built-in behavior:
+//   fn is_due(r)   -> bool or ()      decide whether a pending reminder is due now
+//   fn tags(r)     -> array           tags added automatically to new reminders
+//   fn route(r)    -> array or ()     where a due reminder goes: "terminal", "desktop", a push
+//                                     channel ("ntfy", "gotify", "matrix"), or a shell command
+//                                     that gets the reminder JSON on stdin
+// `r` is the reminder as a map (id, text, due_time, priority, tags, ...) plus
+// `minutes_until_due`. Helpers: now_hour(), now_minute(), now_weekday() (1 = Monday).
+// A script that fails at runtime is reported and the built-in behavior is used instead.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{Datelike, Local, Timelike};
+use rhai::{Array, Dynamic, Engine, Map, Scope, AST};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::RulesConfig;
+use crate::reminder::Reminder;
+
+pub struct Rules {
+    engine: Engine,
+    ast: AST,
+}
+
+impl Rules {
+    // Compile the rules script, or return None if there isn't one
+    pub fn load(config: &RulesConfig) -> Result<Option<Self>> {
+        let path = match &config.file {
+            Some(file) => PathBuf::from(shellexpand::tilde(file).to_string()),
+            None => match dirs::config_dir() {
+                Some(dir) => dir.join("remindme").join("rules.rhai"),
+                None => return Ok(None),
+            },
+        };
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let script = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read rules file {}", path.display()))?;
+
+        let mut engine = Engine::new();
+        engine.set_max_operations(100_000);
+        engine.register_fn("now_hour", || Local::now().hour() as i64);
+        engine.register_fn("now_minute", || Local::now().minute() as i64);
+        engine.register_fn("now_weekday", || Local::now().weekday().number_from_monday() as i64);
+
+        let ast = engine
+            .compile(&script)
+            .map_err(|e| anyhow!("Failed to compile rules file {}: {}", path.display(), e))?;
+
+        Ok(Some(Rules { engine, ast }))
+    }
+
+    // Custom due-ness, or None to fall back to `Reminder::is_due`
+    pub fn is_due(&self, reminder: &Reminder) -> Option<bool> {
+        let result = self.call("is_due", reminder)?;
+        if result.is_unit() {
+            return None;
+        }
+        match result.as_bool() {
+            Ok(due) => Some(due),
+            Err(_) => {
+                eprintln!("Rules: is_due() must return a bool or (), got {}", result.type_name());
+                None
+            }
+        }
+    }
+
+    // Tags the script wants added to a new reminder
+    pub fn tags(&self, reminder: &Reminder) -> Vec<String> {
+        self.call("tags", reminder)
+            .map(|result| strings("tags", result))
+            .unwrap_or_default()
+    }
+
+    // Channels for a due reminder, or None to use the defaults
+    pub fn route(&self, reminder: &Reminder) -> Option<Vec<String>> {
+        let result = self.call("route", reminder)?;
+        if result.is_unit() {
+            return None;
+        }
+        Some(strings("route", result))
+    }
+
+    // Call a script function with the reminder; None if it isn't defined or fails
+    fn call(&self, name: &str, reminder: &Reminder) -> Option<Dynamic> {
+        if !self.ast.iter_functions().any(|f| f.name == name && f.params.len() == 1) {
+            return None;
+        }
+
+        let mut map = match rhai::serde::to_dynamic(reminder) {
+            Ok(value) => value.try_cast::<Map>().unwrap_or_default(),
+            Err(e) => {
+                eprintln!("Rules: failed to convert reminder for {}(): {}", name, e);
+                return None;
+            }
+        };
+        let minutes_until_due = (reminder.due_time - Local::now()).num_minutes();
+        map.insert("minutes_until_due".into(), minutes_until_due.into());
+
+        match self.engine.call_fn::<Dynamic>(&mut Scope::new(), &self.ast, name, (map,)) {
+            Ok(result) => Some(result),
+            Err(e) => {
+                eprintln!("Rules: {}() failed: {}", name, e);
+                None
+            }
+        }
+    }
+}
+
+fn strings(name: &str, value: Dynamic) -> Vec<String> {
+    let items = if value.is_string() {
+        vec![value]
+    } else {
+        match value.try_cast::<Array>() {
+            Some(items) => items,
+            None => {
+                eprintln!("Rules: {}() must return a string or an array of strings", name);
+                return Vec::new();
+            }
+        }
+    };
+
+    items
+        .into_iter()
+        .filter_map(|item| item.into_string().ok())
+        .collect()
+}
@@ -0,0 +1,92 @@
+// Finding reminders by text, notes and tags (`remind search`)
+//
+// Matching ignores case. Each hit says which fields matched and shows the match with some of the
+// text around it, so a reminder found through a line deep in its notes is easy to recognize.
+
+use std::ops::Range;
+
+use crate::reminder::Reminder;
+
+// Characters of context shown on each side of a match
+const CONTEXT: usize = 30;
+
+pub struct Hit<'a> {
+    pub reminder: &'a Reminder,
+    // (field, the match in context) for every field that matched
+    pub fields: Vec<(&'static str, Snippet)>,
+}
+
+// A match with the text around it; `before` and `after` are cut to CONTEXT characters
+pub struct Snippet {
+    pub before: String,
+    pub matched: String,
+    pub after: String,
+}
+
+impl Snippet {
+    // Plain text, or with the match wrapped by `mark`, e.g. to make it bold in a terminal
+    pub fn render(&self, mark: impl Fn(&str) -> String) -> String {
+        format!("{}{}{}", self.before, mark(&self.matched), self.after)
+    }
+}
+
+pub fn search<'a>(reminders: &'a [Reminder], query: &str) -> Vec<Hit<'a>> {
+    reminders
+        .iter()
+        .filter_map(|reminder| {
+            let mut fields = Vec::new();
+            if let Some(snippet) = snippet(&reminder.text, query) {
+                fields.push(("text", snippet));
+            }
+            if let Some(snippet) = reminder.notes.as_deref().and_then(|notes| snippet(notes, query)) {
+                fields.push(("notes", snippet));
+            }
+            // A tag is short, so it's shown whole
+            let tag = query.trim_start_matches('#');
+            if let Some(snippet) = reminder.tags.iter().find_map(|t| snippet(&format!("#{}", t), tag)) {
+                fields.push(("tags", snippet));
+            }
+            (!fields.is_empty()).then_some(Hit { reminder, fields })
+        })
+        .collect()
+}
+
+fn snippet(value: &str, query: &str) -> Option<Snippet> {
+    let found = find(value, query)?;
+    let before: Vec<char> = value[..found.start].chars().collect();
+    let after: Vec<char> = value[found.end..].chars().collect();
+    let mut start = before.len().saturating_sub(CONTEXT);
+    // Start at a word rather than in the middle of one
+    if start > 0
+        && let Some(space) = before[start..].iter().position(|c| c.is_whitespace())
+    {
+        start += space + 1;
+    }
+    let end = after.len().min(CONTEXT);
+    let one_line = |chars: &[char]| chars.iter().map(|c| if c.is_whitespace() { ' ' } else { *c }).collect::<String>();
+    Some(Snippet {
+        before: format!("{}{}", if start > 0 { "…" } else { "" }, one_line(&before[start..])),
+        matched: one_line(&value[found.clone()].chars().collect::<Vec<_>>()),
+        after: format!("{}{}", one_line(&after[..end]), if end < after.len() { "…" } else { "" }),
+    })
+}
+
+// Byte range of the first occurrence of `query` in `value`, ignoring case
+fn find(value: &str, query: &str) -> Option<Range<usize>> {
+    let query: Vec<char> = query.chars().collect();
+    if query.is_empty() {
+        return None;
+    }
+    let same = |a: char, b: char| a.to_lowercase().eq(b.to_lowercase());
+    value.char_indices().find_map(|(start, _)| {
+        let mut chars = value[start..].char_indices();
+        for &q in &query {
+            let (_, c) = chars.next()?;
+            if !same(c, q) {
+                return None;
+            }
+        }
+        let end = chars.next().map_or(value.len(), |(offset, _)| start + offset);
+        Some(start..end)
+    })
+}
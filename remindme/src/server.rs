@@ -0,0 +1,240 @@
+// HTTP REST API over the reminder storage (`remind serve`)
+//
+// Endpoints (all JSON, all require `Authorization: Bearer <token>`):
+//   GET    /reminders                 list reminders
+//   POST   /reminders                 add a reminder
+//   GET    /reminders/:id             fetch one reminder
+//   PATCH  /reminders/:id             update text/time/date/recurrence/completed/meta
+//   DELETE /reminders/:id             delete a reminder
+//   POST   /reminders/:id/complete    mark a reminder as completed
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::io::Read;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::cli;
+use crate::config::DateOrder;
+use crate::error::RemindError;
+use crate::holidays::Calendar;
+use crate::reminder::Reminder;
+use crate::storage::Storage;
+
+// The body of POST /reminders, also taken by `remind api`
+#[derive(Deserialize)]
+pub struct NewReminder {
+    text: String,
+    time: String,
+    date: Option<String>,
+    recurrence: Option<String>,
+    #[serde(default)]
+    meta: BTreeMap<String, String>,
+}
+
+impl NewReminder {
+    pub fn build(self, date_order: DateOrder) -> Result<Reminder> {
+        let due_time = cli::parse_datetime_with_default_date(&self.time, self.date.as_deref(), date_order)?;
+        let recurrence = cli::parse_recurrence(self.recurrence.as_deref().unwrap_or("none"))?;
+
+        let mut reminder = Reminder::new(self.text, due_time, recurrence);
+        for (key, value) in &self.meta {
+            reminder.set_meta(key, value);
+        }
+        Ok(reminder)
+    }
+}
+
+// The body of PATCH /reminders/:id, also taken by `remind api`
+#[derive(Deserialize)]
+pub struct ReminderPatch {
+    text: Option<String>,
+    time: Option<String>,
+    date: Option<String>,
+    recurrence: Option<String>,
+    completed: Option<bool>,
+    // Merged into the existing metadata; an empty value removes the key
+    meta: Option<BTreeMap<String, String>>,
+}
+
+impl ReminderPatch {
+    // Change `reminder` and save it, the way `remind edit` and `remind complete` would: a new time
+    // or recurrence starts the schedule over, and completing moves a recurring reminder on and
+    // adds its follow-ups
+    pub fn apply(self, storage: &mut Storage, mut reminder: Reminder, calendar: &Calendar, date_order: DateOrder) -> Result<Reminder> {
+        if let Some(text) = self.text {
+            reminder.text = text;
+        }
+        let rescheduled = self.time.is_some() || self.date.is_some();
+        if let Some(time) = self.time {
+            reminder.due_time = cli::parse_datetime_with_default_date(&time, self.date.as_deref(), date_order)?;
+        } else if let Some(date) = self.date {
+            let time = reminder.due_time.format("%H:%M").to_string();
+            reminder.due_time = cli::parse_datetime(&format!("{} {}", date, time), date_order)?;
+        }
+        if rescheduled {
+            reminder.nominal_due = None;
+            reminder.anchor = None;
+        }
+        if let Some(recurrence) = self.recurrence {
+            cli::parse_schedule(&recurrence)?.apply(&mut reminder, date_order)?;
+        }
+        for (key, value) in self.meta.iter().flatten() {
+            reminder.set_meta(key, value);
+        }
+        match self.completed {
+            Some(true) if !reminder.completed => Ok(storage.complete_reminder(reminder, calendar)?.0),
+            completed => {
+                if let Some(completed) = completed {
+                    reminder.set_completed(completed);
+                }
+                storage.update_reminder(reminder.clone())?;
+                Ok(reminder)
+            }
+        }
+    }
+}
+
+// Largest request body read, so a client can't make the server buffer without limit
+const MAX_BODY_BYTES: u64 = 1024 * 1024;
+
+// An error response: HTTP status plus message
+struct ApiError(u16, String);
+
+impl From<anyhow::Error> for ApiError {
+    // The status follows the kind of error anywhere in the chain: bad input is the client's
+    // fault, a read-only or changed file isn't, and anything else is ours
+    fn from(e: anyhow::Error) -> Self {
+        let status = e
+            .chain()
+            .find_map(|cause| {
+                cause.downcast_ref::<RemindError>().map(|error| match error {
+                    RemindError::InvalidInput(_) => 400,
+                    RemindError::NotFound(_) => 404,
+                    RemindError::ReadOnly(_) => 403,
+                    RemindError::Conflict(_) => 409,
+                    RemindError::StorageInit(_) | RemindError::Access { .. } | RemindError::Config(_) => 500,
+                })
+            })
+            .unwrap_or(500);
+        ApiError(status, e.to_string())
+    }
+}
+
+type ApiResult = std::result::Result<(u16, serde_json::Value), ApiError>;
+
+pub fn serve(storage: &mut Storage, listen: &str, token: &str, calendar: &Calendar, date_order: DateOrder) -> Result<()> {
+    let server = Server::http(listen)
+        .map_err(|e| anyhow!("Failed to listen on {}: {}", listen, e))?;
+
+    println!("Serving reminders API on http://{}", listen);
+
+    for mut request in server.incoming_requests() {
+        let result = if is_authorized(&request, token) {
+            handle(storage, &mut request, calendar, date_order)
+        } else {
+            Err(ApiError(401, "Missing or invalid bearer token".to_string()))
+        };
+
+        let (status, body) = match result {
+            Ok(response) => response,
+            Err(ApiError(status, message)) => (status, json!({ "error": message })),
+        };
+
+        println!("{} {} -> {}", request.method(), request.url(), status);
+
+        let content_type = Header::from_bytes("Content-Type", "application/json").expect("static header is valid");
+        let response = Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(content_type);
+
+        if let Err(e) = request.respond(response) {
+            eprintln!("Failed to send response: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+fn is_authorized(request: &Request, token: &str) -> bool {
+    let expected = format!("Bearer {}", token);
+    request
+        .headers()
+        .iter()
+        .any(|h| h.field.equiv("Authorization") && constant_time_eq(h.value.as_str().as_bytes(), expected.as_bytes()))
+}
+
+// Compares every byte whatever the first mismatch, so response times don't reveal how much of
+// a guessed token was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+fn handle(storage: &mut Storage, request: &mut Request, calendar: &Calendar, date_order: DateOrder) -> ApiResult {
+    let path = request.url().split('?').next().unwrap_or("").trim_end_matches('/').to_string();
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let method = request.method().clone();
+
+    match (method, segments.as_slice()) {
+        (Method::Get, ["reminders"]) => {
+            let reminders = storage.load()?;
+            Ok((200, json!(reminders)))
+        }
+
+        (Method::Post, ["reminders"]) => {
+            let new: NewReminder = read_json(request)?;
+            let reminder = new.build(date_order)?;
+            storage.add_reminder(reminder.clone())?;
+            Ok((201, json!(reminder)))
+        }
+
+        (Method::Get, ["reminders", id]) => {
+            let reminder = find(storage, id)?;
+            Ok((200, json!(reminder)))
+        }
+
+        (Method::Patch, ["reminders", id]) => {
+            let patch: ReminderPatch = read_json(request)?;
+            let reminder = find(storage, id)?;
+            let reminder = patch.apply(storage, reminder, calendar, date_order)?;
+            Ok((200, json!(reminder)))
+        }
+
+        (Method::Delete, ["reminders", id]) => {
+            if storage.delete_reminder(id)? {
+                Ok((200, json!({ "deleted": id })))
+            } else {
+                Err(ApiError(404, format!("No reminder with id {}", id)))
+            }
+        }
+
+        (Method::Post, ["reminders", id, "complete"]) => {
+            let reminder = find(storage, id)?;
+            let (reminder, _) = storage.complete_reminder(reminder, calendar)?;
+            Ok((200, json!(reminder)))
+        }
+
+        _ => Err(ApiError(404, format!("No route for {} {}", request.method(), path))),
+    }
+}
+
+fn find(storage: &Storage, id: &str) -> std::result::Result<Reminder, ApiError> {
+    storage
+        .get_reminder_by_id(id)?
+        .ok_or_else(|| ApiError(404, format!("No reminder with id {}", id)))
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(request: &mut Request) -> std::result::Result<T, ApiError> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES + 1)
+        .read_to_string(&mut body)
+        .map_err(|e| ApiError(400, format!("Failed to read request body: {}", e)))?;
+    if body.len() as u64 > MAX_BODY_BYTES {
+        return Err(ApiError(413, format!("Request body is larger than {} bytes", MAX_BODY_BYTES)));
+    }
+
+    serde_json::from_str(&body).map_err(|e| ApiError(400, format!("Invalid JSON body: {}", e)))
+}
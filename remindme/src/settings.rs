@@ -0,0 +1,92 @@
+// The TUI's settings view (`,`): try out notifications and choose their sound
+//
+// The sound files found on this system are listed with the one in use marked. Any of them can be
+// played before choosing it, and the choice is written to `notifications.sound_file` in the
+// config, so finding a sound that works doesn't take editing files or setting REMINDME_SOUND.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::config::NotificationConfig;
+use crate::i18n::tr;
+use crate::sound;
+use crate::utils;
+
+pub struct Settings {
+    // The configured sound file, if it isn't a common one, then the common ones found here
+    pub sounds: Vec<String>,
+    // Highlighted sound
+    pub index: usize,
+}
+
+impl Settings {
+    // Starts on the sound in use
+    pub fn load(config: &NotificationConfig) -> Self {
+        let sounds = sound::choices(config);
+        let current = sound::get_sound_path(config);
+        let index = sounds.iter().position(|path| *path == current).unwrap_or(0);
+        Settings { sounds, index }
+    }
+
+    pub fn selected(&self) -> Option<&str> {
+        self.sounds.get(self.index).map(String::as_str)
+    }
+
+    pub fn up(&mut self) {
+        self.index = self.index.saturating_sub(1);
+    }
+
+    pub fn down(&mut self) {
+        self.index = (self.index + 1).min(self.sounds.len().saturating_sub(1));
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect, config: &NotificationConfig) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(6), Constraint::Min(0)].as_ref())
+            .split(area);
+
+        let current = sound::get_sound_path(config);
+        let source = if std::env::var_os("REMINDME_SOUND").is_some() {
+            tr!("from REMINDME_SOUND, which overrides the choice below")
+        } else if config.sound_file.is_some() {
+            "notifications.sound_file".to_string()
+        } else {
+            tr!("found automatically")
+        };
+        let bold = |text: String| Span::styled(text, Style::default().add_modifier(Modifier::BOLD));
+        let lines = vec![
+            Line::from(vec![Span::raw(tr!("Popup sound: ")), bold(config.sound.clone())]),
+            Line::from(vec![Span::raw(tr!("Sound file:  ")), bold(current.clone()), Span::raw(format!(" ({})", source))]),
+            Line::raw(""),
+            Line::raw(tr!("n: send a test notification   p: play the highlighted sound   Enter: use it")),
+        ];
+        let summary = Paragraph::new(lines).block(Block::default().title(tr!("Notifications")).borders(Borders::ALL));
+        f.render_widget(summary, chunks[0]);
+
+        let items: Vec<ListItem> = self
+            .sounds
+            .iter()
+            .map(|path| {
+                let mark = match (*path == current, utils::accessible()) {
+                    (true, false) => "✓ ".to_string(),
+                    (true, true) => format!("{} ", tr!("in use:")),
+                    (false, _) => "  ".to_string(),
+                };
+                ListItem::new(format!("{}{}", mark, path))
+            })
+            .collect();
+        let title = if items.is_empty() { tr!("Sound files (none found)") } else { tr!("Sound files") };
+        let list = List::new(items)
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            .highlight_symbol(if utils::accessible() { "> " } else { "" });
+        let mut state = ListState::default().with_selected(Some(self.index).filter(|_| !self.sounds.is_empty()));
+        f.render_stateful_widget(list, chunks[1], &mut state);
+    }
+}
@@ -0,0 +1,164 @@
+// Sending a reminder to someone else (`remind share`, `remind import`)
+//
+// A bundle is a small JSON file with one reminder and a BLAKE2b signature over it. With
+// share.key set to the same secret on both ends, the signature is keyed, so `import` knows
+// the bundle came from someone holding the key and wasn't changed on the way; unsigned bundles
+// are then refused without --allow-unsigned. Without a key it's a plain checksum that only
+// catches a damaged file.
+//
+// An imported reminder gets a new ID and starts with a clean notification history; its text,
+// schedule, recurrence, tags and metadata come over as they were.
+
+use anyhow::{Context, Result};
+use blake2::digest::consts::U32;
+use blake2::digest::{KeyInit, Mac};
+use blake2::{Blake2b, Blake2bMac, Digest};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use uuid::Uuid;
+
+use crate::compress;
+use crate::config::ShareConfig;
+use crate::error::RemindError;
+use crate::i18n::tr;
+use crate::reminder::Reminder;
+use crate::storage::Storage;
+use crate::utils;
+
+const FORMAT: &str = "remindme-share";
+const VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct Bundle {
+    format: String,
+    version: u32,
+    shared_at: DateTime<Local>,
+    // Kept as a JSON value so the signed bytes are the same on both ends, whatever
+    // fields either version of remindme knows about
+    reminder: Value,
+    // Whether `signature` was made with share.key
+    keyed: bool,
+    signature: String,
+}
+
+// Write the reminder with `id` as a bundle to `out`, or to stdout
+pub fn share(storage: &Storage, config: &ShareConfig, id: &str, out: Option<&str>) -> Result<()> {
+    let reminder = storage.get_reminder_by_id(id)?.ok_or_else(|| RemindError::NotFound(id.to_string()))?;
+    let value = serde_json::to_value(&reminder)?;
+    let key = key(config);
+    let bundle = Bundle {
+        format: FORMAT.to_string(),
+        version: VERSION,
+        shared_at: Local::now(),
+        signature: hex(&digest(&value, key.as_deref())?),
+        keyed: key.is_some(),
+        reminder: value,
+    };
+    let json = serde_json::to_string_pretty(&bundle)?;
+
+    match out {
+        Some(path) => {
+            let path = shellexpand::tilde(path).to_string();
+            fs::write(&path, json + "\n").map_err(RemindError::access(&path))?;
+            println!("{}", tr!("Shared '{}' as {}.", reminder.headline(), path));
+            if key.is_none() {
+                println!("{}", tr!("Not signed: set share.key to let the recipient check where it came from."));
+            }
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+// Add the reminder from the bundle at `path` under a new ID
+pub fn import(storage: &Storage, config: &ShareConfig, path: &str, allow_unsigned: bool) -> Result<()> {
+    let path = shellexpand::tilde(path).to_string();
+    let data = compress::decompress(fs::read(&path).map_err(RemindError::access(&path))?)?;
+    let bundle: Bundle = serde_json::from_slice(&data)
+        .map_err(|e| RemindError::InvalidInput(format!("{} is not a reminder bundle: {}", path, e)))?;
+    if bundle.format != FORMAT {
+        return Err(RemindError::InvalidInput(format!("{} is not a reminder bundle", path)).into());
+    }
+    if bundle.version > VERSION {
+        return Err(RemindError::InvalidInput(format!(
+            "{} was made by a newer version of remindme (bundle version {})",
+            path, bundle.version
+        ))
+        .into());
+    }
+    verify(&bundle, key(config).as_deref(), allow_unsigned)?;
+
+    let mut reminder: Reminder =
+        serde_json::from_value(bundle.reminder).context("The bundle holds an invalid reminder")?;
+    let now = Local::now();
+    reminder.id = Uuid::new_v4().to_string();
+    reminder.created_at = now;
+    reminder.updated_at = Some(now);
+    reminder.clear_notified();
+    reminder.pomodoros.clear();
+    let imported = tr!("Imported '{}' (due {}).", reminder.headline(), utils::format_datetime(&reminder.due_time));
+    storage.add_reminder(reminder)?;
+    println!("{}", imported);
+    Ok(())
+}
+
+fn key(config: &ShareConfig) -> Option<String> {
+    std::env::var("REMINDME_SHARE_KEY")
+        .ok()
+        .filter(|key| !key.is_empty())
+        .or_else(|| config.key.clone())
+}
+
+// With a key, only bundles signed with it are accepted, and unsigned ones when `allow_unsigned`
+// says so; without one, a signed bundle can't be checked and is refused as well
+fn verify(bundle: &Bundle, key: Option<&str>, allow_unsigned: bool) -> Result<()> {
+    match (bundle.keyed, key) {
+        (true, None) => {
+            return Err(RemindError::InvalidInput(tr!(
+                "Refusing to import the bundle: it is signed, but share.key isn't set here, so it can't be checked."
+            ))
+            .into());
+        }
+        (false, Some(_)) if !allow_unsigned => {
+            return Err(RemindError::InvalidInput(tr!(
+                "Refusing to import the bundle: it isn't signed, so there's no telling who made it. Use --allow-unsigned to import it anyway."
+            ))
+            .into());
+        }
+        (false, Some(_)) => {
+            println!("{}", tr!("Warning: the bundle isn't signed, so there's no telling who made it."));
+        }
+        _ => {}
+    }
+    let key = key.filter(|_| bundle.keyed);
+    if hex(&digest(&bundle.reminder, key)?) != bundle.signature {
+        let problem = if key.is_some() {
+            "its signature doesn't match share.key: it was changed or signed with another key"
+        } else {
+            "its checksum doesn't match: the file was damaged or changed"
+        };
+        return Err(RemindError::InvalidInput(format!("Refusing to import the bundle, {}", problem)).into());
+    }
+    Ok(())
+}
+
+// BLAKE2b-256 of the reminder's JSON, keyed with a hash of `key` when there is one
+fn digest(reminder: &Value, key: Option<&str>) -> Result<Vec<u8>> {
+    // serde_json sorts object keys, so both ends serialize the value alike
+    let data = serde_json::to_vec(reminder)?;
+    Ok(match key {
+        Some(key) => {
+            let key = Blake2b::<U32>::digest(key.as_bytes());
+            let mut mac = <Blake2bMac<U32> as KeyInit>::new_from_slice(&key).expect("a 32-byte key fits BLAKE2b");
+            mac.update(&data);
+            mac.finalize().into_bytes().to_vec()
+        }
+        None => Blake2b::<U32>::digest(&data).to_vec(),
+    })
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,145 @@
+// Daily sheet for printing or pasting into a journal (`remind print --today`)
+//
+// The day's reminders in order of their time, each with a checkbox and the first line of its
+// notes, as plain text sized for a printed page or as a Markdown task list (--markdown) that
+// journal apps like Obsidian or Logseq turn into checkable items. Occurrences already completed
+// that day are ticked, and on today's sheet reminders still overdue from earlier days are carried
+// over at the end.
+
+use chrono::{DateTime, Duration, Local, NaiveDate};
+
+use crate::holidays::Calendar;
+use crate::i18n::tr;
+use crate::reminder::{RecurrenceType, Reminder};
+use crate::utils;
+use crate::week;
+
+// Longest notes excerpt, in characters
+const EXCERPT: usize = 60;
+// Width of the plain-text sheet, which fits a printed page in a monospaced font
+const WIDTH: usize = 72;
+
+struct Entry<'a> {
+    reminder: &'a Reminder,
+    when: String,
+    done: bool,
+}
+
+// The sheet for `day`
+pub fn render(reminders: &[Reminder], day: NaiveDate, calendar: &Calendar, markdown: bool) -> String {
+    let start = week::midnight(day);
+    let end = week::midnight(day + Duration::days(1));
+
+    let mut timed: Vec<(DateTime<Local>, Entry)> = week::occurrences_between(reminders, start, end, calendar)
+        .into_iter()
+        .map(|(time, reminder)| (time, Entry { reminder, when: when(reminder, &time), done: reminder.completed }))
+        .collect();
+    // A recurring reminder completed that day has moved on to its next occurrence; tick it at the
+    // time it was completed
+    for reminder in reminders.iter().filter(|r| !matches!(r.recurrence, RecurrenceType::None)) {
+        for completed in reminder.completions.iter().filter(|t| **t >= start && **t < end) {
+            timed.push((*completed, Entry { reminder, when: when(reminder, completed), done: true }));
+        }
+    }
+    // All-day reminders first, the rest by time
+    timed.sort_by_key(|(time, entry)| (!entry.reminder.is_all_day(), *time));
+    let entries: Vec<Entry> = timed.into_iter().map(|(_, entry)| entry).collect();
+
+    let carried: Vec<Entry> = if day == Local::now().date_naive() {
+        let mut overdue: Vec<&Reminder> = reminders.iter().filter(|r| !r.completed && r.due_time < start).collect();
+        overdue.sort_by_key(|r| r.due_time);
+        overdue
+            .into_iter()
+            .map(|reminder| Entry { reminder, when: utils::format_date(reminder.due_time.date_naive(), "%b %-d"), done: false })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let title = utils::format_date(day, "%A, %-d %B %Y");
+    if markdown {
+        markdown_sheet(&title, &entries, &carried)
+    } else {
+        text_sheet(&title, &entries, &carried)
+    }
+}
+
+fn text_sheet(title: &str, entries: &[Entry], carried: &[Entry]) -> String {
+    let mut sheet = format!("{}\n{}\n\n", title, "=".repeat(title.chars().count()));
+    let width = entries.iter().chain(carried).map(|entry| entry.when.chars().count()).max().unwrap_or(0);
+    let indent = " ".repeat(4 + width + 2);
+    let lines = |sheet: &mut String, entries: &[Entry]| {
+        for entry in entries {
+            let mark = if entry.done { "[x]" } else { "[ ]" };
+            let text = format!("{}{}", entry.reminder.headline(), tags(entry.reminder));
+            sheet.push_str(&format!("{} {:<width$}  {}\n", mark, entry.when, text, width = width));
+            if let Some(excerpt) = excerpt(entry.reminder, WIDTH.saturating_sub(indent.len()).max(20)) {
+                sheet.push_str(&format!("{}{}\n", indent, excerpt));
+            }
+        }
+    };
+
+    if entries.is_empty() {
+        sheet.push_str(&format!("{}\n", tr!("Nothing scheduled.")));
+    }
+    lines(&mut sheet, entries);
+    if !carried.is_empty() {
+        let heading = tr!("Carried over");
+        sheet.push_str(&format!("\n{}\n{}\n", heading, "-".repeat(heading.chars().count())));
+        lines(&mut sheet, carried);
+    }
+
+    let heading = tr!("Notes");
+    sheet.push_str(&format!("\n{}\n{}\n", heading, "-".repeat(heading.chars().count())));
+    for _ in 0..3 {
+        sheet.push_str(&format!("\n{}\n", "_".repeat(WIDTH)));
+    }
+    sheet
+}
+
+fn markdown_sheet(title: &str, entries: &[Entry], carried: &[Entry]) -> String {
+    let mut sheet = format!("# {}\n\n", title);
+    let lines = |sheet: &mut String, entries: &[Entry]| {
+        for entry in entries {
+            let mark = if entry.done { "[x]" } else { "[ ]" };
+            sheet.push_str(&format!("- {} **{}** {}{}\n", mark, entry.when, entry.reminder.headline(), tags(entry.reminder)));
+            if let Some(excerpt) = excerpt(entry.reminder, EXCERPT) {
+                sheet.push_str(&format!("  {}\n", excerpt));
+            }
+        }
+    };
+
+    if entries.is_empty() {
+        sheet.push_str(&format!("{}\n", tr!("Nothing scheduled.")));
+    }
+    lines(&mut sheet, entries);
+    if !carried.is_empty() {
+        sheet.push_str(&format!("\n## {}\n\n", tr!("Carried over")));
+        lines(&mut sheet, carried);
+    }
+    sheet.push_str(&format!("\n## {}\n\n", tr!("Notes")));
+    sheet
+}
+
+fn when(reminder: &Reminder, time: &DateTime<Local>) -> String {
+    if reminder.is_all_day() { tr!("all day") } else { utils::format_time(time) }
+}
+
+fn tags(reminder: &Reminder) -> String {
+    reminder.tags.iter().map(|tag| format!(" #{}", tag)).collect()
+}
+
+// The first line of the notes, cut at a word to at most `max` characters
+fn excerpt(reminder: &Reminder, max: usize) -> Option<String> {
+    let line = reminder.notes.as_deref()?.lines().map(str::trim).find(|line| !line.is_empty())?;
+    let max = max.min(EXCERPT);
+    if line.chars().count() <= max {
+        return Some(line.to_string());
+    }
+    let cut: String = line.chars().take(max - 1).collect();
+    let cut = match cut.rfind(' ') {
+        Some(space) if space > max / 2 => &cut[..space],
+        _ => cut.as_str(),
+    };
+    Some(format!("{}…", cut.trim_end()))
+}
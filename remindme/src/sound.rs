@@ -1,56 +1,298 @@
-use anyhow::Result;
-use rodio::{source::Source, Decoder, OutputStream};
-use std::fs::File;
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use rodio::{source::Source, Decoder, OutputStream, Sink};
+use std::cell::RefCell;
+use std::fs::{self, File};
 use std::io::BufReader;
 use std::path::Path;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tracing::warn;
 
-pub fn play_notification_sound() -> Result<()> {
-    // Try to get sound path from config or use default
-    let sound_path = get_sound_path();
+use crate::config::{Config, NotificationConfig};
+use crate::i18n::tr;
+use crate::reminder::Priority;
+use crate::termux;
 
-    // Check if file exists before trying to play
-    if !Path::new(&sound_path).exists() {
-        return Err(anyhow::anyhow!("Sound file not found: {}", sound_path));
+// Common system notification sounds, in order of preference
+const CANDIDATES: [&str; 5] = [
+    "/usr/share/sounds/freedesktop/stereo/complete.oga",
+    "/usr/share/sounds/freedesktop/stereo/bell.oga",
+    "/usr/share/sounds/ubuntu/notifications/Blip.ogg",
+    "/usr/share/sounds/gnome/default/alerts/glass.ogg",
+    "~/.config/remindme/notification.mp3", // For custom user sounds
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SoundAction {
+    List,
+    Test,
+    Set,
+}
+
+// How much louder each repeat of an escalating alarm gets
+const ESCALATION_STEP: f32 = 0.25;
+
+// A sound file to play, `times` times in a row
+struct Request {
+    path: String,
+    times: u32,
+}
+
+// Plays sounds on a thread of its own, one after another, so whoever asked for one carries on
+// straight away. The audio device is only opened once there's something to play. Dropping the
+// player waits for the sounds still queued, or they'd be cut off when the program exits.
+pub struct SoundPlayer {
+    // The configured notification sound
+    path: String,
+    volume: f32,
+    urgent_repeat: u32,
+    queue: RefCell<Option<(Sender<Request>, JoinHandle<()>)>>,
+}
+
+impl SoundPlayer {
+    pub fn new(config: &NotificationConfig) -> Self {
+        SoundPlayer {
+            path: get_sound_path(config),
+            volume: config.volume,
+            urgent_repeat: config.urgent_repeat,
+            queue: RefCell::new(None),
+        }
     }
 
-    // Get a output stream handle to the default physical sound device
-    let (_stream, stream_handle) = OutputStream::try_default()?;
-    
-    // Load the sound file
-    let file = File::open(sound_path)?;
-    let source = Decoder::new(BufReader::new(file))?;
-    
-    // Play the sound
-    stream_handle.play_raw(source.convert_samples())?;
-    
-    // The sound plays in a separate thread, so we need to wait a bit to ensure it completes
-    std::thread::sleep(std::time::Duration::from_millis(500));
-    
+    // The notification sound, repeated for urgent reminders
+    pub fn play_notification(&self, priority: Priority) -> Result<()> {
+        let times = if priority == Priority::Urgent { self.urgent_repeat } else { 1 };
+        self.play(&self.path, times)
+    }
+
+    // Queue a sound file, e.g. to hear a candidate before choosing it. Only a missing file fails
+    // here; anything going wrong while playing is logged
+    pub fn play(&self, path: &str, times: u32) -> Result<()> {
+        if !Path::new(path).exists() {
+            return Err(anyhow!("Sound file not found: {}", path));
+        }
+        let mut queue = self.queue.borrow_mut();
+        let (sender, _) = queue.get_or_insert_with(|| {
+            let (sender, requests) = mpsc::channel();
+            let volume = self.volume;
+            (sender, thread::spawn(move || run(requests, volume)))
+        });
+        sender
+            .send(Request { path: path.to_string(), times: times.max(1) })
+            .map_err(|_| anyhow!("The sound player has stopped"))
+    }
+}
+
+impl Drop for SoundPlayer {
+    fn drop(&mut self) {
+        if let Some((sender, thread)) = self.queue.take() {
+            // Ends the player's loop once the queue is empty
+            drop(sender);
+            let _ = thread.join();
+        }
+    }
+}
+
+// Rings the notification sound every `notifications.escalate_every` seconds, starting at `volume`
+// and a step louder each time up to `escalate_volume`, until it's acknowledged or dropped
+pub struct Escalation {
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Escalation {
+    // None unless escalation is switched on
+    pub fn start(config: &NotificationConfig) -> Option<Self> {
+        if config.escalate_every == 0 {
+            return None;
+        }
+        let path = get_sound_path(config);
+        let every = Duration::from_secs(config.escalate_every);
+        let (volume, max) = (config.volume, config.escalate_volume.max(config.volume));
+        let (stop, stopped) = mpsc::channel();
+        let thread = thread::spawn(move || escalate(&path, every, volume, max, stopped));
+        Some(Escalation { stop: Some(stop), thread: Some(thread) })
+    }
+}
+
+impl Drop for Escalation {
+    fn drop(&mut self) {
+        // Stops the sound playing now, too
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn escalate(path: &str, every: Duration, mut volume: f32, max: f32, stopped: Receiver<()>) {
+    let output = if termux::detected() { None } else { OutputStream::try_default().ok() };
+    // The ring still playing, if any; replacing it stops it
+    let mut _ringing: Option<Sink> = None;
+    loop {
+        let ring = match &output {
+            Some((_, handle)) => Sink::try_new(handle).map_err(anyhow::Error::from).and_then(|sink| {
+                sink.set_volume(volume);
+                sink.append(Decoder::new(BufReader::new(File::open(path)?))?);
+                Ok(Some(sink))
+            }),
+            None => termux::play(path).map(|_| None),
+        };
+        match ring {
+            Ok(sink) => _ringing = sink,
+            Err(e) => warn!(path, error = %e, "Failed to play the escalating alarm"),
+        }
+        match stopped.recv_timeout(every) {
+            Err(RecvTimeoutError::Timeout) => volume = (volume + ESCALATION_STEP).min(max),
+            _ => return,
+        }
+    }
+}
+
+// The player's thread: queue each request on one sink, then wait for the last one to finish
+fn run(requests: Receiver<Request>, volume: f32) {
+    let mut output: Option<(OutputStream, Sink)> = None;
+    for request in requests {
+        if let Err(e) = queue(&request, volume, &mut output) {
+            warn!(path = %request.path, error = %e, "Failed to play notification sound");
+        }
+    }
+    if let Some((_stream, sink)) = output {
+        sink.sleep_until_end();
+    }
+}
+
+fn queue(request: &Request, volume: f32, output: &mut Option<(OutputStream, Sink)>) -> Result<()> {
+    // rodio finds no audio device on Android; Termux:API's media player can play it, once and at
+    // the phone's own volume
+    if termux::detected() {
+        return termux::play(&request.path);
+    }
+
+    let sink = match output {
+        Some((_, sink)) => sink,
+        None => {
+            // Get a output stream handle to the default physical sound device
+            let (stream, handle) = OutputStream::try_default()?;
+            let sink = Sink::try_new(&handle)?;
+            sink.set_volume(volume);
+            &output.insert((stream, sink)).1
+        }
+    };
+    let file = File::open(&request.path)?;
+    let source = Decoder::new(BufReader::new(file))?.buffered();
+    for _ in 0..request.times {
+        sink.append(source.clone());
+    }
     Ok(())
 }
 
-fn get_sound_path() -> String {
-    // First check if a custom sound is configured
+// `remind sound`: list the sounds to choose from, play one, or choose one. `sound` is a file or a
+// number from the list; without one, `test` plays the sound in use
+pub fn command(config: &Config, action: SoundAction, sound: Option<&str>) -> Result<()> {
+    let choices = choices(&config.notifications);
+    let current = get_sound_path(&config.notifications);
+    let pick = |sound: &str| -> Result<String> {
+        match sound.parse::<usize>() {
+            Ok(n) => choices
+                .get(n.wrapping_sub(1))
+                .cloned()
+                .ok_or_else(|| anyhow!(tr!("There's no sound {}; `remind sound list` shows {}.", n, choices.len()))),
+            Err(_) => Ok(shellexpand::tilde(sound).to_string()),
+        }
+    };
+    match action {
+        SoundAction::List => {
+            if choices.is_empty() {
+                println!("{}", tr!("No sound files found; set one with `remind sound set PATH`."));
+            }
+            for (i, path) in choices.iter().enumerate() {
+                let mark = if *path == current { "*" } else { " " };
+                println!("{} {}. {}", mark, i + 1, path);
+            }
+            if std::env::var_os("REMINDME_SOUND").is_some() {
+                println!("\n{}", tr!("REMINDME_SOUND is set, so it's used instead of notifications.sound_file."));
+            }
+        }
+        SoundAction::Test => {
+            let path = match sound {
+                Some(sound) => pick(sound)?,
+                None => current,
+            };
+            validate(&path)?;
+            println!("{}", tr!("Playing {}", path));
+            // Dropping the player waits until it's done
+            SoundPlayer::new(&config.notifications).play(&path, 1)?;
+        }
+        SoundAction::Set => {
+            let sound = sound.ok_or_else(|| anyhow!(tr!("Which sound? Give a file or a number from `remind sound list`.")))?;
+            let path = pick(sound)?;
+            validate(&path)?;
+            let path = fs::canonicalize(&path).map_or(path, |p| p.display().to_string());
+            Config::set_value("notifications", "sound_file", path.as_str())?;
+            println!("{}", tr!("Notifications will play {}.", path));
+            if config.notifications.sound == "none" {
+                println!("{}", tr!("Sounds are off (notifications.sound = \"none\"); set it to \"default\" to hear it."));
+            }
+        }
+    }
+    Ok(())
+}
+
+// The configured sound file, if it isn't a common one, then the common ones found here
+pub fn choices(config: &NotificationConfig) -> Vec<String> {
+    let mut sounds = candidates();
+    let current = get_sound_path(config);
+    if Path::new(&current).exists() && !sounds.contains(&current) {
+        sounds.insert(0, current);
+    }
+    sounds
+}
+
+// The file exists and can be decoded
+fn validate(path: &str) -> Result<()> {
+    if !Path::new(path).exists() {
+        return Err(anyhow::anyhow!("Sound file not found: {}", path));
+    }
+    let file = File::open(path)?;
+    Decoder::new(BufReader::new(file)).with_context(|| format!("Can't decode {}", path))?;
+    Ok(())
+}
+
+// Make sure the sound can be found, decoded and sent to an output device, without playing it
+pub fn check(config: &NotificationConfig) -> Result<String> {
+    let sound_path = get_sound_path(config);
+    validate(&sound_path)?;
+    if termux::detected() {
+        return Ok(sound_path);
+    }
+    OutputStream::try_default().context("No audio output device")?;
+    Ok(sound_path)
+}
+
+pub fn get_sound_path(config: &NotificationConfig) -> String {
+    // First check if a custom sound is set, for this run or in the config
     if let Ok(custom_path) = std::env::var("REMINDME_SOUND") {
         return custom_path;
     }
-    
-    // Otherwise check common system notification sounds
-    let potential_paths = [
-        "/usr/share/sounds/freedesktop/stereo/complete.oga",
-        "/usr/share/sounds/freedesktop/stereo/bell.oga",
-        "/usr/share/sounds/ubuntu/notifications/Blip.ogg",
-        "/usr/share/sounds/gnome/default/alerts/glass.ogg",
-        "~/.config/remindme/notification.mp3", // For custom user sounds
-    ];
-    
-    for path in potential_paths {
-        let expanded_path = shellexpand::tilde(path).to_string();
-        if Path::new(&expanded_path).exists() {
-            return expanded_path;
-        }
+    if let Some(path) = &config.sound_file {
+        return shellexpand::tilde(path).to_string();
     }
     
-    // If none found, return a default (which will fail if not found)
-    "/usr/share/sounds/freedesktop/stereo/bell.oga".to_string()
+    // Otherwise use the first common system notification sound there is, or a default (which
+    // will fail if not found)
+    candidates().into_iter().next().unwrap_or_else(|| "/usr/share/sounds/freedesktop/stereo/bell.oga".to_string())
+}
+
+// The common notification sounds found on this system
+pub fn candidates() -> Vec<String> {
+    CANDIDATES
+        .iter()
+        .map(|path| shellexpand::tilde(path).to_string())
+        .filter(|path| Path::new(path).exists())
+        .collect()
 }
\ No newline at end of file
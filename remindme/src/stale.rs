@@ -0,0 +1,126 @@
+// Stale reminders: pending ones nobody has touched in a while (`remind stale [--days 14]`)
+//
+// A reminder counts as touched when it's added, edited, snoozed or completed; being notified
+// doesn't count, so one that keeps going off without anyone acting on it goes stale. Recurring
+// reminders that move on by themselves once notified never do.
+//
+// With [stale] digest = true the notifier also sends one notification a week listing them, on
+// digest_day at digest_time. When it was last sent is kept in stale-digest.json next to the
+// config, so a run that's missed the time (the machine was off) still sends it once.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::cli;
+use crate::config::{Config, StaleConfig};
+use crate::dnd::Dnd;
+use crate::i18n::tr;
+use crate::reminder::Reminder;
+use crate::storage::{self, Storage};
+
+// How many reminders the digest names before "and N more"
+const DIGEST_NAMES: usize = 5;
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct DigestLog {
+    last_sent: Option<DateTime<Local>>,
+}
+
+impl DigestLog {
+    fn path() -> Result<PathBuf> {
+        storage::state_path("stale-digest.json")
+    }
+
+    fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(DigestLog::default());
+        }
+
+        let data = fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_slice(&data).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        let json = serde_json::to_vec_pretty(self).context("Failed to serialize the digest log")?;
+        fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+// The stale reminders, the longest untouched first
+pub fn find(reminders: &[Reminder], now: DateTime<Local>, days: i64) -> Vec<&Reminder> {
+    let mut stale: Vec<&Reminder> = reminders.iter().filter(|r| r.is_stale(now, Duration::days(days))).collect();
+    stale.sort_by_key(|r| r.last_touched());
+    stale
+}
+
+// `remind stale`: list them with how long each has been left alone
+pub fn command(storage: &Storage, config: &Config, days: Option<i64>) -> Result<()> {
+    let days = days.unwrap_or(config.stale.days);
+    if days < 0 {
+        return Err(anyhow!("--days can't be negative"));
+    }
+    let now = Local::now();
+    let reminders = storage.load()?;
+    let stale = find(&reminders, now, days);
+    if stale.is_empty() {
+        println!("{}", tr!("No reminders left untouched for {} days.", days));
+        return Ok(());
+    }
+
+    println!("{}", tr!("Untouched for {} days or more:", days));
+    for reminder in stale {
+        let idle = (now - reminder.last_touched()).num_days();
+        println!("{}", reminder.summary(config.display.relative_times));
+        println!("    {}", tr!("last touched {} days ago", idle));
+    }
+    println!("\n{}", tr!("Edit, snooze, complete or delete them, or go through them with remind review."));
+    Ok(())
+}
+
+// The digest's title and text if one is due now; sending it is left to the notifier. Nothing is
+// due while do-not-disturb is on, so it goes out once DND ends
+pub fn digest(storage: &Storage, config: &Config, now: DateTime<Local>) -> Result<Option<(String, String)>> {
+    if !config.stale.digest {
+        return Ok(None);
+    }
+    let Some(slot) = latest_slot(&config.stale, now)? else {
+        return Ok(None);
+    };
+    let mut log = DigestLog::load()?;
+    if log.last_sent.is_some_and(|sent| sent >= slot) || Dnd::load()?.is_active(now, &config.notifications)? {
+        return Ok(None);
+    }
+    log.last_sent = Some(now);
+    log.save()?;
+
+    // A week without stale reminders needs no notification
+    let reminders = storage.load()?;
+    let stale = find(&reminders, now, config.stale.days);
+    if stale.is_empty() {
+        return Ok(None);
+    }
+    let mut names: Vec<String> = stale.iter().take(DIGEST_NAMES).map(|r| r.headline()).collect();
+    if stale.len() > DIGEST_NAMES {
+        names.push(tr!("and {} more", stale.len() - DIGEST_NAMES));
+    }
+    let title = tr!("{} reminder(s) untouched for {}+ days", stale.len(), config.stale.days);
+    Ok(Some((title, names.join(", "))))
+}
+
+// The most recent digest_day at digest_time that isn't after `now`
+fn latest_slot(config: &StaleConfig, now: DateTime<Local>) -> Result<Option<DateTime<Local>>> {
+    let weekday = config.digest_weekday()?;
+    let time = cli::parse_time(&config.digest_time)?;
+    let slot = (0..=7)
+        .map(|back| now.date_naive() - Duration::days(back))
+        .filter(|date| date.weekday() == weekday)
+        .filter_map(|date| Local.from_local_datetime(&date.and_time(time)).earliest())
+        .find(|slot| *slot <= now);
+    Ok(slot)
+}
@@ -0,0 +1,100 @@
+// One-line summaries for status bars (`remind status`)
+//
+// Bars poll the command every few seconds, so the output is a single line with no
+// decoration beyond what the bar understands: JSON for waybar, %{F} color tags for
+// polybar, plain text for i3status/i3blocks and everything else.
+
+use chrono::{DateTime, Duration, Local};
+use clap::ValueEnum;
+use serde_json::json;
+
+use crate::i18n::tr;
+use crate::reminder::Reminder;
+use crate::utils;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StatusFormat {
+    Plain,
+    Waybar,
+    Polybar,
+}
+
+pub struct Summary<'a> {
+    pub overdue: Vec<&'a Reminder>,
+    pub upcoming: Vec<&'a Reminder>,
+}
+
+impl<'a> Summary<'a> {
+    // Pending reminders that are overdue, and those due within `window` from now
+    pub fn new(reminders: &'a [Reminder], now: DateTime<Local>, window: Duration) -> Self {
+        let mut overdue: Vec<&Reminder> = reminders.iter().filter(|r| !r.completed && r.due_time <= now).collect();
+        let mut upcoming: Vec<&Reminder> = reminders
+            .iter()
+            .filter(|r| !r.completed && r.due_time > now && r.due_time <= now + window)
+            .collect();
+        overdue.sort_by_key(|r| r.due_time);
+        upcoming.sort_by_key(|r| r.due_time);
+
+        Summary { overdue, upcoming }
+    }
+
+    pub fn text(&self) -> String {
+        match (self.overdue.len(), self.upcoming.len()) {
+            (0, 0) => String::new(),
+            (due, 0) => tr!("{} due", due),
+            (0, soon) => tr!("{} upcoming", soon),
+            (due, soon) => tr!("{} due, {} upcoming", due, soon),
+        }
+    }
+
+    fn class(&self) -> &'static str {
+        if !self.overdue.is_empty() {
+            "urgent"
+        } else if !self.upcoming.is_empty() {
+            "upcoming"
+        } else {
+            "idle"
+        }
+    }
+
+    fn tooltip(&self) -> String {
+        let mut next: Vec<&Reminder> = self.overdue.iter().chain(self.upcoming.iter()).copied().collect();
+        // Pinned reminders lead, as they do in `list`; the stable sort keeps the rest by due time
+        next.sort_by_key(|r| r.pin_rank());
+        next.iter()
+            .take(10)
+            .map(|r| format!("{} {} {}", utils::format_date(r.due_time.date_naive(), "%a"), utils::format_time(&r.due_time), r.text))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // A whole sentence for a screen reader, also when nothing is due, so silence isn't ambiguous
+    pub fn spoken(&self) -> String {
+        let count = |n: usize| if n == 1 { tr!("1 reminder") } else { tr!("{} reminders", n) };
+        match (self.overdue.len(), self.upcoming.len()) {
+            (0, 0) => tr!("No reminders due."),
+            (due, 0) => tr!("{} due now.", count(due)),
+            (0, soon) => tr!("{} coming up.", count(soon)),
+            (due, soon) => tr!("{} due now and {} coming up.", count(due), soon),
+        }
+    }
+
+    pub fn render(&self, format: StatusFormat) -> String {
+        match format {
+            StatusFormat::Plain if utils::accessible() => self.spoken(),
+            StatusFormat::Plain => self.text(),
+            StatusFormat::Waybar => json!({
+                "text": self.text(),
+                "tooltip": self.tooltip(),
+                "class": self.class(),
+                "alt": self.class(),
+            })
+            .to_string(),
+            StatusFormat::Polybar => match self.class() {
+                "urgent" => format!("%{{F#ff5555}}{}%{{F-}}", self.text()),
+                "upcoming" => format!("%{{F#f1fa8c}}{}%{{F-}}", self.text()),
+                _ => self.text(),
+            },
+        }
+    }
+}
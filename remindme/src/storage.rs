@@ -1,39 +1,203 @@
 use anyhow::{anyhow, Context, Result}; // Added anyhow macro here
+use crate::compress::{self, Compression};
+use crate::config::Config;
+use crate::crypto::Encryption;
+use crate::error::RemindError;
+use crate::holidays::Calendar;
+use crate::hooks::{Event, Hooks};
+use crate::index::{self, IndexEntry};
+use crate::journal::{self, Op};
+use crate::merge;
 use crate::reminder::Reminder;
-use serde_json;
+use crate::remote::Remote;
+use crate::rules::Rules;
+use crate::termux;
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use chrono::{DateTime, Local};
+use std::cell::Cell;
 use std::fs::{self, File};
 use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
 
 pub struct Storage {
     file_path: PathBuf,
+    encryption: Option<Encryption>,
+    hooks: Hooks,
+    rules: Option<Rules>,
+    journal: bool,
+    compact_after: usize,
+    // Set for ssh:// storage, whose file_path is the local copy
+    remote: Option<Remote>,
+    // How `archive` compresses the archive file
+    archive_compression: Compression,
+    // The reminders file and journal as last loaded or saved, to notice another process writing them
+    checksum: Cell<Option<Checksum>>,
+    // Why the reminders can't be changed, if they can't: --read-only, a file this user can't
+    // write (e.g. shared team reminders on a read-only mount), or an unreachable remote
+    read_only: Option<String>,
+    // On the team's shared file: the user, who's only notified of reminders assigned to them or to nobody
+    member: Option<String>,
+}
+
+type Checksum = [u8; 32];
+
+// One change to a reminder, with the reminder as it was loaded (None for an addition), so the
+// change can be made again on top of what another process wrote in the meantime
+#[derive(Clone)]
+pub struct Change {
+    pub op: Op,
+    pub base: Option<Reminder>,
 }
 
 impl Storage {
     pub fn new() -> Result<Self> {
-        let config_dir = dirs::config_dir()
-            .ok_or_else(|| anyhow!("Failed to determine config directory"))?
-            .join("remindme"); // Changed from "remind-rs" to "remindme"
+        let data_dir = data_dir()?.join("remindme");
         
         // Create directory if it doesn't exist
-        fs::create_dir_all(&config_dir)?;
+        fs::create_dir_all(&data_dir)
+            .with_context(|| format!("Failed to create storage directory {}", data_dir.display()))?;
+        
+        let file_path = data_dir.join("reminders.json");
+        if let Some(legacy_dir) = dirs::config_dir().map(|dir| dir.join("remindme")) {
+            migrate_legacy(&legacy_dir.join("reminders.json"), &file_path)?;
+        }
+        
+        Ok(Storage { file_path, encryption: None, hooks: Hooks::default(), rules: None, journal: false, compact_after: 0, remote: None, archive_compression: Compression::None, checksum: Cell::new(None), read_only: None, member: None })
+    }
+
+    // Open storage at an explicit file path, creating its parent directory if needed
+    pub fn with_path(file_path: PathBuf) -> Result<Self> {
+        if let Some(parent) = file_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create storage directory {}", parent.display()))?;
+        }
         
-        let file_path = config_dir.join("reminders.json");
+        Ok(Storage { file_path, encryption: None, hooks: Hooks::default(), rules: None, journal: false, compact_after: 0, remote: None, archive_compression: Compression::None, checksum: Cell::new(None), read_only: None, member: None })
+    }
+
+    // Pick the storage file: --storage-file flag, then REMINDME_STORAGE, then config, then the default
+    pub fn open(cli_path: Option<&str>, config: &Config) -> Result<Self> {
+        let custom_path = cli_path
+            .map(str::to_string)
+            .or_else(|| std::env::var("REMINDME_STORAGE").ok().filter(|p| !p.is_empty()))
+            .or_else(|| config.storage.file.clone());
+        let shared = custom_path.is_some() && custom_path == config.team.file;
+        let remote = custom_path.as_deref().map(Remote::parse).transpose()?.flatten();
+        
+        let mut storage = match (&remote, custom_path) {
+            (Some(remote), _) => data_dir().and_then(|dir| Self::with_path(remote.cache_path(&dir.join("remindme")))),
+            (None, Some(path)) => Self::with_path(PathBuf::from(shellexpand::tilde(&path).to_string())),
+            (None, None) => Self::new(),
+        }
+        .map_err(RemindError::StorageInit)?;
+        
+        storage.encryption = Encryption::from_config(&config.storage).map_err(RemindError::StorageInit)?;
+        storage.hooks = Hooks::new(&config.hooks);
+        storage.journal = config.storage.journal;
+        storage.compact_after = config.storage.compact_after;
+        storage.archive_compression = config.maintenance.compression;
+        if shared {
+            storage.member = config.team.me();
+        }
+        
+        if let Some(remote) = remote {
+            remote.pull(&storage.file_path).map_err(RemindError::StorageInit)?;
+            // Other clients read the remote file, so every change is pushed in full
+            storage.journal = false;
+            if !remote.reachable() {
+                storage.read_only = Some(format!("{} couldn't be reached", remote));
+            }
+            storage.remote = Some(remote);
+        } else if !writable(&storage.file_path) {
+            debug!(path = %storage.file_path.display(), "Reminders file isn't writable; opening it read-only");
+            storage.read_only = Some("it isn't writable".to_string());
+        }
         
-        Ok(Storage { file_path })
+        // A broken rules script shouldn't lock the user out of their reminders
+        storage.rules = Rules::load(&config.rules).unwrap_or_else(|e| {
+            eprintln!("Warning: {:#}; custom rules are disabled", e);
+            None
+        });
+        Ok(storage)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.file_path
+    }
+
+    pub fn hooks(&self) -> &Hooks {
+        &self.hooks
+    }
+
+    pub fn rules(&self) -> Option<&Rules> {
+        self.rules.as_ref()
+    }
+
+    // Whether `notify` tells this user about `reminder`
+    pub fn notifies(&self, reminder: &Reminder) -> bool {
+        self.member
+            .as_deref()
+            .is_none_or(|me| reminder.assignee.is_none() || reminder.is_assigned_to(me))
+    }
+
+    // Refuse every change from here on, as for --read-only
+    pub fn set_read_only(&mut self, reason: &str) {
+        self.read_only = Some(reason.to_string());
+    }
+
+    // The error a change gets on read-only storage
+    pub fn check_writable(&self) -> Result<()> {
+        match &self.read_only {
+            Some(reason) => Err(RemindError::ReadOnly(format!(
+                "{} is read-only ({}), so reminders can't be changed; commands that only read them, like list and search, still work",
+                self.file_path.display(),
+                reason
+            ))
+            .into()),
+            None => Ok(()),
+        }
     }
 
     pub fn load(&self) -> Result<Vec<Reminder>> {
         // Create empty file if it doesn't exist
         if !self.file_path.exists() {
-            File::create(&self.file_path)?;
-            return Ok(Vec::new());
+            if self.read_only.is_some() {
+                return Ok(Vec::new());
+            }
+            File::create(&self.file_path).map_err(RemindError::access(&self.file_path))?;
         }
 
         // Read file contents
-        let mut file = File::open(&self.file_path)?;
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
+        let mut data = Vec::new();
+        File::open(&self.file_path)
+            .and_then(|mut file| file.read_to_end(&mut data))
+            .map_err(RemindError::access(&self.file_path))?;
+        
+        self.checksum.set(Some(checksum(&data, &self.file_path)));
+        
+        // Replay changes journaled since the last snapshot
+        let mut reminders = self.parse(data)?;
+        journal::apply(&mut reminders, journal::read(&self.file_path)?);
+        debug!(count = reminders.len(), path = %self.file_path.display(), "Loaded reminders");
+        Ok(reminders)
+    }
+
+    // Parse raw file contents (possibly encrypted), e.g. from the file itself or a git revision
+    pub fn parse(&self, mut data: Vec<u8>) -> Result<Vec<Reminder>> {
+        // Decrypt if needed; a plaintext file is accepted and gets encrypted on the next save
+        if Encryption::is_encrypted(&data) {
+            let encryption = self.encryption.as_ref().ok_or_else(|| {
+                anyhow!("Reminders file is encrypted; set storage.encryption in config.toml to read it")
+            })?;
+            data = encryption.decrypt(&data)?;
+        }
+        // Archives and exports may be compressed
+        let data = compress::decompress(data)?;
+        
+        let contents = String::from_utf8(data)
+            .context("Reminders file is not valid UTF-8")?;
 
         // Handle empty file
         if contents.trim().is_empty() {
@@ -47,54 +211,410 @@ impl Storage {
         Ok(reminders)
     }
 
+    // Replace every reminder; refused if another process wrote the file since it was loaded, as
+    // saving would throw its changes away
     pub fn save(&self, reminders: &[Reminder]) -> Result<()> {
+        if self.changed_on_disk() {
+            return Err(RemindError::Conflict(format!(
+                "{} was changed by another process while this command ran; nothing was saved. Run it again",
+                self.file_path.display()
+            ))
+            .into());
+        }
+        self.snapshot(reminders)
+    }
+
+    // Save `reminders`, which were loaded and then changed by `changes`. If another process wrote
+    // the file in between, the changes are made again on top of what it wrote; a change to a
+    // reminder that process changed too is refused rather than overwriting it.
+    pub fn save_changes(&self, reminders: &[Reminder], changes: Vec<Change>) -> Result<()> {
+        if !self.changed_on_disk() {
+            return self.snapshot(reminders);
+        }
+        info!(path = %self.file_path.display(), "Reminders changed on disk; applying the changes again");
+        let mut current = self.load()?;
+        rebase(&mut current, changes)?;
+        self.snapshot(&current)
+    }
+
+    // Whether the file or journal differ from when they were last loaded or saved
+    fn changed_on_disk(&self) -> bool {
+        let Some(known) = self.checksum.get() else {
+            return false;
+        };
+        let data = fs::read(&self.file_path).unwrap_or_default();
+        checksum(&data, &self.file_path) != known
+    }
+
+    // Write all reminders as the new snapshot
+    fn snapshot(&self, reminders: &[Reminder]) -> Result<()> {
+        self.check_writable()?;
+        let merged;
+        let reminders = match &self.remote {
+            Some(remote) => {
+                merged = self.reconcile(remote, reminders)?;
+                &merged
+            }
+            None => reminders,
+        };
+        self.write(&self.file_path, reminders, Compression::None)?;
+        if let Some(remote) = &self.remote {
+            remote.push(&self.file_path, fs::read(&self.file_path).map_err(RemindError::access(&self.file_path))?)?;
+        }
+        
+        // The snapshot now contains everything the journal recorded
+        journal::archive(&self.file_path)?;
+        
+        // An index would leak due times of an encrypted file, so only keep one for plaintext
+        if self.encryption.is_some() {
+            index::remove(&self.file_path);
+        } else {
+            index::write(&self.file_path, index::entries(reminders))?;
+        }
+        
+        let data = fs::read(&self.file_path).map_err(RemindError::access(&self.file_path))?;
+        self.checksum.set(Some(checksum(&data, &self.file_path)));
+        debug!(count = reminders.len(), path = %self.file_path.display(), "Saved reminders");
+        Ok(())
+    }
+
+    // Merge in whatever another client pushed to the remote file since it was fetched
+    fn reconcile(&self, remote: &Remote, reminders: &[Reminder]) -> Result<Vec<Reminder>> {
+        let Some(change) = remote.changed(&self.file_path)? else {
+            return Ok(reminders.to_vec());
+        };
+        let base = change.base.map(|data| self.parse(data)).transpose()?;
+        let theirs = self.parse(change.current)?;
+        let result = merge::merge(base.as_deref(), reminders, &theirs, &mut merge::newest_wins)?;
+        info!(remote = %remote, conflicts = result.conflicts.len(), "Merged changes from another client");
+        Ok(result.reminders)
+    }
+
+    // Serialize, compress and encrypt reminders into `path`
+    fn write(&self, path: &Path, reminders: &[Reminder], compression: Compression) -> Result<()> {
         let json = serde_json::to_string_pretty(reminders)
             .context("Failed to serialize reminders to JSON")?;
+        let json = compress::compress(json.as_bytes(), compression)?;
         
-        let mut file = File::create(&self.file_path)
-            .context("Failed to create or open reminders file")?;
+        let data = match &self.encryption {
+            Some(encryption) => encryption.encrypt(&json)?,
+            None => json,
+        };
         
-        file.write_all(json.as_bytes())
+        // Write a temporary file and rename it over the old one, so a crash never leaves a half-written file
+        let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".tmp");
+        let tmp_path = path.with_file_name(tmp_name);
+        
+        let mut file = File::create(&tmp_path)
+            .map_err(RemindError::access(&tmp_path))?;
+        
+        file.write_all(&data)
+            .and_then(|_| file.sync_all())
             .context("Failed to write reminders to file")?;
+        drop(file);
         
+        fs::rename(&tmp_path, path)
+            .context("Failed to replace reminders file")?;
         Ok(())
     }
 
-    pub fn add_reminder(&self, reminder: Reminder) -> Result<()> {
-        let mut reminders = self.load()?;
-        reminders.push(reminder);
-        self.save(&reminders)?;
+    // Reminders moved out of the way by `remind maintain`, in the same format as the reminders file
+    pub fn archive_path(&self) -> PathBuf {
+        let mut name = self.file_path.file_name().unwrap_or_default().to_os_string();
+        name.push(".archive");
+        self.file_path.with_file_name(name)
+    }
+
+    // Add reminders to the archive; they stay encrypted if the reminders file is
+    pub fn archive(&self, reminders: &[Reminder]) -> Result<()> {
+        self.check_writable()?;
+        let path = self.archive_path();
+        let mut archived = self.archived()?;
+        // A reminder restored with `remind merge` and archived again replaces its old copy
+        archived.retain(|old| !reminders.iter().any(|r| r.id == old.id));
+        archived.extend_from_slice(reminders);
+        self.write(&path, &archived, self.archive_compression)?;
+        info!(count = reminders.len(), path = %path.display(), "Archived reminders");
+        Ok(())
+    }
+
+    // Everything in the archive; empty when nothing was archived yet
+    pub fn archived(&self) -> Result<Vec<Reminder>> {
+        let path = self.archive_path();
+        match fs::read(&path) {
+            Ok(data) => self.parse(data).with_context(|| format!("Failed to read {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(RemindError::access(&path)(e).into()),
+        }
+    }
+
+    // Ids, due times and completion of every reminder, without parsing the whole file when the index is fresh
+    pub fn index(&self) -> Result<Vec<IndexEntry>> {
+        if self.encryption.is_none()
+            && let Some(entries) = index::read(&self.file_path)
+        {
+            return Ok(entries);
+        }
+        
+        // Read-only storage is answered from the file itself; its directory may not take an index
+        let entries = index::entries(&self.load()?);
+        if self.encryption.is_none() && self.read_only.is_none() {
+            index::write(&self.file_path, entries.clone())?;
+        }
+        Ok(entries)
+    }
+
+    // Whether any pending reminder's due time has passed, answered from the index
+    pub fn any_past_due(&self, now: DateTime<Local>) -> Result<bool> {
+        Ok(self.index()?.iter().any(|e| !e.completed && e.next_alert.unwrap_or(e.due_time) <= now))
+    }
+
+    // Fold the journal into the reminders file, e.g. before committing it to git
+    pub fn compact(&self) -> Result<()> {
+        if journal::path_for(&self.file_path).exists() {
+            self.snapshot(&self.load()?)?;
+        }
+        Ok(())
+    }
+
+    // Journaling is skipped for encrypted storage, since the journal would be plaintext
+    fn uses_journal(&self) -> bool {
+        self.journal && self.encryption.is_none()
+    }
+
+    // Persist one change: append it to the journal, or rewrite the whole file without one
+    fn record(&self, change: Change, reminders: impl FnOnce() -> Result<Vec<Reminder>>) -> Result<()> {
+        self.check_writable()?;
+        if !self.uses_journal() {
+            return self.save_changes(&reminders()?, vec![change]);
+        }
+        
+        // Appending never overwrites another process's changes; the checksum is stale afterwards
+        self.checksum.set(None);
+        if journal::append(&self.file_path, change.op)? >= self.compact_after {
+            debug!(path = %self.file_path.display(), "Compacting journal");
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    pub fn add_reminder(&self, mut reminder: Reminder) -> Result<()> {
+        if let Some(rules) = &self.rules {
+            for tag in rules.tags(&reminder) {
+                reminder.add_tag(&tag);
+            }
+        }
+        
+        self.record(Change { op: Op::Add { reminder: reminder.clone() }, base: None }, || {
+            let mut reminders = self.load()?;
+            reminders.push(reminder.clone());
+            Ok(reminders)
+        })?;
+        info!(id = %reminder.id, "Added reminder");
+        self.hooks.fire(Event::Add, &reminder);
         Ok(())
     }
 
     pub fn delete_reminder(&self, id: &str) -> Result<bool> {
         let mut reminders = self.load()?;
-        let initial_len = reminders.len();
-        reminders.retain(|r| r.id != id);
-        
-        if reminders.len() == initial_len {
+        let Some(pos) = reminders.iter().position(|r| r.id == id) else {
             return Ok(false); // No reminder was deleted
-        }
+        };
         
-        self.save(&reminders)?;
+        let deleted = reminders.remove(pos);
+        let change = Change { op: Op::Delete { id: id.to_string() }, base: Some(deleted.clone()) };
+        self.record(change, || Ok(reminders))?;
+        info!(id, "Deleted reminder");
+        self.hooks.fire(Event::Delete, &deleted);
         Ok(true)
     }
 
     pub fn update_reminder(&mut self, updated_reminder: Reminder) -> Result<()> {
+        self.update(updated_reminder, true)
+    }
+
+    // Save what notifying changed (when it was sent, the next occurrence); unlike an edit, that
+    // doesn't count as touching the reminder for `stale`
+    pub fn update_notified(&mut self, reminder: Reminder) -> Result<()> {
+        self.update(reminder, false)
+    }
+
+    fn update(&mut self, mut updated_reminder: Reminder, touched: bool) -> Result<()> {
         let mut reminders = self.load()?;
+        updated_reminder.updated_at = Some(chrono::Local::now());
+        if touched {
+            updated_reminder.touched_at = updated_reminder.updated_at;
+        }
         
         // Find and replace the reminder with the same ID
         if let Some(pos) = reminders.iter().position(|r| r.id == updated_reminder.id) {
-            reminders[pos] = updated_reminder;
-            self.save(&reminders)?;
+            let completed_now = updated_reminder.completed && !reminders[pos].completed;
+            let base = std::mem::replace(&mut reminders[pos], updated_reminder.clone());
+            let change = Change { op: Op::Update { reminder: updated_reminder.clone() }, base: Some(base) };
+            self.record(change, || Ok(reminders))?;
+            debug!(id = %updated_reminder.id, "Updated reminder");
+            if completed_now {
+                self.hooks.fire(Event::Complete, &updated_reminder);
+            }
             Ok(())
         } else {
-            Err(anyhow::anyhow!("Reminder not found"))
+            Err(RemindError::NotFound(updated_reminder.id).into())
+        }
+    }
+
+    // Complete the reminder's current occurrence, firing the on-complete hook even when a recurring
+    // reminder moves on to its next occurrence rather than finishing. Its follow-up, if it has one,
+    // is added and returned too
+    pub fn complete_reminder(&mut self, mut reminder: Reminder, calendar: &Calendar) -> Result<(Reminder, Option<Reminder>)> {
+        let moved_on = reminder.complete(calendar);
+        self.update_reminder(reminder.clone())?;
+        if moved_on {
+            self.hooks.fire(Event::Complete, &reminder);
         }
+        let follow_up = reminder.follow_up(Local::now());
+        if let Some(next) = &follow_up {
+            self.add_reminder(next.clone())?;
+        }
+        Ok((reminder, follow_up))
     }
 
     pub fn get_reminder_by_id(&self, id: &str) -> Result<Option<Reminder>> {
         let reminders = self.load()?;
         Ok(reminders.into_iter().find(|r| r.id == id))
     }
-}
\ No newline at end of file
+}
+
+// Make `changes` again on `reminders` as another process left them
+pub fn rebase(reminders: &mut Vec<Reminder>, changes: Vec<Change>) -> Result<()> {
+    for Change { op, base } in changes {
+        let id = match &op {
+            Op::Add { reminder } | Op::Update { reminder } => reminder.id.clone(),
+            Op::Delete { id } => id.clone(),
+        };
+        let current = reminders.iter().find(|r| r.id == id);
+        let conflict = |what: &str| {
+            let text = base.as_ref().map_or(id.as_str(), |r| r.text.as_str());
+            RemindError::Conflict(format!("'{}' was {} by another process at the same time; nothing was saved", text, what))
+        };
+        match (&op, current, &base) {
+            (Op::Update { .. }, None, _) => return Err(conflict("deleted").into()),
+            // Already gone
+            (Op::Delete { .. }, None, _) => continue,
+            (Op::Update { .. } | Op::Delete { .. }, Some(current), Some(base)) if !same(current, base)? => {
+                return Err(conflict("changed").into());
+            }
+            _ => {}
+        }
+        journal::apply(reminders, vec![op]);
+    }
+    Ok(())
+}
+
+fn same(a: &Reminder, b: &Reminder) -> Result<bool> {
+    Ok(serde_json::to_value(a)? == serde_json::to_value(b)?)
+}
+
+// BLAKE2b-256 over the reminders file's contents `data` and its journal
+fn checksum(data: &[u8], file_path: &Path) -> Checksum {
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update(data);
+    hasher.update(fs::read(journal::path_for(file_path)).unwrap_or_default());
+    hasher.finalize().into()
+}
+
+// Whether this user can write the reminders file, or create it in its directory when it's missing
+fn writable(path: &Path) -> bool {
+    match fs::OpenOptions::new().append(true).open(path) {
+        Ok(_) => true,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .and_then(|dir| fs::metadata(dir).ok())
+            .is_none_or(|metadata| !metadata.permissions().readonly()),
+        Err(e) => !matches!(e.kind(), std::io::ErrorKind::PermissionDenied | std::io::ErrorKind::ReadOnlyFilesystem),
+    }
+}
+
+// Where reminders are kept by default, before the remindme directory
+fn data_dir() -> Result<PathBuf> {
+    if termux::detected() {
+        return Ok(termux::data_dir());
+    }
+    dirs::data_dir().ok_or_else(|| anyhow!("Failed to determine data directory"))
+}
+
+// Older versions kept reminders in the config directory. Move them (with their journal and index)
+// to the data directory once, and leave a symlink behind so older binaries still find them.
+fn migrate_legacy(legacy_path: &Path, file_path: &Path) -> Result<()> {
+    // On macOS and Windows the config and data directories are the same
+    if let (Ok(legacy), Ok(current)) = (legacy_path.canonicalize(), file_path.canonicalize())
+        && legacy == current
+    {
+        return Ok(());
+    }
+    // The symlink left by an earlier migration, or nothing to migrate
+    let Ok(metadata) = fs::symlink_metadata(legacy_path) else {
+        return Ok(());
+    };
+    if !metadata.is_file() {
+        return Ok(());
+    }
+    if file_path.exists() {
+        eprintln!(
+            "Warning: found reminders in both {} and {}; using the latter. Combine them with `remind merge {}`, then delete it.",
+            legacy_path.display(),
+            file_path.display(),
+            legacy_path.display()
+        );
+        return Ok(());
+    }
+    
+    for (from, to) in [
+        (journal::path_for(legacy_path), journal::path_for(file_path)),
+        (journal::history_path_for(legacy_path), journal::history_path_for(file_path)),
+        (index::path_for(legacy_path), index::path_for(file_path)),
+        (legacy_path.to_path_buf(), file_path.to_path_buf()),
+    ] {
+        if from.exists() {
+            move_file(&from, &to)?;
+        }
+    }
+    
+    #[cfg(unix)]
+    if let Err(e) = std::os::unix::fs::symlink(file_path, legacy_path) {
+        warn!(error = %e, path = %legacy_path.display(), "Failed to leave a symlink to the new reminders file");
+    }
+    
+    info!(from = %legacy_path.display(), to = %file_path.display(), "Moved reminders to the data directory");
+    eprintln!("Moved your reminders to {}.", file_path.display());
+    Ok(())
+}
+
+// A file kept between runs, such as the do-not-disturb state, in the data directory. Older
+// versions kept these in the config directory; one found there is moved over the first time.
+pub fn state_path(name: &str) -> Result<PathBuf> {
+    let dir = data_dir()?.join("remindme");
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create storage directory {}", dir.display()))?;
+    let path = dir.join(name);
+    if let Some(legacy) = dirs::config_dir().map(|dir| dir.join("remindme").join(name))
+        && legacy != path
+        && legacy.is_file()
+        && !path.exists()
+    {
+        move_file(&legacy, &path)?;
+    }
+    Ok(path)
+}
+
+// Rename, or copy and remove when the directories are on different filesystems
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to)
+        .with_context(|| format!("Failed to move {} to {}", from.display(), to.display()))?;
+    fs::remove_file(from).map_err(RemindError::access(from))?;
+    Ok(())
+}
@@ -0,0 +1,173 @@
+// Best guesses for dates, times and recurrences the parsers in cli.rs couldn't read
+//
+// Used only to make error messages more helpful ("Did you mean 17:30?"); nothing here is
+// accepted as input. cli.rs checks that a suggested recurrence actually parses before
+// showing it.
+
+use chrono::{Datelike, Local, NaiveDate, NaiveTime};
+
+use crate::config::DateOrder;
+
+const DATE_WORDS: &[&str] = &[
+    "today", "tomorrow", "monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday",
+];
+
+const RECURRENCE_WORDS: &[&str] = &[
+    "none", "daily", "weekdays", "weekday", "weekly", "monthly", "yearly", "annually", "every", "other", "day",
+    "days", "week", "weeks", "month", "year", "of", "first", "second", "third", "fourth", "last", "monday",
+    "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday", "mondays", "tuesdays", "wednesdays",
+    "thursdays", "fridays", "saturdays", "sundays", "last-day-of-month",
+];
+
+const MONTHS: &[&str] = &[
+    "january", "february", "march", "april", "may", "june", "july", "august", "september", "october", "november",
+    "december",
+];
+
+// "5.30", "1730", "17h30", "5p" and the like
+pub fn time(text: &str) -> Option<NaiveTime> {
+    let text: String = text.trim().to_lowercase().split_whitespace().collect();
+
+    let (clock, pm) = match (text.strip_suffix('a'), text.strip_suffix('p')) {
+        (Some(clock), _) => (clock, Some(false)),
+        (_, Some(clock)) => (clock, Some(true)),
+        _ => (text.as_str(), None),
+    };
+
+    let (hour, minute) = if let Some((hour, minute)) = clock.split_once(['.', ',', '-', 'h']) {
+        (hour, if minute.is_empty() { "0" } else { minute })
+    } else if (3..=4).contains(&clock.len()) && clock.chars().all(|c| c.is_ascii_digit()) {
+        clock.split_at(clock.len() - 2)
+    } else if pm.is_some() {
+        (clock, "0")
+    } else {
+        return None;
+    };
+
+    let (hour, minute): (u32, u32) = (hour.parse().ok()?, minute.parse().ok()?);
+    match pm {
+        Some(pm) if (1..=12).contains(&hour) => NaiveTime::from_hms_opt(hour % 12 + if pm { 12 } else { 0 }, minute, 0),
+        Some(_) => None,
+        None => NaiveTime::from_hms_opt(hour, minute, 0),
+    }
+}
+
+// A date in a format we don't accept, a date without a year, a month name, or a misspelled keyword
+pub fn date(text: &str, order: DateOrder) -> Option<String> {
+    let text = text.trim().to_lowercase();
+    if let Some(word) = closest(&text, DATE_WORDS) {
+        return Some(word.to_string());
+    }
+    if let Some(day) = text.strip_prefix("next-").or_else(|| text.strip_prefix("next ")) {
+        return closest(day, &DATE_WORDS[2..]).map(|day| format!("next-{}", day));
+    }
+
+    let today = Local::now().date_naive();
+    let parts: Vec<&str> = text
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect();
+    let numbers: Option<Vec<u32>> = parts.iter().map(|part| part.parse().ok()).collect();
+
+    let date = match numbers.as_deref() {
+        // 2025/06/03, 2025.06.03
+        Some([year, month, day]) if *year > 31 => NaiveDate::from_ymd_opt(*year as i32, *month, *day),
+        // 03-06-2025, 03.06.25
+        Some([a, b, year]) => {
+            let year = if *year < 100 { 2000 + *year } else { *year };
+            let (month, day) = if text.contains('/') { ordered(*a, *b, order) } else { (*b, *a) };
+            NaiveDate::from_ymd_opt(year as i32, month, day)
+        }
+        // 03.06 or 6/3 without a year
+        Some([a, b]) => {
+            let (month, day) = if text.contains('/') { ordered(*a, *b, order) } else { (*b, *a) };
+            upcoming(today, month, day)
+        }
+        // "june 3", "3rd june 2025"
+        _ => month_name_date(&parts, today),
+    };
+    date.map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+// Correct misspelled words in a recurrence phrase, e.g. "evry other tusday"
+pub fn recurrence(text: &str) -> Option<String> {
+    let text = text.trim().to_lowercase();
+    match text.as_str() {
+        "biweekly" | "fortnightly" => return Some("every other week".to_string()),
+        _ => {}
+    }
+
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|word| {
+            if word.parse::<u32>().is_ok() || RECURRENCE_WORDS.contains(&word) {
+                word.to_string()
+            } else {
+                closest(word, RECURRENCE_WORDS).unwrap_or(word).to_string()
+            }
+        })
+        .collect();
+    let suggestion = words.join(" ");
+    (suggestion != text).then_some(suggestion)
+}
+
+fn ordered(a: u32, b: u32, order: DateOrder) -> (u32, u32) {
+    match order {
+        DateOrder::Mdy => (a, b),
+        DateOrder::Dmy => (b, a),
+    }
+}
+
+// The next date with this month and day, this year or next
+fn upcoming(today: NaiveDate, month: u32, day: u32) -> Option<NaiveDate> {
+    let date = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+    if date >= today {
+        Some(date)
+    } else {
+        NaiveDate::from_ymd_opt(today.year() + 1, month, day)
+    }
+}
+
+fn month_name_date(parts: &[&str], today: NaiveDate) -> Option<NaiveDate> {
+    let month = parts.iter().find_map(|part| {
+        MONTHS.iter().position(|name| part.len() >= 3 && name.starts_with(*part)).map(|i| i as u32 + 1)
+    })?;
+    let numbers: Vec<u32> = parts
+        .iter()
+        .filter_map(|part| part.trim_end_matches(|c: char| c.is_ascii_alphabetic()).parse().ok())
+        .collect();
+    match numbers.as_slice() {
+        [day] => upcoming(today, month, *day),
+        [day, year] if *year > 31 => NaiveDate::from_ymd_opt(*year as i32, month, *day),
+        _ => None,
+    }
+}
+
+// The candidate closest to `word`, if it is within two typos
+fn closest<'a>(word: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    if word.len() < 3 {
+        return None;
+    }
+    let mut best: Option<(&str, usize)> = None;
+    for candidate in candidates {
+        let distance = edit_distance(word, candidate);
+        if distance <= 2 && best.is_none_or(|(_, d)| distance < d) {
+            best = Some((candidate, distance));
+        }
+    }
+    best.map(|(candidate, _)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
@@ -0,0 +1,161 @@
+// Git-backed sync of the storage file
+//
+// The directory holding the reminders file is used as a git repository. A sync commits
+// local changes, fetches the configured remote, merges the two histories reminder by
+// reminder (see `merge.rs`) instead of line by line, and pushes the result back.
+
+use anyhow::{anyhow, Context, Result};
+use std::path::Path;
+use std::process::{Command, Output};
+
+use crate::config::SyncConfig;
+use crate::merge;
+use crate::storage::Storage;
+
+pub struct SyncReport {
+    pub committed: bool,
+    pub merged: bool,
+    pub pushed: bool,
+    pub conflicts: Vec<merge::Conflict>,
+}
+
+pub fn sync_git(storage: &Storage, config: &SyncConfig) -> Result<SyncReport> {
+    let file_path = storage.path();
+    let repo_dir = file_path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let file_name = file_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| anyhow!("Invalid storage file path: {}", file_path.display()))?;
+
+    let git = Git { dir: repo_dir };
+
+    if !repo_dir.join(".git").exists() {
+        git.run(&["init", "--quiet"])?;
+        println!("Initialized git repository in {}", repo_dir.display());
+    }
+
+    // Make sure the file exists and holds every journaled change before committing it
+    storage.load()?;
+    storage.compact()?;
+
+    let mut report = SyncReport {
+        committed: commit_if_changed(&git, file_name, "Update reminders")?,
+        merged: false,
+        pushed: false,
+        conflicts: Vec::new(),
+    };
+
+    let remote = match &config.git_remote {
+        Some(remote) => remote.clone(),
+        None if git.output(&["remote", "get-url", "origin"])?.status.success() => "origin".to_string(),
+        None => {
+            println!("No remote configured (set sync.git_remote in config.toml); changes were only committed locally.");
+            return Ok(report);
+        }
+    };
+    let branch = config.git_branch.as_deref().unwrap_or("main");
+
+    // A missing remote branch just means this is the first push
+    let fetch = git.output(&["fetch", "--quiet", &remote, branch])?;
+    if fetch.status.success() {
+        if git.output(&["merge-base", "--is-ancestor", "FETCH_HEAD", "HEAD"])?.status.success() {
+            // Remote has nothing we don't already have
+        } else if git.output(&["merge-base", "--is-ancestor", "HEAD", "FETCH_HEAD"])?.status.success() {
+            git.run(&["merge", "--quiet", "--ff-only", "FETCH_HEAD"])?;
+            report.merged = true;
+        } else {
+            report.conflicts = merge_diverged(&git, storage, file_name)?;
+            report.merged = true;
+        }
+    } else {
+        let stderr = String::from_utf8_lossy(&fetch.stderr);
+        if !stderr.contains("couldn't find remote ref") {
+            return Err(anyhow!("git fetch from {} failed: {}", remote, stderr.trim()));
+        }
+    }
+
+    git.run(&["push", "--quiet", &remote, &format!("HEAD:{}", branch)])?;
+    report.pushed = true;
+
+    Ok(report)
+}
+
+// Both sides have new commits: merge the reminders and record a merge commit
+fn merge_diverged(git: &Git, storage: &Storage, file_name: &str) -> Result<Vec<merge::Conflict>> {
+    // Histories started independently on two machines have no common ancestor
+    let merge_base = git.output(&["merge-base", "HEAD", "FETCH_HEAD"])?;
+    let base_commit = merge_base
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&merge_base.stdout).trim().to_string());
+
+    let base = match base_commit {
+        Some(commit) => git.show(&commit, file_name)?.map(|data| storage.parse(data)).transpose()?,
+        None => None,
+    };
+    let local = storage.load()?;
+    let remote = match git.show("FETCH_HEAD", file_name)? {
+        Some(data) => storage.parse(data)?,
+        None => Vec::new(),
+    };
+
+    let result = merge::merge(base.as_deref(), &local, &remote, &mut merge::newest_wins)?;
+
+    // Record both parents but take no content from git; the merged file is written below
+    git.run(&["merge", "--quiet", "--no-commit", "--no-ff", "--allow-unrelated-histories", "-s", "ours", "FETCH_HEAD"])?;
+    storage.save(&result.reminders)?;
+    git.run(&["add", "--", file_name])?;
+    git.run(&["commit", "--quiet", "--no-verify", "-m", "Merge reminders"])?;
+
+    Ok(result.conflicts)
+}
+
+fn commit_if_changed(git: &Git, file_name: &str, message: &str) -> Result<bool> {
+    git.run(&["add", "--", file_name])?;
+
+    let has_head = git.output(&["rev-parse", "--verify", "--quiet", "HEAD"])?.status.success();
+    let unchanged = has_head && git.output(&["diff", "--cached", "--quiet", "--", file_name])?.status.success();
+    if unchanged {
+        return Ok(false);
+    }
+
+    git.run(&["commit", "--quiet", "--no-verify", "-m", message, "--", file_name])?;
+    Ok(true)
+}
+
+struct Git<'a> {
+    dir: &'a Path,
+}
+
+impl Git<'_> {
+    fn output(&self, args: &[&str]) -> Result<Output> {
+        Command::new("git")
+            .arg("-C")
+            .arg(self.dir)
+            .args(args)
+            .output()
+            .context("Failed to run git (is it installed?)")
+    }
+
+    // Run a git command and fail with its stderr if it doesn't succeed
+    fn run(&self, args: &[&str]) -> Result<Output> {
+        let output = self.output(args)?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "git {} failed: {}",
+                args.first().unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(output)
+    }
+
+    // File contents at a revision, or None if the file doesn't exist there
+    fn show(&self, rev: &str, file_name: &str) -> Result<Option<Vec<u8>>> {
+        let output = self.output(&["show", &format!("{}:{}", rev, file_name)])?;
+        Ok(output.status.success().then_some(output.stdout))
+    }
+}
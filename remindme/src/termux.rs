@@ -0,0 +1,71 @@
+// Termux on Android
+//
+// There is no D-Bus notification daemon and no audio device rodio can open, so popups, sound
+// and vibration go through the Termux:API commands instead (`pkg install termux-api`, plus
+// the Termux:API app). Detected at runtime, so the same build works in the app and over SSH
+// into it, where TERMUX_VERSION is not set.
+
+use anyhow::{Context, Result, anyhow};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::reminder::Priority;
+
+// Termux's install prefix and home, fixed by the app's package name
+const PREFIX: &str = "/data/data/com.termux/files/usr";
+const HOME: &str = "/data/data/com.termux/files/home";
+
+pub fn detected() -> bool {
+    env::var_os("TERMUX_VERSION").is_some() || Path::new(PREFIX).is_dir()
+}
+
+// Where reminders live: the usual XDG data dir, or the Termux home when HOME isn't set
+// (e.g. jobs started by termux-job-scheduler)
+pub fn data_dir() -> PathBuf {
+    dirs::data_dir().unwrap_or_else(|| Path::new(HOME).join(".local").join("share"))
+}
+
+// A notification in the Android shade; `tag` replaces an earlier one with the same tag
+pub fn notify(title: &str, content: &str, priority: Priority, tag: &str, sound: bool) -> Result<()> {
+    let priority = match priority {
+        Priority::Low => "low",
+        Priority::Medium => "default",
+        Priority::High => "high",
+        Priority::Urgent => "max",
+    };
+    let mut command = Command::new("termux-notification");
+    command
+        .args(["--title", title, "--content", content])
+        .args(["--priority", priority, "--id", tag, "--group", "remindme"]);
+    if sound {
+        command.arg("--sound");
+    }
+    run(&mut command)
+}
+
+pub fn vibrate(milliseconds: u32) -> Result<()> {
+    run(Command::new("termux-vibrate").args(["-d", &milliseconds.to_string()]))
+}
+
+pub fn play(path: &str) -> Result<()> {
+    run(Command::new("termux-media-player").args(["play", path]))
+}
+
+// Whether the Termux:API commands are installed
+pub fn check() -> Result<()> {
+    Command::new("termux-notification")
+        .arg("--help")
+        .output()
+        .context("termux-notification not found")?;
+    Ok(())
+}
+
+fn run(command: &mut Command) -> Result<()> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let status = command.status().with_context(|| format!("Failed to run {}", program))?;
+    if !status.success() {
+        return Err(anyhow!("{} failed: {}", program, status));
+    }
+    Ok(())
+}
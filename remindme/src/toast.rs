@@ -0,0 +1,114 @@
+// Short-lived messages in the TUI: what just happened, or what went wrong
+//
+// Toasts stack in the top-right corner of the view, newest at the bottom, and go away on their
+// own: successes after a few seconds, errors after a little longer so there's time to read them.
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use std::time::{Duration, Instant};
+
+use crate::i18n::tr;
+use crate::utils;
+
+// Toasts shown at once; older ones make room
+const MAX_SHOWN: usize = 3;
+const WIDTH: u16 = 48;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Success,
+    Error,
+}
+
+impl Severity {
+    fn color(self) -> Color {
+        match self {
+            Severity::Info => Color::Cyan,
+            Severity::Success => Color::Green,
+            Severity::Error => Color::Red,
+        }
+    }
+
+    // Said before the text for screen readers, which can't tell the colors apart
+    fn label(self) -> String {
+        match self {
+            Severity::Info => tr!("Note:"),
+            Severity::Success => tr!("Done:"),
+            Severity::Error => tr!("Error:"),
+        }
+    }
+
+    fn lifetime(self) -> Duration {
+        match self {
+            Severity::Info | Severity::Success => Duration::from_secs(4),
+            Severity::Error => Duration::from_secs(8),
+        }
+    }
+}
+
+struct Toast {
+    text: String,
+    severity: Severity,
+    expires: Instant,
+}
+
+#[derive(Default)]
+pub struct Toasts {
+    toasts: Vec<Toast>,
+}
+
+impl Toasts {
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.push(text.into(), Severity::Info);
+    }
+
+    pub fn success(&mut self, text: impl Into<String>) {
+        self.push(text.into(), Severity::Success);
+    }
+
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.push(text.into(), Severity::Error);
+    }
+
+    fn push(&mut self, text: String, severity: Severity) {
+        let text = if utils::accessible() { format!("{} {}", severity.label(), text) } else { text };
+        self.toasts.push(Toast { text, severity, expires: Instant::now() + severity.lifetime() });
+        if self.toasts.len() > MAX_SHOWN {
+            self.toasts.remove(0);
+        }
+    }
+
+    // Drop the toasts whose time is up
+    pub fn expire(&mut self) {
+        let now = Instant::now();
+        self.toasts.retain(|toast| toast.expires > now);
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        let width = WIDTH.min(area.width);
+        let mut y = area.y;
+        for toast in &self.toasts {
+            let color = toast.severity.color();
+            // Room for the wrapped text plus the border
+            let lines = (toast.text.chars().count() as u16).div_ceil(width.saturating_sub(2).max(1)).max(1);
+            let height = (lines + 2).min(area.y + area.height - y);
+            if height < 3 {
+                break;
+            }
+            let rect = Rect { x: area.x + area.width - width, y, width, height };
+            let paragraph = Paragraph::new(Line::raw(toast.text.as_str()))
+                .style(Style::default().fg(color))
+                .wrap(Wrap { trim: true })
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(color)));
+            f.render_widget(Clear, rect);
+            f.render_widget(paragraph, rect);
+            y += height;
+        }
+    }
+}
@@ -1,6 +1,8 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use chrono::{DateTime, Datelike, Local};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -9,19 +11,37 @@ use ratatui::{
     layout::{Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
     text::{Span, Text, Line}, // Add Line import
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame, Terminal,
 };
+use std::collections::BTreeMap;
 use std::io;
+use std::time::Duration;
 
-use crate::reminder::Reminder;
+use crate::colors;
+use crate::config::{Config, TuiLayout};
+use crate::form::{Field, Form, FormWidget};
+use crate::holidays::Calendar;
+use crate::i18n::tr;
+use crate::reminder::{RecurrenceType, Reminder, SortOrder};
+use crate::cache::{self, CachedStorage};
+use crate::notification::Notifier;
+use crate::settings::Settings;
+use crate::sound::SoundPlayer;
 use crate::storage::Storage;
 use crate::cli; 
+use crate::palette::{self, Action};
+use crate::pick;
+use crate::toast::Toasts;
+use crate::utils;
+use crate::view::View;
 
 #[derive(PartialEq, Eq)] // Add these derive macros
 enum InputMode {
     Normal,
     Editing,
+    Tags, // Editing the selected reminder's tags in the status bar
+    Palette, // Typing a command after `:`
 }
 
 #[derive(PartialEq, Eq)] // Add these derive macros
@@ -29,195 +49,493 @@ enum CurrentView {
     List,
     Add,
     Edit,
+    Settings,
     Help,
 }
 
-// Add this enum to track which field is active in the form
-#[derive(PartialEq, Eq, Clone, Copy)]
-enum ActiveField {
-    Text,
-    Time,
-    Date,
-    Recurrence,
-    Submit,
-}
-
 #[allow(dead_code)]
 struct App {
     reminders: Vec<Reminder>,
-    storage: Storage,
+    // Navigating and redrawing read reminders from memory; the file is only read again when it changes
+    storage: CachedStorage,
+    config: Config,
     current_view: CurrentView,
     input_mode: InputMode,
     input: String,
     selected_index: usize,
-    new_reminder_text: String,
-    new_reminder_time: String,
-    new_reminder_date: String,
-    new_reminder_recurrence: String,
+    form: Form, // The add or edit form, kept when it's left with Esc
     editing_reminder_id: Option<String>, // Add this field for editing
-    active_field: ActiveField,   // Add this field
-    error_message: Option<String>,
+    toasts: Toasts,
+    tags: Vec<(String, usize)>, // Every tag and how many reminders have it, for the sidebar
+    tag_filter: Option<String>, // Only reminders with this tag are listed
+    total: usize, // Reminders whatever the filter
+    sidebar_focused: bool,
+    tag_index: usize, // Highlighted sidebar entry; 0 is "All"
+    view: View,
+    overdue_count: usize, // In the split layout, the first this many reminders are the overdue pane
+    clipboard: Option<arboard::Clipboard>, // Opened on first use, and kept so copied text stays available
+    undo: Option<Reminder>, // The last deleted reminder, which `u` brings back
+    palette_index: usize, // Highlighted command among the palette's matches
+    settings: Settings,
+    sounds: SoundPlayer, // Plays the settings view's previews without holding up the TUI
+    redraw: bool, // Something printed over the screen, e.g. a notification's fallback, so draw it all again
 }
 
 impl App {
-    fn new(storage: Storage) -> Result<Self> {
-        let reminders = storage.load()?;
+    fn new(storage: Storage, config: &Config) -> Result<Self> {
+        let storage = CachedStorage::new(storage)?;
         
-        Ok(Self {
-            reminders,
+        let mut app = Self {
+            reminders: Vec::new(),
             storage,
+            config: config.clone(),
             current_view: CurrentView::List,
             input_mode: InputMode::Normal,
             input: String::new(),
             selected_index: 0,
-            new_reminder_text: String::new(),
-            new_reminder_time: String::new(),
-            new_reminder_date: String::new(),
-            new_reminder_recurrence: String::from("none"), // Initialize with default value
+            form: Form::new(&config.defaults),
             editing_reminder_id: None, // No reminder being edited initially
-            active_field: ActiveField::Text,  // Initialize to first field
-            error_message: None,
-        })
+            toasts: Toasts::default(),
+            tags: Vec::new(),
+            tag_filter: None,
+            total: 0,
+            sidebar_focused: false,
+            tag_index: 0,
+            view: View::load(&config.tui),
+            overdue_count: 0,
+            clipboard: None,
+            undo: None,
+            palette_index: 0,
+            settings: Settings::load(&config.notifications),
+            sounds: SoundPlayer::new(&config.notifications),
+            redraw: false,
+        };
+        app.refresh_reminders()?;
+        Ok(app)
     }
     
-    // Add method to get current active input based on field
-    fn get_active_input_mut(&mut self) -> &mut String {
-        match self.active_field {
-            ActiveField::Text => &mut self.new_reminder_text,
-            ActiveField::Time => &mut self.new_reminder_time,
-            ActiveField::Date => &mut self.new_reminder_date,
-            ActiveField::Recurrence => &mut self.new_reminder_recurrence,
-            ActiveField::Submit => &mut self.input, // Dummy, not used
+    // The form's due time and recurrence, or None after showing what's wrong with them
+    fn parse_form(&mut self) -> Option<(DateTime<Local>, RecurrenceType)> {
+        if self.form.text.is_empty() {
+            self.toasts.error(tr!("Reminder text cannot be empty"));
+            return None;
+        }
+        if self.form.time.is_empty() {
+            self.toasts.error(tr!("Time must be specified (HH:MM)"));
+            return None;
+        }
+        let date = (!self.form.date.is_empty()).then_some(self.form.date.as_str());
+        let due_time = match cli::parse_datetime_with_default_date(&self.form.time, date, self.config.input.date_order) {
+            Ok(due_time) => due_time,
+            Err(e) => {
+                self.toasts.error(tr!("Invalid date/time: {}", e));
+                return None;
+            }
+        };
+        // A cleared recurrence means it doesn't repeat
+        let recurrence = if self.form.recurrence.trim().is_empty() { "none" } else { &self.form.recurrence };
+        match cli::parse_recurrence(recurrence) {
+            Ok(recurrence) => Some((due_time, recurrence)),
+            Err(e) => {
+                self.toasts.error(tr!("Invalid recurrence: {}", e));
+                None
+            }
+        }
+    }
+
+    // Save the form as a new reminder, or into the one being edited
+    fn submit_form(&mut self) -> Result<()> {
+        let Some((due_time, recurrence)) = self.parse_form() else {
+            return Ok(());
+        };
+        let drifts = match self.editing_reminder_id.take() {
+            // Only the form's fields change; tags, notes and the rest stay as they were
+            Some(id) => {
+                let Some(mut reminder) = self.storage.reminders()?.iter().find(|r| r.id == id).cloned() else {
+                    self.toasts.error(tr!("The reminder was deleted in the meantime"));
+                    return Ok(());
+                };
+                reminder.text = self.form.text.clone();
+                reminder.due_time = due_time;
+                reminder.recurrence = recurrence;
+                reminder.nominal_due = None;
+                reminder.anchor = None;
+                let drifts = reminder.moves_in_short_months();
+                self.storage.update_reminder(reminder)?;
+                self.toasts.success(tr!("Reminder updated"));
+                drifts
+            }
+            // New ones also get the default priority and tags
+            None => {
+                let mut reminder = Reminder::new(self.form.text.clone(), due_time, recurrence);
+                match self.config.defaults.priority() {
+                    Ok(priority) => reminder.priority = priority,
+                    Err(e) => self.toasts.error(e.to_string()),
+                }
+                for tag in &self.config.defaults.tags {
+                    reminder.add_tag(tag);
+                }
+                let drifts = reminder.moves_in_short_months();
+                self.storage.add_reminder(reminder);
+                self.toasts.success(tr!("Reminder added"));
+                drifts
+            }
+        };
+        if drifts {
+            self.toasts.info(tr!("Not every month has that day, so shorter months get it on their last day"));
         }
+        self.flush()?;
+        self.form = Form::new(&self.config.defaults);
+        self.current_view = CurrentView::List;
+        self.input_mode = InputMode::Normal;
+        self.refresh_reminders()
     }
     
-    // Add method to create a reminder from form data
-    fn create_reminder(&mut self) -> Result<()> {
-        // Validate fields
-        if self.new_reminder_text.is_empty() {
-            self.error_message = Some("Reminder text cannot be empty".to_string());
+    // Write the changes; a change another process made to the same reminder first wins, and the
+    // one here is reported instead of overwriting it
+    fn flush(&mut self) -> Result<()> {
+        match self.storage.flush() {
+            Err(e) if cache::is_conflict(&e) => {
+                self.toasts.error(e.to_string());
+                self.refresh_reminders()
+            }
+            result => result,
+        }
+    }
+
+    fn refresh_reminders(&mut self) -> Result<()> {
+        let reminders = self.storage.reminders()?;
+        self.tags = tag_counts(reminders);
+        self.total = reminders.len();
+        // A filter whose last reminder went away shows everything again
+        if let Some(filter) = &self.tag_filter
+            && !self.tags.iter().any(|(tag, _)| tag.eq_ignore_ascii_case(filter))
+        {
+            self.tag_filter = None;
+            self.tag_index = 0;
+        }
+        self.reminders = reminders
+            .iter()
+            .filter(|r| self.tag_filter.as_ref().is_none_or(|filter| r.tags.iter().any(|t| t.eq_ignore_ascii_case(filter))))
+            .cloned()
+            .collect();
+        let now = Local::now();
+        self.reminders.sort_by(|a, b| self.view.sort.compare(a, b, now, &self.config.priority));
+        self.reminders.sort_by_key(|r| r.pin_rank());
+        if self.view.layout == TuiLayout::Split {
+            self.reminders.sort_by_key(|r| !is_overdue(r, now));
+            self.overdue_count = self.reminders.iter().take_while(|r| is_overdue(r, now)).count();
+        }
+        self.tag_index = self.tag_index.min(self.tags.len());
+        Ok(())
+    }
+
+    // Change the layout or order, keeping the same reminder selected
+    fn change_view(&mut self, change: impl FnOnce(&mut View)) -> Result<()> {
+        let selected = self.reminders.get(self.selected_index).map(|r| r.id.clone());
+        change(&mut self.view);
+        self.save_view();
+        self.refresh_reminders()?;
+        self.selected_index = selected.and_then(|id| self.reminders.iter().position(|r| r.id == id)).unwrap_or(0);
+        Ok(())
+    }
+
+    fn resize_top_pane(&mut self, steps: i16) {
+        if self.view.layout != TuiLayout::List {
+            self.view.resize(steps);
+            self.save_view();
+        }
+    }
+
+    // Remember the view for the next session; failing to is worth a note, not an error
+    fn save_view(&mut self) {
+        if let Err(e) = self.view.save() {
+            self.toasts.error(tr!("Couldn't save the layout: {}", format!("{:#}", e)));
+        }
+    }
+
+    // Move the selection to the other pane, if it has anything in it
+    fn switch_pane(&mut self) {
+        if self.selected_index < self.overdue_count {
+            if self.overdue_count < self.reminders.len() {
+                self.selected_index = self.overdue_count;
+            }
+        } else if self.overdue_count > 0 {
+            self.selected_index = 0;
+        }
+    }
+
+    // Show only the reminders with the tag highlighted in the sidebar, or all of them for "All"
+    fn apply_tag_filter(&mut self) -> Result<()> {
+        self.tag_filter = self.tag_index.checked_sub(1).and_then(|i| self.tags.get(i)).map(|(tag, _)| tag.clone());
+        self.selected_index = 0;
+        self.sidebar_focused = false;
+        self.refresh_reminders()
+    }
+
+    fn start_adding(&mut self) {
+        // A half-written new reminder is kept, but not the one that was being edited
+        if self.editing_reminder_id.take().is_some() {
+            self.form = Form::new(&self.config.defaults);
+        }
+        self.current_view = CurrentView::Add;
+        self.input_mode = InputMode::Editing;
+        self.form.active = Field::Text;
+    }
+
+    fn delete_selected(&mut self) -> Result<()> {
+        let Some(reminder) = self.reminders.get(self.selected_index).cloned() else {
             return Ok(());
+        };
+        self.storage.delete_reminder(&reminder.id);
+        self.flush()?;
+        self.toasts.success(tr!("Deleted '{}' - press u to undo", reminder.text));
+        self.undo = Some(reminder);
+        self.refresh_reminders()?;
+        if self.selected_index >= self.reminders.len() && !self.reminders.is_empty() {
+            self.selected_index = self.reminders.len() - 1;
         }
-        
-        if self.new_reminder_time.is_empty() {
-            self.error_message = Some("Time must be specified (HH:MM)".to_string());
+        Ok(())
+    }
+
+    // Bring back the reminder deleted last
+    fn undo_delete(&mut self) -> Result<()> {
+        let Some(reminder) = self.undo.take() else {
+            self.toasts.info(tr!("Nothing to undo"));
             return Ok(());
+        };
+        let id = reminder.id.clone();
+        self.toasts.success(tr!("Restored '{}'", reminder.text));
+        self.storage.restore_reminder(reminder);
+        self.flush()?;
+        self.refresh_reminders()?;
+        if let Some(pos) = self.reminders.iter().position(|r| r.id == id) {
+            self.selected_index = pos;
         }
-        
-        // Parse time with optional date
-        let date_option = if self.new_reminder_date.is_empty() {
-            None
-        } else {
-            Some(self.new_reminder_date.as_str())
+        Ok(())
+    }
+
+    // Save a changed copy of the selected reminder and say what happened
+    fn change_selected(&mut self, change: impl FnOnce(&mut Reminder) -> Result<String>) -> Result<()> {
+        let Some(mut reminder) = self.reminders.get(self.selected_index).cloned() else {
+            return Ok(());
         };
-        
-        match cli::parse_datetime_with_default_date(&self.new_reminder_time, date_option) {
-            Ok(due_time) => {
-                // Parse recurrence
-                let recurrence_type = match cli::parse_recurrence(&self.new_reminder_recurrence) {
-                    Ok(rec) => rec,
-                    Err(e) => {
-                        self.error_message = Some(format!("Invalid recurrence: {}", e));
-                        return Ok(());
-                    }
+        let done = change(&mut reminder)?;
+        self.toasts.success(done);
+        self.storage.update_reminder(reminder)?;
+        self.flush()?;
+        self.refresh_reminders()
+    }
+
+    // Run the highlighted palette command; returns whether it was quit
+    fn run_palette(&mut self) -> Result<bool> {
+        let input = std::mem::take(&mut self.input);
+        self.input_mode = InputMode::Normal;
+        let Some(command) = palette::matches(&input).get(self.palette_index).copied() else {
+            self.toasts.error(tr!("No command matches '{}'", input.trim()));
+            return Ok(false);
+        };
+        self.current_view = CurrentView::List;
+        // A mistyped argument is reported in the status bar rather than ending the TUI
+        if let Err(e) = self.run_command(command.action, palette::argument(&input)) {
+            self.toasts.error(format!("{}: {:#}", command.name, e));
+        }
+        Ok(command.action == Action::Quit)
+    }
+
+    fn run_command(&mut self, action: Action, argument: &str) -> Result<()> {
+        match action {
+            Action::Add => self.start_adding(),
+            Action::Edit => self.start_editing_selected_reminder()?,
+            Action::Delete => self.delete_selected()?,
+            Action::Complete => {
+                let Some(reminder) = self.reminders.get(self.selected_index).cloned() else {
+                    return Ok(());
                 };
-                
-                // Create and save the reminder
-                let reminder = Reminder::new(
-                    self.new_reminder_text.clone(),
-                    due_time,
-                    recurrence_type
-                );
-                
-                self.storage.add_reminder(reminder)?;
-                
-                // Clear form and error
-                self.new_reminder_text.clear();
-                self.new_reminder_time.clear();
-                self.new_reminder_date.clear();
-                self.new_reminder_recurrence = "none".to_string();
-                self.error_message = None;
-                
-                // Return to list view
-                self.current_view = CurrentView::List;
-                self.input_mode = InputMode::Normal;
+                let calendar = Calendar::from_config(&self.config.holidays)?;
+                let (reminder, follow_up) = self.storage.complete_reminder(reminder, &calendar)?;
+                self.toasts.success(tr!("Completed '{}'", reminder.text));
+                if let Some(next) = follow_up {
+                    self.toasts.info(tr!("Follow-up '{}' due {}", next.text, utils::format_datetime(&next.due_time)));
+                }
+                self.flush()?;
                 self.refresh_reminders()?;
-                
-                Ok(())
-            },
-            Err(e) => {
-                self.error_message = Some(format!("Invalid date/time: {}", e));
-                Ok(())
             }
+            Action::Snooze => {
+                let duration = cli::parse_duration(if argument.is_empty() { "10m" } else { argument })?;
+                self.change_selected(|reminder| {
+                    reminder.snooze(Local::now() + duration);
+                    Ok(tr!("Snoozed '{}' until {}", reminder.text, utils::format_datetime(&reminder.due_time)))
+                })?;
+            }
+            Action::Filter => {
+                let tag = argument.trim_start_matches('#');
+                self.tag_index = match self.tags.iter().position(|(t, _)| t.eq_ignore_ascii_case(tag)) {
+                    Some(i) => i + 1,
+                    None if tag.is_empty() => 0,
+                    None => return Err(anyhow!(tr!("no reminders are tagged #{}", tag))),
+                };
+                self.apply_tag_filter()?;
+            }
+            Action::Tags => self.start_editing_tags(),
+            Action::Layout => {
+                let layout = match argument {
+                    "" => None,
+                    "list" => Some(TuiLayout::List),
+                    "split" => Some(TuiLayout::Split),
+                    "calendar" => Some(TuiLayout::Calendar),
+                    other => return Err(anyhow!(tr!("unknown layout '{}'; use list, split or calendar", other))),
+                };
+                self.change_view(|view| match layout {
+                    Some(layout) => view.layout = layout,
+                    None => view.next_layout(),
+                })?;
+            }
+            Action::Sort => {
+                let sort = match argument {
+                    "" => self.view.sort.next(),
+                    other => SortOrder::from_str(other, true)
+                        .map_err(|_| anyhow!(tr!("unknown order '{}'; use due, priority, created or text", other)))?,
+                };
+                self.change_view(|view| view.sort = sort)?;
+            }
+            Action::Copy => self.copy_selected(false),
+            Action::CopyDetails => self.copy_selected(true),
+            Action::Settings => self.open_settings(),
+            Action::Help => self.current_view = CurrentView::Help,
+            Action::Quit => {}
         }
+        Ok(())
     }
-    
-    fn update_reminder(&mut self) -> Result<()> {
-        // Validate fields
-        if self.new_reminder_text.is_empty() {
-            self.error_message = Some("Reminder text cannot be empty".to_string());
-            return Ok(());
+
+    fn open_settings(&mut self) {
+        self.settings = Settings::load(&self.config.notifications);
+        self.sidebar_focused = false;
+        self.current_view = CurrentView::Settings;
+    }
+
+    // A popup like a reminder's, with the configured sound
+    fn test_notification(&mut self) -> Result<()> {
+        let storage = Storage::with_path(self.storage.path().to_path_buf())?;
+        Notifier::new(storage, self.config.clone()).announce(&tr!("RemindMe test"), &tr!("This is how your reminders will show up"));
+        self.redraw = true;
+        self.toasts.info(tr!("Test notification sent"));
+        Ok(())
+    }
+
+    fn play_selected_sound(&mut self) {
+        let Some(path) = self.settings.selected() else {
+            return;
+        };
+        if let Err(e) = self.sounds.play(path, 1) {
+            self.toasts.error(tr!("Couldn't play {}: {}", path, e));
         }
-        
-        if self.new_reminder_time.is_empty() {
-            self.error_message = Some("Time must be specified (HH:MM)".to_string());
-            return Ok(());
+    }
+
+    // Use the highlighted sound from now on, saved to config.toml; a config that can't be written
+    // is reported like a sound that can't be played, and the TUI carries on
+    fn choose_sound(&mut self) {
+        let Some(path) = self.settings.selected().map(str::to_string) else {
+            return;
+        };
+        if let Err(e) = Config::set_value("notifications", "sound_file", path.as_str()) {
+            self.toasts.error(tr!("Couldn't set the sound: {}", format!("{:#}", e)));
+            return;
         }
-        
-        let date_option = if self.new_reminder_date.is_empty() {
-            None
-        } else {
-            Some(self.new_reminder_date.as_str())
+        self.toasts.success(tr!("Sound set to {}", path));
+        if std::env::var_os("REMINDME_SOUND").is_some() {
+            self.toasts.info(tr!("REMINDME_SOUND is set and still overrides it"));
+        }
+        self.config.notifications.sound_file = Some(path);
+    }
+
+    // Copy the selected reminder's text, or everything about it with `details`
+    fn copy_selected(&mut self, details: bool) {
+        let Some(reminder) = self.reminders.get(self.selected_index) else {
+            return;
         };
-        
-        match cli::parse_datetime_with_default_date(&self.new_reminder_time, date_option) {
-            Ok(due_time) => {
-                // Parse recurrence
-                let recurrence_type = match cli::parse_recurrence(&self.new_reminder_recurrence) {
-                    Ok(rec) => rec,
-                    Err(e) => {
-                        self.error_message = Some(format!("Invalid recurrence: {}", e));
-                        return Ok(());
-                    }
-                };
-                
-                if let Some(id) = &self.editing_reminder_id {
-                    // Create updated reminder
-                    let updated_reminder = Reminder::new_with_id(
-                        id.clone(),
-                        self.new_reminder_text.clone(),
-                        due_time,
-                        recurrence_type
-                    );
-                    
-                    // Update in storage
-                    self.storage.update_reminder(updated_reminder)?;
-                    
-                    // Clear form and editing state
-                    self.new_reminder_text.clear();
-                    self.new_reminder_time.clear();
-                    self.new_reminder_date.clear();
-                    self.new_reminder_recurrence = "none".to_string();
-                    self.editing_reminder_id = None;
-                    self.error_message = None;
-                    
-                    // Return to list view
-                    self.current_view = CurrentView::List;
-                    self.input_mode = InputMode::Normal;
-                    self.refresh_reminders()?;
+        let text = if details { pick::details(reminder, &self.config) } else { reminder.text.clone() };
+        match self.clipboard().and_then(|clipboard| Ok(clipboard.set_text(text)?)) {
+            Ok(()) if details => self.toasts.success(tr!("Copied the reminder's details")),
+            Ok(()) => self.toasts.success(tr!("Copied the reminder's text")),
+            Err(e) => self.toasts.error(tr!("Couldn't copy: {}", e)),
+        }
+    }
+
+    // Paste into the active field of the form, or into the text from the button; the fields are
+    // single lines, so line breaks become spaces
+    fn paste(&mut self) {
+        match self.clipboard().and_then(|clipboard| Ok(clipboard.get_text()?)) {
+            Ok(text) => {
+                let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                match self.form.active_value_mut() {
+                    Some(value) => value.push_str(&line),
+                    None => self.form.text.push_str(&line),
                 }
-                
-                Ok(())
-            },
-            Err(e) => {
-                self.error_message = Some(format!("Invalid date/time: {}", e));
-                Ok(())
             }
+            Err(e) => self.toasts.error(tr!("Couldn't paste: {}", e)),
         }
     }
-    
-    fn refresh_reminders(&mut self) -> Result<()> {
-        self.reminders = self.storage.load()?;
+
+    fn clipboard(&mut self) -> Result<&mut arboard::Clipboard> {
+        let clipboard = match self.clipboard.take() {
+            Some(clipboard) => clipboard,
+            None => arboard::Clipboard::new()?,
+        };
+        Ok(self.clipboard.insert(clipboard))
+    }
+
+    fn start_editing_tags(&mut self) {
+        let Some(reminder) = self.reminders.get(self.selected_index) else {
+            return;
+        };
+        self.input = reminder.tags.join(" ");
+        if !self.input.is_empty() {
+            self.input.push(' ');
+        }
+        self.input_mode = InputMode::Tags;
+    }
+
+    // Existing tags that complete the word being typed, leaving out ones already entered
+    fn tag_suggestions(&self) -> Vec<&str> {
+        let mut entered: Vec<&str> = self.input.split_whitespace().map(|t| t.trim_start_matches('#')).collect();
+        let typed = if self.input.ends_with(char::is_whitespace) { "" } else { entered.pop().unwrap_or("") };
+        let prefix = typed.to_lowercase();
+        self.tags
+            .iter()
+            .map(|(tag, _)| tag.as_str())
+            .filter(|tag| tag.to_lowercase().starts_with(&prefix) && !entered.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+            .collect()
+    }
+
+    // Replace the word being typed with the first suggestion
+    fn complete_tag(&mut self) {
+        let Some(tag) = self.tag_suggestions().first().map(|tag| tag.to_string()) else {
+            return;
+        };
+        if !self.input.ends_with(char::is_whitespace) {
+            let start = self.input.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+            self.input.truncate(start);
+        }
+        self.input.push_str(&tag);
+        self.input.push(' ');
+    }
+
+    fn save_tags(&mut self) -> Result<()> {
+        if let Some(mut reminder) = self.reminders.get(self.selected_index).cloned() {
+            reminder.tags.clear();
+            for tag in self.input.split_whitespace() {
+                reminder.add_tag(tag);
+            }
+            let id = reminder.id.clone();
+            self.storage.update_reminder(reminder)?;
+            self.flush()?;
+            self.refresh_reminders()?;
+            // Keep the reminder selected, unless the tags now leave it out of the filtered list
+            self.selected_index = self.reminders.iter().position(|r| r.id == id).unwrap_or(0);
+        }
+        self.input.clear();
+        self.input_mode = InputMode::Normal;
         Ok(())
     }
 
@@ -232,22 +550,63 @@ impl App {
         self.editing_reminder_id = Some(reminder.id.clone());
         
         // Populate form fields with the reminder's data
-        self.new_reminder_text = reminder.text.clone();
-        self.new_reminder_time = reminder.due_time.format("%H:%M").to_string();
-        self.new_reminder_date = reminder.due_time.format("%Y-%m-%d").to_string();
-        self.new_reminder_recurrence = format!("{:?}", reminder.recurrence).to_lowercase();
+        self.form = Form {
+            text: reminder.text.clone(),
+            time: reminder.due_time.format("%H:%M").to_string(),
+            date: reminder.due_time.format("%Y-%m-%d").to_string(),
+            recurrence: reminder.recurrence.to_string(),
+            active: Field::Text,
+        };
         
         // Set the view and mode
         self.current_view = CurrentView::Edit;
         self.input_mode = InputMode::Editing;
-        self.active_field = ActiveField::Text;
-        self.error_message = None;
         
         Ok(())
     }
 }
 
-pub fn start_tui(storage: Storage) -> Result<()> {
+fn is_overdue(reminder: &Reminder, now: DateTime<Local>) -> bool {
+    !reminder.completed && reminder.due_time <= now
+}
+
+// Width of the countdown column, enough for "12d overdue"
+const COUNTDOWN_WIDTH: usize = 11;
+
+// Time left until the reminder is due ("in 12m") or how late it is ("3h overdue"), in the largest
+// whole unit, colored by how close it is; nothing for completed reminders
+fn countdown(reminder: &Reminder, now: DateTime<Local>, soon_minutes: i64) -> (String, Style) {
+    if reminder.completed {
+        return (String::new(), Style::default());
+    }
+    let minutes = (reminder.due_time - now).num_minutes();
+    let amount = match minutes.abs() {
+        m if m < 60 => format!("{}m", m),
+        m if m < 48 * 60 => format!("{}h", m / 60),
+        m => format!("{}d", m / (24 * 60)),
+    };
+    if reminder.due_time <= now {
+        (format!("{} overdue", amount), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+    } else if minutes < 1 {
+        ("now".to_string(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+    } else if minutes < soon_minutes {
+        (format!("in {}", amount), Style::default().fg(Color::Yellow))
+    } else {
+        (format!("in {}", amount), Style::default().fg(Color::DarkGray))
+    }
+}
+
+// Tags in alphabetical order with the number of reminders carrying each; tags differing only in
+// case count as one
+fn tag_counts(reminders: &[Reminder]) -> Vec<(String, usize)> {
+    let mut counts: BTreeMap<String, (String, usize)> = BTreeMap::new();
+    for tag in reminders.iter().flat_map(|r| &r.tags) {
+        counts.entry(tag.to_lowercase()).or_insert_with(|| (tag.clone(), 0)).1 += 1;
+    }
+    counts.into_values().collect()
+}
+
+pub fn start_tui(storage: Storage, config: &Config, focus: Option<&str>) -> Result<()> {
     // Set up terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -259,7 +618,10 @@ pub fn start_tui(storage: Storage) -> Result<()> {
     terminal.show_cursor()?;
 
     // Create app state
-    let mut app = App::new(storage)?;
+    let mut app = App::new(storage, config)?;
+    if let Some(pos) = focus.and_then(|id| app.reminders.iter().position(|r| r.id == id)) {
+        app.selected_index = pos;
+    }
     
     // Run the main loop
     let result = run_app(&mut terminal, &mut app);
@@ -279,9 +641,13 @@ pub fn start_tui(storage: Storage) -> Result<()> {
 fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
     loop {
         // First determine if cursor should be visible
-        let show_cursor = app.input_mode == InputMode::Editing && app.current_view == CurrentView::Add;
+        let show_cursor = (app.input_mode == InputMode::Editing && app.form.active != Field::Submit)
+            || matches!(app.input_mode, InputMode::Tags | InputMode::Palette);
         
         // Then draw the UI
+        if std::mem::take(&mut app.redraw) {
+            terminal.clear()?;
+        }
         terminal.draw(|f| ui(f, app))?;
         
         // Update cursor visibility after drawing
@@ -291,20 +657,31 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()>
             terminal.hide_cursor()?;
         }
 
+        // Wake up now and then without a key press, so toasts go away and countdowns tick on time
+        app.toasts.expire();
+        if !event::poll(Duration::from_millis(250))? {
+            continue;
+        }
         if let Event::Key(key) = event::read()? {
             match app.input_mode {
                 InputMode::Normal => match key.code {
                     KeyCode::Char('q') => return Ok(()),
-                    KeyCode::Char('a') => {
-                        app.current_view = CurrentView::Add;
-                        app.input_mode = InputMode::Editing;
-                        app.active_field = ActiveField::Text;
-                        app.error_message = None;
-                    },
-                    KeyCode::Char('e') => {
-                        if app.current_view == CurrentView::List && !app.reminders.is_empty() {
-                            app.start_editing_selected_reminder()?;
-                        }
+                    KeyCode::Char('a') => app.start_adding(),
+                    KeyCode::Char(',') => app.open_settings(),
+                    // The settings view's own keys
+                    KeyCode::Up if app.current_view == CurrentView::Settings => app.settings.up(),
+                    KeyCode::Down if app.current_view == CurrentView::Settings => app.settings.down(),
+                    KeyCode::Char('p') if app.current_view == CurrentView::Settings => app.play_selected_sound(),
+                    KeyCode::Char('n') if app.current_view == CurrentView::Settings => app.test_notification()?,
+                    KeyCode::Enter if app.current_view == CurrentView::Settings => app.choose_sound(),
+                    KeyCode::Esc if app.current_view == CurrentView::Settings => app.current_view = CurrentView::List,
+                    KeyCode::Char(':') => {
+                        app.input.clear();
+                        app.palette_index = 0;
+                        app.input_mode = InputMode::Palette;
+                    },
+                    KeyCode::Char('e') if app.current_view == CurrentView::List && !app.reminders.is_empty() => {
+                        app.start_editing_selected_reminder()?;
                     },
                     // Other normal mode handlers remain the same
                     KeyCode::Char('h') => {
@@ -314,97 +691,133 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()>
                         app.current_view = CurrentView::List;
                         app.refresh_reminders()?;
                     },
-                    KeyCode::Char('d') => {
-                        if app.current_view == CurrentView::List && !app.reminders.is_empty() {
-                            let reminder = &app.reminders[app.selected_index];
-                            app.storage.delete_reminder(&reminder.id)?;
-                            app.refresh_reminders()?;
-                            if app.selected_index >= app.reminders.len() && !app.reminders.is_empty() {
-                                app.selected_index = app.reminders.len() - 1;
-                            }
-                        }
+                    KeyCode::Char('s') if app.current_view == CurrentView::List => {
+                        app.change_view(View::next_layout)?;
                     },
-                    KeyCode::Up => {
-                        if app.selected_index > 0 {
-                            app.selected_index -= 1;
-                        }
+                    KeyCode::Char('o') if app.current_view == CurrentView::List => {
+                        app.change_view(|view| view.sort = view.sort.next())?;
                     },
-                    KeyCode::Down => {
-                        if !app.reminders.is_empty() && app.selected_index < app.reminders.len() - 1 {
-                            app.selected_index += 1;
-                        }
+                    KeyCode::Char('+') | KeyCode::Char('=') if app.current_view == CurrentView::List => {
+                        app.resize_top_pane(1);
+                    },
+                    KeyCode::Char('-') if app.current_view == CurrentView::List => {
+                        app.resize_top_pane(-1);
+                    },
+                    KeyCode::Tab if app.current_view == CurrentView::List && app.view.layout == TuiLayout::Split => {
+                        app.switch_pane();
+                    },
+                    KeyCode::Char('y') if app.current_view == CurrentView::List => {
+                        app.copy_selected(false);
+                    },
+                    KeyCode::Char('Y') if app.current_view == CurrentView::List => {
+                        app.copy_selected(true);
+                    },
+                    KeyCode::Char('t') if app.current_view == CurrentView::List && !app.reminders.is_empty() => {
+                        app.start_editing_tags();
+                    },
+                    // The tag sidebar: Left to go into it, Enter to filter by the highlighted tag
+                    KeyCode::Left if app.current_view == CurrentView::List && !app.tags.is_empty() => {
+                        app.sidebar_focused = true;
+                    },
+                    KeyCode::Right | KeyCode::Esc if app.sidebar_focused => {
+                        app.sidebar_focused = false;
+                    },
+                    KeyCode::Up if app.sidebar_focused => {
+                        app.tag_index = app.tag_index.saturating_sub(1);
+                    },
+                    KeyCode::Down if app.sidebar_focused => {
+                        app.tag_index = (app.tag_index + 1).min(app.tags.len());
+                    },
+                    KeyCode::Enter if app.sidebar_focused => {
+                        app.apply_tag_filter()?;
+                    },
+                    KeyCode::Enter if app.current_view == CurrentView::List && app.total == 0 => app.start_adding(),
+                    KeyCode::Char('d') if app.current_view == CurrentView::List && !app.reminders.is_empty() => {
+                        app.delete_selected()?;
+                    },
+                    KeyCode::Char('u') if app.current_view == CurrentView::List => {
+                        app.undo_delete()?;
+                    },
+                    KeyCode::Up if app.selected_index > 0 => {
+                        app.selected_index -= 1;
+                    },
+                    KeyCode::Down if !app.reminders.is_empty() && app.selected_index < app.reminders.len() - 1 => {
+                        app.selected_index += 1;
                     },
                     _ => {}
                 },
-                InputMode::Editing => match key.code {
+                // `q` and every other letter are typed here; Esc leaves the palette
+                InputMode::Palette => match key.code {
                     KeyCode::Esc => {
+                        app.input.clear();
                         app.input_mode = InputMode::Normal;
-                        app.current_view = CurrentView::List;
-                        app.error_message = None;
                     },
                     KeyCode::Enter => {
-                        match app.current_view {
-                            CurrentView::Add => {
-                                match app.active_field {
-                                    ActiveField::Text => app.active_field = ActiveField::Time,
-                                    ActiveField::Time => app.active_field = ActiveField::Date,
-                                    ActiveField::Date => app.active_field = ActiveField::Recurrence,
-                                    ActiveField::Recurrence => app.active_field = ActiveField::Submit,
-                                    ActiveField::Submit => app.create_reminder()?,
-                                }
-                            },
-                            CurrentView::Edit => {
-                                match app.active_field {
-                                    ActiveField::Text => app.active_field = ActiveField::Time,
-                                    ActiveField::Time => app.active_field = ActiveField::Date,
-                                    ActiveField::Date => app.active_field = ActiveField::Recurrence,
-                                    ActiveField::Recurrence => app.active_field = ActiveField::Submit,
-                                    ActiveField::Submit => app.update_reminder()?,
-                                }
-                            },
-                            _ => {}
+                        let quit = app.run_palette()?;
+                        if quit {
+                            return Ok(());
                         }
                     },
+                    // Complete the command name, leaving room for an argument
                     KeyCode::Tab => {
-                        // Cycle through fields in the add form
-                        if app.current_view == CurrentView::Add {
-                            match app.active_field {
-                                ActiveField::Text => app.active_field = ActiveField::Time,
-                                ActiveField::Time => app.active_field = ActiveField::Date,
-                                ActiveField::Date => {
-                                    app.active_field = ActiveField::Recurrence;
-                                    // If the recurrence field is empty, initialize it with the default
-                                    if app.new_reminder_recurrence.is_empty() {
-                                        app.new_reminder_recurrence = String::from("none");
-                                    }
-                                },
-                                ActiveField::Recurrence => app.active_field = ActiveField::Submit,
-                                ActiveField::Submit => app.active_field = ActiveField::Text,
-                            }
+                        if let Some(command) = palette::matches(&app.input).get(app.palette_index) {
+                            app.input = format!("{} ", command.name);
+                            app.palette_index = 0;
                         }
                     },
-                    KeyCode::BackTab => {
-                        // Cycle backwards through fields in the add form
-                        if app.current_view == CurrentView::Add {
-                            match app.active_field {
-                                ActiveField::Text => app.active_field = ActiveField::Submit,
-                                ActiveField::Time => app.active_field = ActiveField::Text,
-                                ActiveField::Date => app.active_field = ActiveField::Time,
-                                ActiveField::Recurrence => app.active_field = ActiveField::Date,
-                                ActiveField::Submit => app.active_field = ActiveField::Recurrence,
-                            }
+                    KeyCode::Up => app.palette_index = app.palette_index.saturating_sub(1),
+                    KeyCode::Down => {
+                        app.palette_index = (app.palette_index + 1).min(palette::matches(&app.input).len().saturating_sub(1));
+                    },
+                    KeyCode::Char(c) => {
+                        app.input.push(c);
+                        app.palette_index = 0;
+                    },
+                    KeyCode::Backspace => {
+                        app.input.pop();
+                        app.palette_index = 0;
+                    },
+                    _ => {},
+                },
+                InputMode::Tags => match key.code {
+                    KeyCode::Esc => {
+                        app.input.clear();
+                        app.input_mode = InputMode::Normal;
+                    },
+                    KeyCode::Enter => app.save_tags()?,
+                    KeyCode::Tab => app.complete_tag(),
+                    KeyCode::Char(c) => app.input.push(c),
+                    KeyCode::Backspace => {
+                        app.input.pop();
+                    },
+                    _ => {},
+                },
+                InputMode::Editing => match key.code {
+                    KeyCode::Esc => {
+                        app.input_mode = InputMode::Normal;
+                        app.current_view = CurrentView::List;
+                    },
+                    KeyCode::Enter if app.form.active == Field::Submit => app.submit_form()?,
+                    KeyCode::Enter | KeyCode::Tab => app.form.active = app.form.active.next(),
+                    KeyCode::BackTab => app.form.active = app.form.active.previous(),
+                    KeyCode::Up | KeyCode::Down if matches!(app.form.active, Field::Time | Field::Date) => {
+                        let big = key.modifiers.contains(KeyModifiers::SHIFT);
+                        if let Err(e) = app.form.step(key.code == KeyCode::Up, big, app.config.input.date_order) {
+                            app.toasts.error(e.to_string());
                         }
                     },
+                    // In a field every other key is typed, so pasting there takes Ctrl+V; on the button
+                    // nothing is typed and `p` pastes into the text
+                    KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::CONTROL) => app.paste(),
+                    KeyCode::Char('p') if app.form.active == Field::Submit => app.paste(),
                     KeyCode::Char(c) => {
-                        if app.current_view == CurrentView::Add && app.active_field != ActiveField::Submit {
-                            let input = app.get_active_input_mut();
-                            input.push(c);
+                        if let Some(value) = app.form.active_value_mut() {
+                            value.push(c);
                         }
                     },
                     KeyCode::Backspace => {
-                        if app.current_view == CurrentView::Add && app.active_field != ActiveField::Submit {
-                            let input = app.get_active_input_mut();
-                            input.pop();
+                        if let Some(value) = app.form.active_value_mut() {
+                            value.pop();
                         }
                     },
                     _ => {},
@@ -427,7 +840,7 @@ fn ui(f: &mut Frame, app: &App) {
         .split(f.area());
 
     // Create the title bar
-    let title = Paragraph::new("RemindMe - TUI")
+    let title = Paragraph::new(tr!("RemindMe - TUI"))
         .style(Style::default().fg(Color::White))
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(title, chunks[0]);
@@ -435,428 +848,405 @@ fn ui(f: &mut Frame, app: &App) {
     // Render the appropriate view
     match app.current_view {
         CurrentView::List => render_list_view(f, app, chunks[1]),
-        CurrentView::Add => render_add_view(f, app, chunks[1]),
-        CurrentView::Edit => render_edit_view(f, app, chunks[1]),
+        CurrentView::Add | CurrentView::Edit => {
+            let submit = if app.current_view == CurrentView::Add { tr!("Add Reminder") } else { tr!("Update Reminder") };
+            FormWidget { form: &app.form, submit: &submit, typing: app.input_mode == InputMode::Editing }.render(f, chunks[1]);
+        },
+        CurrentView::Settings => app.settings.render(f, chunks[1], &app.config.notifications),
         CurrentView::Help => render_help_view(f, app, chunks[1]),
     }
+    app.toasts.render(f, chunks[1]);
     
     // Create the status bar with updated Text/Span handling
     let status = match app.current_view {
         CurrentView::List => {
             let spans = vec![
-                Span::raw("Press "),
+                Span::raw(tr!("Press ")),
                 Span::styled("q", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to quit, "),
+                Span::raw(tr!(" to quit, ")),
                 Span::styled("a", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to add, "),
+                Span::raw(tr!(" to add, ")),
                 Span::styled("e", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to edit, "),
+                Span::raw(tr!(" to edit, ")),
                 Span::styled("d", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to delete, "),
+                Span::raw(tr!(" to delete, ")),
+                Span::styled("t", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(tr!(" to tag, ")),
+                Span::styled("←", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(tr!(" for tags, ")),
                 Span::styled("h", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" for help"),
+                Span::raw(tr!(" for help")),
             ];
             Text::from(Line::from(spans))
         },
         CurrentView::Add => {
             let spans = vec![
-                Span::raw("Press "),
+                Span::raw(tr!("Press ")),
                 Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw("/"),
                 Span::styled("Shift+Tab", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to move between fields, "),
+                Span::raw(tr!(" to move between fields, ")),
                 Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to submit, "),
+                Span::raw(tr!(" to submit, ")),
                 Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to cancel"),
+                Span::raw(tr!(" to cancel")),
             ];
             Text::from(Line::from(spans))
         },
         CurrentView::Edit => {
             let spans = vec![
-                Span::raw("Press "),
+                Span::raw(tr!("Press ")),
                 Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw("/"),
                 Span::styled("Shift+Tab", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to move between fields, "),
+                Span::raw(tr!(" to move between fields, ")),
                 Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to submit, "),
+                Span::raw(tr!(" to submit, ")),
                 Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to cancel"),
+                Span::raw(tr!(" to cancel")),
             ];
             Text::from(Line::from(spans))
         },
         CurrentView::Help => {
             let spans = vec![
-                Span::raw("Press "),
+                Span::raw(tr!("Press ")),
+                Span::styled("l", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(tr!(" to go back to the list view")),
+            ];
+            Text::from(Line::from(spans))
+        },
+        CurrentView::Settings => {
+            let spans = vec![
+                Span::raw(tr!("Press ")),
+                Span::styled("n", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(tr!(" to test, ")),
+                Span::styled("p", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(tr!(" to play, ")),
+                Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(tr!(" to choose a sound, ")),
+                Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(tr!(" or ")),
                 Span::styled("l", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to go back to the list view"),
+                Span::raw(tr!(" to go back")),
             ];
             Text::from(Line::from(spans))
         },
     };
 
+    // Editing tags takes over the status bar, with the tags that Tab would complete in its title
+    if app.input_mode == InputMode::Tags {
+        let suggestions = app.tag_suggestions();
+        let title = if suggestions.is_empty() {
+            tr!("Tags (space-separated, Enter to save, Esc to cancel)")
+        } else {
+            format!("Tab: {}", suggestions.iter().take(8).map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" "))
+        };
+        let label = tr!("Tags: ");
+        let input = Paragraph::new(format!("{}{}", label, app.input))
+            .block(Block::default().title(title).borders(Borders::ALL));
+        f.render_widget(input, chunks[2]);
+        f.set_cursor_position((chunks[2].x + 1 + (label.chars().count() + app.input.chars().count()) as u16, chunks[2].y + 1));
+        return;
+    }
+
+    if app.input_mode == InputMode::Palette {
+        render_palette(f, app, chunks[1]);
+        let input = Paragraph::new(format!(":{}", app.input))
+            .block(Block::default().title(tr!("Command (Tab completes, Enter runs, Esc cancels)")).borders(Borders::ALL));
+        f.render_widget(input, chunks[2]);
+        f.set_cursor_position((chunks[2].x + 2 + app.input.chars().count() as u16, chunks[2].y + 1));
+        return;
+    }
+
     let status_bar = Paragraph::new(status)
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(status_bar, chunks[2]);
 }
 
-fn render_list_view(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    let items: Vec<_> = app.reminders
+// The commands matching what's typed, over the bottom of the view
+fn render_palette(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let matches = palette::matches(&app.input);
+    let width = matches.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    let items: Vec<_> = matches
         .iter()
         .enumerate()
-        .map(|(i, r)| {
-            let status = if r.completed { "[✓]" } else { "[ ]" };
-            let style = if i == app.selected_index {
+        .map(|(i, command)| {
+            let style = if i == app.palette_index {
                 Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
             };
-            
-            ListItem::new(format!("{} {} - {}", status, r.text, r.due_time.format("%Y-%m-%d %H:%M")))
-                .style(style)
+            let marker = match (utils::accessible(), i == app.palette_index) {
+                (false, _) => "",
+                (true, true) => "> ",
+                (true, false) => "  ",
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{}{:<width$}  ", marker, command.name, width = width), style),
+                Span::raw(command.description),
+            ]))
         })
         .collect();
-
-    let list = List::new(items)
-        .block(Block::default().title("Reminders").borders(Borders::ALL))
-        .highlight_style(Style::default().add_modifier(Modifier::BOLD));
-
-    f.render_widget(list, area);
+    
+    let height = (items.len() as u16 + 2).min(area.height);
+    let popup = ratatui::layout::Rect { y: area.y + area.height - height, height, ..area };
+    let list = List::new(items).block(Block::default().title(tr!("Commands")).borders(Borders::ALL));
+    f.render_widget(Clear, popup);
+    f.render_widget(list, popup);
 }
 
-fn render_add_view(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    // Create a layout for the form
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),  // Text field
-            Constraint::Length(3),  // Time field
-            Constraint::Length(3),  // Date field
-            Constraint::Length(3),  // Recurrence field
-            Constraint::Length(3),  // Submit button
-            Constraint::Min(1),     // Error message area
-        ].as_ref())
-        .split(area);
-    
-    // Render the text field
-    let text_style = if app.active_field == ActiveField::Text {
-        Style::default().fg(Color::Yellow)
+fn render_list_view(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    if app.total == 0 {
+        render_empty_state(f, area);
+        return;
+    }
+    // Tags get a sidebar on the left once there are any
+    let area = if app.tags.is_empty() {
+        area
     } else {
-        Style::default()
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(24), Constraint::Min(0)].as_ref())
+            .split(area);
+        render_tag_sidebar(f, app, columns[0]);
+        columns[1]
     };
     
-    let text_input = Paragraph::new(app.new_reminder_text.as_str())
-        .style(text_style)
-        .block(Block::default()
-            .title("Reminder Text")
-            .borders(Borders::ALL)
-            .border_style(if app.active_field == ActiveField::Text {
-                Style::default().fg(Color::Yellow)
-            } else {
-                Style::default()
-            }));
-    f.render_widget(text_input, chunks[0]);
-    
-    // Render the time field
-    let time_style = if app.active_field == ActiveField::Time {
-        Style::default().fg(Color::Yellow)
-    } else {
-        Style::default()
+    let title = match &app.tag_filter {
+        Some(tag) => tr!("Reminders #{} (by {})", tag, app.view.sort.label()),
+        None => tr!("Reminders (by {})", app.view.sort.label()),
     };
-    
-    let time_input = Paragraph::new(app.new_reminder_time.as_str())
-        .style(time_style)
-        .block(Block::default()
-            .title("Time (HH:MM)")
-            .borders(Borders::ALL)
-            .border_style(if app.active_field == ActiveField::Time {
-                Style::default().fg(Color::Yellow)
-            } else {
-                Style::default()
-            }));
-    f.render_widget(time_input, chunks[1]);
-    
-    // Render the date field
-    let date_style = if app.active_field == ActiveField::Date {
-        Style::default().fg(Color::Yellow)
-    } else {
-        Style::default()
-    };
-
-    let date_text = if app.new_reminder_date.is_empty() {
-        if app.active_field == ActiveField::Date && app.input_mode == InputMode::Editing {
-            // Show empty string when actively editing an empty date field
-            ""
-        } else {
-            // Show placeholder when not editing
-            "(Optional - defaults to today/tomorrow)"
+    let top = Constraint::Percentage(app.view.ratio);
+    match app.view.layout {
+        TuiLayout::List => render_reminders(f, app, area, 0..app.reminders.len(), title, false),
+        // Overdue on top and upcoming below, each scrolling on its own
+        TuiLayout::Split => {
+            let panes = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([top, Constraint::Min(0)].as_ref())
+                .split(area);
+            let focused = app.selected_index < app.overdue_count;
+            render_reminders(f, app, panes[0], 0..app.overdue_count, tr!("Overdue ({})", app.overdue_count), focused);
+            render_reminders(f, app, panes[1], app.overdue_count..app.reminders.len(), tr!("{} - upcoming", title), !focused);
         }
-    } else {
-        app.new_reminder_date.as_str()
-    };
+        TuiLayout::Calendar => {
+            let panes = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([top, Constraint::Min(0)].as_ref())
+                .split(area);
+            render_calendar(f, app, panes[0]);
+            render_reminders(f, app, panes[1], 0..app.reminders.len(), title, false);
+        }
+    }
+}
 
-    let date_input = Paragraph::new(date_text)
-        .style(date_style)
-        .block(Block::default()
-            .title("Date (YYYY-MM-DD)")
-            .borders(Borders::ALL)
-            .border_style(if app.active_field == ActiveField::Date {
-                Style::default().fg(Color::Yellow)
-            } else {
-                Style::default()
-            }));
-    f.render_widget(date_input, chunks[2]);
-    
-    // Render the recurrence field
-    let recurrence_style = if app.active_field == ActiveField::Recurrence {
-        Style::default().fg(Color::Yellow)
-    } else {
-        Style::default()
-    };
-    
-    let recurrence_input = Paragraph::new(app.new_reminder_recurrence.as_str())
-        .style(recurrence_style)
-        .block(Block::default()
-            .title("Recurrence (none/daily/weekly/monthly/yearly)")
-            .borders(Borders::ALL)
-            .border_style(if app.active_field == ActiveField::Recurrence {
-                Style::default().fg(Color::Yellow)
-            } else {
-                Style::default()
-            }));
-    f.render_widget(recurrence_input, chunks[3]);
-    
-    // Render the submit button
-    let submit_style = if app.active_field == ActiveField::Submit {
-        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-    };
-    
-    let submit_button = Paragraph::new("[ Add Reminder ]")
-        .style(submit_style)
+// Shown instead of the list while there are no reminders at all: how to get started
+fn render_empty_state(f: &mut Frame, area: ratatui::layout::Rect) {
+    let key = |k: &'static str| Span::styled(k, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    let lines = vec![
+        Line::from(Span::styled(tr!("No reminders yet"), Style::default().add_modifier(Modifier::BOLD))),
+        Line::raw(""),
+        Line::from(vec![Span::raw(tr!("Press ")), key("Enter"), Span::raw(tr!(" or ")), key("a"), Span::raw(tr!(" to add your first one."))]),
+        Line::raw(""),
+        Line::from(vec![key("a"), Span::raw(tr!("  add a reminder; Tab moves between fields, ↑/↓ change the time and date"))]),
+        Line::from(vec![key("e"), Span::raw(tr!("  edit the selected reminder"))]),
+        Line::from(vec![key("d"), Span::raw(tr!("  delete it, ")), key("u"), Span::raw(tr!(" to undo"))]),
+        Line::from(vec![key("t"), Span::raw(tr!("  tag it"))]),
+        Line::from(vec![key("s"), Span::raw(tr!("  switch layout, ")), key("o"), Span::raw(tr!(" change the order"))]),
+        Line::from(vec![key(":"), Span::raw(tr!("  every command by name"))]),
+        Line::from(vec![key("h"), Span::raw(tr!("  all keys, ")), key("q"), Span::raw(tr!(" quit"))]),
+        Line::raw(""),
+        Line::raw(tr!("Reminders added from the command line (remindme add) show up here too.")),
+    ];
+    let panel = Paragraph::new(lines)
         .alignment(ratatui::layout::Alignment::Center)
-        .block(Block::default()
-            .borders(Borders::ALL)
-            .border_style(if app.active_field == ActiveField::Submit {
-                Style::default().fg(Color::Green)
-            } else {
-                Style::default()
-            }));
-    f.render_widget(submit_button, chunks[4]);
+        .block(Block::default().title(tr!("Welcome to RemindMe")).borders(Borders::ALL));
+    f.render_widget(panel, area);
+}
+
+// The month of the selected reminder: today in reverse, days with pending reminders in bold
+// (red once overdue), and the selected reminder's day in yellow
+fn render_calendar(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let now = Local::now();
+    let today = now.date_naive();
+    let selected = app.reminders.get(app.selected_index).map(|r| r.due_time.date_naive());
+    let shown = selected.unwrap_or(today);
+    let first = shown.with_day(1).unwrap_or(shown);
+    let start = utils::start_of_week(first);
     
-    // Render error message if any
-    if let Some(error) = &app.error_message {
-        let error_msg = Paragraph::new(error.as_str())
-            .style(Style::default().fg(Color::Red))
-            .block(Block::default()
-                .borders(Borders::NONE));
-        f.render_widget(error_msg, chunks[5]);
-    }
+    let mut lines = vec![Line::from(Span::styled(utils::format_date(first, "%B %Y"), Style::default().add_modifier(Modifier::BOLD)))];
+    let weekdays: Vec<Span> = (0..7)
+        .map(|i| {
+            let name: String = utils::format_date(start + chrono::Duration::days(i), "%a").chars().take(2).collect();
+            Span::raw(format!("{:>3}", name))
+        })
+        .collect();
+    lines.push(Line::from(weekdays));
     
-    // Set the cursor to the active field's end of text
-    if app.active_field != ActiveField::Submit && app.input_mode == InputMode::Editing {
-        let input = match app.active_field {
-            ActiveField::Text => &app.new_reminder_text,
-            ActiveField::Time => &app.new_reminder_time,
-            ActiveField::Date => {
-                if app.new_reminder_date.is_empty() { &app.input } else { &app.new_reminder_date }
-            },
-            ActiveField::Recurrence => &app.new_reminder_recurrence,
-            _ => &app.input,
-        };
-        
-        // Add 1 to x position to account for left border, and cursor inside the field
-        let cursor_position = match app.active_field {
-            ActiveField::Text => chunks[0].x + input.len() as u16 + 1,
-            ActiveField::Time => chunks[1].x + input.len() as u16 + 1,
-            ActiveField::Date => {
-                if app.new_reminder_date.is_empty() {
-                    // Position at start of input field for empty date
-                    chunks[2].x + 1
-                } else {
-                    chunks[2].x + app.new_reminder_date.len() as u16 + 1
+    let mut week_start = start;
+    while week_start.month() == first.month() || week_start < first {
+        let days: Vec<Span> = (0..7)
+            .map(|i| {
+                let day = week_start + chrono::Duration::days(i);
+                if day.month() != first.month() {
+                    return Span::raw("   ");
                 }
-            },
-            ActiveField::Recurrence => chunks[3].x + input.len() as u16 + 1,
-            _ => 0,
-        };
-        
-        // Add 1 to y position to account for top border and title
-        let cursor_y = match app.active_field {
-            ActiveField::Text => chunks[0].y + 1,
-            ActiveField::Time => chunks[1].y + 1,
-            ActiveField::Date => chunks[2].y + 1,
-            ActiveField::Recurrence => chunks[3].y + 1,
-            _ => 0,
-        };
-        
-        f.set_cursor_position((cursor_position, cursor_y));
+                let pending: Vec<&Reminder> = app.reminders.iter().filter(|r| !r.completed && r.due_time.date_naive() == day).collect();
+                let mut style = Style::default();
+                if !pending.is_empty() {
+                    style = style.add_modifier(Modifier::BOLD);
+                    if pending.iter().any(|r| is_overdue(r, now)) {
+                        style = style.fg(Color::Red);
+                    }
+                }
+                if Some(day) == selected {
+                    style = style.fg(Color::Yellow);
+                }
+                if day == today {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                // Spelled out for screen readers: > selected, ! overdue, * something due
+                if utils::accessible() {
+                    let mark = if Some(day) == selected {
+                        '>'
+                    } else if pending.iter().any(|r| is_overdue(r, now)) {
+                        '!'
+                    } else if !pending.is_empty() {
+                        '*'
+                    } else {
+                        ' '
+                    };
+                    return Span::styled(format!("{}{:>2}", mark, day.day()), style);
+                }
+                Span::styled(format!("{:>3}", day.day()), style)
+            })
+            .collect();
+        lines.push(Line::from(days));
+        week_start += chrono::Duration::days(7);
     }
+    
+    let calendar = Paragraph::new(lines).block(Block::default().title(tr!("Calendar")).borders(Borders::ALL));
+    f.render_widget(calendar, area);
 }
 
-fn render_edit_view(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    // Create a layout for the form
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),  // Text field
-            Constraint::Length(3),  // Time field
-            Constraint::Length(3),  // Date field
-            Constraint::Length(3),  // Recurrence field
-            Constraint::Length(3),  // Submit button
-            Constraint::Min(1),     // Error message area
-        ].as_ref())
-        .split(area);
-    
-    // Render the text field
-    let text_style = if app.active_field == ActiveField::Text {
-        Style::default().fg(Color::Yellow)
-    } else {
-        Style::default()
-    };
-    
-    let text_input = Paragraph::new(app.new_reminder_text.as_str())
-        .style(text_style)
-        .block(Block::default()
-            .title("Reminder Text")
-            .borders(Borders::ALL)
-            .border_style(if app.active_field == ActiveField::Text {
-                Style::default().fg(Color::Yellow)
-            } else {
-                Style::default()
-            }));
-    f.render_widget(text_input, chunks[0]);
-    
-    // Render the time field
-    let time_style = if app.active_field == ActiveField::Time {
-        Style::default().fg(Color::Yellow)
-    } else {
-        Style::default()
-    };
-    
-    let time_input = Paragraph::new(app.new_reminder_time.as_str())
-        .style(time_style)
-        .block(Block::default()
-            .title("Time (HH:MM)")
-            .borders(Borders::ALL)
-            .border_style(if app.active_field == ActiveField::Time {
-                Style::default().fg(Color::Yellow)
-            } else {
-                Style::default()
-            }));
-    f.render_widget(time_input, chunks[1]);
-    
-    // Render the date field
-    let date_style = if app.active_field == ActiveField::Date {
-        Style::default().fg(Color::Yellow)
-    } else {
-        Style::default()
-    };
-    
-    let date_input = Paragraph::new(app.new_reminder_date.as_str())
-        .style(date_style)
-        .block(Block::default()
-            .title("Date (YYYY-MM-DD)")
-            .borders(Borders::ALL)
-            .border_style(if app.active_field == ActiveField::Date {
-                Style::default().fg(Color::Yellow)
-            } else {
-                Style::default()
-            }));
-    f.render_widget(date_input, chunks[2]);
-    
-    // Render the recurrence field
-    let recurrence_style = if app.active_field == ActiveField::Recurrence {
-        Style::default().fg(Color::Yellow)
-    } else {
-        Style::default()
-    };
-    
-    let recurrence_input = Paragraph::new(app.new_reminder_recurrence.as_str())
-        .style(recurrence_style)
-        .block(Block::default()
-            .title("Recurrence (none/daily/weekly/monthly/yearly)")
-            .borders(Borders::ALL)
-            .border_style(if app.active_field == ActiveField::Recurrence {
-                Style::default().fg(Color::Yellow)
-            } else {
-                Style::default()
-            }));
-    f.render_widget(recurrence_input, chunks[3]);
-    
-    // Render the submit button
-    let submit_style = if app.active_field == ActiveField::Submit {
-        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-    };
-    
-    let submit_button = Paragraph::new("[ Update Reminder ]")
-        .style(submit_style)
-        .alignment(ratatui::layout::Alignment::Center)
-        .block(Block::default()
-            .borders(Borders::ALL)
-            .border_style(if app.active_field == ActiveField::Submit {
-                Style::default().fg(Color::Green)
+// The reminders in `range` as a list that scrolls to keep the selected one in view
+fn render_reminders(f: &mut Frame, app: &App, area: ratatui::layout::Rect, range: std::ops::Range<usize>, title: String, focused: bool) {
+    let start = range.start;
+    let selected = range.contains(&app.selected_index).then(|| app.selected_index - start);
+    // Taken on every draw, so the countdowns move along with the event loop's tick
+    let now = Local::now();
+    let accessible = utils::accessible();
+    let items: Vec<_> = app.reminders[range]
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            let status = match (r.completed, accessible) {
+                (true, false) => "[✓]".to_string(),
+                (false, false) => "[ ]".to_string(),
+                (true, true) => tr!("done"),
+                (false, true) => tr!("todo"),
+            };
+            let style = if start + i == app.selected_index {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
-            }));
-    f.render_widget(submit_button, chunks[4]);
-    
-    // Render error message if any
-    if let Some(error) = &app.error_message {
-        let error_msg = Paragraph::new(error.as_str())
-            .style(Style::default().fg(Color::Red))
-            .block(Block::default()
-                .borders(Borders::NONE));
-        f.render_widget(error_msg, chunks[5]);
-    }
+            };
+            
+            let (left, left_style) = countdown(r, now, app.config.tui.soon_minutes);
+            let mut spans = vec![
+                Span::raw(format!("{} ", status)),
+                Span::styled(format!("{:>width$} ", left, width = COUNTDOWN_WIDTH), left_style),
+            ];
+            if r.pinned.is_some() {
+                spans.push(Span::raw(if accessible { format!("{} ", tr!("pinned:")) } else { "📌 ".to_string() }));
+            }
+            if let Some(emoji) = r.emoji() {
+                spans.push(Span::raw(format!("{} ", emoji)));
+            }
+            spans.push(Span::raw(r.text.clone()));
+            for tag in &r.tags {
+                let tag_style = match colors::tag_color(&app.config.display, tag) {
+                    Some(color) => Style::default().fg(color),
+                    None => Style::default(),
+                };
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(format!("#{}", tag), tag_style));
+            }
+            spans.push(Span::raw(format!(" - {}", utils::format_datetime(&r.due_time))));
+            
+            ListItem::new(Line::from(spans)).style(style)
+        })
+        .collect();
+
+    let border = if focused { Style::default().fg(Color::Yellow) } else { Style::default() };
+    // The yellow border and selection need words and a marker when they can't be seen
+    let title = if accessible && focused { tr!("{} (focused)", title) } else { title };
+    let list = List::new(items)
+        .block(Block::default().title(title).borders(Borders::ALL).border_style(border))
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_symbol(if accessible { "> " } else { "" });
+
+    let mut state = ListState::default().with_selected(selected);
+    f.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_tag_sidebar(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let entries = std::iter::once((tr!("All"), app.total, None))
+        .chain(app.tags.iter().map(|(tag, count)| (format!("#{}", tag), *count, Some(tag))));
+    let items: Vec<_> = entries
+        .enumerate()
+        .map(|(i, (label, count, tag))| {
+            let active = tag.map(|t| t.as_str()) == app.tag_filter.as_deref();
+            let mut style = match tag.and_then(|t| colors::tag_color(&app.config.display, t)) {
+                Some(color) => Style::default().fg(color),
+                None => Style::default(),
+            };
+            if active {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            if app.sidebar_focused && i == app.tag_index {
+                style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+            }
+            let marker = match (active, app.sidebar_focused && i == app.tag_index && utils::accessible()) {
+                (_, true) => "> ",
+                (true, false) => "▸ ",
+                (false, false) => "  ",
+            };
+            ListItem::new(Line::from(Span::raw(format!("{}{} ({})", marker, label, count)))).style(style)
+        })
+        .collect();
     
-    // Set the cursor position
-    if app.active_field != ActiveField::Submit && app.input_mode == InputMode::Editing {
-        let input = match app.active_field {
-            ActiveField::Text => &app.new_reminder_text,
-            ActiveField::Time => &app.new_reminder_time,
-            ActiveField::Date => &app.new_reminder_date,
-            ActiveField::Recurrence => &app.new_reminder_recurrence,
-            _ => &app.input,
-        };
-        
-        let cursor_position = match app.active_field {
-            ActiveField::Text => chunks[0].x + input.len() as u16 + 1,
-            ActiveField::Time => chunks[1].x + input.len() as u16 + 1,
-            ActiveField::Date => chunks[2].x + input.len() as u16 + 1,
-            ActiveField::Recurrence => chunks[3].x + input.len() as u16 + 1,
-            _ => 0,
-        };
-        
-        let cursor_y = match app.active_field {
-            ActiveField::Text => chunks[0].y + 1,
-            ActiveField::Time => chunks[1].y + 1,
-            ActiveField::Date => chunks[2].y + 1,
-            ActiveField::Recurrence => chunks[3].y + 1,
-            _ => 0,
-        };
-        
-        f.set_cursor_position((cursor_position, cursor_y));
-    }
+    let border = if app.sidebar_focused { Style::default().fg(Color::Yellow) } else { Style::default() };
+    let list = List::new(items).block(Block::default().title(tr!("Tags")).borders(Borders::ALL).border_style(border));
+    f.render_widget(list, area);
 }
 
 fn render_help_view(f: &mut Frame, _app: &App, area: ratatui::layout::Rect) {
-    let help_text = Text::from(
-        "HELP\n\n\
-         q - Quit\n\
-         a - Add new reminder\n\
-         d - Delete selected reminder\n\
-         h - Show this help\n\
-         l - Show reminder list\n\
-         ↑/↓ - Navigate through reminders"
-    );
-
-    let text = Paragraph::new(help_text)
-        .block(Block::default().title("Help").borders(Borders::ALL));
+    let keys = [
+        ("q", tr!("Quit")),
+        (":", tr!("Command palette: every action by name, e.g. :snooze 30m or :filter work")),
+        ("a", tr!("Add new reminder")),
+        ("e", tr!("Edit the selected reminder")),
+        ("d", tr!("Delete selected reminder")),
+        ("u", tr!("Undo the last delete")),
+        ("t", tr!("Edit the selected reminder's tags (Tab completes)")),
+        ("←/→", tr!("Move into/out of the tag sidebar; Enter filters by a tag")),
+        ("s", tr!("Switch layout: list, overdue/upcoming panes (Tab switches panes), calendar")),
+        ("+/-", tr!("Make the top pane bigger/smaller")),
+        ("o", tr!("Sort by due time, priority, created or text")),
+        ("y/Y", tr!("Copy the selected reminder's text/details")),
+        ("Ctrl+V", tr!("Paste into a field of the add/edit form (p on its button pastes into the text)")),
+        ("↑/↓", tr!("In the form's time/date: 5 minutes/a day later or earlier; with Shift an hour/a week")),
+        (",", tr!("Settings: send a test notification, play and choose its sound")),
+        ("h", tr!("Show this help")),
+        ("l", tr!("Show reminder list")),
+        ("↑/↓", tr!("Navigate through reminders")),
+    ];
+    let mut lines = vec![Line::raw(tr!("HELP")), Line::raw("")];
+    lines.extend(keys.iter().map(|(key, action)| Line::raw(format!("{} - {}", key, action))));
+
+    let text = Paragraph::new(lines)
+        .block(Block::default().title(tr!("Help")).borders(Borders::ALL));
     
     f.render_widget(text, area);
 }
\ No newline at end of file
@@ -0,0 +1,225 @@
+// remind:// links (`remind handle-uri LINK`), for bookmarklets, web pages and emails
+//
+// Once `remind handle-uri --install` has registered the scheme, clicking a link like
+//   remind://add?text=Call%20Sam&time=15:00&tag=work
+// in a browser or mail client adds the reminder. Links can only add: anyone can send one, so none
+// can complete, change or delete a reminder. Parameters, URL-encoded:
+//   text        what to be reminded of (required)
+//   time, date  as for `remind add`; time defaults to [defaults] time
+//   in          instead of a time, a delay from now, e.g. 30m or 2h
+//   recurrence  e.g. weekly or "every other tuesday"
+//   tag         repeated or comma-separated; [defaults] tags are added too
+//   notes, priority, context, url (kept as the `url` metadata)
+// There is usually no terminal to print to, so the outcome is also shown as a notification.
+
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use chrono::Local;
+#[cfg(not(target_os = "macos"))]
+use anyhow::Context;
+#[cfg(all(unix, not(target_os = "macos")))]
+use std::fs;
+#[cfg(not(target_os = "macos"))]
+use std::process::Command;
+
+use crate::cli;
+use crate::config::Config;
+use crate::error::RemindError;
+use crate::reminder::{Priority, Reminder};
+use crate::storage::Storage;
+use crate::workspace;
+
+// The desktop entry registered for x-scheme-handler/remind
+#[cfg(all(unix, not(target_os = "macos")))]
+const DESKTOP_FILE: &str = "remindme-uri.desktop";
+
+// Add the reminder a link describes
+pub fn handle(storage: &mut Storage, config: &Config, uri: &str) -> Result<Reminder> {
+    let rest = uri
+        .trim()
+        .get(..7)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("remind:"))
+        .map(|_| uri.trim()[7..].trim_start_matches('/'))
+        .ok_or_else(|| RemindError::InvalidInput(format!("Not a remind:// link: {}", uri)))?;
+    let (action, query) = rest.split_once('?').unwrap_or((rest, ""));
+    if !action.trim_end_matches('/').eq_ignore_ascii_case("add") {
+        return Err(RemindError::InvalidInput(format!(
+            "remind:// links can only add reminders (remind://add?text=...), not '{}'",
+            action
+        ))
+        .into());
+    }
+
+    let mut text = None;
+    let (mut time, mut date, mut delay, mut recurrence) = (None, None, None, None);
+    let (mut notes, mut priority, mut context, mut url) = (None, None, None, None);
+    let mut tags = Vec::new();
+    for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+        let value = value.trim().to_string();
+        match key.as_ref() {
+            "text" => text = Some(value),
+            "time" => time = Some(value),
+            "date" => date = Some(value),
+            "in" => delay = Some(value),
+            "recurrence" => recurrence = Some(value),
+            "tag" | "tags" => tags.extend(value.split(',').map(|tag| tag.trim().to_string()).filter(|tag| !tag.is_empty())),
+            "notes" => notes = Some(value),
+            "priority" => priority = Some(value),
+            "context" => context = Some(value),
+            "url" => url = Some(value),
+            other => return Err(RemindError::InvalidInput(format!("Unknown parameter '{}' in the link", other)).into()),
+        }
+    }
+    let text = text
+        .filter(|text| !text.is_empty())
+        .ok_or_else(|| RemindError::InvalidInput("The link has no text, e.g. remind://add?text=Call%20Sam&time=15:00".into()))?;
+
+    // Whatever isn't given comes from [defaults] in the config, as for `remind add`
+    let defaults = &config.defaults;
+    let mut reminder = match delay {
+        Some(_) if time.is_some() || date.is_some() => {
+            return Err(RemindError::InvalidInput("Give either in or time and date in the link, not both".into()).into());
+        }
+        Some(delay) => {
+            let recurrence = cli::parse_recurrence(recurrence.as_deref().unwrap_or("none"))?;
+            Reminder::new(text, Local::now() + cli::parse_duration(&delay)?, recurrence)
+        }
+        None => {
+            let recurrence = recurrence.or_else(|| defaults.recurrence.clone()).unwrap_or_else(|| "none".to_string());
+            let schedule = cli::parse_schedule(&recurrence)?;
+            let time = time
+                .or_else(|| schedule.time.map(|t| t.format("%H:%M").to_string()))
+                .or_else(|| defaults.time.clone())
+                .ok_or_else(|| RemindError::InvalidInput("The link has no time or in, and there's no defaults.time in config.toml".into()))?;
+            let due_time = cli::parse_datetime_with_default_date(&time, date.as_deref(), config.input.date_order)?;
+            Reminder::new(text, schedule.recurrence.align(due_time), schedule.recurrence)
+        }
+    };
+    for tag in defaults.tags.iter().chain(&tags) {
+        reminder.add_tag(tag);
+    }
+    if let Some(context) = context {
+        reminder.set_context(&context);
+    }
+    if let Some(workspace) = workspace::current(config)? {
+        workspace.apply(&mut reminder);
+    }
+    if let Some(notes) = notes {
+        reminder.set_notes(&notes);
+    }
+    if let Some(url) = url {
+        reminder.set_meta("url", &url);
+    }
+    reminder.priority = match priority {
+        Some(priority) => Priority::from_str(&priority, true)
+            .map_err(|_| RemindError::InvalidInput(format!("Unknown priority '{}'. Use low, medium, high or urgent", priority)))?,
+        None => defaults.priority()?,
+    };
+
+    storage.add_reminder(reminder.clone())?;
+    Ok(reminder)
+}
+
+// Register this binary for remind:// links: a desktop entry made the default handler through xdg-mime
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn install(storage_file: Option<&str>) -> Result<String> {
+    let exe = std::env::current_exe().context("Failed to find the remind binary")?;
+    let mut exec = vec![desktop_quote(&exe.to_string_lossy())];
+    if let Some(file) = storage_file {
+        exec.push("--storage-file".to_string());
+        exec.push(desktop_quote(file));
+    }
+    exec.push("handle-uri".to_string());
+    exec.push("%u".to_string());
+
+    let dir = dirs::data_dir().ok_or_else(|| anyhow!("Failed to determine the data directory"))?.join("applications");
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(DESKTOP_FILE);
+    let entry = format!(
+        "[Desktop Entry]\nType=Application\nName=RemindMe links\nComment=Add reminders from remind:// links\nExec={}\nNoDisplay=true\nTerminal=false\nMimeType=x-scheme-handler/remind;\n",
+        exec.join(" ")
+    );
+    fs::write(&path, entry).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let status = Command::new("xdg-mime")
+        .args(["default", DESKTOP_FILE, "x-scheme-handler/remind"])
+        .status()
+        .context("Failed to run xdg-mime (from xdg-utils)")?;
+    if !status.success() {
+        return Err(anyhow!("xdg-mime exited with {}", status));
+    }
+    // Only some desktops need the cache refreshed; without the tool the entry still works
+    let _ = Command::new("update-desktop-database").arg(&dir).status();
+    Ok(path.display().to_string())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn uninstall() -> Result<String> {
+    let path = dirs::data_dir().ok_or_else(|| anyhow!("Failed to determine the data directory"))?.join("applications").join(DESKTOP_FILE);
+    if path.exists() {
+        fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    Ok(path.display().to_string())
+}
+
+// Exec= arguments with spaces or special characters are double-quoted, with ", `, $ and \ escaped
+#[cfg(all(unix, not(target_os = "macos")))]
+fn desktop_quote(arg: &str) -> String {
+    if !arg.chars().any(|c| c.is_whitespace() || "\"'`$\\<>~|&;*?#()".contains(c)) {
+        return arg.to_string();
+    }
+    let escaped: String = arg
+        .chars()
+        .flat_map(|c| if "\"`$\\".contains(c) { vec!['\\', c] } else { vec![c] })
+        .collect();
+    format!("\"{}\"", escaped)
+}
+
+// Register the scheme for the current user under HKCU\Software\Classes
+#[cfg(windows)]
+pub fn install(storage_file: Option<&str>) -> Result<String> {
+    let exe = std::env::current_exe().context("Failed to find the remind binary")?;
+    let mut command = format!("\"{}\"", exe.display());
+    if let Some(file) = storage_file {
+        command.push_str(&format!(" --storage-file \"{}\"", file));
+    }
+    command.push_str(" handle-uri \"%1\"");
+
+    let key = r"HKCU\Software\Classes\remind";
+    let command_key = format!(r"{}\shell\open\command", key);
+    let entries: [&[&str]; 3] = [
+        &["add", key, "/ve", "/d", "URL:remind link", "/f"],
+        &["add", key, "/v", "URL Protocol", "/d", "", "/f"],
+        &["add", command_key.as_str(), "/ve", "/d", command.as_str(), "/f"],
+    ];
+    for args in entries {
+        let status = Command::new("reg").args(args).status().context("Failed to run reg")?;
+        if !status.success() {
+            return Err(anyhow!("reg exited with {}", status));
+        }
+    }
+    Ok(key.to_string())
+}
+
+#[cfg(windows)]
+pub fn uninstall() -> Result<String> {
+    let key = r"HKCU\Software\Classes\remind";
+    let status = Command::new("reg").args(["delete", key, "/f"]).status().context("Failed to run reg")?;
+    if !status.success() {
+        return Err(anyhow!("reg exited with {}", status));
+    }
+    Ok(key.to_string())
+}
+
+// A URL scheme on macOS has to be declared in an app bundle's Info.plist
+#[cfg(target_os = "macos")]
+pub fn install(_storage_file: Option<&str>) -> Result<String> {
+    Err(anyhow!(
+        "On macOS remind:// links need an app bundle that declares the scheme; wrap `remind handle-uri` in one (e.g. with Platypus)"
+    ))
+}
+
+#[cfg(target_os = "macos")]
+pub fn uninstall() -> Result<String> {
+    Err(anyhow!("On macOS remind:// links are handled by an app bundle; remove that app instead"))
+}
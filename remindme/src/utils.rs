@@ -1,10 +1,172 @@
 // Common utilities for the application
 
-use chrono::{DateTime, Local};
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, Local, Locale, NaiveDate, NaiveDateTime, TimeZone};
+use std::env;
+use std::io::Write;
+use std::sync::OnceLock;
+
+use crate::config::{Clock, DisplayConfig, WeekStart};
+use crate::error::RemindError;
+use crate::i18n::tr;
+
+// How dates and times are shown, from [display] in config.toml
+struct DisplayFormat {
+    clock: Clock,
+    locale: Locale,
+    week_start: WeekStart,
+    a11y: bool,
+}
+
+static DISPLAY: OnceLock<DisplayFormat> = OnceLock::new();
+
+// Set the display format once at startup; before that (or without a config) it's 24h and English
+pub fn init_display(config: &DisplayConfig) -> Result<()> {
+    let locale = match &config.locale {
+        Some(name) => parse_locale(name).ok_or_else(|| {
+            RemindError::Config(format!("Unknown display.locale '{}'. Expected a name like en_US or de_DE", name))
+        })?,
+        // Whatever the environment says, as `date` would use it; unknown ones fall back to English
+        None => env_locale().and_then(|name| parse_locale(&name)).unwrap_or(Locale::POSIX),
+    };
+    let _ = DISPLAY.set(DisplayFormat { clock: config.clock, locale, week_start: config.week_start, a11y: config.a11y });
+    Ok(())
+}
+
+// Whether output is meant for a screen reader (--a11y or display.a11y): state is spelled out in
+// words rather than shown only by color or a symbol
+pub fn accessible() -> bool {
+    display().a11y
+}
+
+// "de_DE", "de_DE.UTF-8" or "C"
+fn parse_locale(name: &str) -> Option<Locale> {
+    match name.split(['.', '@']).next().unwrap_or(name) {
+        "C" | "POSIX" => Some(Locale::POSIX),
+        name => Locale::try_from(name).ok(),
+    }
+}
+
+fn env_locale() -> Option<String> {
+    ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .find_map(|var| env::var(var).ok().filter(|value| !value.is_empty()))
+}
+
+fn display() -> &'static DisplayFormat {
+    DISPLAY.get_or_init(|| DisplayFormat {
+        clock: Clock::TwentyFour,
+        locale: Locale::POSIX,
+        week_start: WeekStart::Monday,
+        a11y: false,
+    })
+}
 
-#[allow(dead_code)]
 pub fn format_datetime(dt: &DateTime<Local>) -> String {
-    dt.format("%Y-%m-%d %H:%M").to_string()
+    format!("{} {}", dt.format("%Y-%m-%d"), format_time(dt))
+}
+
+// 17:30 or 5:30 PM, depending on display.clock
+pub fn format_time(dt: &DateTime<Local>) -> String {
+    match display().clock {
+        Clock::TwentyFour => dt.format("%H:%M").to_string(),
+        Clock::Twelve => {
+            // Some locales (de_DE, fr_FR) have no names for AM and PM
+            let period = dt.format_localized("%p", display().locale).to_string();
+            let period = if period.is_empty() { dt.format("%p").to_string() } else { period };
+            format!("{} {}", dt.format("%-I:%M"), period)
+        }
+    }
+}
+
+// A date with month and weekday names in the display locale, e.g. "%a %Y-%m-%d"
+pub fn format_date(date: NaiveDate, format: &str) -> String {
+    date.format_localized(format, display().locale).to_string()
+}
+
+// The first day of the week `date` is in, honoring display.week_start
+pub fn start_of_week(date: NaiveDate) -> NaiveDate {
+    let days = match display().week_start {
+        WeekStart::Monday => date.weekday().num_days_from_monday(),
+        WeekStart::Sunday => date.weekday().num_days_from_sunday(),
+    };
+    date - Duration::days(days as i64)
+}
+
+// A local time, the earlier one if it happens twice and the hour after if it's skipped
+pub fn local(naive: NaiveDateTime) -> DateTime<Local> {
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .or_else(|| Local.from_local_datetime(&(naive + Duration::hours(1))).earliest())
+        .unwrap_or_else(|| Local.from_utc_datetime(&naive))
+}
+
+// The number of days in `month` of `year`
+pub fn days_in_month(month: u32, year: i32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 => {
+            if (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0) {
+                29
+            } else {
+                28
+            }
+        }
+        _ => panic!("Invalid month"),
+    }
+}
+
+// Format a due time either relative to now ("in 3h", "2 days ago") or as an absolute timestamp
+pub fn format_due(dt: &DateTime<Local>, relative: bool) -> String {
+    if relative {
+        format_relative(dt, &Local::now())
+    } else {
+        format_datetime(dt)
+    }
+}
+
+// Human-relative description of `dt` as seen from `now`
+pub fn format_relative(dt: &DateTime<Local>, now: &DateTime<Local>) -> String {
+    let delta = *dt - *now;
+    let minutes = delta.num_minutes();
+    let days_apart = (dt.date_naive() - now.date_naive()).num_days();
+
+    if minutes.abs() < 1 {
+        return tr!("now");
+    }
+
+    if minutes > 0 {
+        match minutes {
+            m if m < 60 => tr!("in {}m", m),
+            m if m < 12 * 60 => tr!("in {}h", m / 60),
+            _ => match days_apart {
+                0 => tr!("today {}", format_time(dt)),
+                1 => tr!("tomorrow {}", format_time(dt)),
+                d if d < 30 => tr!("in {} days", d),
+                _ => format_datetime(dt),
+            },
+        }
+    } else {
+        match -minutes {
+            m if m < 60 => tr!("{}m ago", m),
+            m if m < 12 * 60 => tr!("{}h ago", m / 60),
+            _ => match -days_apart {
+                0 => tr!("today {}", format_time(dt)),
+                1 => tr!("yesterday {}", format_time(dt)),
+                d if d < 30 => tr!("{} days ago", d),
+                _ => format_datetime(dt),
+            },
+        }
+    }
+}
+
+// A span of minutes the way durations are typed, e.g. 3d, 1h30m or 45m
+pub fn format_minutes(minutes: i64) -> String {
+    let parts = [(minutes / (24 * 60), "d"), (minutes % (24 * 60) / 60, "h"), (minutes % 60, "m")];
+    let text: String = parts.iter().filter(|(n, _)| *n > 0).map(|(n, unit)| format!("{}{}", n, unit)).collect();
+    if text.is_empty() { "0m".to_string() } else { text }
 }
 
 #[allow(dead_code)]
@@ -14,4 +176,22 @@ pub fn truncate_string(s: &str, max_len: usize) -> String {
     } else {
         format!("{}...", &s[..max_len - 3])
     }
-}
\ No newline at end of file
+}
+
+// Show `label` and the time left until `end` on one line, redrawn in place, until `end` is reached
+pub fn countdown(label: &str, end: DateTime<Local>) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    loop {
+        let remaining = end - Local::now();
+        if remaining <= Duration::zero() {
+            break;
+        }
+        // Round up so the display reaches 00:00:00 only when the time is up
+        let left = (remaining.num_milliseconds() + 999) / 1000;
+        print!("\r{}  {:02}:{:02}:{:02} ", label, left / 3600, left / 60 % 60, left % 60);
+        stdout.flush()?;
+        std::thread::sleep(std::time::Duration::from_millis(250));
+    }
+    println!();
+    Ok(())
+}
@@ -0,0 +1,64 @@
+// How the TUI is laid out: the layout, the size of its top pane and the order of the list
+//
+// `[tui]` in config.toml sets the starting point. Changes made in the TUI are saved to tui.json
+// next to it and used from then on, so the TUI opens the way it was left; delete the file to go
+// back to the config.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::{TuiConfig, TuiLayout};
+use crate::reminder::SortOrder;
+use crate::storage;
+
+// Limits for the top pane, so neither pane disappears
+const MIN_RATIO: u16 = 20;
+const MAX_RATIO: u16 = 80;
+const RATIO_STEP: u16 = 5;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct View {
+    pub layout: TuiLayout,
+    pub ratio: u16,
+    #[serde(default)]
+    pub sort: SortOrder,
+}
+
+impl View {
+    fn path() -> Result<PathBuf> {
+        storage::state_path("tui.json")
+    }
+
+    // The view saved by the last TUI session, or the configured one
+    pub fn load(config: &TuiConfig) -> Self {
+        let saved = Self::path()
+            .ok()
+            .and_then(|path| fs::read(path).ok())
+            .and_then(|data| serde_json::from_slice::<View>(&data).ok());
+        let view = saved.unwrap_or(View { layout: config.layout, ratio: config.ratio, sort: config.sort });
+        View { ratio: view.ratio.clamp(MIN_RATIO, MAX_RATIO), ..view }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        let json = serde_json::to_vec_pretty(self).context("Failed to serialize TUI layout")?;
+        fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    // List, then split panes, then calendar, and round again
+    pub fn next_layout(&mut self) {
+        self.layout = match self.layout {
+            TuiLayout::List => TuiLayout::Split,
+            TuiLayout::Split => TuiLayout::Calendar,
+            TuiLayout::Calendar => TuiLayout::List,
+        };
+    }
+
+    // Make the top pane bigger (or smaller for a negative `steps`)
+    pub fn resize(&mut self, steps: i16) {
+        let ratio = self.ratio as i16 + steps * RATIO_STEP as i16;
+        self.ratio = (ratio.max(0) as u16).clamp(MIN_RATIO, MAX_RATIO);
+    }
+}
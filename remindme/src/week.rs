@@ -0,0 +1,157 @@
+// Week view as a standalone HTML page (`remind export --format html --week [DATE]`)
+//
+// One column per day with the reminders due in it, in order of their time, for planning sessions:
+// open it in a browser or print it (it fits a landscape page). Recurring reminders show every
+// occurrence in the week from the one due next, after the weekend/holiday policy moved it. The
+// page has its styles inline and loads nothing, so it can be mailed or kept as a single file.
+
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone};
+
+use crate::holidays::Calendar;
+use crate::i18n::tr;
+use crate::reminder::{Priority, RecurrenceType, Reminder};
+use crate::utils;
+
+const STYLE: &str = "
+body { font-family: system-ui, sans-serif; margin: 1.5rem; color: #222; }
+h1 { font-size: 1.4rem; margin: 0 0 1rem; }
+.week { display: grid; grid-template-columns: repeat(7, 1fr); border: 1px solid #bbb; }
+.day { border-left: 1px solid #bbb; min-height: 24rem; }
+.day:first-child { border-left: none; }
+.day h2 { font-size: 0.95rem; margin: 0; padding: 0.4rem; background: #eee; border-bottom: 1px solid #bbb; }
+.day.today h2 { background: #dbe8ff; }
+.day.off h2 { color: #888; }
+ul { list-style: none; margin: 0; padding: 0.3rem; }
+li { margin-bottom: 0.4rem; padding: 0.2rem 0.3rem; border-left: 3px solid #9bb; font-size: 0.85rem; }
+li.high { border-left-color: #e90; }
+li.urgent { border-left-color: #d22; font-weight: 600; }
+li.done { color: #888; text-decoration: line-through; }
+.time { font-variant-numeric: tabular-nums; color: #555; margin-right: 0.3rem; }
+.tags { color: #668; font-size: 0.75rem; }
+footer { margin-top: 0.8rem; color: #777; font-size: 0.75rem; }
+@media print {
+  @page { size: landscape; margin: 1cm; }
+  body { margin: 0; }
+  .day h2, .day.today h2 { background: none; }
+  li { break-inside: avoid; }
+}
+";
+
+// Each occurrence due from `start` up to `end`, in order, with the reminder it belongs to
+pub fn occurrences_between<'a>(
+    reminders: &'a [Reminder],
+    start: DateTime<Local>,
+    end: DateTime<Local>,
+    calendar: &Calendar,
+) -> Vec<(DateTime<Local>, &'a Reminder)> {
+    let mut found = Vec::new();
+    for reminder in reminders {
+        // Completed recurring reminders have no occurrences left; completed one-offs still show
+        if reminder.completed && !matches!(reminder.recurrence, RecurrenceType::None) {
+            continue;
+        }
+        let mut next = reminder.clone();
+        // Bounded so a reminder every minute can't fill the page forever
+        for _ in 0..1_000 {
+            if next.due_time >= end {
+                break;
+            }
+            if next.due_time >= start {
+                found.push((next.due_time, reminder));
+            }
+            if matches!(next.recurrence, RecurrenceType::None | RecurrenceType::Custom(_)) {
+                break;
+            }
+            let before = next.due_time;
+            next.schedule_next(calendar);
+            if next.due_time <= before {
+                break;
+            }
+        }
+    }
+    found.sort_by_key(|(time, _)| *time);
+    found
+}
+
+// The page for the week starting on `first`
+pub fn html(reminders: &[Reminder], first: NaiveDate, calendar: &Calendar) -> String {
+    let days: Vec<NaiveDate> = (0..7).map(|i| first + Duration::days(i)).collect();
+    let start = midnight(first);
+    let end = midnight(first + Duration::days(7));
+    let occurrences = occurrences_between(reminders, start, end, calendar);
+    let today = Local::now().date_naive();
+
+    let title = tr!(
+        "Week of {} to {}",
+        utils::format_date(first, "%a %-d %b"),
+        utils::format_date(first + Duration::days(6), "%a %-d %b %Y")
+    );
+    let mut page = String::new();
+    page.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    page.push_str(&format!("<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n", escape(&title), STYLE));
+    page.push_str(&format!("<h1>{}</h1>\n<div class=\"week\">\n", escape(&title)));
+    for day in &days {
+        let mut classes = vec!["day"];
+        if *day == today {
+            classes.push("today");
+        }
+        if !calendar.is_business_day(*day) {
+            classes.push("off");
+        }
+        page.push_str(&format!("<section class=\"{}\">\n", classes.join(" ")));
+        page.push_str(&format!("<h2>{}</h2>\n<ul>\n", escape(&utils::format_date(*day, "%A %-d"))));
+        for (time, reminder) in occurrences.iter().filter(|(time, _)| time.date_naive() == *day) {
+            page.push_str(&item(reminder, time));
+        }
+        page.push_str("</ul>\n</section>\n");
+    }
+    page.push_str("</div>\n");
+    page.push_str(&format!(
+        "<footer>{}</footer>\n</body>\n</html>\n",
+        escape(&tr!("{} reminder(s). Made by remindme on {}.", occurrences.len(), utils::format_datetime(&Local::now())))
+    ));
+    page
+}
+
+fn item(reminder: &Reminder, time: &DateTime<Local>) -> String {
+    let mut classes = Vec::new();
+    match reminder.priority {
+        Priority::High => classes.push("high"),
+        Priority::Urgent => classes.push("urgent"),
+        Priority::Low | Priority::Medium => {}
+    }
+    if reminder.completed {
+        classes.push("done");
+    }
+    let when = if reminder.is_all_day() { tr!("all day") } else { utils::format_time(time) };
+    let tags: Vec<String> = reminder.tags.iter().map(|tag| format!("#{}", tag)).collect();
+    let tags = if tags.is_empty() {
+        String::new()
+    } else {
+        format!(" <span class=\"tags\">{}</span>", escape(&tags.join(" ")))
+    };
+    let class = if classes.is_empty() { String::new() } else { format!(" class=\"{}\"", classes.join(" ")) };
+    format!(
+        "<li{}><span class=\"time\">{}</span>{}{}</li>\n",
+        class,
+        escape(&when),
+        escape(&reminder.headline()),
+        tags
+    )
+}
+
+// Local midnight, or the first moment of the day when a DST change skips midnight
+pub fn midnight(day: NaiveDate) -> DateTime<Local> {
+    let naive = day.and_time(chrono::NaiveTime::MIN);
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .unwrap_or_else(|| Local.from_utc_datetime(&naive))
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
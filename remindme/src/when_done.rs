@@ -0,0 +1,74 @@
+// Completing a reminder when a command succeeds (`remind when-done --id ID -- COMMAND...`)
+//
+// Ties a reminder to the automation that does the work, e.g.
+//   remind when-done --id 3f2a --retry-in 1d -- certbot renew
+// The command runs with the terminal's input and output. Exit status 0 completes the reminder as
+// `remind complete` would (a recurring one moves to its next occurrence, a follow-up is added);
+// anything else, or a command that can't be started, leaves it pending and due again after
+// --retry-in at the earliest. remind exits with the command's status (127 if it couldn't start), so cron and
+// scripts still see whether it worked. An unknown ID or a bad --retry-in is an error like any
+// other: status 1, and the command isn't run.
+
+use anyhow::{Context, Result};
+use chrono::Local;
+use std::process::Command;
+use tracing::info;
+
+use crate::cli;
+use crate::config::Config;
+use crate::holidays::Calendar;
+use crate::i18n::tr;
+use crate::pick;
+use crate::storage::Storage;
+use crate::utils;
+
+pub fn run(storage: &mut Storage, config: &Config, id: &str, retry_in: &str, command: &[String]) -> Result<i32> {
+    // Checked up front, so a typo in the ID or --retry-in doesn't waste a run of the command
+    let reminder = pick::find(storage.load()?, id)?;
+    let retry_in = cli::parse_duration(retry_in)?;
+    if reminder.completed {
+        println!("{}", tr!("'{}' is already completed; running the command anyway.", reminder.text));
+    }
+
+    let (program, args) = command.split_first().context("No command given after --")?;
+    let code = match Command::new(program).args(args).status() {
+        // Killed by a signal there's no code; that's a plain failure
+        Ok(status) => status.code().unwrap_or(1),
+        // A command that can't be run fails the way a shell reports it
+        Err(e) => {
+            eprintln!("{}", tr!("Failed to start {}: {}", program, e));
+            127
+        }
+    };
+    info!(id = %reminder.id, command = %command.join(" "), code, "Command finished");
+
+    // The reminder may have changed while the command ran
+    let mut reminder = pick::find(storage.load()?, &reminder.id)?;
+    if reminder.completed {
+        return Ok(code);
+    }
+    if code == 0 {
+        let calendar = Calendar::from_config(&config.holidays)?;
+        let (reminder, follow_up) = storage.complete_reminder(reminder, &calendar)?;
+        if reminder.completed {
+            println!("{}", tr!("The command succeeded; '{}' is completed.", reminder.text));
+        } else {
+            println!("{}", tr!("The command succeeded; '{}' is next due {}.", reminder.text, utils::format_datetime(&reminder.due_time)));
+        }
+        if let Some(next) = follow_up {
+            println!("{}", tr!("Follow-up '{}' added, due {}.", next.text, utils::format_datetime(&next.due_time)));
+        }
+    } else {
+        // Only ever later: a reminder not due until next week stays there
+        let retry = Local::now() + retry_in;
+        if reminder.due_time < retry {
+            reminder.snooze(retry);
+            storage.update_reminder(reminder.clone())?;
+        }
+        println!(
+            "{}",
+            tr!("The command failed (exit status {}); '{}' stays pending, due {}.", code, reminder.text, utils::format_datetime(&reminder.due_time))
+        );
+    }
+    Ok(code)
+}
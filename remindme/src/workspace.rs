@@ -0,0 +1,159 @@
+// Workspaces: switching between projects in one reminders file (`remind workspace use NAME`)
+//
+// A workspace is a saved filter plus defaults, from a [workspaces.NAME] table in the config.
+// While one is in use, `list` shows only its reminders and `add` gives new reminders its tags and
+// context, so nothing has to be split into separate files. A name that isn't in the config works
+// too, as a workspace of reminders tagged with that name.
+//
+// The workspace in use is remembered in workspace.json next to the reminders; REMINDME_WORKSPACE
+// overrides it, e.g. to give one terminal its own project ("" for none).
+
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::{Config, WorkspaceConfig};
+use crate::i18n::tr;
+use crate::reminder::{self, Reminder};
+use crate::storage;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum WorkspaceAction {
+    /// Switch to a workspace
+    Use,
+    /// Stop using a workspace, so list shows everything again
+    Off,
+    /// Show the workspaces in the config
+    List,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct State {
+    current: Option<String>,
+}
+
+impl State {
+    fn path() -> Result<PathBuf> {
+        storage::state_path("workspace.json")
+    }
+
+    fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(State::default());
+        }
+
+        let data = fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_slice(&data).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        let json = serde_json::to_vec_pretty(self).context("Failed to serialize the workspace")?;
+        fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+// The workspace in use, with its settings
+pub struct Workspace {
+    pub name: String,
+    pub settings: WorkspaceConfig,
+}
+
+impl Workspace {
+    // `name` from the config, or one that just tags with its name
+    fn named(name: &str, config: &Config) -> Self {
+        let settings = config.workspaces.get(name).cloned().unwrap_or_else(|| WorkspaceConfig {
+            tags: vec![name.to_string()],
+            ..WorkspaceConfig::default()
+        });
+        Workspace { name: name.to_string(), settings }
+    }
+
+    // Whether `list` shows the reminder while this workspace is in use
+    pub fn includes(&self, reminder: &Reminder) -> bool {
+        let tags = self.settings.filter.as_ref().unwrap_or(&self.settings.tags);
+        let tagged = tags.is_empty()
+            || tags
+                .iter()
+                .any(|tag| reminder.tags.iter().any(|t| t.eq_ignore_ascii_case(tag.trim_start_matches('#'))));
+        let context = self.settings.context.as_deref().and_then(reminder::normalize_context);
+        tagged && (context.is_none() || reminder.context == context)
+    }
+
+    // Give a new reminder the workspace's tags and, unless it has one, its context
+    pub fn apply(&self, reminder: &mut Reminder) {
+        for tag in &self.settings.tags {
+            reminder.add_tag(tag);
+        }
+        if reminder.context.is_none()
+            && let Some(context) = &self.settings.context
+        {
+            reminder.set_context(context);
+        }
+    }
+}
+
+// The workspace in use: REMINDME_WORKSPACE if it's set, otherwise the one last chosen
+pub fn current(config: &Config) -> Result<Option<Workspace>> {
+    let name = match std::env::var("REMINDME_WORKSPACE") {
+        Ok(name) => Some(name),
+        Err(_) => State::load()?.current,
+    };
+    Ok(name.filter(|name| !name.trim().is_empty()).map(|name| Workspace::named(name.trim(), config)))
+}
+
+// `remind workspace`: switch, stop, list, or without an action show the one in use
+pub fn command(config: &Config, action: Option<WorkspaceAction>, name: Option<&str>) -> Result<()> {
+    match (action, name) {
+        (Some(WorkspaceAction::Use), Some(name)) => {
+            let name = name.trim();
+            State { current: Some(name.to_string()) }.save()?;
+            if config.workspaces.contains_key(name) {
+                println!("{}", tr!("Now in workspace {}.", name));
+            } else {
+                println!("{}", tr!("Now in workspace {} (not in the config, so it's the reminders tagged #{}).", name, name));
+            }
+        }
+        (Some(WorkspaceAction::Use), None) => return Err(anyhow!("Give the workspace to use, e.g. remind workspace use projectx")),
+        (Some(WorkspaceAction::Off), _) => {
+            State::default().save()?;
+            println!("{}", tr!("No workspace in use; list shows every reminder."));
+        }
+        (Some(WorkspaceAction::List), _) => {
+            let current = current(config)?.map(|workspace| workspace.name);
+            if config.workspaces.is_empty() {
+                println!("{}", tr!("No workspaces in the config. Add a [workspaces.NAME] table, or just use a tag name."));
+            }
+            for (name, settings) in &config.workspaces {
+                let mark = if current.as_deref() == Some(name.as_str()) { "*" } else { " " };
+                println!("{} {}{}", mark, name, describe(settings));
+            }
+        }
+        (None, _) => match current(config)? {
+            Some(workspace) => println!("{}", tr!("Workspace: {}{}", workspace.name, describe(&workspace.settings))),
+            None => println!("{}", tr!("No workspace in use.")),
+        },
+    }
+    Ok(())
+}
+
+// " (tags: #a #b, context: @office)", or nothing for a workspace without either
+fn describe(settings: &WorkspaceConfig) -> String {
+    let mut parts = Vec::new();
+    if !settings.tags.is_empty() {
+        let tags: Vec<String> = settings.tags.iter().map(|tag| format!("#{}", tag.trim_start_matches('#'))).collect();
+        parts.push(format!("tags: {}", tags.join(" ")));
+    }
+    if let Some(filter) = &settings.filter {
+        let tags: Vec<String> = filter.iter().map(|tag| format!("#{}", tag.trim_start_matches('#'))).collect();
+        parts.push(format!("shows: {}", tags.join(" ")));
+    }
+    if let Some(context) = settings.context.as_deref().and_then(reminder::normalize_context) {
+        parts.push(format!("context: {}", context));
+    }
+    if parts.is_empty() { String::new() } else { format!(" ({})", parts.join(", ")) }
+}
@@ -139,10 +139,10 @@ rm -f ~/.local/share/applications/remindme.desktop
 read -p "Do you want to remove all reminders and configuration? (y/N) " -n 1 -r
 echo
 if [[ \$REPLY =~ ^[Yy]$ ]]; then
-    rm -rf ~/.config/remindme
+    rm -rf ~/.config/remindme ~/.local/share/remindme
     echo "All reminders and configuration removed."
 else
-    echo "Kept reminders and configuration at ~/.config/remindme"
+    echo "Kept reminders at ~/.local/share/remindme and configuration at ~/.config/remindme"
 fi
 
 echo "RemindMe has been uninstalled."
@@ -215,7 +215,7 @@ This was configured during installation.
 
 ## Configuration
 
-Reminders are stored in \`~/.config/remindme/reminders.json\`.
+Reminders are stored in \`~/.local/share/remindme/reminders.json\`.
 
 ## Uninstalling
 